@@ -4,6 +4,7 @@
 import type { ExecCommandSource } from "./ExecCommandSource";
 import type { ExecCommandStatus } from "./ExecCommandStatus";
 import type { ParsedCommand } from "./ParsedCommand";
+import type { ResourceLimitKind } from "./ResourceLimitKind";
 
 export type ExecCommandEndEvent = { 
 /**
@@ -61,4 +62,9 @@ formatted_output: string,
 /**
  * Completion status for this command execution.
  */
-status: ExecCommandStatus, };
+status: ExecCommandStatus, 
+/**
+ * Set when a sandbox resource limit stopped the command, as opposed to
+ * the command failing on its own.
+ */
+resource_limit_exceeded?: ResourceLimitKind, };
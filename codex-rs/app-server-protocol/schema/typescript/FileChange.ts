@@ -2,4 +2,16 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type FileChange = { "type": "add", content: string, } | { "type": "delete", content: string, } | { "type": "update", unified_diff: string, move_path: string | null, };
+export type FileChange = { "type": "add", content: string, 
+/**
+ * Unix permission bits the file is created with, e.g. `0o755`.
+ */
+mode?: number, } | { "type": "delete", content: string, } | { "type": "update", unified_diff: string, move_path: string | null, 
+/**
+ * Unix permission bits the file is changed to. The diff is empty for a mode-only change.
+ */
+mode?: number, } | { "type": "binary", size: number, 
+/**
+ * Whether an existing file is overwritten rather than a new one added.
+ */
+replace: boolean, mode?: number, } | { "type": "symlink", target: string, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response payload for `Op::GetMcpPrompt`.
+ */
+export type McpGetPromptResponseEvent = { server: string, name: string, 
+/**
+ * The prompt messages flattened into a single user message, or an error
+ * describing why the prompt could not be fetched.
+ */
+result: { Ok : string } | { Err : string }, };
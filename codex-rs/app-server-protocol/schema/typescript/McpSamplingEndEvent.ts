@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TokenUsage } from "./TokenUsage";
+
+export type McpSamplingEndEvent = { 
+/**
+ * Server that sent `sampling/createMessage`.
+ */
+server: string, 
+/**
+ * Model the request ran on.
+ */
+model: string, 
+/**
+ * Text of each message the server sent, in order.
+ */
+messages: Array<string>, 
+/**
+ * Generated text, or the reason the request failed.
+ */
+result: { Ok : string } | { Err : string }, token_usage: TokenUsage | null, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type McpToolsChangedEvent = { 
+/**
+ * Server that sent `notifications/tools/list_changed`.
+ */
+server: string, 
+/**
+ * Names of tools the server now offers that it did not before.
+ */
+added: Array<string>, 
+/**
+ * Names of tools the server no longer offers.
+ */
+removed: Array<string>, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sandbox resource limit that stopped a command.
+ */
+export type ResourceLimitKind = "memory" | "cpu_time" | "processes" | "file_size" | "wall_clock";
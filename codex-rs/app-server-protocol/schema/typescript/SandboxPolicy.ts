@@ -12,7 +12,12 @@ export type SandboxPolicy = { "type": "danger-full-access" } | { "type": "read-o
 /**
  * Read access granted while running under this policy.
  */
-access?: ReadOnlyAccess, } | { "type": "external-sandbox", 
+access?: ReadOnlyAccess, 
+/**
+ * Paths that stay unreadable even when read access would otherwise
+ * grant them. See [`SandboxPolicy::denied_read_paths`].
+ */
+denied_read_paths?: Array<string>, } | { "type": "external-sandbox", 
 /**
  * Whether the external sandbox permits outbound network traffic.
  */
@@ -41,4 +46,9 @@ exclude_tmpdir_env_var: boolean,
  * When set to `true`, will NOT include the `/tmp` among the default
  * writable roots on UNIX. Defaults to `false`.
  */
-exclude_slash_tmp: boolean, };
+exclude_slash_tmp: boolean, 
+/**
+ * Paths that stay unreadable even when read access would otherwise
+ * grant them. See [`SandboxPolicy::denied_read_paths`].
+ */
+denied_read_paths?: Array<string>, };
@@ -4,4 +4,13 @@
 import type { RateLimitSnapshot } from "./RateLimitSnapshot";
 import type { TokenUsageInfo } from "./TokenUsageInfo";
 
-export type TokenCountEvent = { info: TokenUsageInfo | null, rate_limits: RateLimitSnapshot | null, };
+export type TokenCountEvent = { 
+/**
+ * Usage as reported by the server.
+ */
+info: TokenUsageInfo | null, rate_limits: RateLimitSnapshot | null, 
+/**
+ * Tokens in the current context as counted locally, with the model's
+ * tokenizer when it is known and a byte estimate otherwise.
+ */
+estimated_tokens?: number, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileChange } from "./FileChange";
+
+export type WorkspaceOverlayChangesEvent = { 
+/**
+ * Pending changes keyed by their path in the real workspace.
+ */
+changes: { [key in string]?: FileChange }, 
+/**
+ * Unified diff of the pending changes against the real workspace.
+ */
+unified_diff: string, };
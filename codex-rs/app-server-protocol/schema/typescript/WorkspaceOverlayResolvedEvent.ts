@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WorkspaceOverlayResolvedEvent = { 
+/**
+ * Paths whose changes were merged into the real workspace.
+ */
+accepted: Array<string>, 
+/**
+ * Paths whose changes were discarded.
+ */
+rejected: Array<string>, 
+/**
+ * Accepted paths that were discarded because the workspace or overlay
+ * file changed after the changes were reported.
+ */
+conflicts: Array<string>, };
@@ -122,14 +122,18 @@ export type { MacOsAutomationValue } from "./MacOsAutomationValue";
 export type { MacOsPermissions } from "./MacOsPermissions";
 export type { MacOsPreferencesValue } from "./MacOsPreferencesValue";
 export type { McpAuthStatus } from "./McpAuthStatus";
+export type { McpGetPromptResponseEvent } from "./McpGetPromptResponseEvent";
 export type { McpInvocation } from "./McpInvocation";
+export type { McpListPromptsResponseEvent } from "./McpListPromptsResponseEvent";
 export type { McpListToolsResponseEvent } from "./McpListToolsResponseEvent";
+export type { McpSamplingEndEvent } from "./McpSamplingEndEvent";
 export type { McpStartupCompleteEvent } from "./McpStartupCompleteEvent";
 export type { McpStartupFailure } from "./McpStartupFailure";
 export type { McpStartupStatus } from "./McpStartupStatus";
 export type { McpStartupUpdateEvent } from "./McpStartupUpdateEvent";
 export type { McpToolCallBeginEvent } from "./McpToolCallBeginEvent";
 export type { McpToolCallEndEvent } from "./McpToolCallEndEvent";
+export type { McpToolsChangedEvent } from "./McpToolsChangedEvent";
 export type { MessagePhase } from "./MessagePhase";
 export type { ModeKind } from "./ModeKind";
 export type { ModelRerouteEvent } from "./ModelRerouteEvent";
@@ -152,6 +156,8 @@ export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { Profile } from "./Profile";
+export type { Prompt } from "./Prompt";
+export type { PromptArgument } from "./PromptArgument";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -178,6 +184,7 @@ export type { RequestUserInputEvent } from "./RequestUserInputEvent";
 export type { RequestUserInputQuestion } from "./RequestUserInputQuestion";
 export type { RequestUserInputQuestionOption } from "./RequestUserInputQuestionOption";
 export type { Resource } from "./Resource";
+export type { ResourceLimitKind } from "./ResourceLimitKind";
 export type { ResourceTemplate } from "./ResourceTemplate";
 export type { ResponseItem } from "./ResponseItem";
 export type { ResumeConversationParams } from "./ResumeConversationParams";
@@ -247,4 +254,6 @@ export type { WebSearchBeginEvent } from "./WebSearchBeginEvent";
 export type { WebSearchEndEvent } from "./WebSearchEndEvent";
 export type { WebSearchItem } from "./WebSearchItem";
 export type { WebSearchMode } from "./WebSearchMode";
+export type { WorkspaceOverlayChangesEvent } from "./WorkspaceOverlayChangesEvent";
+export type { WorkspaceOverlayResolvedEvent } from "./WorkspaceOverlayResolvedEvent";
 export * as v2 from "./v2";
@@ -40,4 +40,11 @@ cwd?: string | null,
 /**
  * Optional substring filter for the extracted thread title.
  */
-searchTerm?: string | null, };
+searchTerm?: string | null, 
+/**
+ * Optional full-text query over user messages, agent messages and commands.
+ * When set, results are ordered by relevance instead of `sortKey`, each match is
+ * described in `searchMatches`, `limit` caps the number of results, and
+ * `cursor` and `searchTerm` are ignored. Requires the SQLite state DB.
+ */
+query?: string | null, };
@@ -2,10 +2,16 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Thread } from "./Thread";
+import type { ThreadSearchMatch } from "./ThreadSearchMatch";
 
 export type ThreadListResponse = { data: Array<Thread>, 
 /**
  * Opaque cursor to pass to the next call to continue after the last item.
  * if None, there are no more items to return.
  */
-nextCursor: string | null, };
+nextCursor: string | null, 
+/**
+ * For full-text queries, the best match in each returned thread, in the same order
+ * as `data`. Empty for plain listings.
+ */
+searchMatches: Array<ThreadSearchMatch>, };
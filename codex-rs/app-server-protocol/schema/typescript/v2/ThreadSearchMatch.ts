@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+
+export type ThreadSearchMatch = { threadId: string, kind: ThreadSearchMatchKind, 
+/**
+ * Excerpt of the matching content with matched terms wrapped in `[` and `]`.
+ */
+snippet: string, };
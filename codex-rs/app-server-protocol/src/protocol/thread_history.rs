@@ -984,28 +984,51 @@ fn convert_dynamic_tool_content_items(
 
 fn map_patch_change_kind(change: &codex_protocol::protocol::FileChange) -> PatchChangeKind {
     match change {
-        codex_protocol::protocol::FileChange::Add { .. } => PatchChangeKind::Add,
+        codex_protocol::protocol::FileChange::Add { .. }
+        | codex_protocol::protocol::FileChange::Binary { replace: false, .. }
+        | codex_protocol::protocol::FileChange::Symlink { .. } => PatchChangeKind::Add,
         codex_protocol::protocol::FileChange::Delete { .. } => PatchChangeKind::Delete,
         codex_protocol::protocol::FileChange::Update { move_path, .. } => PatchChangeKind::Update {
             move_path: move_path.clone(),
         },
+        codex_protocol::protocol::FileChange::Binary { replace: true, .. } => {
+            PatchChangeKind::Update { move_path: None }
+        }
     }
 }
 
 fn format_file_change_diff(change: &codex_protocol::protocol::FileChange) -> String {
     match change {
-        codex_protocol::protocol::FileChange::Add { content } => content.clone(),
+        codex_protocol::protocol::FileChange::Add { content, mode } => {
+            with_file_mode(content.clone(), *mode)
+        }
         codex_protocol::protocol::FileChange::Delete { content } => content.clone(),
         codex_protocol::protocol::FileChange::Update {
             unified_diff,
             move_path,
+            mode,
         } => {
-            if let Some(path) = move_path {
+            let diff = if let Some(path) = move_path {
                 format!("{unified_diff}\n\nMoved to: {}", path.display())
             } else {
                 unified_diff.clone()
-            }
+            };
+            with_file_mode(diff, *mode)
+        }
+        codex_protocol::protocol::FileChange::Binary { size, mode, .. } => {
+            with_file_mode(format!("Binary file ({size} bytes)"), *mode)
         }
+        codex_protocol::protocol::FileChange::Symlink { target } => {
+            format!("Symlink to: {}", target.display())
+        }
+    }
+}
+
+fn with_file_mode(diff: String, mode: Option<u32>) -> String {
+    match mode {
+        Some(mode) if diff.is_empty() => format!("Mode: {mode:o}"),
+        Some(mode) => format!("{diff}\n\nMode: {mode:o}"),
+        None => diff,
     }
 }
 
@@ -1613,6 +1636,7 @@ mod tests {
                 duration: Duration::from_millis(12),
                 formatted_output: String::new(),
                 status: CoreExecCommandStatus::Completed,
+                resource_limit_exceeded: None,
             }),
             EventMsg::McpToolCallEnd(McpToolCallEndEvent {
                 call_id: "mcp-1".into(),
@@ -1766,6 +1790,7 @@ mod tests {
                 duration: Duration::ZERO,
                 formatted_output: String::new(),
                 status: CoreExecCommandStatus::Declined,
+                resource_limit_exceeded: None,
             }),
             EventMsg::PatchApplyEnd(PatchApplyEndEvent {
                 call_id: "patch-declined".into(),
@@ -1777,6 +1802,7 @@ mod tests {
                     PathBuf::from("README.md"),
                     codex_protocol::protocol::FileChange::Add {
                         content: "hello\n".into(),
+                        mode: None,
                     },
                 )]
                 .into_iter()
@@ -1869,6 +1895,7 @@ mod tests {
                 duration: Duration::from_millis(5),
                 formatted_output: "done\n".into(),
                 status: CoreExecCommandStatus::Completed,
+                resource_limit_exceeded: None,
             }),
             EventMsg::TurnComplete(TurnCompleteEvent {
                 turn_id: "turn-b".into(),
@@ -1951,6 +1978,7 @@ mod tests {
                 duration: Duration::from_millis(5),
                 formatted_output: "done\n".into(),
                 status: CoreExecCommandStatus::Completed,
+                resource_limit_exceeded: None,
             }),
             EventMsg::TurnComplete(TurnCompleteEvent {
                 turn_id: "turn-b".into(),
@@ -946,6 +946,8 @@ pub enum SandboxPolicy {
     ReadOnly {
         #[serde(default)]
         access: ReadOnlyAccess,
+        #[serde(default)]
+        denied_read_paths: Vec<String>,
     },
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -966,6 +968,8 @@ pub enum SandboxPolicy {
         exclude_tmpdir_env_var: bool,
         #[serde(default)]
         exclude_slash_tmp: bool,
+        #[serde(default)]
+        denied_read_paths: Vec<String>,
     },
 }
 
@@ -975,11 +979,13 @@ impl SandboxPolicy {
             SandboxPolicy::DangerFullAccess => {
                 codex_protocol::protocol::SandboxPolicy::DangerFullAccess
             }
-            SandboxPolicy::ReadOnly { access } => {
-                codex_protocol::protocol::SandboxPolicy::ReadOnly {
-                    access: access.to_core(),
-                }
-            }
+            SandboxPolicy::ReadOnly {
+                access,
+                denied_read_paths,
+            } => codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                access: access.to_core(),
+                denied_read_paths: denied_read_paths.clone(),
+            },
             SandboxPolicy::ExternalSandbox { network_access } => {
                 codex_protocol::protocol::SandboxPolicy::ExternalSandbox {
                     network_access: match network_access {
@@ -994,12 +1000,14 @@ impl SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                denied_read_paths,
             } => codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: writable_roots.clone(),
                 read_only_access: read_only_access.to_core(),
                 network_access: *network_access,
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                denied_read_paths: denied_read_paths.clone(),
             },
         }
     }
@@ -1011,11 +1019,13 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
             codex_protocol::protocol::SandboxPolicy::DangerFullAccess => {
                 SandboxPolicy::DangerFullAccess
             }
-            codex_protocol::protocol::SandboxPolicy::ReadOnly { access } => {
-                SandboxPolicy::ReadOnly {
-                    access: ReadOnlyAccess::from(access),
-                }
-            }
+            codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                access,
+                denied_read_paths,
+            } => SandboxPolicy::ReadOnly {
+                access: ReadOnlyAccess::from(access),
+                denied_read_paths,
+            },
             codex_protocol::protocol::SandboxPolicy::ExternalSandbox { network_access } => {
                 SandboxPolicy::ExternalSandbox {
                     network_access: match network_access {
@@ -1030,12 +1040,14 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                denied_read_paths,
             } => SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 read_only_access: ReadOnlyAccess::from(read_only_access),
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                denied_read_paths,
             },
         }
     }
@@ -2124,6 +2136,12 @@ pub struct ThreadListParams {
     /// Optional substring filter for the extracted thread title.
     #[ts(optional = nullable)]
     pub search_term: Option<String>,
+    /// Optional full-text query over user messages, agent messages and commands.
+    /// When set, results are ordered by relevance instead of `sortKey`, each match is
+    /// described in `searchMatches`, `limit` caps the number of results, and
+    /// `cursor` and `searchTerm` are ignored. Requires the SQLite state DB.
+    #[ts(optional = nullable)]
+    pub query: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
@@ -2160,6 +2178,29 @@ pub struct ThreadListResponse {
     /// Opaque cursor to pass to the next call to continue after the last item.
     /// if None, there are no more items to return.
     pub next_cursor: Option<String>,
+    /// For full-text queries, the best match in each returned thread, in the same order
+    /// as `data`. Empty for plain listings.
+    #[serde(default)]
+    pub search_matches: Vec<ThreadSearchMatch>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchMatch {
+    pub thread_id: String,
+    pub kind: ThreadSearchMatchKind,
+    /// Excerpt of the matching content with matched terms wrapped in `[` and `]`.
+    pub snippet: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -4196,6 +4237,7 @@ mod tests {
                 include_platform_defaults: false,
                 readable_roots: vec![readable_root.clone()],
             },
+            denied_read_paths: Vec::new(),
         };
 
         let core_policy = v2_policy.to_core();
@@ -4206,6 +4248,7 @@ mod tests {
                     include_platform_defaults: false,
                     readable_roots: vec![readable_root],
                 },
+                denied_read_paths: Vec::new(),
             }
         );
 
@@ -4225,6 +4268,7 @@ mod tests {
             network_access: true,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         };
 
         let core_policy = v2_policy.to_core();
@@ -4239,6 +4283,7 @@ mod tests {
                 network_access: true,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             }
         );
 
@@ -4256,6 +4301,7 @@ mod tests {
             policy,
             SandboxPolicy::ReadOnly {
                 access: ReadOnlyAccess::FullAccess,
+                denied_read_paths: Vec::new(),
             }
         );
     }
@@ -4278,6 +4324,7 @@ mod tests {
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             }
         );
     }
@@ -595,6 +595,7 @@ fn trigger_zsh_fork_multi_cmd_approval(
     turn_params.approval_policy = Some(AskForApproval::OnRequest);
     turn_params.sandbox_policy = Some(SandboxPolicy::ReadOnly {
         access: ReadOnlyAccess::FullAccess,
+        denied_read_paths: Vec::new(),
     });
 
     let turn_response = client.turn_start(turn_params)?;
@@ -715,6 +716,7 @@ fn trigger_cmd_approval(
         Some(AskForApproval::OnRequest),
         Some(SandboxPolicy::ReadOnly {
             access: ReadOnlyAccess::FullAccess,
+            denied_read_paths: Vec::new(),
         }),
         dynamic_tools,
     )
@@ -737,6 +739,7 @@ fn trigger_patch_approval(
         Some(AskForApproval::OnRequest),
         Some(SandboxPolicy::ReadOnly {
             access: ReadOnlyAccess::FullAccess,
+            denied_read_paths: Vec::new(),
         }),
         dynamic_tools,
     )
@@ -914,6 +917,7 @@ fn thread_list(endpoint: &Endpoint, config_overrides: &[String], limit: u32) ->
         archived: None,
         cwd: None,
         search_term: None,
+        query: None,
     })?;
     println!("< thread/list response: {response:?}");
 
@@ -1686,7 +1686,9 @@ async fn handle_token_count_event(
     token_count_event: TokenCountEvent,
     outgoing: &ThreadScopedOutgoingMessageSender,
 ) {
-    let TokenCountEvent { info, rate_limits } = token_count_event;
+    let TokenCountEvent {
+        info, rate_limits, ..
+    } = token_count_event;
     if let Some(token_usage) = info.map(ThreadTokenUsage::from) {
         let notification = ThreadTokenUsageUpdatedNotification {
             thread_id: conversation_id.to_string(),
@@ -2588,6 +2590,7 @@ mod tests {
             TokenCountEvent {
                 info: Some(info),
                 rate_limits: Some(rate_limits),
+                estimated_tokens: None,
             },
             &outgoing,
         )
@@ -2642,6 +2645,7 @@ mod tests {
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                estimated_tokens: None,
             },
             &outgoing,
         )
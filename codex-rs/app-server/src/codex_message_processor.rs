@@ -152,6 +152,8 @@ use codex_app_server_protocol::ThreadRealtimeStopResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchKind;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -272,6 +274,7 @@ use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::user_input::MAX_USER_INPUT_TEXT_CHARS;
 use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_rmcp_client::perform_oauth_login_return_url;
+use codex_state::ThreadSearchKind;
 use codex_utils_json_to_toml::json_to_toml;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -2609,6 +2612,7 @@ impl CodexMessageProcessor {
             archived,
             cwd,
             search_term,
+            query,
         } = params;
 
         let requested_page_size = limit
@@ -2619,21 +2623,24 @@ impl CodexMessageProcessor {
             ThreadSortKey::CreatedAt => CoreThreadSortKey::CreatedAt,
             ThreadSortKey::UpdatedAt => CoreThreadSortKey::UpdatedAt,
         };
-        let (summaries, next_cursor) = match self
-            .list_threads_common(
-                requested_page_size,
-                cursor,
-                core_sort_key,
-                ThreadListFilters {
-                    model_providers,
-                    source_kinds,
-                    archived: archived.unwrap_or(false),
-                    cwd: cwd.map(PathBuf::from),
-                    search_term,
-                },
-            )
-            .await
-        {
+        let filters = ThreadListFilters {
+            model_providers,
+            source_kinds,
+            archived: archived.unwrap_or(false),
+            cwd: cwd.map(PathBuf::from),
+            search_term,
+        };
+        let listing = match query.filter(|query| !query.trim().is_empty()) {
+            Some(query) => self
+                .search_threads_common(requested_page_size, query.as_str(), filters)
+                .await
+                .map(|(summaries, search_matches)| (summaries, None, search_matches)),
+            None => self
+                .list_threads_common(requested_page_size, cursor, core_sort_key, filters)
+                .await
+                .map(|(summaries, next_cursor)| (summaries, next_cursor, Vec::new())),
+        };
+        let (summaries, next_cursor, search_matches) = match listing {
             Ok(r) => r,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -2676,7 +2683,11 @@ impl CodexMessageProcessor {
                 thread
             })
             .collect();
-        let response = ThreadListResponse { data, next_cursor };
+        let response = ThreadListResponse {
+            data,
+            next_cursor,
+            search_matches,
+        };
         self.outgoing.send_response(request_id, response).await;
     }
 
@@ -3860,6 +3871,84 @@ impl CodexMessageProcessor {
         Ok((items, next_cursor))
     }
 
+    async fn search_threads_common(
+        &self,
+        limit: usize,
+        query: &str,
+        filters: ThreadListFilters,
+    ) -> Result<(Vec<ConversationSummary>, Vec<ThreadSearchMatch>), JSONRPCErrorError> {
+        let ThreadListFilters {
+            model_providers,
+            source_kinds,
+            archived,
+            cwd,
+            search_term: _,
+        } = filters;
+        let model_provider_filter = match model_providers {
+            Some(providers) if providers.is_empty() => None,
+            Some(providers) => Some(providers),
+            None => Some(vec![self.config.model_provider_id.clone()]),
+        };
+        let fallback_provider = self.config.model_provider_id.clone();
+        let (allowed_sources, source_kind_filter) = compute_source_filters(source_kinds);
+        // The source kind filter applies after the query, so overfetch when it is set.
+        let fetch_limit = if source_kind_filter.is_some() {
+            THREAD_LIST_MAX_LIMIT
+        } else {
+            limit
+        };
+        let hits = RolloutRecorder::search_threads(
+            &self.config,
+            query,
+            fetch_limit,
+            allowed_sources.as_slice(),
+            model_provider_filter.as_deref(),
+            archived,
+            cwd.as_deref(),
+        )
+        .await
+        .map_err(|err| JSONRPCErrorError {
+            code: INTERNAL_ERROR_CODE,
+            message: format!("failed to search threads: {err}"),
+            data: None,
+        })?;
+        let state_db_ctx = get_state_db(&self.config, None).await;
+
+        let mut summaries = Vec::with_capacity(limit.min(hits.len()));
+        let mut search_matches = Vec::with_capacity(limit.min(hits.len()));
+        for hit in hits {
+            let Some(summary) = summary_from_thread_list_item(
+                hit.thread,
+                fallback_provider.as_str(),
+                state_db_ctx.as_ref(),
+            )
+            .await
+            else {
+                continue;
+            };
+            if source_kind_filter
+                .as_ref()
+                .is_none_or(|filter| source_kind_matches(&summary.source, filter))
+            {
+                search_matches.push(ThreadSearchMatch {
+                    thread_id: summary.conversation_id.to_string(),
+                    kind: match hit.kind {
+                        ThreadSearchKind::UserMessage => ThreadSearchMatchKind::UserMessage,
+                        ThreadSearchKind::AgentMessage => ThreadSearchMatchKind::AgentMessage,
+                        ThreadSearchKind::Command => ThreadSearchMatchKind::Command,
+                    },
+                    snippet: hit.snippet,
+                });
+                summaries.push(summary);
+                if summaries.len() >= limit {
+                    break;
+                }
+            }
+        }
+
+        Ok((summaries, search_matches))
+    }
+
     async fn list_models(
         outgoing: Arc<OutgoingMessageSender>,
         thread_manager: Arc<ThreadManager>,
@@ -26,8 +26,10 @@ use crate::transport::CHANNEL_CAPACITY;
 use crate::transport::ConnectionState;
 use crate::transport::OutboundConnectionState;
 use crate::transport::TransportEvent;
+use crate::transport::WebSocketSecurity;
 use crate::transport::route_outgoing_envelope;
 use crate::transport::start_stdio_connection;
+use crate::transport::start_unix_socket_acceptor;
 use crate::transport::start_websocket_acceptor;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_app_server_protocol::ConfigWarningNotification;
@@ -69,6 +71,7 @@ mod transport;
 
 pub use crate::error_code::INPUT_TOO_LARGE_ERROR_CODE;
 pub use crate::error_code::INVALID_PARAMS_ERROR_CODE;
+pub use crate::transport::AppServerListenOptions;
 pub use crate::transport::AppServerTransport;
 
 const LOG_FORMAT_ENV_VAR: &str = "LOG_FORMAT";
@@ -306,6 +309,7 @@ pub async fn run_main(
         loader_overrides,
         default_analytics_enabled,
         AppServerTransport::Stdio,
+        AppServerListenOptions::default(),
     )
     .await
 }
@@ -316,6 +320,7 @@ pub async fn run_main_with_transport(
     loader_overrides: LoaderOverrides,
     default_analytics_enabled: bool,
     transport: AppServerTransport,
+    listen_options: AppServerListenOptions,
 ) -> IoResult<()> {
     let (transport_event_tx, mut transport_event_rx) =
         mpsc::channel::<TransportEvent>(CHANNEL_CAPACITY);
@@ -325,7 +330,7 @@ pub async fn run_main_with_transport(
 
     enum TransportRuntime {
         Stdio,
-        WebSocket {
+        Listener {
             accept_handle: JoinHandle<()>,
             shutdown_token: CancellationToken,
         },
@@ -334,18 +339,37 @@ pub async fn run_main_with_transport(
     let mut stdio_handles = Vec::<JoinHandle<()>>::new();
     let transport_runtime = match transport {
         AppServerTransport::Stdio => {
+            listen_options.ensure_unused()?;
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
             TransportRuntime::Stdio
         }
-        AppServerTransport::WebSocket { bind_address } => {
+        AppServerTransport::WebSocket { bind_address }
+        | AppServerTransport::SecureWebSocket { bind_address } => {
+            let tls = matches!(transport, AppServerTransport::SecureWebSocket { .. });
+            let security = WebSocketSecurity::load(&listen_options, tls)?;
             let shutdown_token = CancellationToken::new();
             let accept_handle = start_websocket_acceptor(
                 bind_address,
+                security,
                 transport_event_tx.clone(),
                 shutdown_token.clone(),
             )
             .await?;
-            TransportRuntime::WebSocket {
+            TransportRuntime::Listener {
+                accept_handle,
+                shutdown_token,
+            }
+        }
+        AppServerTransport::UnixSocket { path } => {
+            listen_options.ensure_unused()?;
+            let shutdown_token = CancellationToken::new();
+            let accept_handle = start_unix_socket_acceptor(
+                path,
+                transport_event_tx.clone(),
+                shutdown_token.clone(),
+            )
+            .await?;
+            TransportRuntime::Listener {
                 accept_handle,
                 shutdown_token,
             }
@@ -566,8 +590,8 @@ pub async fn run_main_with_transport(
         let mut thread_created_rx = processor.thread_created_receiver();
         let mut running_turn_count_rx = processor.subscribe_running_assistant_turn_count();
         let mut connections = HashMap::<ConnectionId, ConnectionState>::new();
-        let websocket_accept_shutdown = match &transport_runtime {
-            TransportRuntime::WebSocket { shutdown_token, .. } => Some(shutdown_token.clone()),
+        let listener_accept_shutdown = match &transport_runtime {
+            TransportRuntime::Listener { shutdown_token, .. } => Some(shutdown_token.clone()),
             TransportRuntime::Stdio => None,
         };
         async move {
@@ -582,7 +606,7 @@ pub async fn run_main_with_transport(
                     shutdown_state.update(running_turn_count, connections.len()),
                     ShutdownAction::Finish
                 ) {
-                    if let Some(shutdown_token) = &websocket_accept_shutdown {
+                    if let Some(shutdown_token) = &listener_accept_shutdown {
                         shutdown_token.cancel();
                     }
                     let _ = outbound_control_tx
@@ -766,7 +790,7 @@ pub async fn run_main_with_transport(
     let _ = processor_handle.await;
     let _ = outbound_handle.await;
 
-    if let TransportRuntime::WebSocket {
+    if let TransportRuntime::Listener {
         accept_handle,
         shutdown_token,
     } = transport_runtime
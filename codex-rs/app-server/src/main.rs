@@ -1,4 +1,5 @@
 use clap::Parser;
+use codex_app_server::AppServerListenOptions;
 use codex_app_server::AppServerTransport;
 use codex_app_server::run_main_with_transport;
 use codex_arg0::Arg0DispatchPaths;
@@ -14,13 +15,16 @@ const MANAGED_CONFIG_PATH_ENV_VAR: &str = "CODEX_APP_SERVER_MANAGED_CONFIG_PATH"
 #[derive(Debug, Parser)]
 struct AppServerArgs {
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `wss://IP:PORT`, `unix:///PATH`.
     #[arg(
         long = "listen",
         value_name = "URL",
         default_value = AppServerTransport::DEFAULT_LISTEN_URL
     )]
     listen: AppServerTransport,
+
+    #[command(flatten)]
+    listen_options: AppServerListenOptions,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -39,6 +43,7 @@ fn main() -> anyhow::Result<()> {
             loader_overrides,
             false,
             transport,
+            args.listen_options,
         )
         .await?;
         Ok(())
@@ -17,6 +17,8 @@ use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -24,15 +26,29 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
 use tokio::net::TcpListener;
-use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tokio_tungstenite::accept_async_with_config;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::pki_types::PrivateKeyDer;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::accept_hdr_async_with_config;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::header::WWW_AUTHENTICATE;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
@@ -51,34 +67,70 @@ fn colorize(text: &str, style: Style) -> String {
 }
 
 #[allow(clippy::print_stderr)]
-fn print_websocket_startup_banner(addr: SocketAddr) {
+fn print_websocket_startup_banner(addr: SocketAddr, security: &WebSocketSecurity) {
     let title = colorize("codex app-server (WebSockets)", Style::new().bold().cyan());
     let listening_label = colorize("listening on:", Style::new().dimmed());
-    let listen_url = colorize(&format!("ws://{addr}"), Style::new().green());
+    let scheme = if security.tls_acceptor.is_some() {
+        "wss"
+    } else {
+        "ws"
+    };
+    let listen_url = colorize(&format!("{scheme}://{addr}"), Style::new().green());
     let note_label = colorize("note:", Style::new().dimmed());
     eprintln!("{title}");
     eprintln!("  {listening_label} {listen_url}");
+    if security.auth_token.is_some() {
+        let auth_label = colorize("auth:", Style::new().dimmed());
+        eprintln!("  {auth_label} clients must send `Authorization: Bearer <token>`");
+    }
     if addr.ip().is_loopback() {
         eprintln!(
             "  {note_label} binds localhost only (use SSH port-forwarding for remote access)"
         );
-    } else {
+    } else if security.tls_acceptor.is_none() || security.auth_token.is_none() {
         eprintln!(
-            "  {note_label} this is a raw WS server; consider running behind TLS/auth for real remote use"
+            "  {note_label} set --tls-cert/--tls-key and --ws-auth-token-file for real remote use"
         );
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg(unix)]
+#[allow(clippy::print_stderr)]
+fn print_unix_socket_startup_banner(path: &Path) {
+    let title = colorize("codex app-server (Unix socket)", Style::new().bold().cyan());
+    let listening_label = colorize("listening on:", Style::new().dimmed());
+    let listen_url = colorize(&format!("unix://{}", path.display()), Style::new().green());
+    let note_label = colorize("note:", Style::new().dimmed());
+    eprintln!("{title}");
+    eprintln!("  {listening_label} {listen_url}");
+    eprintln!(
+        "  {note_label} newline-delimited JSON; only this user can connect (use codex-stdio-to-uds to relay stdio)"
+    );
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppServerTransport {
     Stdio,
-    WebSocket { bind_address: SocketAddr },
+    WebSocket {
+        bind_address: SocketAddr,
+    },
+    /// WebSocket over TLS, using the certificate from `--tls-cert`/`--tls-key`.
+    SecureWebSocket {
+        bind_address: SocketAddr,
+    },
+    /// Newline-delimited JSON over a Unix domain socket created with mode
+    /// 0600, like stdio but for any number of clients.
+    UnixSocket {
+        path: PathBuf,
+    },
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum AppServerTransportParseError {
     UnsupportedListenUrl(String),
     InvalidWebSocketListenUrl(String),
+    InvalidUnixSocketListenUrl(String),
 }
 
 impl std::fmt::Display for AppServerTransportParseError {
@@ -86,11 +138,15 @@ impl std::fmt::Display for AppServerTransportParseError {
         match self {
             AppServerTransportParseError::UnsupportedListenUrl(listen_url) => write!(
                 f,
-                "unsupported --listen URL `{listen_url}`; expected `stdio://` or `ws://IP:PORT`"
+                "unsupported --listen URL `{listen_url}`; expected `stdio://`, `ws://IP:PORT`, `wss://IP:PORT` or `unix:///PATH`"
             ),
             AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url) => write!(
                 f,
-                "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT`"
+                "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT` or `wss://IP:PORT`"
+            ),
+            AppServerTransportParseError::InvalidUnixSocketListenUrl(listen_url) => write!(
+                f,
+                "invalid unix socket --listen URL `{listen_url}`; expected `unix:///ABSOLUTE/PATH`"
             ),
         }
     }
@@ -113,6 +169,23 @@ impl AppServerTransport {
             return Ok(Self::WebSocket { bind_address });
         }
 
+        if let Some(socket_addr) = listen_url.strip_prefix("wss://") {
+            let bind_address = socket_addr.parse::<SocketAddr>().map_err(|_| {
+                AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url.to_string())
+            })?;
+            return Ok(Self::SecureWebSocket { bind_address });
+        }
+
+        if let Some(path) = listen_url.strip_prefix("unix://") {
+            let path = PathBuf::from(path);
+            if !path.is_absolute() {
+                return Err(AppServerTransportParseError::InvalidUnixSocketListenUrl(
+                    listen_url.to_string(),
+                ));
+            }
+            return Ok(Self::UnixSocket { path });
+        }
+
         Err(AppServerTransportParseError::UnsupportedListenUrl(
             listen_url.to_string(),
         ))
@@ -127,6 +200,155 @@ impl FromStr for AppServerTransport {
     }
 }
 
+/// Authentication and TLS settings for the WebSocket listeners.
+#[derive(Debug, Clone, Default, Eq, PartialEq, clap::Args)]
+pub struct AppServerListenOptions {
+    /// File holding a shared secret that WebSocket clients must send as
+    /// `Authorization: Bearer <secret>` during the handshake.
+    #[arg(long = "ws-auth-token-file", value_name = "FILE")]
+    pub ws_auth_token_file: Option<PathBuf>,
+
+    /// PEM certificate chain served by a `wss://` listener.
+    #[arg(long = "tls-cert", value_name = "FILE")]
+    pub tls_cert_file: Option<PathBuf>,
+
+    /// PEM private key for the `--tls-cert` certificate.
+    #[arg(long = "tls-key", value_name = "FILE")]
+    pub tls_key_file: Option<PathBuf>,
+}
+
+impl AppServerListenOptions {
+    /// Rejects WebSocket-only options for transports that would ignore them.
+    pub(crate) fn ensure_unused(&self) -> IoResult<()> {
+        if *self == Self::default() {
+            return Ok(());
+        }
+        Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            "--ws-auth-token-file, --tls-cert and --tls-key only apply to ws:// and wss:// listeners",
+        ))
+    }
+}
+
+/// Handshake requirements for accepted WebSocket connections.
+#[derive(Clone, Default)]
+pub(crate) struct WebSocketSecurity {
+    tls_acceptor: Option<TlsAcceptor>,
+    auth_token: Option<Arc<str>>,
+}
+
+impl WebSocketSecurity {
+    pub(crate) fn load(options: &AppServerListenOptions, tls: bool) -> IoResult<Self> {
+        let tls_acceptor = match (tls, &options.tls_cert_file, &options.tls_key_file) {
+            (true, Some(cert_file), Some(key_file)) => {
+                Some(load_tls_acceptor(cert_file, key_file)?)
+            }
+            (true, _, _) => {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "wss:// listeners require --tls-cert and --tls-key",
+                ));
+            }
+            (false, None, None) => None,
+            (false, _, _) => {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "--tls-cert and --tls-key require a wss:// listen URL",
+                ));
+            }
+        };
+        let auth_token = options
+            .ws_auth_token_file
+            .as_deref()
+            .map(read_auth_token)
+            .transpose()?;
+        Ok(Self {
+            tls_acceptor,
+            auth_token,
+        })
+    }
+}
+
+fn read_auth_token(path: &Path) -> IoResult<Arc<str>> {
+    let contents = std::fs::read_to_string(path).map_err(|err| {
+        std::io::Error::new(
+            err.kind(),
+            format!("failed to read {}: {err}", path.display()),
+        )
+    })?;
+    let token = contents.trim();
+    if token.is_empty() {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} does not contain an auth token", path.display()),
+        ));
+    }
+    Ok(Arc::from(token))
+}
+
+fn load_tls_acceptor(cert_file: &Path, key_file: &Path) -> IoResult<TlsAcceptor> {
+    let invalid_input = |path: &Path, err: &dyn std::fmt::Display| {
+        std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("failed to load {}: {err}", path.display()),
+        )
+    };
+    let certs = CertificateDer::pem_file_iter(cert_file)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .map_err(|err| invalid_input(cert_file, &err))?;
+    let key =
+        PrivateKeyDer::from_pem_file(key_file).map_err(|err| invalid_input(key_file, &err))?;
+
+    codex_utils_rustls_provider::ensure_rustls_crypto_provider();
+    let config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|err| invalid_input(cert_file, &err))?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Accepts the handshake when no token is configured or the request carries
+/// it as a bearer token; otherwise answers `401 Unauthorized`.
+#[allow(clippy::result_large_err)]
+fn authorize_websocket_handshake(
+    auth_token: Option<&str>,
+    request: &Request,
+    response: Response,
+) -> Result<Response, ErrorResponse> {
+    let Some(auth_token) = auth_token else {
+        return Ok(response);
+    };
+    let presented = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        // Auth schemes are case-insensitive (RFC 9110 section 11.1).
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+        .map(|(_, token)| token.trim_start());
+    if presented
+        .is_some_and(|presented| constant_time_eq(presented.as_bytes(), auth_token.as_bytes()))
+    {
+        return Ok(response);
+    }
+
+    let mut response = ErrorResponse::new(Some("Unauthorized".to_string()));
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    Err(response)
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0u8, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
 #[derive(Debug)]
 pub(crate) enum TransportEvent {
     ConnectionOpened {
@@ -271,13 +493,18 @@ pub(crate) async fn start_stdio_connection(
 
 pub(crate) async fn start_websocket_acceptor(
     bind_address: SocketAddr,
+    security: WebSocketSecurity,
     transport_event_tx: mpsc::Sender<TransportEvent>,
     shutdown_token: CancellationToken,
 ) -> IoResult<JoinHandle<()>> {
     let listener = TcpListener::bind(bind_address).await?;
     let local_addr = listener.local_addr()?;
-    print_websocket_startup_banner(local_addr);
-    info!("app-server websocket listening on ws://{local_addr}");
+    print_websocket_startup_banner(local_addr, &security);
+    info!(
+        tls = security.tls_acceptor.is_some(),
+        auth = security.auth_token.is_some(),
+        "app-server websocket listening on {local_addr}"
+    );
 
     let connection_counter = Arc::new(AtomicU64::new(1));
     Ok(tokio::spawn(async move {
@@ -294,13 +521,33 @@ pub(crate) async fn start_websocket_acceptor(
                             let connection_id =
                                 ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
                             let transport_event_tx_for_connection = transport_event_tx.clone();
+                            let WebSocketSecurity { tls_acceptor, auth_token } = security.clone();
                             tokio::spawn(async move {
-                                run_websocket_connection(
-                                    connection_id,
-                                    stream,
-                                    transport_event_tx_for_connection,
-                                )
-                                .await;
+                                match tls_acceptor {
+                                    Some(tls_acceptor) => match tls_acceptor.accept(stream).await {
+                                        Ok(stream) => {
+                                            run_websocket_connection(
+                                                connection_id,
+                                                stream,
+                                                auth_token,
+                                                transport_event_tx_for_connection,
+                                            )
+                                            .await;
+                                        }
+                                        Err(err) => {
+                                            warn!(%peer_addr, "failed to complete TLS handshake: {err}");
+                                        }
+                                    },
+                                    None => {
+                                        run_websocket_connection(
+                                            connection_id,
+                                            stream,
+                                            auth_token,
+                                            transport_event_tx_for_connection,
+                                        )
+                                        .await;
+                                    }
+                                }
                             });
                         }
                         Err(err) => {
@@ -313,13 +560,23 @@ pub(crate) async fn start_websocket_acceptor(
     }))
 }
 
-async fn run_websocket_connection(
+async fn run_websocket_connection<S>(
     connection_id: ConnectionId,
-    stream: TcpStream,
+    stream: S,
+    auth_token: Option<Arc<str>>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
-) {
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    // tungstenite's callback signature fixes the error type to a full response.
+    #[allow(clippy::result_large_err)]
+    let authorize = |request: &Request, response: Response| {
+        authorize_websocket_handshake(auth_token.as_deref(), request, response)
+    };
     let websocket_stream =
-        match accept_async_with_config(stream, Some(WebSocketConfig::default())).await {
+        match accept_hdr_async_with_config(stream, authorize, Some(WebSocketConfig::default()))
+            .await
+        {
             Ok(stream) => stream,
             Err(err) => {
                 warn!("failed to complete websocket handshake: {err}");
@@ -376,15 +633,14 @@ async fn run_websocket_connection(
         .await;
 }
 
-async fn run_websocket_outbound_loop(
-    mut websocket_writer: futures::stream::SplitSink<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-        WebSocketMessage,
-    >,
+async fn run_websocket_outbound_loop<S>(
+    mut websocket_writer: futures::stream::SplitSink<WebSocketStream<S>, WebSocketMessage>,
     mut writer_rx: mpsc::Receiver<OutgoingMessage>,
     mut writer_control_rx: mpsc::Receiver<WebSocketMessage>,
     disconnect_token: CancellationToken,
-) {
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     loop {
         tokio::select! {
             _ = disconnect_token.cancelled() => {
@@ -413,16 +669,16 @@ async fn run_websocket_outbound_loop(
     }
 }
 
-async fn run_websocket_inbound_loop(
-    mut websocket_reader: futures::stream::SplitStream<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-    >,
+async fn run_websocket_inbound_loop<S>(
+    mut websocket_reader: futures::stream::SplitStream<WebSocketStream<S>>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
     writer_tx_for_reader: mpsc::Sender<OutgoingMessage>,
     writer_control_tx: mpsc::Sender<WebSocketMessage>,
     connection_id: ConnectionId,
     disconnect_token: CancellationToken,
-) {
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     loop {
         tokio::select! {
             _ = disconnect_token.cancelled() => {
@@ -468,6 +724,207 @@ async fn run_websocket_inbound_loop(
     }
 }
 
+#[cfg(unix)]
+pub(crate) async fn start_unix_socket_acceptor(
+    path: PathBuf,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+    shutdown_token: CancellationToken,
+) -> IoResult<JoinHandle<()>> {
+    remove_stale_unix_socket(&path)?;
+    let listener = bind_owner_only_unix_socket(&path)?;
+    print_unix_socket_startup_banner(&path);
+    info!("app-server listening on unix://{}", path.display());
+
+    let connection_counter = Arc::new(AtomicU64::new(1));
+    Ok(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    info!("unix socket acceptor shutting down");
+                    break;
+                }
+                accept_result = listener.accept() => {
+                    match accept_result {
+                        Ok((stream, _)) => {
+                            let connection_id =
+                                ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
+                            info!(?connection_id, "unix socket client connected");
+                            let transport_event_tx_for_connection = transport_event_tx.clone();
+                            tokio::spawn(async move {
+                                run_unix_socket_connection(
+                                    connection_id,
+                                    stream,
+                                    transport_event_tx_for_connection,
+                                )
+                                .await;
+                            });
+                        }
+                        Err(err) => {
+                            error!("failed to accept unix socket connection: {err}");
+                        }
+                    }
+                }
+            }
+        }
+        if let Err(err) = std::fs::remove_file(&path) {
+            warn!("failed to remove unix socket {}: {err}", path.display());
+        }
+    }))
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn start_unix_socket_acceptor(
+    path: PathBuf,
+    _transport_event_tx: mpsc::Sender<TransportEvent>,
+    _shutdown_token: CancellationToken,
+) -> IoResult<JoinHandle<()>> {
+    Err(std::io::Error::new(
+        ErrorKind::Unsupported,
+        format!(
+            "cannot listen on unix://{}: unix sockets are not supported on this platform",
+            path.display()
+        ),
+    ))
+}
+
+/// Binds a socket at `path` that only its owner can connect to. The socket is
+/// created inside a fresh owner-only directory and restricted to `0600` there,
+/// so it is never reachable with the umask's permissions, then moved into place.
+#[cfg(unix)]
+fn bind_owner_only_unix_socket(path: &Path) -> IoResult<tokio::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some(parent) = path.parent() else {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} has no parent directory", path.display()),
+        ));
+    };
+    let staging_dir = tempfile::Builder::new()
+        .prefix(".codex-")
+        .permissions(std::fs::Permissions::from_mode(0o700))
+        .tempdir_in(parent)?;
+    let staged_path = staging_dir.path().join("sock");
+    let listener = tokio::net::UnixListener::bind(&staged_path)?;
+    std::fs::set_permissions(&staged_path, std::fs::Permissions::from_mode(0o600))?;
+    std::fs::rename(&staged_path, path)?;
+    Ok(listener)
+}
+
+/// Removes a socket file left behind by an app-server that is no longer
+/// running, refusing to touch anything else.
+#[cfg(unix)]
+fn remove_stale_unix_socket(path: &Path) -> IoResult<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(std::io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        ));
+    }
+    if std::os::unix::net::UnixStream::connect(path).is_ok() {
+        return Err(std::io::Error::new(
+            ErrorKind::AddrInUse,
+            format!("another process is already listening on {}", path.display()),
+        ));
+    }
+    std::fs::remove_file(path)
+}
+
+#[cfg(unix)]
+async fn run_unix_socket_connection(
+    connection_id: ConnectionId,
+    stream: tokio::net::UnixStream,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) {
+    let (writer_tx, mut writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
+    let writer_tx_for_reader = writer_tx.clone();
+    let disconnect_token = CancellationToken::new();
+    if transport_event_tx
+        .send(TransportEvent::ConnectionOpened {
+            connection_id,
+            writer: writer_tx,
+            disconnect_sender: Some(disconnect_token.clone()),
+        })
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let (reader, mut writer) = stream.into_split();
+    let outbound_disconnect_token = disconnect_token.clone();
+    let mut outbound_task = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = outbound_disconnect_token.cancelled() => break,
+                outgoing_message = writer_rx.recv() => {
+                    let Some(outgoing_message) = outgoing_message else {
+                        break;
+                    };
+                    let Some(mut json) = serialize_outgoing_message(outgoing_message) else {
+                        continue;
+                    };
+                    json.push('\n');
+                    if writer.write_all(json.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    let inbound_disconnect_token = disconnect_token.clone();
+    let transport_event_tx_for_reader = transport_event_tx.clone();
+    let mut inbound_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            tokio::select! {
+                _ = inbound_disconnect_token.cancelled() => break,
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        if !forward_incoming_message(
+                            &transport_event_tx_for_reader,
+                            &writer_tx_for_reader,
+                            connection_id,
+                            &line,
+                        )
+                        .await
+                        {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("unix socket receive error: {err}");
+                        break;
+                    }
+                },
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = &mut outbound_task => {
+            disconnect_token.cancel();
+            inbound_task.abort();
+        }
+        _ = &mut inbound_task => {
+            disconnect_token.cancel();
+            outbound_task.abort();
+        }
+    }
+
+    let _ = transport_event_tx
+        .send(TransportEvent::ConnectionClosed { connection_id })
+        .await;
+}
+
 async fn forward_incoming_message(
     transport_event_tx: &mpsc::Sender<TransportEvent>,
     writer: &mpsc::Sender<OutgoingMessage>,
@@ -702,7 +1159,7 @@ mod tests {
             .expect_err("hostname bind address should be rejected");
         assert_eq!(
             err.to_string(),
-            "invalid websocket --listen URL `ws://localhost:1234`; expected `ws://IP:PORT`"
+            "invalid websocket --listen URL `ws://localhost:1234`; expected `ws://IP:PORT` or `wss://IP:PORT`"
         );
     }
 
@@ -712,10 +1169,208 @@ mod tests {
             .expect_err("unsupported scheme should fail");
         assert_eq!(
             err.to_string(),
-            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://` or `ws://IP:PORT`"
+            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://`, `ws://IP:PORT`, `wss://IP:PORT` or `unix:///PATH`"
         );
     }
 
+    #[test]
+    fn app_server_transport_parses_secure_websocket_and_unix_socket_listen_urls() {
+        assert_eq!(
+            AppServerTransport::from_listen_url("wss://0.0.0.0:4500")
+                .expect("wss listen URL should parse"),
+            AppServerTransport::SecureWebSocket {
+                bind_address: "0.0.0.0:4500".parse().expect("valid socket address"),
+            }
+        );
+        assert_eq!(
+            AppServerTransport::from_listen_url("unix:///run/codex/app-server.sock")
+                .expect("unix listen URL should parse"),
+            AppServerTransport::UnixSocket {
+                path: PathBuf::from("/run/codex/app-server.sock"),
+            }
+        );
+    }
+
+    #[test]
+    fn app_server_transport_rejects_relative_unix_socket_path() {
+        let err = AppServerTransport::from_listen_url("unix://app-server.sock")
+            .expect_err("relative unix socket path should fail");
+        assert_eq!(
+            err,
+            AppServerTransportParseError::InvalidUnixSocketListenUrl(
+                "unix://app-server.sock".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn websocket_security_requires_matching_tls_options() {
+        let err = WebSocketSecurity::load(&AppServerListenOptions::default(), true)
+            .err()
+            .expect("wss without a certificate should fail");
+        assert_eq!(
+            err.to_string(),
+            "wss:// listeners require --tls-cert and --tls-key"
+        );
+
+        let options = AppServerListenOptions {
+            tls_cert_file: Some(PathBuf::from("cert.pem")),
+            tls_key_file: Some(PathBuf::from("key.pem")),
+            ..Default::default()
+        };
+        let err = WebSocketSecurity::load(&options, false)
+            .err()
+            .expect("ws with a certificate should fail");
+        assert_eq!(
+            err.to_string(),
+            "--tls-cert and --tls-key require a wss:// listen URL"
+        );
+        assert_eq!(
+            options.ensure_unused().map_err(|err| err.kind()),
+            Err(ErrorKind::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn websocket_security_reads_trimmed_auth_token() {
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let token_file = temp_dir.path().join("token");
+        std::fs::write(&token_file, "s3cret\n").expect("write token");
+        let options = AppServerListenOptions {
+            ws_auth_token_file: Some(token_file.clone()),
+            ..Default::default()
+        };
+
+        let security = WebSocketSecurity::load(&options, false).expect("load security");
+        assert_eq!(security.auth_token.as_deref(), Some("s3cret"));
+
+        std::fs::write(&token_file, " \n").expect("write empty token");
+        assert!(WebSocketSecurity::load(&options, false).is_err());
+    }
+
+    #[test]
+    fn websocket_handshake_requires_configured_bearer_token() {
+        let request_with = |authorization: Option<&str>| {
+            let mut builder = Request::builder().uri("ws://127.0.0.1:4500");
+            if let Some(authorization) = authorization {
+                builder = builder.header(AUTHORIZATION, authorization);
+            }
+            builder.body(()).expect("valid request")
+        };
+
+        assert!(
+            authorize_websocket_handshake(None, &request_with(None), Response::new(())).is_ok()
+        );
+        assert!(
+            authorize_websocket_handshake(
+                Some("s3cret"),
+                &request_with(Some("Bearer s3cret")),
+                Response::new(()),
+            )
+            .is_ok()
+        );
+        assert!(
+            authorize_websocket_handshake(
+                Some("s3cret"),
+                &request_with(Some("bearer s3cret")),
+                Response::new(()),
+            )
+            .is_ok()
+        );
+        for authorization in [
+            None,
+            Some("Bearer wrong"),
+            Some("s3cret"),
+            Some("Basic s3cret"),
+        ] {
+            let response = authorize_websocket_handshake(
+                Some("s3cret"),
+                &request_with(authorization),
+                Response::new(()),
+            )
+            .expect_err("handshake should be rejected");
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_socket_transport_exchanges_jsonl_with_owner_only_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = temp_dir.path().join("app-server.sock");
+        let (transport_event_tx, mut transport_event_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let shutdown_token = CancellationToken::new();
+        let accept_handle = start_unix_socket_acceptor(
+            socket_path.clone(),
+            transport_event_tx,
+            shutdown_token.clone(),
+        )
+        .await
+        .expect("start unix socket acceptor");
+        let mode = std::fs::metadata(&socket_path)
+            .expect("socket metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let stream = tokio::net::UnixStream::connect(&socket_path)
+            .await
+            .expect("connect to unix socket");
+        let (reader, mut writer) = stream.into_split();
+        writer
+            .write_all(b"{\"id\":1,\"method\":\"initialize\"}\n")
+            .await
+            .expect("write request");
+
+        let Some(TransportEvent::ConnectionOpened {
+            connection_id,
+            writer: outgoing_writer,
+            disconnect_sender,
+        }) = transport_event_rx.recv().await
+        else {
+            panic!("expected connection opened event");
+        };
+        assert!(disconnect_sender.is_some());
+        match transport_event_rx.recv().await {
+            Some(TransportEvent::IncomingMessage {
+                connection_id: message_connection_id,
+                message: JSONRPCMessage::Request(request),
+            }) => {
+                assert_eq!(message_connection_id, connection_id);
+                assert_eq!(request.method, "initialize");
+            }
+            _ => panic!("expected incoming request"),
+        }
+
+        outgoing_writer
+            .send(OutgoingMessage::Error(OutgoingError {
+                id: codex_app_server_protocol::RequestId::Integer(1),
+                error: JSONRPCErrorError {
+                    code: OVERLOADED_ERROR_CODE,
+                    message: "Server overloaded; retry later.".to_string(),
+                    data: None,
+                },
+            }))
+            .await
+            .expect("send response");
+        let line = timeout(
+            Duration::from_secs(5),
+            BufReader::new(reader).lines().next_line(),
+        )
+        .await
+        .expect("response should arrive")
+        .expect("read response")
+        .expect("response line");
+        let response: serde_json::Value = serde_json::from_str(&line).expect("valid json");
+        assert_eq!(response["id"], json!(1));
+
+        shutdown_token.cancel();
+        accept_handle.await.expect("acceptor should stop");
+        assert!(!socket_path.exists());
+    }
+
     #[tokio::test]
     async fn enqueue_incoming_request_returns_overload_error_when_queue_is_full() {
         let connection_id = ConnectionId(42);
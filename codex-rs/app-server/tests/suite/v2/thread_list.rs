@@ -14,6 +14,7 @@ use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::SessionSource;
 use codex_app_server_protocol::ThreadListResponse;
+use codex_app_server_protocol::ThreadSearchMatchKind;
 use codex_app_server_protocol::ThreadSortKey;
 use codex_app_server_protocol::ThreadSourceKind;
 use codex_app_server_protocol::ThreadStartParams;
@@ -79,6 +80,7 @@ async fn list_threads_with_sort(
             archived,
             cwd: None,
             search_term: None,
+            query: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -347,6 +349,7 @@ async fn thread_list_pagination_next_cursor_none_on_last_page() -> Result<()> {
     let ThreadListResponse {
         data: data1,
         next_cursor: cursor1,
+        ..
     } = list_threads(
         &mut mcp,
         None,
@@ -374,6 +377,7 @@ async fn thread_list_pagination_next_cursor_none_on_last_page() -> Result<()> {
     let ThreadListResponse {
         data: data2,
         next_cursor: cursor2,
+        ..
     } = list_threads(
         &mut mcp,
         Some(cursor1),
@@ -493,6 +497,7 @@ async fn thread_list_respects_cwd_filter() -> Result<()> {
             archived: None,
             cwd: Some(target_cwd.to_string_lossy().into_owned()),
             search_term: None,
+            query: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -575,6 +580,7 @@ sqlite = true
             archived: None,
             cwd: None,
             search_term: Some("needle".to_string()),
+            query: None,
         })
         .await?;
     let resp: JSONRPCResponse = timeout(
@@ -593,6 +599,115 @@ sqlite = true
     Ok(())
 }
 
+#[tokio::test]
+async fn thread_list_query_returns_search_matches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+suppress_unstable_features_warning = true
+
+[features]
+sqlite = true
+"#,
+    )?;
+
+    let first_match = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T10-00-00",
+        "2025-01-02T10:00:00Z",
+        "rotate the signing keys",
+        Some("mock_provider"),
+        None,
+    )?;
+    let _non_match = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T11-00-00",
+        "2025-01-02T11:00:00Z",
+        "no hit here",
+        Some("mock_provider"),
+        None,
+    )?;
+    let second_match = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "why did key rotation fail",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let state_db = codex_state::StateRuntime::init(
+        codex_home.path().to_path_buf(),
+        "mock_provider".into(),
+        None,
+    )
+    .await?;
+    state_db.mark_backfill_complete(None).await?;
+
+    let mut mcp = init_mcp(codex_home.path()).await?;
+    // A plain listing read-repairs the manually created rollouts into sqlite, which also
+    // indexes their contents.
+    list_threads(
+        &mut mcp,
+        None,
+        Some(10),
+        Some(vec!["mock_provider".to_string()]),
+        None,
+        None,
+    )
+    .await?;
+
+    let request_id = mcp
+        .send_thread_list_request(codex_app_server_protocol::ThreadListParams {
+            cursor: None,
+            limit: Some(10),
+            sort_key: None,
+            model_providers: Some(vec!["mock_provider".to_string()]),
+            source_kinds: None,
+            archived: None,
+            cwd: None,
+            search_term: None,
+            query: Some("rotat".to_string()),
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadListResponse {
+        data,
+        next_cursor,
+        search_matches,
+    } = to_response::<ThreadListResponse>(resp)?;
+
+    assert_eq!(next_cursor, None);
+    let mut ids: Vec<_> = data.iter().map(|thread| thread.id.clone()).collect();
+    let match_ids: Vec<_> = search_matches
+        .iter()
+        .map(|search_match| search_match.thread_id.clone())
+        .collect();
+    assert_eq!(match_ids, ids);
+    ids.sort();
+    let mut expected = vec![first_match, second_match];
+    expected.sort();
+    assert_eq!(ids, expected);
+    for search_match in &search_matches {
+        assert_eq!(search_match.kind, ThreadSearchMatchKind::UserMessage);
+        assert!(
+            search_match.snippet.contains("[rotate]")
+                || search_match.snippet.contains("[rotation]"),
+            "unexpected snippet: {}",
+            search_match.snippet
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn thread_list_empty_source_kinds_defaults_to_interactive_only() -> Result<()> {
     let codex_home = TempDir::new()?;
@@ -1418,6 +1533,7 @@ async fn thread_list_invalid_cursor_returns_error() -> Result<()> {
             archived: None,
             cwd: None,
             search_term: None,
+            query: None,
         })
         .await?;
     let error: JSONRPCError = timeout(
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::io;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use tree_sitter::Parser;
@@ -18,7 +20,7 @@ use crate::MaybeApplyPatchVerified;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::unified_diff_from_contents;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,6 +132,18 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_view(argv, cwd, &|path| Some(path.to_path_buf()))
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], but reads the current contents
+/// of each file from the path `view` maps it to, such as its copy in a
+/// copy-on-write overlay. `view` returns `None` for files that do not exist
+/// in the tree the patch will be applied to.
+pub fn maybe_parse_apply_patch_verified_with_view(
+    argv: &[String],
+    cwd: &Path,
+    view: &dyn Fn(&Path) -> Option<PathBuf>,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
     if let [body] = argv
@@ -164,11 +178,40 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
             for hunk in hunks {
                 let path = hunk.resolve_path(&effective_cwd);
                 match hunk {
-                    Hunk::AddFile { contents, .. } => {
-                        changes.insert(path, ApplyPatchFileChange::Add { content: contents });
+                    Hunk::AddFile { contents, mode, .. } => {
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Add {
+                                content: contents,
+                                mode,
+                            },
+                        );
+                    }
+                    Hunk::AddBinaryFile { contents, mode, .. } => {
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Binary {
+                                contents,
+                                replace: false,
+                                mode,
+                            },
+                        );
+                    }
+                    Hunk::ReplaceBinaryFile { contents, mode, .. } => {
+                        changes.insert(
+                            path,
+                            ApplyPatchFileChange::Binary {
+                                contents,
+                                replace: true,
+                                mode,
+                            },
+                        );
+                    }
+                    Hunk::AddSymlink { target, .. } => {
+                        changes.insert(path, ApplyPatchFileChange::Symlink { target });
                     }
                     Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
+                        let content = match read_to_string_through_view(view, &path) {
                             Ok(content) => content,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(
@@ -182,12 +225,43 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Delete { content });
                     }
                     Hunk::UpdateFile {
-                        move_path, chunks, ..
+                        move_path,
+                        chunks,
+                        mode,
+                        ..
                     } => {
+                        let update = if chunks.is_empty() {
+                            // A mode-only change leaves the contents alone, which may not be text.
+                            read_through_view(view, &path)
+                                .map(|contents| ApplyPatchFileUpdate {
+                                    unified_diff: String::new(),
+                                    content: String::from_utf8_lossy(&contents).into_owned(),
+                                })
+                                .map_err(|e| {
+                                    ApplyPatchError::IoError(IoError {
+                                        context: format!("Failed to read {}", path.display()),
+                                        source: e,
+                                    })
+                                })
+                        } else {
+                            read_to_string_through_view(view, &path)
+                                .map_err(|e| {
+                                    ApplyPatchError::IoError(IoError {
+                                        context: format!(
+                                            "Failed to read file to update {}",
+                                            path.display()
+                                        ),
+                                        source: e,
+                                    })
+                                })
+                                .and_then(|original| {
+                                    unified_diff_from_contents(&path, original, &chunks)
+                                })
+                        };
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        } = match update {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -199,6 +273,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                                 unified_diff,
                                 move_path: move_path.map(|p| effective_cwd.join(p)),
                                 new_content: contents,
+                                mode,
                             },
                         );
                     }
@@ -216,6 +291,25 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
     }
 }
 
+/// Reads the file at `path` as it appears through `view`.
+fn read_through_view(view: &dyn Fn(&Path) -> Option<PathBuf>, path: &Path) -> io::Result<Vec<u8>> {
+    match view(path) {
+        Some(content_path) => std::fs::read(content_path),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist", path.display()),
+        )),
+    }
+}
+
+fn read_to_string_through_view(
+    view: &dyn Fn(&Path) -> Option<PathBuf>,
+    path: &Path,
+) -> io::Result<String> {
+    String::from_utf8(read_through_view(view, path)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
 /// Extract the heredoc body (and optional `cd` workdir) from a `bash -lc` script
 /// that invokes the apply_patch tool using a heredoc.
 ///
@@ -371,6 +465,7 @@ fn extract_apply_patch_from_bash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::unified_diff_from_chunks;
     use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -424,6 +519,7 @@ mod tests {
         vec![Hunk::AddFile {
             path: PathBuf::from("foo"),
             contents: "hi\n".to_string(),
+            mode: None,
         }]
     }
 
@@ -489,7 +585,8 @@ mod tests {
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None,
                     }]
                 );
             }
@@ -514,7 +611,8 @@ mod tests {
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None,
                     }]
                 );
             }
@@ -554,7 +652,8 @@ PATCH"#,
                     hunks,
                     vec![Hunk::AddFile {
                         path: PathBuf::from("foo"),
-                        contents: "hi\n".to_string()
+                        contents: "hi\n".to_string(),
+                        mode: None,
                     }]
                 );
             }
@@ -756,6 +855,7 @@ PATCH"#,
                         .to_string(),
                         move_path: None,
                         new_content: "updated session directory content\n".to_string(),
+                        mode: None,
                     },
                 )]),
                 patch: argv[1].clone(),
@@ -810,4 +910,43 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_verified_patch_reads_files_through_the_view() {
+        let workspace = tempdir().unwrap();
+        let upper = tempdir().unwrap();
+        fs::write(workspace.path().join("edited.txt"), "workspace\n").unwrap();
+        fs::write(upper.path().join("edited.txt"), "overlay\n").unwrap();
+        fs::write(workspace.path().join("removed.txt"), "gone\n").unwrap();
+        let view = |path: &Path| match path.strip_prefix(workspace.path()) {
+            Ok(relative) if relative == Path::new("removed.txt") => None,
+            Ok(relative) if upper.path().join(relative).exists() => {
+                Some(upper.path().join(relative))
+            }
+            _ => Some(path.to_path_buf()),
+        };
+
+        let update = vec![
+            "apply_patch".to_string(),
+            wrap_patch("*** Update File: edited.txt\n@@\n-overlay\n+patched"),
+        ];
+        let action =
+            match maybe_parse_apply_patch_verified_with_view(&update, workspace.path(), &view) {
+                MaybeApplyPatchVerified::Body(action) => action,
+                other => panic!("expected verified body, got {other:?}"),
+            };
+        assert_matches!(
+            action.changes().get(&workspace.path().join("edited.txt")),
+            Some(ApplyPatchFileChange::Update { new_content, .. }) if new_content == "patched\n"
+        );
+
+        let delete = vec![
+            "apply_patch".to_string(),
+            wrap_patch("*** Delete File: removed.txt"),
+        ];
+        assert_matches!(
+            maybe_parse_apply_patch_verified_with_view(&delete, workspace.path(), &view),
+            MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::IoError(_))
+        );
+    }
 }
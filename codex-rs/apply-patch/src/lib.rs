@@ -2,23 +2,26 @@ mod invocation;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod transaction;
 
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
-use anyhow::Context;
 use anyhow::Result;
 pub use parser::Hunk;
 pub use parser::ParseError;
 use parser::ParseError::*;
 use parser::UpdateFileChunk;
 pub use parser::parse_patch;
+use parser::parse_patch_with_line_numbers;
 use similar::TextDiff;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_view;
 pub use standalone_executable::main;
+pub use transaction::HunkFailure;
 
 use crate::invocation::ExtractHeredocError;
 
@@ -43,6 +46,9 @@ pub enum ApplyPatchError {
     /// Error that occurs while computing replacements when applying patch chunks
     #[error("{0}")]
     ComputeReplacements(String),
+    /// One or more hunks did not apply to the current file contents, so no file was changed.
+    #[error("{}", transaction::format_hunk_failures(.0))]
+    HunkFailures(Vec<HunkFailure>),
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -95,6 +101,8 @@ pub struct ApplyPatchArgs {
 pub enum ApplyPatchFileChange {
     Add {
         content: String,
+        /// Unix permission bits the new file is created with.
+        mode: Option<u32>,
     },
     Delete {
         content: String,
@@ -104,6 +112,17 @@ pub enum ApplyPatchFileChange {
         move_path: Option<PathBuf>,
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
+        /// Unix permission bits to set. The diff is empty for a mode-only change.
+        mode: Option<u32>,
+    },
+    /// Adds or, when `replace` is set, overwrites a file with binary contents.
+    Binary {
+        contents: Vec<u8>,
+        replace: bool,
+        mode: Option<u32>,
+    },
+    Symlink {
+        target: PathBuf,
     },
 }
 
@@ -166,7 +185,13 @@ impl ApplyPatchAction {
 + {content}
 *** End Patch"#,
         );
-        let changes = HashMap::from([(path.to_path_buf(), ApplyPatchFileChange::Add { content })]);
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::Add {
+                content,
+                mode: None,
+            },
+        )]);
         #[expect(clippy::expect_used)]
         Self {
             changes,
@@ -185,8 +210,8 @@ pub fn apply_patch(
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
-    let hunks = match parse_patch(patch) {
-        Ok(source) => source.hunks,
+    let (hunks, line_numbers) = match parse_patch_with_line_numbers(patch) {
+        Ok((source, line_numbers)) => (source.hunks, line_numbers),
         Err(e) => {
             match &e {
                 InvalidPatchError(message) => {
@@ -207,25 +232,37 @@ pub fn apply_patch(
         }
     };
 
-    apply_hunks(&hunks, stdout, stderr)?;
+    apply_hunks_at_lines(&hunks, &line_numbers, stdout, stderr)?;
 
     Ok(())
 }
 
-/// Applies hunks and continues to update stdout/stderr
+/// Applies hunks and continues to update stdout/stderr. Either every hunk is applied or no file
+/// is changed.
 pub fn apply_hunks(
     hunks: &[Hunk],
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_hunks_at_lines(hunks, &[], stdout, stderr)
+}
+
+fn apply_hunks_at_lines(
+    hunks: &[Hunk],
+    line_numbers: &[usize],
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
     let _existing_paths: Vec<&Path> = hunks
         .iter()
         .filter_map(|hunk| match hunk {
-            Hunk::AddFile { .. } => {
+            Hunk::AddFile { .. } | Hunk::AddBinaryFile { .. } | Hunk::AddSymlink { .. } => {
                 // The file is being added, so it doesn't exist yet.
                 None
             }
-            Hunk::DeleteFile { path } => Some(path.as_path()),
+            Hunk::DeleteFile { path } | Hunk::ReplaceBinaryFile { path, .. } => {
+                Some(path.as_path())
+            }
             Hunk::UpdateFile {
                 path, move_path, ..
             } => match move_path {
@@ -244,8 +281,8 @@ pub fn apply_hunks(
         })
         .collect::<Vec<&Path>>();
 
-    // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks) {
+    // Delegate to a helper that stages every hunk and then applies them to the filesystem.
+    match transaction::apply_hunks_to_files(hunks, line_numbers) {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
@@ -253,7 +290,11 @@ pub fn apply_hunks(
         Err(err) => {
             let msg = err.to_string();
             writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
-            if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            if let Some(ApplyPatchError::HunkFailures(failures)) =
+                err.downcast_ref::<ApplyPatchError>()
+            {
+                Err(ApplyPatchError::HunkFailures(failures.clone()))
+            } else if let Some(io) = err.downcast_ref::<std::io::Error>() {
                 Err(ApplyPatchError::from(io))
             } else {
                 Err(ApplyPatchError::IoError(IoError {
@@ -265,8 +306,6 @@ pub fn apply_hunks(
     }
 }
 
-/// Applies each parsed patch hunk to the filesystem.
-/// Returns an error if any of the changes could not be applied.
 /// Tracks file paths affected by applying a patch.
 pub struct AffectedPaths {
     pub added: Vec<PathBuf>,
@@ -274,70 +313,6 @@ pub struct AffectedPaths {
     pub deleted: Vec<PathBuf>,
 }
 
-/// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
-fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
-    if hunks.is_empty() {
-        anyhow::bail!("No files were modified.");
-    }
-
-    let mut added: Vec<PathBuf> = Vec::new();
-    let mut modified: Vec<PathBuf> = Vec::new();
-    let mut deleted: Vec<PathBuf> = Vec::new();
-    for hunk in hunks {
-        match hunk {
-            Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
-                    && !parent.as_os_str().is_empty()
-                {
-                    std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
-                    })?;
-                }
-                std::fs::write(path, contents)
-                    .with_context(|| format!("Failed to write file {}", path.display()))?;
-                added.push(path.clone());
-            }
-            Hunk::DeleteFile { path } => {
-                std::fs::remove_file(path)
-                    .with_context(|| format!("Failed to delete file {}", path.display()))?;
-                deleted.push(path.clone());
-            }
-            Hunk::UpdateFile {
-                path,
-                move_path,
-                chunks,
-            } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
-                if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
-                        })?;
-                    }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
-                        .with_context(|| format!("Failed to remove original {}", path.display()))?;
-                    modified.push(dest.clone());
-                } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
-                    modified.push(path.clone());
-                }
-            }
-        }
-    }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
-}
-
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -358,7 +333,15 @@ fn derive_new_contents_from_chunks(
             }));
         }
     };
+    derive_new_contents(path, original_contents, chunks)
+}
 
+/// Apply the chunks to `original_contents`, the current contents of the file at `path`.
+fn derive_new_contents(
+    path: &Path,
+    original_contents: String,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
 
     // Drop the trailing empty element that results from the final newline so
@@ -520,16 +503,34 @@ pub fn unified_diff_from_chunks_with_context(
     chunks: &[UpdateFileChunk],
     context: usize,
 ) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
-    let AppliedPatch {
+    let applied = derive_new_contents_from_chunks(path, chunks)?;
+    Ok(unified_diff_from_applied_patch(applied, context))
+}
+
+/// Like [`unified_diff_from_chunks`], but applies the chunks to
+/// `original_contents` instead of reading the file at `path`.
+pub(crate) fn unified_diff_from_contents(
+    path: &Path,
+    original_contents: String,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    let applied = derive_new_contents(path, original_contents, chunks)?;
+    Ok(unified_diff_from_applied_patch(applied, 1))
+}
+
+fn unified_diff_from_applied_patch(
+    AppliedPatch {
         original_contents,
         new_contents,
-    } = derive_new_contents_from_chunks(path, chunks)?;
+    }: AppliedPatch,
+    context: usize,
+) -> ApplyPatchFileUpdate {
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
-    Ok(ApplyPatchFileUpdate {
+    ApplyPatchFileUpdate {
         unified_diff,
         content: new_contents,
-    })
+    }
 }
 
 /// Print the summary of changes in git-style format.
@@ -554,6 +555,7 @@ pub fn print_summary(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
     use std::string::ToString;
@@ -1052,6 +1054,199 @@ g
         );
     }
 
+    #[test]
+    fn test_hunks_apply_on_top_of_earlier_hunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("staged.txt");
+        let patch = wrap_patch(&format!(
+            "*** Add File: {0}\n+one\n*** Update File: {0}\n@@\n-one\n+two",
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two\n");
+    }
+
+    #[test]
+    fn test_failed_hunks_are_all_reported_and_nothing_is_written() {
+        let dir = tempdir().unwrap();
+        let updated = dir.path().join("updated.txt");
+        let missing = dir.path().join("missing.txt");
+        let added = dir.path().join("added.txt");
+        fs::write(&updated, "keep\n").unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-keep\n+changed\n*** Update File: {}\n@@\n-a\n+b\n*** Add File: {}\n+new\n*** Update File: {}\n@@\n-absent\n+b",
+            updated.display(),
+            missing.display(),
+            added.display(),
+            updated.display(),
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let err = apply_patch(&patch, &mut stdout, &mut stderr).unwrap_err();
+
+        let ApplyPatchError::HunkFailures(failures) = err else {
+            panic!("expected hunk failures, got {err:?}");
+        };
+        assert_eq!(
+            failures
+                .iter()
+                .map(|failure| (failure.hunk_number, failure.line_number))
+                .collect::<Vec<_>>(),
+            vec![(2, Some(6)), (4, Some(12))]
+        );
+        assert_eq!(fs::read_to_string(&updated).unwrap(), "keep\n");
+        assert!(!added.exists());
+        assert_eq!(String::from_utf8(stdout).unwrap(), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_update_keeps_file_mode_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("script.sh");
+        let link = dir.path().join("link.sh");
+        fs::write(&script, "echo old\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink(&script, &link).unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-echo old\n+echo new",
+            link.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&script).unwrap(), "echo new\n");
+        assert_eq!(
+            fs::metadata(&script).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_staged_changes_share_a_key_across_symlinked_spellings() {
+        let dir = tempdir().unwrap();
+        let real_dir = dir.path().join("real");
+        let alias_dir = dir.path().join("alias");
+        fs::create_dir(&real_dir).unwrap();
+        std::os::unix::fs::symlink(&real_dir, &alias_dir).unwrap();
+        fs::write(real_dir.join("file.txt"), "old\n").unwrap();
+        std::os::unix::fs::symlink("file.txt", real_dir.join("link.txt")).unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Delete File: {}\n*** Update File: {}\n@@\n-old\n+new",
+            alias_dir.join("file.txt").display(),
+            alias_dir.join("link.txt").display(),
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert_matches!(
+            result,
+            Err(ApplyPatchError::HunkFailures(failures))
+                if failures.len() == 1 && failures[0].message.contains("deleted earlier")
+        );
+        assert_eq!(
+            fs::read_to_string(real_dir.join("file.txt")).unwrap(),
+            "old\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_mode_binary_files_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("script.sh");
+        let data = dir.path().join("data.bin");
+        fs::write(&script, "echo hi\n").unwrap();
+        fs::write(&data, [0xff, 0xfe]).unwrap();
+        let patch = wrap_patch(&format!(
+            "*** Update File: {script}\n\
+             *** Set Mode: 755\n\
+             *** Replace Binary File: {data}\n\
+             +AAEC\n\
+             *** Add Binary File: {logo}\n\
+             *** Set Mode: 600\n\
+             +/w==\n\
+             *** Add Symlink: {link}\n\
+             *** Link to: script.sh",
+            script = script.display(),
+            data = data.display(),
+            logo = dir.path().join("assets/logo.bin").display(),
+            link = dir.path().join("latest").display(),
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(fs::read_to_string(&script).unwrap(), "echo hi\n");
+        assert_eq!(mode(&script), 0o755);
+        assert_eq!(fs::read(&data).unwrap(), vec![0, 1, 2]);
+        let logo = dir.path().join("assets/logo.bin");
+        assert_eq!(fs::read(&logo).unwrap(), vec![0xff]);
+        assert_eq!(mode(&logo), 0o600);
+        assert_eq!(
+            fs::read_link(dir.path().join("latest")).unwrap(),
+            PathBuf::from("script.sh")
+        );
+    }
+
+    #[test]
+    fn test_set_mode_rejects_bits_beyond_permissions() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("setuid.sh");
+        let hunks = vec![Hunk::AddFile {
+            path: path.clone(),
+            contents: "echo hi\n".to_string(),
+            mode: Some(0o4755),
+        }];
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_hunks(&hunks, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_replace_binary_file_requires_existing_file() {
+        let dir = tempdir().unwrap();
+        let added = dir.path().join("added.txt");
+        let patch = wrap_patch(&format!(
+            "*** Add File: {}\n+hi\n*** Replace Binary File: {}\n+AA==",
+            added.display(),
+            dir.path().join("missing.bin").display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert_matches!(result, Err(ApplyPatchError::HunkFailures(failures)) if failures.len() == 1);
+        assert!(!added.exists());
+    }
+
     #[test]
     fn test_apply_patch_fails_on_write_error() {
         let dir = tempdir().unwrap();
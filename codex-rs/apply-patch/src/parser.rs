@@ -7,13 +7,20 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
-//! add_hunk: "*** Add File: " filename LF add_line+
+//! hunk: add_hunk | delete_hunk | update_hunk | add_binary_hunk | replace_binary_hunk
+//!     | add_symlink_hunk
+//! add_hunk: "*** Add File: " filename LF set_mode? add_line+
 //! delete_hunk: "*** Delete File: " filename LF
-//! update_hunk: "*** Update File: " filename LF change_move? change?
+//! update_hunk: "*** Update File: " filename LF change_move? set_mode? change?
+//! add_binary_hunk: "*** Add Binary File: " filename LF set_mode? base64_line*
+//! replace_binary_hunk: "*** Replace Binary File: " filename LF set_mode? base64_line*
+//! add_symlink_hunk: "*** Add Symlink: " filename LF link_to
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
 //!
+//! set_mode: "*** Set Mode: " /[0-7]{3}/ LF
+//! base64_line: "+" /[A-Za-z0-9+\/=]+/ LF
+//! link_to: "*** Link to: " filename LF
 //! change_move: "*** Move to: " filename LF
 //! change: (change_context | change_line)+ eof_line?
 //! change_context: ("@@" | "@@ " /(.+)/) LF
@@ -23,6 +30,8 @@
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -33,7 +42,12 @@ const END_PATCH_MARKER: &str = "*** End Patch";
 const ADD_FILE_MARKER: &str = "*** Add File: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const REPLACE_BINARY_FILE_MARKER: &str = "*** Replace Binary File: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
+const SET_MODE_MARKER: &str = "*** Set Mode: ";
+const LINK_TO_MARKER: &str = "*** Link to: ";
 const EOF_MARKER: &str = "*** End of File";
 const CHANGE_CONTEXT_MARKER: &str = "@@ ";
 const EMPTY_CHANGE_CONTEXT_MARKER: &str = "@@";
@@ -61,6 +75,8 @@ pub enum Hunk {
     AddFile {
         path: PathBuf,
         contents: String,
+        /// Unix permission bits from `*** Set Mode:`, e.g. `0o755`.
+        mode: Option<u32>,
     },
     DeleteFile {
         path: PathBuf,
@@ -70,8 +86,27 @@ pub enum Hunk {
         move_path: Option<PathBuf>,
 
         /// Chunks should be in order, i.e. the `change_context` of one chunk
-        /// should occur later in the file than the previous chunk.
+        /// should occur later in the file than the previous chunk. Empty when
+        /// the hunk only changes the file mode.
         chunks: Vec<UpdateFileChunk>,
+        mode: Option<u32>,
+    },
+    /// Creates a file from base64-encoded contents.
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+        mode: Option<u32>,
+    },
+    /// Overwrites an existing file with base64-encoded contents.
+    ReplaceBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+        mode: Option<u32>,
+    },
+    AddSymlink {
+        path: PathBuf,
+        /// Stored in the link as written in the patch, so relative targets stay relative.
+        target: PathBuf,
     },
 }
 
@@ -81,6 +116,9 @@ impl Hunk {
             Hunk::AddFile { path, .. } => cwd.join(path),
             Hunk::DeleteFile { path } => cwd.join(path),
             Hunk::UpdateFile { path, .. } => cwd.join(path),
+            Hunk::AddBinaryFile { path, .. } => cwd.join(path),
+            Hunk::ReplaceBinaryFile { path, .. } => cwd.join(path),
+            Hunk::AddSymlink { path, .. } => cwd.join(path),
         }
     }
 }
@@ -112,6 +150,18 @@ pub fn parse_patch(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
     parse_patch_text(patch, mode)
 }
 
+/// Like [`parse_patch`], but also returns the patch line each hunk header is on.
+pub(crate) fn parse_patch_with_line_numbers(
+    patch: &str,
+) -> Result<(ApplyPatchArgs, Vec<usize>), ParseError> {
+    let mode = if PARSE_IN_STRICT_MODE {
+        ParseMode::Strict
+    } else {
+        ParseMode::Lenient
+    };
+    parse_patch_text_with_line_numbers(patch, mode)
+}
+
 enum ParseMode {
     /// Parse the patch text argument as is.
     Strict,
@@ -152,6 +202,13 @@ enum ParseMode {
 }
 
 fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, ParseError> {
+    parse_patch_text_with_line_numbers(patch, mode).map(|(args, _)| args)
+}
+
+fn parse_patch_text_with_line_numbers(
+    patch: &str,
+    mode: ParseMode,
+) -> Result<(ApplyPatchArgs, Vec<usize>), ParseError> {
     let lines: Vec<&str> = patch.trim().lines().collect();
     let lines: &[&str] = match check_patch_boundaries_strict(&lines) {
         Ok(()) => &lines,
@@ -164,6 +221,7 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
     };
 
     let mut hunks: Vec<Hunk> = Vec::new();
+    let mut hunk_line_numbers: Vec<usize> = Vec::new();
     // The above checks ensure that lines.len() >= 2.
     let last_line_index = lines.len().saturating_sub(1);
     let mut remaining_lines = &lines[1..last_line_index];
@@ -171,15 +229,19 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
     while !remaining_lines.is_empty() {
         let (hunk, hunk_lines) = parse_one_hunk(remaining_lines, line_number)?;
         hunks.push(hunk);
+        hunk_line_numbers.push(line_number);
         line_number += hunk_lines;
         remaining_lines = &remaining_lines[hunk_lines..]
     }
     let patch = lines.join("\n");
-    Ok(ApplyPatchArgs {
-        hunks,
-        patch,
-        workdir: None,
-    })
+    Ok((
+        ApplyPatchArgs {
+            hunks,
+            patch,
+            workdir: None,
+        },
+        hunk_line_numbers,
+    ))
 }
 
 /// Checks the start and end lines of the patch text for `apply_patch`,
@@ -251,8 +313,9 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
     if let Some(path) = first_line.strip_prefix(ADD_FILE_MARKER) {
         // Add File
         let mut contents = String::new();
-        let mut parsed_lines = 1;
-        for add_line in &lines[1..] {
+        let (mode, mut parsed_lines) = parse_set_mode(&lines[1..], line_number + 1)?;
+        parsed_lines += 1;
+        for add_line in &lines[parsed_lines..] {
             if let Some(line_to_add) = add_line.strip_prefix('+') {
                 contents.push_str(line_to_add);
                 contents.push('\n');
@@ -265,6 +328,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             AddFile {
                 path: PathBuf::from(path),
                 contents,
+                mode,
             },
             parsed_lines,
         ));
@@ -291,6 +355,10 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             parsed_lines += 1;
         }
 
+        let (mode, mode_lines) = parse_set_mode(remaining_lines, line_number + parsed_lines)?;
+        remaining_lines = &remaining_lines[mode_lines..];
+        parsed_lines += mode_lines;
+
         let mut chunks = Vec::new();
         // NOTE: we need to know to stop once we reach the next special marker header.
         while !remaining_lines.is_empty() {
@@ -315,7 +383,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             remaining_lines = &remaining_lines[chunk_lines..]
         }
 
-        if chunks.is_empty() {
+        if chunks.is_empty() && mode.is_none() {
             return Err(InvalidHunkError {
                 message: format!("Update file hunk for path '{path}' is empty"),
                 line_number,
@@ -327,19 +395,114 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
                 path: PathBuf::from(path),
                 move_path: move_path.map(PathBuf::from),
                 chunks,
+                mode,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        let (contents, mode, parsed_lines) = parse_binary_contents(lines, line_number)?;
+        return Ok((
+            AddBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+                mode,
+            },
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(REPLACE_BINARY_FILE_MARKER) {
+        let (contents, mode, parsed_lines) = parse_binary_contents(lines, line_number)?;
+        return Ok((
+            ReplaceBinaryFile {
+                path: PathBuf::from(path),
+                contents,
+                mode,
             },
             parsed_lines,
         ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        let Some(target) = lines
+            .get(1)
+            .and_then(|line| line.trim().strip_prefix(LINK_TO_MARKER))
+            .filter(|target| !target.is_empty())
+        else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Add symlink hunk for path '{path}' must be followed by '*** Link to: {{target}}'"
+                ),
+                line_number,
+            });
+        };
+        return Ok((
+            AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            },
+            2,
+        ));
     }
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}', '*** Add Binary File: {{path}}', '*** Replace Binary File: {{path}}', '*** Add Symlink: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Parses an optional `*** Set Mode: <octal>` line at the start of `lines`. Returns the mode and
+/// the number of lines consumed. Only permission bits are accepted; setuid, setgid and sticky
+/// bits are rejected.
+fn parse_set_mode(lines: &[&str], line_number: usize) -> Result<(Option<u32>, usize), ParseError> {
+    let Some(raw) = lines
+        .first()
+        .and_then(|line| line.trim().strip_prefix(SET_MODE_MARKER))
+    else {
+        return Ok((None, 0));
+    };
+    let raw = raw.trim();
+    let mode = if (3..=4).contains(&raw.len()) {
+        u32::from_str_radix(raw, 8)
+            .ok()
+            .filter(|mode| mode & !0o777 == 0)
+    } else {
+        None
+    };
+    match mode {
+        Some(mode) => Ok((Some(mode), 1)),
+        None => Err(InvalidHunkError {
+            message: format!(
+                "Invalid file mode '{raw}'. Expected octal permission bits such as 644 or 755"
+            ),
+            line_number,
+        }),
+    }
+}
+
+/// Parses the body of an add or replace binary file hunk: an optional `*** Set Mode:` line
+/// followed by `+` lines whose concatenation is base64. `lines[0]` is the hunk header.
+fn parse_binary_contents(
+    lines: &[&str],
+    line_number: usize,
+) -> Result<(Vec<u8>, Option<u32>, usize), ParseError> {
+    let (mode, mode_lines) = parse_set_mode(&lines[1..], line_number + 1)?;
+    let mut parsed_lines = 1 + mode_lines;
+    let mut encoded = String::new();
+    for line in &lines[parsed_lines..] {
+        let Some(chunk) = line.strip_prefix('+') else {
+            break;
+        };
+        encoded.push_str(chunk.trim());
+        parsed_lines += 1;
+    }
+    let contents = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(|err| InvalidHunkError {
+            message: format!("Binary file contents are not valid base64: {err}"),
+            line_number,
+        })?;
+    Ok((contents, mode, parsed_lines))
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -463,7 +626,8 @@ fn test_parse_patch() {
         .hunks,
         vec![AddFile {
             path: PathBuf::from("foo"),
-            contents: "hi\n".to_string()
+            contents: "hi\n".to_string(),
+            mode: None,
         }]
     );
     assert_eq!(
@@ -508,7 +672,8 @@ fn test_parse_patch() {
         vec![
             AddFile {
                 path: PathBuf::from("path/add.py"),
-                contents: "abc\ndef\n".to_string()
+                contents: "abc\ndef\n".to_string(),
+                mode: None,
             },
             DeleteFile {
                 path: PathBuf::from("path/delete.py")
@@ -521,7 +686,8 @@ fn test_parse_patch() {
                     old_lines: vec!["    pass".to_string()],
                     new_lines: vec!["    return 123".to_string()],
                     is_end_of_file: false
-                }]
+                }],
+                mode: None,
             }
         ]
     );
@@ -549,10 +715,12 @@ fn test_parse_patch() {
                     new_lines: vec!["line".to_string()],
                     is_end_of_file: false
                 }],
+                mode: None,
             },
             AddFile {
                 path: PathBuf::from("other.py"),
-                contents: "content\n".to_string()
+                contents: "content\n".to_string(),
+                mode: None,
             }
         ]
     );
@@ -579,6 +747,7 @@ fn test_parse_patch() {
                 new_lines: vec!["import foo".to_string(), "bar".to_string()],
                 is_end_of_file: false,
             }],
+            mode: None,
         }]
     );
 }
@@ -599,6 +768,7 @@ fn test_parse_patch_lenient() {
             new_lines: vec!["import foo".to_string(), "bar".to_string()],
             is_end_of_file: false,
         }],
+        mode: None,
     }];
     let expected_error =
         InvalidPatchError("The first line of the patch must be '*** Begin Patch'".to_string());
@@ -675,13 +845,121 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], 234),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', \
+            '*** Add Binary File: {path}', '*** Replace Binary File: {path}', '*** Add Symlink: {path}'".to_string(),
             line_number: 234
         })
     );
     // Other edge cases are already covered by tests above/below.
 }
 
+#[test]
+fn test_parse_modes_binary_files_and_symlinks() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add File: run.sh\n\
+             *** Set Mode: 755\n\
+             +echo hi\n\
+             *** Update File: tool.sh\n\
+             *** Set Mode: 0644\n\
+             *** Add Binary File: logo.bin\n\
+             +AAEC\n\
+             +/w==\n\
+             *** Replace Binary File: data.bin\n\
+             *** Set Mode: 600\n\
+             +AA==\n\
+             *** Add Symlink: latest\n\
+             *** Link to: releases/v2\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            AddFile {
+                path: PathBuf::from("run.sh"),
+                contents: "echo hi\n".to_string(),
+                mode: Some(0o755),
+            },
+            UpdateFile {
+                path: PathBuf::from("tool.sh"),
+                move_path: None,
+                chunks: Vec::new(),
+                mode: Some(0o644),
+            },
+            AddBinaryFile {
+                path: PathBuf::from("logo.bin"),
+                contents: vec![0, 1, 2, 255],
+                mode: None,
+            },
+            ReplaceBinaryFile {
+                path: PathBuf::from("data.bin"),
+                contents: vec![0],
+                mode: Some(0o600),
+            },
+            AddSymlink {
+                path: PathBuf::from("latest"),
+                target: PathBuf::from("releases/v2"),
+            },
+        ]
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Update File: run.sh\n\
+             *** Set Mode: rwx\n\
+             *** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "Invalid file mode 'rwx'. Expected octal permission bits such as 644 or 755"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Update File: run.sh\n\
+             *** Set Mode: 4755\n\
+             *** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "Invalid file mode '4755'. Expected octal permission bits such as 644 or 755"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add Binary File: logo.bin\n\
+             +not base64!\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .map(|args| args.hunks.len())
+        .map_err(|err| matches!(err, InvalidHunkError { line_number: 2, .. })),
+        Err(true)
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add Symlink: latest\n\
+             *** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message:
+                "Add symlink hunk for path 'latest' must be followed by '*** Link to: {target}'"
+                    .to_string(),
+            line_number: 2,
+        })
+    );
+}
+
 #[test]
 fn test_update_file_chunk() {
     assert_eq!(
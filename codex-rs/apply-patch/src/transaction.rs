@@ -0,0 +1,519 @@
+//! Applies a patch as a single transaction.
+//!
+//! Every hunk is first checked against the current contents of the files it touches (including
+//! changes staged by earlier hunks of the same patch). Only when all hunks succeed are the new
+//! contents written to temp files next to their targets and renamed into place. If any step of
+//! that commit fails, the files already replaced are restored so the worktree is left as it was.
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use anyhow::Context;
+
+use crate::AffectedPaths;
+use crate::AppliedPatch;
+use crate::ApplyPatchError;
+use crate::Hunk;
+use crate::derive_new_contents;
+
+/// A hunk that could not be applied against the current file contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkFailure {
+    /// 1-based position of the hunk within the patch.
+    pub hunk_number: usize,
+    /// Line of the hunk header in the patch text, when known.
+    pub line_number: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for HunkFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line_number {
+            Some(line_number) => write!(f, "line {line_number}: {}", self.message),
+            None => write!(f, "hunk {}: {}", self.hunk_number, self.message),
+        }
+    }
+}
+
+pub(crate) fn format_hunk_failures(failures: &[HunkFailure]) -> String {
+    failures
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Stage every hunk, then commit the result. `line_numbers[i]` is the patch line of `hunks[i]`
+/// and may be empty when the hunks did not come from patch text.
+pub(crate) fn apply_hunks_to_files(
+    hunks: &[Hunk],
+    line_numbers: &[usize],
+) -> anyhow::Result<AffectedPaths> {
+    if hunks.is_empty() {
+        anyhow::bail!("No files were modified.");
+    }
+
+    let mut staged = StagedChanges::default();
+    let mut failures = Vec::new();
+    let mut added: Vec<PathBuf> = Vec::new();
+    let mut modified: Vec<PathBuf> = Vec::new();
+    let mut deleted: Vec<PathBuf> = Vec::new();
+    for (index, hunk) in hunks.iter().enumerate() {
+        let staged_hunk = match hunk {
+            Hunk::AddFile {
+                path,
+                contents,
+                mode,
+            } => staged
+                .write(path, contents.clone().into_bytes(), *mode)
+                .map(|()| added.push(path.clone())),
+            Hunk::AddBinaryFile {
+                path,
+                contents,
+                mode,
+            } => staged
+                .write(path, contents.clone(), *mode)
+                .map(|()| added.push(path.clone())),
+            Hunk::ReplaceBinaryFile {
+                path,
+                contents,
+                mode,
+            } => staged
+                .read_bytes(path)
+                .and_then(|_| staged.write(path, contents.clone(), *mode))
+                .map(|()| modified.push(path.clone())),
+            Hunk::AddSymlink { path, target } => staged
+                .symlink(path, target)
+                .map(|()| added.push(path.clone())),
+            Hunk::DeleteFile { path } => staged.remove(path).map(|()| deleted.push(path.clone())),
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+                mode,
+            } => {
+                // A mode-only update keeps the contents byte for byte, so it also works on
+                // binary files.
+                let new_contents = if chunks.is_empty() {
+                    staged.read_bytes(path)
+                } else {
+                    staged.read_for_update(path).and_then(|original_contents| {
+                        derive_new_contents(path, original_contents, chunks)
+                            .map(|AppliedPatch { new_contents, .. }| new_contents.into_bytes())
+                            .map_err(|err| err.to_string())
+                    })
+                };
+                new_contents.and_then(|new_contents| {
+                    match move_path {
+                        Some(dest) if dest != path => {
+                            staged.write(dest, new_contents, *mode)?;
+                            staged.remove(path)?;
+                            modified.push(dest.clone());
+                        }
+                        _ => {
+                            staged.write(path, new_contents, *mode)?;
+                            modified.push(path.clone());
+                        }
+                    }
+                    Ok(())
+                })
+            }
+        };
+        if let Err(message) = staged_hunk {
+            failures.push(HunkFailure {
+                hunk_number: index + 1,
+                line_number: line_numbers.get(index).copied(),
+                message,
+            });
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(ApplyPatchError::HunkFailures(failures).into());
+    }
+
+    staged.commit()?;
+    Ok(AffectedPaths {
+        added,
+        modified,
+        deleted,
+    })
+}
+
+enum StagedChange {
+    Write {
+        contents: Vec<u8>,
+        /// Permission bits to set. `None` keeps the mode of the file being replaced.
+        mode: Option<u32>,
+    },
+    Symlink(PathBuf),
+    Remove,
+}
+
+/// Final state of every path the patch touches, in the order the paths were first touched.
+#[derive(Default)]
+struct StagedChanges {
+    order: Vec<PathBuf>,
+    changes: HashMap<PathBuf, StagedChange>,
+}
+
+impl StagedChanges {
+    fn stage(&mut self, path: PathBuf, change: StagedChange) {
+        if !self.changes.contains_key(&path) {
+            self.order.push(path.clone());
+        }
+        self.changes.insert(path, change);
+    }
+
+    fn read_for_update(&self, path: &Path) -> Result<String, String> {
+        let contents = self.read_bytes(path)?;
+        String::from_utf8(contents).map_err(|_| {
+            format!(
+                "Failed to read file to update {}: it is not valid UTF-8",
+                path.display()
+            )
+        })
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, String> {
+        match self.changes.get(&write_target(path)) {
+            Some(StagedChange::Write { contents, .. }) => Ok(contents.clone()),
+            Some(StagedChange::Symlink(_)) => Err(format!(
+                "Failed to read file to update {}: it is a symlink added earlier in the patch",
+                path.display()
+            )),
+            Some(StagedChange::Remove) => Err(format!(
+                "Failed to read file to update {}: it is deleted earlier in the patch",
+                path.display()
+            )),
+            None => fs::read(path)
+                .map_err(|err| format!("Failed to read file to update {}: {err}", path.display())),
+        }
+    }
+
+    fn write(&mut self, path: &Path, contents: Vec<u8>, mode: Option<u32>) -> Result<(), String> {
+        let target = write_target(path);
+        if !self.changes.contains_key(&target) {
+            match fs::metadata(&target) {
+                Ok(metadata) if metadata.is_dir() => {
+                    return Err(format!(
+                        "Failed to write file {}: it is a directory",
+                        path.display()
+                    ));
+                }
+                // Opening without truncating checks that the later rename replaces a file the
+                // caller could have written in place.
+                Ok(_) => {
+                    OpenOptions::new()
+                        .write(true)
+                        .open(&target)
+                        .map_err(|err| format!("Failed to write file {}: {err}", path.display()))?;
+                }
+                Err(_) => {}
+            }
+        }
+        self.stage(target, StagedChange::Write { contents, mode });
+        Ok(())
+    }
+
+    /// The link itself is staged at `path`; an existing file or link there is replaced.
+    fn symlink(&mut self, path: &Path, target: &Path) -> Result<(), String> {
+        if !cfg!(unix) {
+            return Err(format!(
+                "Failed to add symlink {}: symlinks are only supported on Unix",
+                path.display()
+            ));
+        }
+        let key = entry_key(path);
+        if !self.changes.contains_key(&key)
+            && fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(format!(
+                "Failed to add symlink {}: it is a directory",
+                path.display()
+            ));
+        }
+        self.stage(key, StagedChange::Symlink(target.to_path_buf()));
+        Ok(())
+    }
+
+    fn remove(&mut self, path: &Path) -> Result<(), String> {
+        let key = entry_key(path);
+        let exists = match self.changes.get(&key) {
+            Some(StagedChange::Write { .. } | StagedChange::Symlink(_)) => true,
+            Some(StagedChange::Remove) => false,
+            None => fs::symlink_metadata(path).is_ok_and(|metadata| !metadata.is_dir()),
+        };
+        if !exists {
+            return Err(format!("Failed to delete file {}", path.display()));
+        }
+        self.stage(key, StagedChange::Remove);
+        Ok(())
+    }
+
+    fn commit(mut self) -> anyhow::Result<()> {
+        let mut created_dirs = Vec::new();
+        let mut temp_files = HashMap::new();
+        for path in &self.order {
+            let temp_file = match self.changes.get(path) {
+                Some(StagedChange::Write { contents, mode }) => {
+                    create_parent_dirs(path, &mut created_dirs)
+                        .and_then(|()| TempFile::write_next_to(path, contents, *mode))
+                }
+                Some(StagedChange::Symlink(target)) => create_parent_dirs(path, &mut created_dirs)
+                    .and_then(|()| TempFile::symlink_next_to(path, target)),
+                Some(StagedChange::Remove) | None => continue,
+            };
+            match temp_file {
+                Ok(temp_file) => {
+                    temp_files.insert(path.clone(), temp_file);
+                }
+                Err(err) => {
+                    drop(temp_files);
+                    remove_created_dirs(&created_dirs);
+                    return Err(err);
+                }
+            }
+        }
+
+        let mut committed: Vec<CommittedPath> = Vec::new();
+        for path in std::mem::take(&mut self.order) {
+            if let Err(err) = commit_path(&path, temp_files.remove(&path), &mut committed) {
+                let unrestored = roll_back(committed);
+                remove_created_dirs(&created_dirs);
+                if unrestored.is_empty() {
+                    return Err(err);
+                }
+                let unrestored = unrestored
+                    .iter()
+                    .map(|backup| backup.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                anyhow::bail!("{err}; originals could not be restored from {unrestored}");
+            }
+        }
+
+        for CommittedPath { backup, .. } in committed {
+            if let Some(backup) = backup {
+                let _ = fs::remove_file(backup);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes go through symlinks, like `std::fs::write`, so the link keeps pointing at the file.
+fn write_target(path: &Path) -> PathBuf {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => {
+            fs::canonicalize(path).unwrap_or_else(|_| entry_key(path))
+        }
+        _ => entry_key(path),
+    }
+}
+
+/// Stages the entry at `path` under its parent resolved through symlinks, so every spelling of
+/// one file, including the canonical path a write through a link resolves to, shares a key.
+fn entry_key(path: &Path) -> PathBuf {
+    let Some(name) = path.file_name() else {
+        return path.to_path_buf();
+    };
+    let mut existing = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(mut key) = fs::canonicalize(existing) {
+            key.extend(missing.iter().rev());
+            key.push(name);
+            return key;
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(dir)) => {
+                missing.push(dir);
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+struct CommittedPath {
+    path: PathBuf,
+    /// Where the original file was moved while the patch is committed.
+    backup: Option<PathBuf>,
+    /// Whether a new file now sits at `path`.
+    replaced: bool,
+}
+
+fn commit_path(
+    path: &Path,
+    temp_file: Option<TempFile>,
+    committed: &mut Vec<CommittedPath>,
+) -> anyhow::Result<()> {
+    let backup = if fs::symlink_metadata(path).is_ok() {
+        let backup = sibling_path(path, "orig");
+        fs::rename(path, &backup)
+            .with_context(|| format!("Failed to replace file {}", path.display()))?;
+        Some(backup)
+    } else {
+        None
+    };
+    committed.push(CommittedPath {
+        path: path.to_path_buf(),
+        backup,
+        replaced: false,
+    });
+
+    if let Some(temp_file) = temp_file {
+        fs::rename(&temp_file.path, path)
+            .with_context(|| format!("Failed to write file {}", path.display()))?;
+        if let Some(last) = committed.last_mut() {
+            last.replaced = true;
+        }
+    }
+    Ok(())
+}
+
+/// Undo the commit in reverse order. Returns the originals that could not be moved back; they
+/// are left at their backup path so nothing is lost.
+fn roll_back(committed: Vec<CommittedPath>) -> Vec<PathBuf> {
+    let mut unrestored = Vec::new();
+    for CommittedPath {
+        path,
+        backup,
+        replaced,
+    } in committed.into_iter().rev()
+    {
+        if replaced {
+            let _ = fs::remove_file(&path);
+        }
+        if let Some(backup) = backup
+            && fs::rename(&backup, &path).is_err()
+        {
+            unrestored.push(backup);
+        }
+    }
+    unrestored
+}
+
+fn create_parent_dirs(path: &Path, created_dirs: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    else {
+        return Ok(());
+    };
+    let mut missing: Vec<PathBuf> = parent
+        .ancestors()
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    missing.reverse();
+    for dir in missing {
+        fs::create_dir(&dir).with_context(|| {
+            format!("Failed to create parent directories for {}", path.display())
+        })?;
+        created_dirs.push(dir);
+    }
+    Ok(())
+}
+
+fn remove_created_dirs(created_dirs: &[PathBuf]) {
+    for dir in created_dirs.iter().rev() {
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+/// A file next to its eventual destination that is deleted unless renamed into place.
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn write_next_to(target: &Path, contents: &[u8], mode: Option<u32>) -> anyhow::Result<Self> {
+        let temp_file = Self {
+            path: sibling_path(target, "tmp"),
+        };
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_file.path)
+            .with_context(|| format!("Failed to write file {}", target.display()))?;
+        file.write_all(contents)
+            .with_context(|| format!("Failed to write file {}", target.display()))?;
+        match mode {
+            Some(mode) if mode & !0o777 != 0 => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("mode {mode:o} sets more than permission bits"),
+            )),
+            Some(mode) => set_mode(&temp_file.path, mode),
+            None => match fs::metadata(target) {
+                Ok(metadata) => fs::set_permissions(&temp_file.path, metadata.permissions()),
+                Err(_) => Ok(()),
+            },
+        }
+        .with_context(|| format!("Failed to write file {}", target.display()))?;
+        Ok(temp_file)
+    }
+
+    #[cfg(unix)]
+    fn symlink_next_to(link: &Path, target: &Path) -> anyhow::Result<Self> {
+        let temp_file = Self {
+            path: sibling_path(link, "tmp"),
+        };
+        std::os::unix::fs::symlink(target, &temp_file.path)
+            .with_context(|| format!("Failed to add symlink {}", link.display()))?;
+        Ok(temp_file)
+    }
+
+    #[cfg(not(unix))]
+    fn symlink_next_to(link: &Path, _target: &Path) -> anyhow::Result<Self> {
+        anyhow::bail!(
+            "Failed to add symlink {}: symlinks are only supported on Unix",
+            link.display()
+        )
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+/// Only the owner write bit has a portable equivalent: the read-only flag.
+#[cfg(not(unix))]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_readonly(mode & 0o200 == 0);
+    fs::set_permissions(path, permissions)
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // Already gone once the file has been renamed into place.
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{file_name}.apply-patch-{}-{id}.{suffix}",
+        std::process::id()
+    ))
+}
@@ -104,7 +104,7 @@ fn test_apply_patch_cli_reports_missing_context() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Update File: modify.txt\n@@\n-missing\n+changed\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to find expected lines in modify.txt:\nmissing\n");
+        .stderr("line 2: Failed to find expected lines in modify.txt:\nmissing\n");
     assert_eq!(fs::read_to_string(&target_path)?, "line1\nline2\n");
 
     Ok(())
@@ -118,7 +118,7 @@ fn test_apply_patch_cli_rejects_missing_file_delete() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Delete File: missing.txt\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to delete file missing.txt\n");
+        .stderr("line 2: Failed to delete file missing.txt\n");
 
     Ok(())
 }
@@ -145,7 +145,7 @@ fn test_apply_patch_cli_requires_existing_file_for_update() -> anyhow::Result<()
         .assert()
         .failure()
         .stderr(
-            "Failed to read file to update missing.txt: No such file or directory (os error 2)\n",
+            "line 2: Failed to read file to update missing.txt: No such file or directory (os error 2)\n",
         );
 
     Ok(())
@@ -201,7 +201,7 @@ fn test_apply_patch_cli_delete_directory_fails() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Delete File: dir\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to delete file dir\n");
+        .stderr("line 2: Failed to delete file dir\n");
 
     Ok(())
 }
@@ -214,7 +214,7 @@ fn test_apply_patch_cli_rejects_invalid_hunk_header() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Frobnicate File: foo\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'\n");
+        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}', '*** Add Binary File: {path}', '*** Replace Binary File: {path}', '*** Add Symlink: {path}'\n");
 
     Ok(())
 }
@@ -240,7 +240,7 @@ fn test_apply_patch_cli_updates_file_appends_trailing_newline() -> anyhow::Resul
 }
 
 #[test]
-fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow::Result<()> {
+fn test_apply_patch_cli_failure_after_partial_success_rolls_back() -> anyhow::Result<()> {
     let tmp = tempdir()?;
     let new_file = tmp.path().join("created.txt");
 
@@ -249,9 +249,31 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
         .assert()
         .failure()
         .stdout("")
-        .stderr("Failed to read file to update missing.txt: No such file or directory (os error 2)\n");
+        .stderr("line 4: Failed to read file to update missing.txt: No such file or directory (os error 2)\n");
 
-    assert_eq!(fs::read_to_string(&new_file)?, "hello\n");
+    assert!(!new_file.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_patch_cli_reports_every_failed_hunk() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let modify_path = tmp.path().join("modify.txt");
+    let delete_path = tmp.path().join("delete.txt");
+    fs::write(&modify_path, "line1\nline2\n")?;
+    fs::write(&delete_path, "obsolete\n")?;
+
+    apply_patch_command(tmp.path())?
+        .arg("*** Begin Patch\n*** Delete File: delete.txt\n*** Update File: modify.txt\n@@\n-missing\n+changed\n*** Add File: nested/new.txt\n+created\n*** Delete File: missing.txt\n*** End Patch")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr("line 3: Failed to find expected lines in modify.txt:\nmissing\nline 9: Failed to delete file missing.txt\n");
+
+    assert_eq!(fs::read_to_string(&modify_path)?, "line1\nline2\n");
+    assert_eq!(fs::read_to_string(&delete_path)?, "obsolete\n");
+    assert!(!tmp.path().join("nested").exists());
 
     Ok(())
 }
@@ -308,7 +308,7 @@ struct AppServerCommand {
     subcommand: Option<AppServerSubcommand>,
 
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `wss://IP:PORT`, `unix:///PATH`.
     #[arg(
         long = "listen",
         value_name = "URL",
@@ -316,6 +316,9 @@ struct AppServerCommand {
     )]
     listen: codex_app_server::AppServerTransport,
 
+    #[command(flatten)]
+    listen_options: codex_app_server::AppServerListenOptions,
+
     /// Controls whether analytics are enabled by default.
     ///
     /// Analytics are disabled by default for app-server. Users have to explicitly opt in
@@ -604,6 +607,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                     codex_core::config_loader::LoaderOverrides::default(),
                     app_server_cli.analytics_default_enabled,
                     transport,
+                    app_server_cli.listen_options,
                 )
                 .await?;
             }
@@ -1406,6 +1410,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn app_server_listen_secure_websocket_with_tls_flags_parses() {
+        let app_server = app_server_from_args(
+            [
+                "codex",
+                "app-server",
+                "--listen",
+                "wss://0.0.0.0:4500",
+                "--tls-cert",
+                "/etc/codex/cert.pem",
+                "--tls-key",
+                "/etc/codex/key.pem",
+                "--ws-auth-token-file",
+                "/etc/codex/token",
+            ]
+            .as_ref(),
+        );
+        assert_eq!(
+            app_server.listen,
+            codex_app_server::AppServerTransport::SecureWebSocket {
+                bind_address: "0.0.0.0:4500".parse().expect("valid socket address"),
+            }
+        );
+        assert_eq!(
+            app_server.listen_options,
+            codex_app_server::AppServerListenOptions {
+                ws_auth_token_file: Some(PathBuf::from("/etc/codex/token")),
+                tls_cert_file: Some(PathBuf::from("/etc/codex/cert.pem")),
+                tls_key_file: Some(PathBuf::from("/etc/codex/key.pem")),
+            }
+        );
+    }
+
     #[test]
     fn app_server_listen_invalid_url_fails_to_parse() {
         let parse_result =
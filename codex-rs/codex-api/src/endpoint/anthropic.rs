@@ -0,0 +1,196 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::responses::ResponsesOptions;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::AnthropicRequest;
+use crate::requests::anthropic::DEFAULT_MAX_OUTPUT_TOKENS;
+use crate::requests::headers::build_conversation_headers;
+use crate::sse::spawn_anthropic_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::Request;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+
+const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const ANTHROPIC_BETA_HEADER: &str = "anthropic-beta";
+const PROMPT_CACHING_BETA: &str = "prompt-caching-2024-07-31";
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Streams turns from providers that speak the Anthropic Messages API.
+pub struct AnthropicClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+    max_output_tokens: u64,
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+            max_output_tokens: DEFAULT_MAX_OUTPUT_TOKENS,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+            ..self
+        }
+    }
+
+    /// Overrides the `max_tokens` cap sent with each request. `None` keeps the default.
+    pub fn with_max_output_tokens(self, max_output_tokens: Option<u64>) -> Self {
+        Self {
+            max_output_tokens: max_output_tokens.unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS),
+            ..self
+        }
+    }
+
+    /// Sends a Responses-style request to `messages` and maps the streamed content blocks
+    /// back into [`crate::ResponseEvent`]s. Request compression, turn state and the
+    /// subagent header in `options` are Responses API features and are ignored.
+    pub async fn stream_request(
+        &self,
+        request: &ResponsesApiRequest,
+        options: ResponsesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let ResponsesOptions {
+            conversation_id,
+            extra_headers,
+            ..
+        } = options;
+        let AnthropicRequest {
+            body,
+            freeform_tools,
+        } = AnthropicRequest::from_responses_request(
+            request,
+            request
+                .max_output_tokens
+                .map_or(self.max_output_tokens, |requested| {
+                    requested.min(self.max_output_tokens)
+                }),
+        );
+
+        let mut headers = extra_headers;
+        headers.extend(build_conversation_headers(conversation_id));
+
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, Self::path(), headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                apply_anthropic_headers(req);
+            })
+            .await?;
+
+        Ok(spawn_anthropic_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            freeform_tools,
+        ))
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+}
+
+/// Moves the bearer token into `x-api-key`, pins the API version and opts into prompt
+/// caching, keeping any values already configured on the provider.
+fn apply_anthropic_headers(req: &mut Request) {
+    let headers = &mut req.headers;
+    if let Some(authorization) = headers.remove(http::header::AUTHORIZATION)
+        && !headers.contains_key(API_KEY_HEADER)
+        && let Some(token) = authorization
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+        && let Ok(value) = HeaderValue::from_str(token)
+    {
+        headers.insert(API_KEY_HEADER, value);
+    }
+    if !headers.contains_key(ANTHROPIC_VERSION_HEADER) {
+        headers.insert(
+            ANTHROPIC_VERSION_HEADER,
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+    }
+    add_beta(headers, PROMPT_CACHING_BETA);
+}
+
+fn add_beta(headers: &mut HeaderMap, beta: &str) {
+    let existing = headers
+        .get(ANTHROPIC_BETA_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let value = match existing {
+        Some(existing) if existing.split(',').any(|item| item.trim() == beta) => return,
+        Some(existing) if !existing.trim().is_empty() => format!("{existing},{beta}"),
+        _ => beta.to_string(),
+    };
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        headers.insert(ANTHROPIC_BETA_HEADER, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn header(req: &Request, name: &str) -> Option<String> {
+        req.headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+
+    #[test]
+    fn anthropic_headers_replace_bearer_auth() {
+        let mut req = Request::new(Method::POST, "https://api.anthropic.com/v1/messages".into());
+        req.headers.insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer sk-ant-test"),
+        );
+        req.headers.insert(
+            ANTHROPIC_BETA_HEADER,
+            HeaderValue::from_static("interleaved-thinking-2025-05-14"),
+        );
+
+        apply_anthropic_headers(&mut req);
+
+        assert_eq!(header(&req, "authorization"), None);
+        assert_eq!(header(&req, API_KEY_HEADER).as_deref(), Some("sk-ant-test"));
+        assert_eq!(
+            header(&req, ANTHROPIC_VERSION_HEADER).as_deref(),
+            Some(ANTHROPIC_VERSION)
+        );
+        assert_eq!(
+            header(&req, ANTHROPIC_BETA_HEADER).as_deref(),
+            Some("interleaved-thinking-2025-05-14,prompt-caching-2024-07-31")
+        );
+
+        apply_anthropic_headers(&mut req);
+        assert_eq!(
+            header(&req, ANTHROPIC_BETA_HEADER).as_deref(),
+            Some("interleaved-thinking-2025-05-14,prompt-caching-2024-07-31")
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod anthropic;
+pub mod chat;
 pub mod compact;
 pub mod memories;
 pub mod models;
@@ -0,0 +1,586 @@
+use crate::common::ResponsesApiRequest;
+use crate::requests::chat::FREEFORM_TOOL_INPUT_ARG;
+use crate::requests::chat::freeform_tool_description;
+use crate::requests::chat::freeform_tool_parameters;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Output token cap sent when the provider does not configure one; the Messages API requires
+/// a cap on every request.
+pub(crate) const DEFAULT_MAX_OUTPUT_TOKENS: u64 = 32_000;
+
+/// Smallest thinking budget the Messages API accepts.
+const MIN_THINKING_BUDGET: u64 = 1_024;
+
+/// A Messages API request derived from a Responses API request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnthropicRequest {
+    pub body: Value,
+    /// Freeform (`custom`) tools that were exposed as tools taking a single `input` string.
+    /// Calls to these are mapped back to `custom_tool_call` items.
+    pub freeform_tools: HashSet<String>,
+}
+
+impl AnthropicRequest {
+    /// Translates the history, tools and reasoning settings of a Responses request.
+    ///
+    /// Reasoning items become thinking blocks, with the block signature carried in
+    /// `encrypted_content`. Prompt-caching breakpoints are placed on the system prompt, the
+    /// tool list and the latest message. Web search calls, compaction summaries and output
+    /// schemas have no Messages API equivalent and are dropped.
+    ///
+    /// `max_output_tokens` is sent as `max_tokens`; the thinking budget is kept below it and
+    /// thinking is disabled when the cap leaves no room for the minimum budget.
+    pub fn from_responses_request(request: &ResponsesApiRequest, max_output_tokens: u64) -> Self {
+        let (mut tools, freeform_tools) = anthropic_tools(&request.tools);
+        let mut messages = anthropic_messages(&request.input);
+
+        let mut body = Map::new();
+        body.insert("model".to_string(), json!(request.model));
+        body.insert("max_tokens".to_string(), json!(max_output_tokens));
+        if !request.instructions.is_empty() {
+            body.insert(
+                "system".to_string(),
+                json!([{
+                    "type": "text",
+                    "text": request.instructions,
+                    "cache_control": cache_control(),
+                }]),
+            );
+        }
+        // Thinking blocks cannot carry a cache breakpoint.
+        if let Some(Value::Object(last_block)) = messages
+            .last_mut()
+            .and_then(|message| message.get_mut("content"))
+            .and_then(Value::as_array_mut)
+            .and_then(|content| {
+                content.iter_mut().rev().find(|block| {
+                    !matches!(
+                        block.get("type").and_then(Value::as_str),
+                        Some("thinking" | "redacted_thinking")
+                    )
+                })
+            })
+        {
+            last_block.insert("cache_control".to_string(), cache_control());
+        }
+        body.insert("messages".to_string(), Value::Array(messages));
+        if let Some(Value::Object(last_tool)) = tools.last_mut() {
+            last_tool.insert("cache_control".to_string(), cache_control());
+        }
+        if !tools.is_empty() {
+            body.insert("tools".to_string(), Value::Array(tools));
+            body.insert(
+                "tool_choice".to_string(),
+                json!({
+                    "type": if request.tool_choice == "required" { "any" } else { "auto" },
+                    "disable_parallel_tool_use": !request.parallel_tool_calls,
+                }),
+            );
+        }
+        if let Some(budget_tokens) = request
+            .reasoning
+            .as_ref()
+            .and_then(|reasoning| reasoning.effort)
+            .and_then(thinking_budget)
+            .map(|budget| budget.min(max_output_tokens.saturating_sub(1)))
+            .filter(|budget| *budget >= MIN_THINKING_BUDGET)
+        {
+            body.insert(
+                "thinking".to_string(),
+                json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+            );
+        }
+        body.insert("stream".to_string(), json!(request.stream));
+
+        Self {
+            body: Value::Object(body),
+            freeform_tools,
+        }
+    }
+}
+
+fn cache_control() -> Value {
+    json!({ "type": "ephemeral" })
+}
+
+/// Thinking budget for a reasoning effort. The Messages API accepts at least 1024 tokens and
+/// requires the budget to stay below `max_tokens`.
+fn thinking_budget(effort: ReasoningEffort) -> Option<u64> {
+    match effort {
+        ReasoningEffort::None => None,
+        ReasoningEffort::Minimal => Some(MIN_THINKING_BUDGET),
+        ReasoningEffort::Low => Some(4_096),
+        ReasoningEffort::Medium => Some(10_000),
+        ReasoningEffort::High => Some(20_000),
+        ReasoningEffort::XHigh => Some(28_000),
+    }
+}
+
+fn anthropic_tools(tools: &[Value]) -> (Vec<Value>, HashSet<String>) {
+    let mut anthropic_tools = Vec::new();
+    let mut freeform_tools = HashSet::new();
+    for tool in tools {
+        let Some(name) = tool.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let description = tool
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match tool.get("type").and_then(Value::as_str) {
+            Some("function") => {
+                let input_schema = tool
+                    .get("parameters")
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+                anthropic_tools.push(json!({
+                    "name": name,
+                    "description": description,
+                    "input_schema": input_schema,
+                }));
+            }
+            Some("custom") => {
+                anthropic_tools.push(json!({
+                    "name": name,
+                    "description": freeform_tool_description(tool, description),
+                    "input_schema": freeform_tool_parameters(),
+                }));
+                freeform_tools.insert(name.to_string());
+            }
+            _ => {}
+        }
+    }
+    (anthropic_tools, freeform_tools)
+}
+
+fn anthropic_messages(items: &[ResponseItem]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    // Tool results are only valid after the assistant turn that issued the call.
+    let mut issued_call_ids = HashSet::new();
+    for item in items {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                let blocks = content.iter().filter_map(content_block).collect();
+                push_blocks(&mut messages, role, blocks);
+            }
+            ResponseItem::Reasoning {
+                summary,
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                let block = match thinking_text(summary, content.as_deref()) {
+                    Some(thinking) => json!({
+                        "type": "thinking",
+                        "thinking": thinking,
+                        "signature": signature,
+                    }),
+                    None => json!({ "type": "redacted_thinking", "data": signature }),
+                };
+                push_blocks(&mut messages, "assistant", vec![block]);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                push_tool_use(&mut messages, call_id, name, input);
+                issued_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let input = json!({ FREEFORM_TOOL_INPUT_ARG: input });
+                push_tool_use(&mut messages, call_id, name, input);
+                issued_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                if issued_call_ids.contains(call_id.as_str()) {
+                    push_blocks(&mut messages, "user", vec![tool_result(call_id, output)]);
+                }
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    messages
+}
+
+fn thinking_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> Option<String> {
+    let mut text = summary
+        .iter()
+        .map(|ReasoningItemReasoningSummary::SummaryText { text }| text.as_str())
+        .collect::<String>();
+    if text.is_empty() {
+        text = content
+            .unwrap_or_default()
+            .iter()
+            .map(|item| match item {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => text.as_str(),
+            })
+            .collect();
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({ "type": "text", "text": text }))
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    if let Some(data_url) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = data_url.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        });
+    }
+    json!({ "type": "image", "source": { "type": "url", "url": image_url } })
+}
+
+fn tool_result(call_id: &str, output: &FunctionCallOutputPayload) -> Value {
+    let content = match &output.body {
+        FunctionCallOutputBody::Text(text) => json!(text),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({ "type": "text", "text": text })
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url } => {
+                        image_block(image_url)
+                    }
+                })
+                .collect(),
+        ),
+    };
+    let mut block = json!({
+        "type": "tool_result",
+        "tool_use_id": call_id,
+        "content": content,
+    });
+    if output.success == Some(false) {
+        block["is_error"] = json!(true);
+    }
+    block
+}
+
+fn push_tool_use(messages: &mut Vec<Value>, call_id: &str, name: &str, input: Value) {
+    let block = json!({
+        "type": "tool_use",
+        "id": call_id,
+        "name": name,
+        "input": input,
+    });
+    push_blocks(messages, "assistant", vec![block]);
+}
+
+/// Appends content blocks, merging them into the previous message when it has the same role
+/// so that user and assistant turns alternate.
+fn push_blocks(messages: &mut Vec<Value>, role: &str, blocks: Vec<Value>) {
+    if blocks.is_empty() {
+        return;
+    }
+    if let Some(last) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some(role)
+        && let Some(content) = last.get_mut("content").and_then(Value::as_array_mut)
+    {
+        content.extend(blocks);
+        return;
+    }
+    messages.push(json!({ "role": role, "content": blocks }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: "be brief".to_string(),
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: false,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::Low),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+            max_output_tokens: None,
+        }
+    }
+
+    fn message(role: &str, content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn translates_history_tools_and_thinking() {
+        let input = vec![
+            message(
+                "developer",
+                vec![ContentItem::InputText {
+                    text: "sandbox: read-only".to_string(),
+                }],
+            ),
+            message(
+                "user",
+                vec![
+                    ContentItem::InputText {
+                        text: "describe".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+            ),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: "Look at the file.".to_string(),
+                }],
+                content: None,
+                encrypted_content: Some("sig-1".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text("boom".to_string()),
+                    success: Some(false),
+                },
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "toolu_2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "toolu_2".to_string(),
+                output: FunctionCallOutputPayload::from_text("Done!".to_string()),
+            },
+        ];
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Run a command.",
+                "strict": false,
+                "parameters": { "type": "object", "properties": {} },
+            }),
+            json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Edit files.",
+                "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+            }),
+            json!({ "type": "web_search" }),
+        ];
+
+        let request = AnthropicRequest::from_responses_request(
+            &request(input, tools),
+            DEFAULT_MAX_OUTPUT_TOKENS,
+        );
+
+        assert_eq!(
+            request.freeform_tools,
+            HashSet::from(["apply_patch".to_string()])
+        );
+        assert_eq!(
+            request.body,
+            json!({
+                "model": "claude-sonnet-4-5",
+                "max_tokens": DEFAULT_MAX_OUTPUT_TOKENS,
+                "system": [{
+                    "type": "text",
+                    "text": "be brief",
+                    "cache_control": { "type": "ephemeral" },
+                }],
+                "messages": [
+                    {
+                        "role": "user",
+                        "content": [
+                            { "type": "text", "text": "sandbox: read-only" },
+                            { "type": "text", "text": "describe" },
+                            {
+                                "type": "image",
+                                "source": { "type": "base64", "media_type": "image/png", "data": "AAAA" },
+                            },
+                        ],
+                    },
+                    {
+                        "role": "assistant",
+                        "content": [
+                            { "type": "thinking", "thinking": "Look at the file.", "signature": "sig-1" },
+                            { "type": "tool_use", "id": "toolu_1", "name": "shell", "input": { "command": ["ls"] } },
+                        ],
+                    },
+                    {
+                        "role": "user",
+                        "content": [{
+                            "type": "tool_result",
+                            "tool_use_id": "toolu_1",
+                            "content": "boom",
+                            "is_error": true,
+                        }],
+                    },
+                    {
+                        "role": "assistant",
+                        "content": [{
+                            "type": "tool_use",
+                            "id": "toolu_2",
+                            "name": "apply_patch",
+                            "input": { "input": "*** Begin Patch" },
+                        }],
+                    },
+                    {
+                        "role": "user",
+                        "content": [{
+                            "type": "tool_result",
+                            "tool_use_id": "toolu_2",
+                            "content": "Done!",
+                            "cache_control": { "type": "ephemeral" },
+                        }],
+                    },
+                ],
+                "tools": [
+                    {
+                        "name": "shell",
+                        "description": "Run a command.",
+                        "input_schema": { "type": "object", "properties": {} },
+                    },
+                    {
+                        "name": "apply_patch",
+                        "description": "Edit files.\n\nThe `input` argument must follow this lark grammar:\nstart: patch",
+                        "input_schema": {
+                            "type": "object",
+                            "properties": { "input": { "type": "string" } },
+                            "required": ["input"],
+                            "additionalProperties": false,
+                        },
+                        "cache_control": { "type": "ephemeral" },
+                    },
+                ],
+                "tool_choice": { "type": "auto", "disable_parallel_tool_use": true },
+                "thinking": { "type": "enabled", "budget_tokens": 4_096 },
+                "stream": true,
+            })
+        );
+    }
+
+    #[test]
+    fn redacted_thinking_round_trips_and_unsigned_reasoning_is_dropped() {
+        let input = vec![
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: None,
+            },
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".to_string()),
+            },
+        ];
+
+        let request = AnthropicRequest::from_responses_request(
+            &request(input, Vec::new()),
+            DEFAULT_MAX_OUTPUT_TOKENS,
+        );
+
+        assert_eq!(
+            request.body["messages"],
+            json!([{
+                "role": "assistant",
+                "content": [{
+                    "type": "redacted_thinking",
+                    "data": "opaque",
+                }],
+            }])
+        );
+        assert_eq!(request.body.get("tools"), None);
+    }
+
+    #[test]
+    fn thinking_budget_stays_below_the_output_cap() {
+        let mut responses_request = request(Vec::new(), Vec::new());
+        responses_request.reasoning = Some(Reasoning {
+            effort: Some(ReasoningEffort::High),
+            summary: None,
+        });
+
+        let request = AnthropicRequest::from_responses_request(&responses_request, 8_192);
+        assert_eq!(request.body["max_tokens"], json!(8_192));
+        assert_eq!(
+            request.body["thinking"],
+            json!({ "type": "enabled", "budget_tokens": 8_191 })
+        );
+
+        let request = AnthropicRequest::from_responses_request(&responses_request, 1_024);
+        assert_eq!(request.body["max_tokens"], json!(1_024));
+        assert_eq!(request.body.get("thinking"), None);
+    }
+}
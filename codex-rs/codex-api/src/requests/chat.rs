@@ -0,0 +1,502 @@
+use crate::common::ResponsesApiRequest;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Argument that carries the raw input of a freeform tool exposed as a function tool.
+pub(crate) const FREEFORM_TOOL_INPUT_ARG: &str = "input";
+
+/// A Chat Completions request derived from a Responses API request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatRequest {
+    pub body: Value,
+    /// Freeform (`custom`) tools that were exposed as function tools taking a single
+    /// `input` string. Calls to these are mapped back to `custom_tool_call` items.
+    pub freeform_tools: HashSet<String>,
+}
+
+impl ChatRequest {
+    /// Translates the history, tools and output controls of a Responses request.
+    /// `max_output_tokens` is sent as `max_tokens`.
+    ///
+    /// Items the Chat Completions API has no equivalent for (reasoning, web search calls,
+    /// compaction summaries, ...) are dropped, and so are images in tool output. Tools other
+    /// than `function` and `custom` are not offered to the model.
+    pub fn from_responses_request(request: &ResponsesApiRequest) -> Self {
+        let (tools, freeform_tools) = chat_tools(&request.tools);
+        let messages = chat_messages(&request.instructions, &request.input);
+
+        let mut body = Map::new();
+        body.insert("model".to_string(), json!(request.model));
+        body.insert("messages".to_string(), Value::Array(messages));
+        if !tools.is_empty() {
+            body.insert("tools".to_string(), Value::Array(tools));
+            body.insert("tool_choice".to_string(), json!(request.tool_choice));
+            body.insert(
+                "parallel_tool_calls".to_string(),
+                json!(request.parallel_tool_calls),
+            );
+        }
+        if let Some(effort) = request
+            .reasoning
+            .as_ref()
+            .and_then(|reasoning| reasoning.effort)
+        {
+            body.insert("reasoning_effort".to_string(), json!(effort));
+        }
+        if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+            body.insert(
+                "response_format".to_string(),
+                json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": format.name,
+                        "schema": format.schema,
+                        "strict": format.strict,
+                    },
+                }),
+            );
+        }
+        if let Some(max_output_tokens) = request.max_output_tokens {
+            body.insert("max_tokens".to_string(), json!(max_output_tokens));
+        }
+        body.insert("stream".to_string(), json!(request.stream));
+        if request.stream {
+            body.insert(
+                "stream_options".to_string(),
+                json!({ "include_usage": true }),
+            );
+        }
+
+        Self {
+            body: Value::Object(body),
+            freeform_tools,
+        }
+    }
+}
+
+fn chat_tools(tools: &[Value]) -> (Vec<Value>, HashSet<String>) {
+    let mut chat_tools = Vec::new();
+    let mut freeform_tools = HashSet::new();
+    for tool in tools {
+        let Some(name) = tool.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let description = tool
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match tool.get("type").and_then(Value::as_str) {
+            Some("function") => {
+                let parameters = tool
+                    .get("parameters")
+                    .cloned()
+                    .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+                chat_tools.push(function_tool(name, description.to_string(), parameters));
+            }
+            Some("custom") => {
+                let description = freeform_tool_description(tool, description);
+                let parameters = freeform_tool_parameters();
+                chat_tools.push(function_tool(name, description, parameters));
+                freeform_tools.insert(name.to_string());
+            }
+            _ => {}
+        }
+    }
+    (chat_tools, freeform_tools)
+}
+
+/// Describes a freeform tool for APIs without grammar-constrained tools, appending the
+/// grammar its `input` must follow.
+pub(crate) fn freeform_tool_description(tool: &Value, description: &str) -> String {
+    let mut description = description.to_string();
+    if let Some(format) = tool.get("format")
+        && let Some(definition) = format.get("definition").and_then(Value::as_str)
+    {
+        let syntax = format
+            .get("syntax")
+            .and_then(Value::as_str)
+            .unwrap_or("text");
+        description.push_str(&format!(
+            "\n\nThe `{FREEFORM_TOOL_INPUT_ARG}` argument must follow this {syntax} grammar:\n{definition}"
+        ));
+    }
+    description
+}
+
+/// JSON schema for a freeform tool exposed as a function taking a single `input` string.
+pub(crate) fn freeform_tool_parameters() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            FREEFORM_TOOL_INPUT_ARG: { "type": "string" },
+        },
+        "required": [FREEFORM_TOOL_INPUT_ARG],
+        "additionalProperties": false,
+    })
+}
+
+fn function_tool(name: &str, description: String, parameters: Value) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": name,
+            "description": description,
+            "parameters": parameters,
+        },
+    })
+}
+
+fn chat_messages(instructions: &str, items: &[ResponseItem]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    if !instructions.is_empty() {
+        messages.push(json!({ "role": "system", "content": instructions }));
+    }
+
+    // Tool results are only valid after the assistant message that issued the call.
+    let mut issued_call_ids = HashSet::new();
+    for item in items {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    "developer" => "system",
+                    other => other,
+                };
+                if let Some(content) = message_content(role, content) {
+                    messages.push(json!({ "role": role, "content": content }));
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                push_tool_call(&mut messages, call_id, name, arguments.clone());
+                issued_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let arguments = json!({ FREEFORM_TOOL_INPUT_ARG: input }).to_string();
+                push_tool_call(&mut messages, call_id, name, arguments);
+                issued_call_ids.insert(call_id.as_str());
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                if issued_call_ids.contains(call_id.as_str()) {
+                    messages.push(json!({
+                        "role": "tool",
+                        "tool_call_id": call_id,
+                        "content": output.body.to_text().unwrap_or_default(),
+                    }));
+                }
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    messages
+}
+
+/// Content for a chat message: a plain string unless a user message carries images.
+fn message_content(role: &str, content: &[ContentItem]) -> Option<Value> {
+    let has_images = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if role == "user" && has_images {
+        let parts = content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url } => {
+                    json!({ "type": "image_url", "image_url": { "url": image_url } })
+                }
+            })
+            .collect();
+        return Some(Value::Array(parts));
+    }
+
+    let text = content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect::<String>();
+    (!text.is_empty()).then_some(Value::String(text))
+}
+
+/// Attach a tool call to the preceding assistant message, or start a new one.
+fn push_tool_call(messages: &mut Vec<Value>, call_id: &str, name: &str, arguments: String) {
+    let tool_call = json!({
+        "id": call_id,
+        "type": "function",
+        "function": { "name": name, "arguments": arguments },
+    });
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some("assistant")
+    {
+        match last.get_mut("tool_calls") {
+            Some(Value::Array(tool_calls)) => tool_calls.push(tool_call),
+            _ => {
+                last.insert("tool_calls".to_string(), json!([tool_call]));
+            }
+        }
+        return;
+    }
+    messages.push(json!({
+        "role": "assistant",
+        "content": Value::Null,
+        "tool_calls": [tool_call],
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputBody;
+    use codex_protocol::models::FunctionCallOutputContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "gpt-oss".to_string(),
+            instructions: "be brief".to_string(),
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+            max_output_tokens: None,
+        }
+    }
+
+    fn message(role: &str, content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn translates_history_into_chat_messages() {
+        let input = vec![
+            message(
+                "developer",
+                vec![ContentItem::InputText {
+                    text: "sandbox: read-only".to_string(),
+                }],
+            ),
+            message(
+                "user",
+                vec![
+                    ContentItem::InputText {
+                        text: "what is in".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+            ),
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some("opaque".to_string()),
+            },
+            message(
+                "assistant",
+                vec![ContentItem::OutputText {
+                    text: "Let me look.".to_string(),
+                }],
+            ),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload::from_text("a.png".to_string()),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call_2".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::ContentItems(vec![
+                        FunctionCallOutputContentItem::InputText {
+                            text: "Done!".to_string(),
+                        },
+                    ]),
+                    success: Some(true),
+                },
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "orphan".to_string(),
+                output: FunctionCallOutputPayload::from_text("dropped".to_string()),
+            },
+        ];
+        let tools = vec![json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Edit files.",
+            "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+        })];
+
+        let request = ChatRequest::from_responses_request(&request(input, tools));
+
+        assert_eq!(
+            request.body["messages"],
+            json!([
+                { "role": "system", "content": "be brief" },
+                { "role": "system", "content": "sandbox: read-only" },
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": "what is in" },
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,AAAA" } },
+                    ],
+                },
+                {
+                    "role": "assistant",
+                    "content": "Let me look.",
+                    "tool_calls": [
+                        {
+                            "id": "call_1",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": r#"{"command":["ls"]}"# },
+                        },
+                        {
+                            "id": "call_2",
+                            "type": "function",
+                            "function": {
+                                "name": "apply_patch",
+                                "arguments": r#"{"input":"*** Begin Patch"}"#,
+                            },
+                        },
+                    ],
+                },
+                { "role": "tool", "tool_call_id": "call_1", "content": "a.png" },
+                { "role": "tool", "tool_call_id": "call_2", "content": "Done!" },
+            ])
+        );
+    }
+
+    #[test]
+    fn translates_tools_and_output_controls() {
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Run a command.",
+                "strict": false,
+                "parameters": { "type": "object", "properties": { "command": { "type": "array" } } },
+            }),
+            json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Edit files.",
+                "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+            }),
+            json!({ "type": "web_search" }),
+            json!({ "type": "local_shell" }),
+        ];
+        let mut responses_request = request(Vec::new(), tools);
+        responses_request.reasoning = Some(crate::common::Reasoning {
+            effort: Some(codex_protocol::openai_models::ReasoningEffort::High),
+            summary: None,
+        });
+        responses_request.text = Some(crate::common::TextControls {
+            verbosity: None,
+            format: Some(crate::common::TextFormat {
+                r#type: crate::common::TextFormatType::JsonSchema,
+                strict: true,
+                schema: json!({ "type": "object" }),
+                name: "codex_output_schema".to_string(),
+            }),
+        });
+        responses_request.max_output_tokens = Some(256);
+
+        let request = ChatRequest::from_responses_request(&responses_request);
+
+        assert_eq!(
+            request.freeform_tools,
+            HashSet::from(["apply_patch".to_string()])
+        );
+        assert_eq!(
+            request.body,
+            json!({
+                "model": "gpt-oss",
+                "messages": [{ "role": "system", "content": "be brief" }],
+                "tools": [
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "shell",
+                            "description": "Run a command.",
+                            "parameters": { "type": "object", "properties": { "command": { "type": "array" } } },
+                        },
+                    },
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "apply_patch",
+                            "description": "Edit files.\n\nThe `input` argument must follow this lark grammar:\nstart: patch",
+                            "parameters": {
+                                "type": "object",
+                                "properties": { "input": { "type": "string" } },
+                                "required": ["input"],
+                                "additionalProperties": false,
+                            },
+                        },
+                    },
+                ],
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+                "reasoning_effort": "high",
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": "codex_output_schema",
+                        "schema": { "type": "object" },
+                        "strict": true,
+                    },
+                },
+                "max_tokens": 256,
+                "stream": true,
+                "stream_options": { "include_usage": true },
+            })
+        );
+    }
+}
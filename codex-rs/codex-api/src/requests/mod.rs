@@ -1,2 +1,4 @@
+pub mod anthropic;
+pub mod chat;
 pub(crate) mod headers;
 pub mod responses;
@@ -0,0 +1,630 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::chat::freeform_tool_input;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_anthropic_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_anthropic_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        freeform_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart {
+        message: AnthropicMessage,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: AnthropicContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: AnthropicDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        usage: Option<AnthropicUsage>,
+    },
+    MessageStop {},
+    Ping {},
+    Error {
+        error: AnthropicError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessage {
+    id: String,
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicContentBlock {
+    Text {},
+    Thinking {},
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Token counts as reported by the Messages API. `input_tokens` excludes cache reads and
+/// writes, and `message_delta` only repeats the fields that changed.
+#[derive(Debug, Default, Deserialize)]
+struct AnthropicUsage {
+    input_tokens: Option<i64>,
+    cache_creation_input_tokens: Option<i64>,
+    cache_read_input_tokens: Option<i64>,
+    output_tokens: Option<i64>,
+}
+
+impl AnthropicUsage {
+    fn merge(&mut self, update: AnthropicUsage) {
+        self.input_tokens = update.input_tokens.or(self.input_tokens);
+        self.cache_creation_input_tokens = update
+            .cache_creation_input_tokens
+            .or(self.cache_creation_input_tokens);
+        self.cache_read_input_tokens = update
+            .cache_read_input_tokens
+            .or(self.cache_read_input_tokens);
+        self.output_tokens = update.output_tokens.or(self.output_tokens);
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        let cached_input_tokens = self.cache_read_input_tokens.unwrap_or(0);
+        let input_tokens = self.input_tokens.unwrap_or(0)
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + cached_input_tokens;
+        let output_tokens = self.output_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    r#type: Option<String>,
+    message: Option<String>,
+}
+
+impl AnthropicError {
+    fn into_api_error(self) -> ApiError {
+        let message = self
+            .message
+            .unwrap_or_else(|| "anthropic messages request failed".to_string());
+        match self.r#type.as_deref() {
+            Some("overloaded_error") => ApiError::ServerOverloaded,
+            Some("rate_limit_error") => ApiError::Retryable {
+                message,
+                delay: None,
+            },
+            Some("invalid_request_error") if message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            _ => ApiError::Stream(message),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ContentBlock {
+    Text(String),
+    Thinking {
+        text: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+/// Folds Messages API content blocks into Responses-style items.
+///
+/// Text and thinking blocks are surfaced as they stream; thinking is reported as a reasoning
+/// summary and its signature is kept in `encrypted_content` so it can be sent back. Tool use
+/// blocks are emitted once their input JSON is complete.
+#[derive(Debug, Default)]
+struct AnthropicStreamState {
+    response_id: String,
+    usage: AnthropicUsage,
+    blocks: HashMap<usize, ContentBlock>,
+}
+
+impl AnthropicStreamState {
+    fn start_block(
+        &mut self,
+        index: usize,
+        block: AnthropicContentBlock,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        let block = match block {
+            AnthropicContentBlock::Text {} => {
+                events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                    String::new(),
+                )));
+                ContentBlock::Text(String::new())
+            }
+            AnthropicContentBlock::Thinking {} => {
+                events.push(ResponseEvent::OutputItemAdded(reasoning_item(
+                    String::new(),
+                    None,
+                )));
+                ContentBlock::Thinking {
+                    text: String::new(),
+                    signature: String::new(),
+                }
+            }
+            AnthropicContentBlock::RedactedThinking { data } => {
+                ContentBlock::RedactedThinking(data)
+            }
+            AnthropicContentBlock::ToolUse { id, name } => ContentBlock::ToolUse {
+                id,
+                name,
+                input: String::new(),
+            },
+            AnthropicContentBlock::Other => return,
+        };
+        self.blocks.insert(index, block);
+    }
+
+    fn apply_delta(
+        &mut self,
+        index: usize,
+        delta: AnthropicDelta,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        match (self.blocks.get_mut(&index), delta) {
+            (Some(ContentBlock::Text(text)), AnthropicDelta::TextDelta { text: delta }) => {
+                text.push_str(&delta);
+                events.push(ResponseEvent::OutputTextDelta(delta));
+            }
+            (
+                Some(ContentBlock::Thinking { text, .. }),
+                AnthropicDelta::ThinkingDelta { thinking },
+            ) => {
+                text.push_str(&thinking);
+                events.push(ResponseEvent::ReasoningSummaryDelta {
+                    delta: thinking,
+                    summary_index: 0,
+                });
+            }
+            (
+                Some(ContentBlock::Thinking { signature, .. }),
+                AnthropicDelta::SignatureDelta { signature: delta },
+            ) => signature.push_str(&delta),
+            (
+                Some(ContentBlock::ToolUse { input, .. }),
+                AnthropicDelta::InputJsonDelta { partial_json },
+            ) => input.push_str(&partial_json),
+            (block, delta) => {
+                debug!("Ignoring anthropic delta {delta:?} for block {block:?}");
+            }
+        }
+    }
+
+    fn stop_block(
+        &mut self,
+        index: usize,
+        freeform_tools: &HashSet<String>,
+        events: &mut Vec<ResponseEvent>,
+    ) {
+        let Some(block) = self.blocks.remove(&index) else {
+            return;
+        };
+        let item = match block {
+            ContentBlock::Text(text) => assistant_message(text),
+            ContentBlock::Thinking { text, signature } => {
+                reasoning_item(text, (!signature.is_empty()).then_some(signature))
+            }
+            ContentBlock::RedactedThinking(data) => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            },
+            ContentBlock::ToolUse { id, name, input } => {
+                let arguments = if input.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    input
+                };
+                if freeform_tools.contains(&name) {
+                    ResponseItem::CustomToolCall {
+                        id: None,
+                        status: None,
+                        call_id: id,
+                        input: freeform_tool_input(&arguments),
+                        name,
+                    }
+                } else {
+                    ResponseItem::FunctionCall {
+                        id: None,
+                        name,
+                        arguments,
+                        call_id: id,
+                    }
+                }
+            }
+        };
+        events.push(ResponseEvent::OutputItemDone(item));
+    }
+}
+
+fn assistant_message(text: String) -> ResponseItem {
+    let content = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![ContentItem::OutputText { text }]
+    };
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content,
+        end_turn: None,
+        phase: None,
+    }
+}
+
+fn reasoning_item(text: String, signature: Option<String>) -> ResponseItem {
+    let summary = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![ReasoningItemReasoningSummary::SummaryText { text }]
+    };
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary,
+        content: None,
+        encrypted_content: signature,
+    }
+}
+
+pub async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = AnthropicStreamState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: AnthropicStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!(
+                    "Failed to parse anthropic SSE event: {e}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+
+        let mut events = Vec::new();
+        match event {
+            AnthropicStreamEvent::MessageStart { message } => {
+                state.response_id = message.id;
+                if let Some(usage) = message.usage {
+                    state.usage.merge(usage);
+                }
+                events.push(ResponseEvent::Created);
+            }
+            AnthropicStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => state.start_block(index, content_block, &mut events),
+            AnthropicStreamEvent::ContentBlockDelta { index, delta } => {
+                state.apply_delta(index, delta, &mut events);
+            }
+            AnthropicStreamEvent::ContentBlockStop { index } => {
+                state.stop_block(index, &freeform_tools, &mut events);
+            }
+            AnthropicStreamEvent::MessageDelta { usage } => {
+                if let Some(usage) = usage {
+                    state.usage.merge(usage);
+                }
+            }
+            AnthropicStreamEvent::MessageStop {} => {
+                events.push(ResponseEvent::Completed {
+                    response_id: std::mem::take(&mut state.response_id),
+                    token_usage: Some(state.usage.token_usage()),
+                    can_append: false,
+                });
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+                return;
+            }
+            AnthropicStreamEvent::Error { error } => {
+                let _ = tx_event.send(Err(error.into_api_error())).await;
+                return;
+            }
+            AnthropicStreamEvent::Ping {} | AnthropicStreamEvent::Other => {}
+        }
+
+        for event in events {
+            if tx_event.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_anthropic_sse(
+        events: Vec<Value>,
+        freeform_tools: &[&str],
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event["type"].as_str().expect("fixture event missing type");
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(32);
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let freeform_tools = freeform_tools.iter().map(ToString::to_string).collect();
+        tokio::spawn(process_anthropic_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            freeform_tools,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    fn message_start() -> Value {
+        json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [],
+                "usage": {
+                    "input_tokens": 10,
+                    "cache_creation_input_tokens": 100,
+                    "cache_read_input_tokens": 1000,
+                    "output_tokens": 1,
+                },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_thinking_text_and_tool_use() {
+        let events = run_anthropic_sse(
+            vec![
+                message_start(),
+                json!({ "type": "ping" }),
+                json!({ "type": "content_block_start", "index": 0,
+                        "content_block": { "type": "thinking", "thinking": "" } }),
+                json!({ "type": "content_block_delta", "index": 0,
+                        "delta": { "type": "thinking_delta", "thinking": "Plan it." } }),
+                json!({ "type": "content_block_delta", "index": 0,
+                        "delta": { "type": "signature_delta", "signature": "sig-1" } }),
+                json!({ "type": "content_block_stop", "index": 0 }),
+                json!({ "type": "content_block_start", "index": 1,
+                        "content_block": { "type": "text", "text": "" } }),
+                json!({ "type": "content_block_delta", "index": 1,
+                        "delta": { "type": "text_delta", "text": "Patching." } }),
+                json!({ "type": "content_block_stop", "index": 1 }),
+                json!({ "type": "content_block_start", "index": 2,
+                        "content_block": { "type": "tool_use", "id": "toolu_1", "name": "apply_patch", "input": {} } }),
+                json!({ "type": "content_block_delta", "index": 2,
+                        "delta": { "type": "input_json_delta", "partial_json": "{\"input\": \"*** Begin" } }),
+                json!({ "type": "content_block_delta", "index": 2,
+                        "delta": { "type": "input_json_delta", "partial_json": " Patch\"}" } }),
+                json!({ "type": "content_block_stop", "index": 2 }),
+                json!({ "type": "content_block_start", "index": 3,
+                        "content_block": { "type": "tool_use", "id": "toolu_2", "name": "list_mcp_resources", "input": {} } }),
+                json!({ "type": "content_block_stop", "index": 3 }),
+                json!({ "type": "message_delta",
+                        "delta": { "stop_reason": "tool_use" }, "usage": { "output_tokens": 42 } }),
+                json!({ "type": "message_stop" }),
+            ],
+            &["apply_patch"],
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .collect::<Vec<_>>();
+
+        assert_matches!(&events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. })
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningSummaryDelta { delta, summary_index: 0 } if delta == "Plan it."
+        );
+        assert_matches!(&events[5], ResponseEvent::OutputTextDelta(delta) if delta == "Patching.");
+
+        let items = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                ResponseItem::Reasoning {
+                    id: String::new(),
+                    summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                        text: "Plan it.".to_string(),
+                    }],
+                    content: None,
+                    encrypted_content: Some("sig-1".to_string()),
+                },
+                assistant_message("Patching.".to_string()),
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "toolu_1".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "list_mcp_resources".to_string(),
+                    arguments: "{}".to_string(),
+                    call_id: "toolu_2".to_string(),
+                },
+            ]
+        );
+
+        assert_matches!(
+            events.last(),
+            Some(ResponseEvent::Completed { response_id, token_usage: Some(usage), can_append: false })
+                if response_id == "msg_1"
+                    && usage == &TokenUsage {
+                        input_tokens: 1110,
+                        cached_input_tokens: 1000,
+                        output_tokens: 42,
+                        reasoning_output_tokens: 0,
+                        total_tokens: 1152,
+                    }
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_stream_errors() {
+        let events = run_anthropic_sse(
+            vec![
+                message_start(),
+                json!({ "type": "error",
+                        "error": { "type": "overloaded_error", "message": "Overloaded" } }),
+            ],
+            &[],
+        )
+        .await;
+        assert_matches!(events.last(), Some(Err(ApiError::ServerOverloaded)));
+
+        let events = run_anthropic_sse(vec![message_start()], &[]).await;
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(message)))
+            if message == "stream closed before message_stop");
+    }
+}
@@ -0,0 +1,617 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::requests::chat::FREEFORM_TOOL_INPUT_ARG;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+/// Sentinel the Chat Completions API sends as the final SSE payload.
+const DONE_SENTINEL: &str = "[DONE]";
+
+pub fn spawn_chat_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_chat_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        freeform_tools,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    usage: Option<ChatUsage>,
+    error: Option<ChatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    delta: Option<ChatDelta>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatDelta {
+    content: Option<String>,
+    /// Raw reasoning, sent as `reasoning_content` or `reasoning` depending on the server.
+    #[serde(alias = "reasoning")]
+    reasoning_content: Option<String>,
+    tool_calls: Option<Vec<ChatToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    index: Option<usize>,
+    id: Option<String>,
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    total_tokens: i64,
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(usage: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: usage.prompt_tokens,
+            cached_input_tokens: usage
+                .prompt_tokens_details
+                .map(|details| details.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: usage.completion_tokens,
+            reasoning_output_tokens: usage
+                .completion_tokens_details
+                .map(|details| details.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: usage.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatError {
+    code: Option<Value>,
+    message: Option<String>,
+}
+
+impl ChatError {
+    fn into_api_error(self) -> ApiError {
+        let code = self.code.as_ref().and_then(Value::as_str);
+        if code == Some("context_length_exceeded") {
+            return ApiError::ContextWindowExceeded;
+        }
+        ApiError::Stream(
+            self.message
+                .unwrap_or_else(|| "chat completion failed".to_string()),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Folds streamed chat deltas into Responses-style items.
+///
+/// Text and reasoning are surfaced as they stream, bracketed by `OutputItemAdded` and
+/// `OutputItemDone`. Tool calls arrive in fragments keyed by index and are only emitted once
+/// the completion finishes.
+#[derive(Debug, Default)]
+struct ChatStreamState {
+    response_id: Option<String>,
+    reasoning: Option<String>,
+    message: Option<String>,
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    token_usage: Option<TokenUsage>,
+    finished: bool,
+}
+
+impl ChatStreamState {
+    fn apply_chunk(&mut self, chunk: ChatChunk, events: &mut Vec<ResponseEvent>) {
+        if self.response_id.is_none() {
+            self.response_id = chunk.id;
+        }
+        if let Some(usage) = chunk.usage {
+            self.token_usage = Some(usage.into());
+        }
+        for choice in chunk.choices {
+            if let Some(delta) = choice.delta {
+                self.apply_delta(delta, events);
+            }
+            if choice.finish_reason.is_some() {
+                self.finished = true;
+            }
+        }
+    }
+
+    fn apply_delta(&mut self, delta: ChatDelta, events: &mut Vec<ResponseEvent>) {
+        if let Some(reasoning) = delta.reasoning_content.filter(|text| !text.is_empty()) {
+            self.finish_message(events);
+            let text = self.reasoning.get_or_insert_with(|| {
+                events.push(ResponseEvent::OutputItemAdded(
+                    reasoning_item(String::new()),
+                ));
+                String::new()
+            });
+            text.push_str(&reasoning);
+            events.push(ResponseEvent::ReasoningContentDelta {
+                delta: reasoning,
+                content_index: 0,
+            });
+        }
+
+        if let Some(content) = delta.content.filter(|text| !text.is_empty()) {
+            self.finish_reasoning(events);
+            let text = self.message.get_or_insert_with(|| {
+                events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                    String::new(),
+                )));
+                String::new()
+            });
+            text.push_str(&content);
+            events.push(ResponseEvent::OutputTextDelta(content));
+        }
+
+        for tool_call in delta.tool_calls.unwrap_or_default() {
+            let index = tool_call
+                .index
+                .unwrap_or_else(|| self.index_for_unindexed_call(tool_call.id.as_deref()));
+            let pending = self.tool_calls.entry(index).or_default();
+            if let Some(id) = tool_call.id {
+                pending.id = Some(id);
+            }
+            if let Some(function) = tool_call.function {
+                if let Some(name) = function.name {
+                    pending.name.push_str(&name);
+                }
+                if let Some(arguments) = function.arguments {
+                    pending.arguments.push_str(&arguments);
+                }
+            }
+        }
+    }
+
+    /// Some servers omit `index` and send each call whole; key those by their id.
+    fn index_for_unindexed_call(&self, id: Option<&str>) -> usize {
+        let existing = self
+            .tool_calls
+            .iter()
+            .find(|(_, call)| id.is_some() && call.id.as_deref() == id)
+            .map(|(index, _)| *index);
+        match (existing, id) {
+            (Some(index), _) => index,
+            (None, Some(_)) => self.tool_calls.len(),
+            (None, None) => self.tool_calls.len().saturating_sub(1),
+        }
+    }
+
+    fn finish_reasoning(&mut self, events: &mut Vec<ResponseEvent>) {
+        if let Some(text) = self.reasoning.take() {
+            events.push(ResponseEvent::OutputItemDone(reasoning_item(text)));
+        }
+    }
+
+    fn finish_message(&mut self, events: &mut Vec<ResponseEvent>) {
+        if let Some(text) = self.message.take() {
+            events.push(ResponseEvent::OutputItemDone(assistant_message(text)));
+        }
+    }
+
+    fn finish(mut self, freeform_tools: &HashSet<String>) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        self.finish_reasoning(&mut events);
+        self.finish_message(&mut events);
+
+        let response_id = self.response_id.unwrap_or_default();
+        for (index, call) in std::mem::take(&mut self.tool_calls) {
+            let call_id = call
+                .id
+                .unwrap_or_else(|| format!("call_{response_id}_{index}"));
+            let item = if freeform_tools.contains(&call.name) {
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id,
+                    input: freeform_tool_input(&call.arguments),
+                    name: call.name,
+                }
+            } else {
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: call.name,
+                    arguments: call.arguments,
+                    call_id,
+                }
+            };
+            events.push(ResponseEvent::OutputItemDone(item));
+        }
+
+        events.push(ResponseEvent::Completed {
+            response_id,
+            token_usage: self.token_usage,
+            can_append: false,
+        });
+        events
+    }
+}
+
+fn assistant_message(text: String) -> ResponseItem {
+    let content = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![ContentItem::OutputText { text }]
+    };
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content,
+        end_turn: None,
+        phase: None,
+    }
+}
+
+fn reasoning_item(text: String) -> ResponseItem {
+    let content = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![ReasoningItemContent::ReasoningText { text }]
+    };
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary: Vec::new(),
+        content: Some(content),
+        encrypted_content: None,
+    }
+}
+
+/// Unwraps the `input` argument of a freeform tool call, falling back to the raw arguments
+/// when the model did not produce the expected object.
+pub(crate) fn freeform_tool_input(arguments: &str) -> String {
+    serde_json::from_str::<Value>(arguments)
+        .ok()
+        .and_then(|value| {
+            value
+                .get(FREEFORM_TOOL_INPUT_ARG)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| arguments.to_string())
+}
+
+pub async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    freeform_tools: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Not every server sends `[DONE]`; a finish reason is enough to complete.
+                if state.finished {
+                    send_events(&tx_event, state.finish(&freeform_tools)).await;
+                } else {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before chat completion finished".into(),
+                        )))
+                        .await;
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            send_events(&tx_event, state.finish(&freeform_tools)).await;
+            return;
+        }
+
+        let chunk: ChatChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse chat SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+        if let Some(error) = chunk.error {
+            let _ = tx_event.send(Err(error.into_api_error())).await;
+            return;
+        }
+
+        let mut events = Vec::new();
+        state.apply_chunk(chunk, &mut events);
+        if !send_events(&tx_event, events).await {
+            return;
+        }
+    }
+}
+
+async fn send_events(
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    events: Vec<ResponseEvent>,
+) -> bool {
+    for event in events {
+        if tx_event.send(Ok(event)).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_chat_sse(
+        chunks: Vec<Value>,
+        done: bool,
+        freeform_tools: &[&str],
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        if done {
+            body.push_str("data: [DONE]\n\n");
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(32);
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let freeform_tools = freeform_tools.iter().map(ToString::to_string).collect();
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            freeform_tools,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    fn delta(id: &str, delta: Value) -> Value {
+        json!({ "id": id, "choices": [{ "index": 0, "delta": delta, "finish_reason": null }] })
+    }
+
+    fn finish(id: &str, reason: &str) -> Value {
+        json!({ "id": id, "choices": [{ "index": 0, "delta": {}, "finish_reason": reason }] })
+    }
+
+    #[tokio::test]
+    async fn streams_reasoning_and_text_then_completes_with_usage() {
+        let events = run_chat_sse(
+            vec![
+                delta(
+                    "chatcmpl-1",
+                    json!({ "role": "assistant", "reasoning_content": "Think" }),
+                ),
+                delta("chatcmpl-1", json!({ "reasoning_content": "ing" })),
+                delta("chatcmpl-1", json!({ "content": "Hel" })),
+                delta("chatcmpl-1", json!({ "content": "lo" })),
+                finish("chatcmpl-1", "stop"),
+                json!({
+                    "id": "chatcmpl-1",
+                    "choices": [],
+                    "usage": {
+                        "prompt_tokens": 10,
+                        "completion_tokens": 5,
+                        "total_tokens": 15,
+                        "prompt_tokens_details": { "cached_tokens": 4 },
+                        "completion_tokens_details": { "reasoning_tokens": 2 },
+                    },
+                }),
+            ],
+            true,
+            &[],
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 9);
+        assert_matches!(
+            &events[0],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. })
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "ing"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning { content: Some(content), .. })
+                if content == &vec![ReasoningItemContent::ReasoningText { text: "Thinking".to_string() }]
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { role, .. }) if role == "assistant"
+        );
+        assert_matches!(&events[6], ResponseEvent::OutputTextDelta(delta) if delta == "lo");
+        assert_matches!(
+            &events[7],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        assert_matches!(
+            &events[8],
+            ResponseEvent::Completed { response_id, token_usage: Some(usage), can_append: false }
+                if response_id == "chatcmpl-1"
+                    && usage == &TokenUsage {
+                        input_tokens: 10,
+                        cached_input_tokens: 4,
+                        output_tokens: 5,
+                        reasoning_output_tokens: 2,
+                        total_tokens: 15,
+                    }
+        );
+    }
+
+    #[tokio::test]
+    async fn accumulates_tool_call_fragments() {
+        let events = run_chat_sse(
+            vec![
+                delta(
+                    "chatcmpl-2",
+                    json!({ "tool_calls": [
+                        { "index": 0, "id": "call_a", "type": "function",
+                          "function": { "name": "shell", "arguments": "{\"command\":" } },
+                        { "index": 1, "id": "call_b", "type": "function",
+                          "function": { "name": "apply_patch", "arguments": "" } },
+                    ] }),
+                ),
+                delta(
+                    "chatcmpl-2",
+                    json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "[\"ls\"]}" } }] }),
+                ),
+                delta(
+                    "chatcmpl-2",
+                    json!({ "tool_calls": [{ "index": 1, "function": { "arguments": "{\"input\":\"*** Begin Patch\"}" } }] }),
+                ),
+                finish("chatcmpl-2", "tool_calls"),
+            ],
+            // Completion is signalled by the finish reason when `[DONE]` never arrives.
+            false,
+            &["apply_patch"],
+        )
+        .await;
+        let items = events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(item) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec![
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["ls"]}"#.to_string(),
+                    call_id: "call_a".to_string(),
+                },
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call_b".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_errors_and_truncated_streams() {
+        let events = run_chat_sse(
+            vec![json!({
+                "error": { "code": "context_length_exceeded", "message": "too long" },
+            })],
+            false,
+            &[],
+        )
+        .await;
+        assert_matches!(events.as_slice(), [Err(ApiError::ContextWindowExceeded)]);
+
+        let events = run_chat_sse(
+            vec![delta("chatcmpl-3", json!({ "content": "partial" }))],
+            false,
+            &[],
+        )
+        .await;
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(message)))
+            if message == "stream closed before chat completion finished");
+    }
+}
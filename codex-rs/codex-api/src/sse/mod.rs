@@ -1,5 +1,11 @@
+pub mod anthropic;
+pub mod chat;
 pub mod responses;
 
+pub use anthropic::process_anthropic_sse;
+pub use anthropic::spawn_anthropic_stream;
+pub use chat::process_chat_sse;
+pub use chat::spawn_chat_stream;
 pub use responses::process_sse;
 pub use responses::spawn_response_stream;
 pub use responses::stream_from_fixture;
@@ -913,6 +913,7 @@ mod tests {
                     network_access: false,
                     exclude_tmpdir_env_var: false,
                     exclude_slash_tmp: false,
+                    denied_read_paths: Vec::new(),
                 })
                 .is_ok()
         );
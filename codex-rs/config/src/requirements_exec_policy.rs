@@ -171,9 +171,11 @@ impl RequirementsExecPolicyToml {
                     pattern: PrefixPattern {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
+                        flags: Vec::new().into(),
                     },
                     decision,
                     justification: justification.clone(),
+                    when: None,
                 });
                 rules_by_program.insert(head.clone(), rule);
             }
@@ -80,8 +80,9 @@ pub(crate) fn convert_apply_patch_to_protocol(
     let mut result = HashMap::with_capacity(changes.len());
     for (path, change) in changes {
         let protocol_change = match change {
-            ApplyPatchFileChange::Add { content } => FileChange::Add {
+            ApplyPatchFileChange::Add { content, mode } => FileChange::Add {
                 content: content.clone(),
+                mode: *mode,
             },
             ApplyPatchFileChange::Delete { content } => FileChange::Delete {
                 content: content.clone(),
@@ -90,9 +91,23 @@ pub(crate) fn convert_apply_patch_to_protocol(
                 unified_diff,
                 move_path,
                 new_content: _new_content,
+                mode,
             } => FileChange::Update {
                 unified_diff: unified_diff.clone(),
                 move_path: move_path.clone(),
+                mode: *mode,
+            },
+            ApplyPatchFileChange::Binary {
+                contents,
+                replace,
+                mode,
+            } => FileChange::Binary {
+                size: contents.len(),
+                replace: *replace,
+                mode: *mode,
+            },
+            ApplyPatchFileChange::Symlink { target } => FileChange::Symlink {
+                target: target.clone(),
             },
         };
         result.insert(path.clone(), protocol_change);
@@ -119,7 +134,8 @@ mod tests {
         assert_eq!(
             got.get(&p),
             Some(&FileChange::Add {
-                content: "hello".to_string()
+                content: "hello".to_string(),
+                mode: None,
             })
         );
     }
@@ -20,6 +20,7 @@ use crate::apps::render_apps_section;
 use crate::commit_attribution::commit_message_trailer_instruction;
 use crate::compact;
 use crate::compact::InitialContextInjection;
+use crate::compact::run_compaction_with_hooks;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
@@ -55,10 +56,16 @@ use async_channel::Receiver;
 use async_channel::Sender;
 use chrono::Local;
 use chrono::Utc;
+use codex_hooks::HookCompactionTrigger;
+use codex_hooks::HookContextDecision;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
+use codex_hooks::HookEventBeforeUserTurn;
+use codex_hooks::HookEventSessionShutdown;
+use codex_hooks::HookEventSessionStart;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
+use codex_hooks::HookSessionSource;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
 use codex_network_proxy::NetworkProxy;
@@ -103,6 +110,7 @@ use codex_utils_stream_parser::AssistantTextStreamParser;
 use codex_utils_stream_parser::ProposedPlanSegment;
 use codex_utils_stream_parser::extract_proposed_plan_text;
 use codex_utils_stream_parser::strip_citations;
+use codex_utils_tokenizer::Tokenizer;
 use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
@@ -171,6 +179,7 @@ use crate::instructions::UserInstructions;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp::effective_mcp_servers;
+use crate::mcp::make_sampler;
 use crate::mcp::maybe_prompt_and_install_mcp_dependencies;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::McpConnectionManager;
@@ -249,6 +258,8 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tool_output_store::ToolOutputStore;
+use crate::tool_output_store::cleanup_stale_tool_outputs;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -265,6 +276,8 @@ use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::WorkspaceOverlay;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
@@ -643,6 +656,12 @@ impl TurnContext {
         })
     }
 
+    /// Tokenizer for the turn's model, or `None` when token counts fall back to
+    /// the byte estimate.
+    pub(crate) fn tokenizer(&self) -> Option<Tokenizer> {
+        Tokenizer::for_model(&self.model_info.slug)
+    }
+
     pub(crate) async fn with_model(&self, model: String, models_manager: &ModelsManager) -> Self {
         let mut config = (*self.config).clone();
         config.model = Some(model.clone());
@@ -1414,6 +1433,15 @@ impl Session {
                 (None, None)
             };
 
+        if config.features.enabled(Feature::ToolOutputSpill) {
+            let codex_home = config.codex_home.clone();
+            tokio::spawn(async move {
+                if let Err(err) = cleanup_stale_tool_outputs(&codex_home, conversation_id).await {
+                    warn!("failed to clean up stale tool outputs: {err}");
+                }
+            });
+        }
+
         let services = SessionServices {
             // Initialize the MCP connection manager with an uninitialized
             // instance. It will be replaced with one created via
@@ -1437,6 +1465,7 @@ impl Session {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
@@ -1465,6 +1494,15 @@ impl Session {
                 config.features.enabled(Feature::RuntimeMetrics),
                 Self::build_model_client_beta_features_header(config.as_ref()),
             ),
+            tool_output_store: if config.features.enabled(Feature::ToolOutputSpill) {
+                ToolOutputStore::new(&config.codex_home, conversation_id)
+            } else {
+                ToolOutputStore::disabled()
+            },
+            workspace_overlay: config
+                .permissions
+                .sandbox_copy_on_write
+                .then(|| Arc::new(WorkspaceOverlay::new(&config.codex_home, conversation_id))),
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
@@ -1488,6 +1526,28 @@ impl Session {
             let mut guard = network_policy_decider_session.write().await;
             *guard = Arc::downgrade(&sess);
         }
+        // Run session_start hooks before SessionConfigured so a hook that blocks or aborts
+        // fails session creation before clients are told the session exists.
+        let hook_session_source = match &initial_history {
+            InitialHistory::New => HookSessionSource::Startup,
+            InitialHistory::Resumed(_) => HookSessionSource::Resume,
+            InitialHistory::Forked(_) => HookSessionSource::Fork,
+        };
+        let session_start_context = sess
+            .run_lifecycle_hooks(
+                session_configuration.cwd.clone(),
+                session_configuration.app_server_client_name.clone(),
+                HookEvent::SessionStart {
+                    event: HookEventSessionStart {
+                        thread_id: conversation_id,
+                        source: hook_session_source,
+                        model: session_configuration.collaboration_mode.model().to_string(),
+                    },
+                },
+            )
+            .await
+            .map_err(anyhow::Error::msg)?;
+
         // Dispatch the SessionConfiguredEvent first and then report any errors.
         // If resuming, include converted initial messages in the payload so UIs can render them immediately.
         let initial_messages = initial_history.get_event_msgs();
@@ -1545,6 +1605,7 @@ impl Session {
             sandbox_state,
             config.codex_home.clone(),
             codex_apps_tools_cache_key(auth),
+            Some(make_sampler(Arc::downgrade(&sess))),
         )
         .await;
         {
@@ -1582,6 +1643,11 @@ impl Session {
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
+        if !session_start_context.is_empty() {
+            let turn_context = sess.new_default_turn().await;
+            let items = hook_context_items(session_start_context);
+            sess.record_conversation_items(&turn_context, &items).await;
+        }
 
         memories::start_memories_startup_task(
             &sess,
@@ -1664,10 +1730,14 @@ impl Session {
 
     pub(crate) async fn get_estimated_token_count(
         &self,
-        turn_context: &TurnContext,
+        turn_context: &Arc<TurnContext>,
     ) -> Option<i64> {
-        let state = self.state.lock().await;
-        state.history.estimate_token_count(turn_context)
+        let history = self.clone_history().await;
+        let turn_context = Arc::clone(turn_context);
+        tokio::task::spawn_blocking(move || history.estimate_token_count(&turn_context))
+            .await
+            .ok()
+            .flatten()
     }
 
     pub(crate) async fn get_base_instructions(&self) -> BaseInstructions {
@@ -2226,6 +2296,48 @@ impl Session {
         turn_context
     }
 
+    /// Overlay directory that sandboxed commands in `cwd` write to when
+    /// `copy_on_write` is enabled.
+    pub(crate) fn workspace_overlay_dir(&self, cwd: &Path) -> std::io::Result<Option<PathBuf>> {
+        self.services
+            .workspace_overlay
+            .as_ref()
+            .map(|overlay| overlay.prepare(cwd))
+            .transpose()
+    }
+
+    /// Copy-on-write overlay of `turn_context`'s cwd that sandboxed commands
+    /// see, so in-process reads can show what those commands wrote. `None`
+    /// when commands in this turn run without the Linux sandbox.
+    pub(crate) fn workspace_overlay_root(&self, turn_context: &TurnContext) -> Option<OverlayRoot> {
+        if matches!(
+            turn_context.sandbox_policy.get(),
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. }
+        ) {
+            return None;
+        }
+        self.services
+            .workspace_overlay
+            .as_ref()
+            .map(|overlay| overlay.root(&turn_context.cwd))
+    }
+
+    /// Reports the changes waiting in the copy-on-write overlay, if any.
+    pub(crate) async fn emit_workspace_overlay_changes(&self, turn_context: &TurnContext) {
+        let Some(overlay) = self.services.workspace_overlay.clone() else {
+            return;
+        };
+        match tokio::task::spawn_blocking(move || overlay.pending_changes()).await {
+            Ok(Ok(Some(changes))) => {
+                self.send_event(turn_context, EventMsg::WorkspaceOverlayChanges(changes))
+                    .await;
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(err)) => warn!("failed to read workspace overlay changes: {err}"),
+            Err(err) => warn!("failed to read workspace overlay changes: {err}"),
+        }
+    }
+
     pub(crate) async fn maybe_emit_unknown_model_warning_for_turn(&self, tc: &TurnContext) {
         if tc.model_info.used_fallback_model_metadata {
             self.send_event(
@@ -3233,11 +3345,7 @@ impl Session {
     }
 
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
-        let history = self.clone_history().await;
-        let base_instructions = self.get_base_instructions().await;
-        let Some(estimated_total_tokens) =
-            history.estimate_token_count_with_base_instructions(&base_instructions)
-        else {
+        let Some(estimated_total_tokens) = self.estimate_context_tokens(turn_context).await else {
             return;
         };
         {
@@ -3310,10 +3418,30 @@ impl Session {
             let state = self.state.lock().await;
             state.token_info_and_rate_limits()
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let estimated_tokens = self.estimate_context_tokens(turn_context).await;
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            estimated_tokens,
+        });
         self.send_event(turn_context, event).await;
     }
 
+    /// Token count of the history and base instructions the next request would
+    /// send. Tokenizing a long history takes a while, so it runs on a blocking
+    /// thread.
+    async fn estimate_context_tokens(&self, turn_context: &TurnContext) -> Option<i64> {
+        let history = self.clone_history().await;
+        let base_instructions = self.get_base_instructions().await;
+        let tokenizer = turn_context.tokenizer();
+        tokio::task::spawn_blocking(move || {
+            history.estimate_token_count_with_base_instructions(&base_instructions, tokenizer)
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
     pub(crate) async fn set_total_tokens_full(&self, turn_context: &TurnContext) {
         if let Some(context_window) = turn_context.model_context_window() {
             let mut state = self.state.lock().await;
@@ -3563,6 +3691,63 @@ impl Session {
         &self.services.hooks
     }
 
+    /// Dispatches a lifecycle hook event and folds the outcomes. Returns any
+    /// context added by `continue` decisions, or a user-facing message when a
+    /// hook blocked the operation or failed with `required = true`.
+    pub(crate) async fn run_lifecycle_hooks(
+        &self,
+        cwd: PathBuf,
+        client: Option<String>,
+        hook_event: HookEvent,
+    ) -> Result<Vec<String>, String> {
+        let event_name = hook_event.kind().as_str();
+        let hook_outcomes = self
+            .hooks()
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd,
+                client,
+                triggered_at: chrono::Utc::now(),
+                hook_event,
+            })
+            .await;
+
+        let mut additional_context = Vec::new();
+        for hook_outcome in hook_outcomes {
+            let hook_name = hook_outcome.hook_name;
+            match hook_outcome.result {
+                HookResult::Success | HookResult::Decision(_) => {}
+                HookResult::Context(HookContextDecision::Continue {
+                    additional_context: context,
+                }) => additional_context.extend(context),
+                HookResult::Context(HookContextDecision::Block { reason }) => {
+                    warn!(
+                        hook_name = %hook_name,
+                        reason = %reason,
+                        "{event_name} hook blocked the operation"
+                    );
+                    return Err(format!("{event_name} hook '{hook_name}' blocked: {reason}"));
+                }
+                HookResult::FailedContinue(error) => {
+                    warn!(
+                        hook_name = %hook_name,
+                        error = %error,
+                        "{event_name} hook failed; continuing"
+                    );
+                }
+                HookResult::FailedAbort(error) => {
+                    warn!(
+                        hook_name = %hook_name,
+                        error = %error,
+                        "{event_name} hook failed; aborting operation"
+                    );
+                    return Err(format!("{event_name} hook '{hook_name}' failed: {error}"));
+                }
+            }
+        }
+        Ok(additional_context)
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
@@ -3593,6 +3778,7 @@ impl Session {
             guard.cancel();
             *guard = CancellationToken::new();
         }
+        let sampler = self.services.mcp_connection_manager.read().await.sampler();
         let (refreshed_manager, cancel_token) = McpConnectionManager::new(
             &mcp_servers,
             store_mode,
@@ -3602,6 +3788,7 @@ impl Session {
             sandbox_state,
             config.codex_home.clone(),
             codex_apps_tools_cache_key(auth.as_ref()),
+            sampler,
         )
         .await;
         {
@@ -3790,6 +3977,16 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::ListMcpPrompts => {
+                handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+            }
+            Op::GetMcpPrompt {
+                server,
+                name,
+                arguments,
+            } => {
+                handlers::get_mcp_prompt(&sess, sub.id.clone(), server, name, arguments).await;
+            }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
@@ -3829,6 +4026,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::SetThreadName { name } => {
                 handlers::set_thread_name(&sess, sub.id.clone(), name).await;
             }
+            Op::ResolveWorkspaceOverlay { decision } => {
+                handlers::resolve_workspace_overlay(&sess, sub.id.clone(), decision).await;
+            }
             Op::RunUserShellCommand { command } => {
                 handlers::run_user_shell_command(&sess, sub.id.clone(), command).await;
             }
@@ -3863,8 +4063,10 @@ mod handlers {
     use crate::config::Config;
 
     use crate::mcp::auth::compute_auth_statuses;
+    use crate::mcp::collect_mcp_prompts_from_manager;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
+    use crate::mcp::prompt_result_text;
     use crate::review_prompts::resolve_review_request;
     use crate::rollout::session_index;
     use crate::tasks::CompactTask;
@@ -3880,6 +4082,7 @@ mod handlers {
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
+    use codex_protocol::protocol::McpGetPromptResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
@@ -3893,6 +4096,7 @@ mod handlers {
     use codex_protocol::protocol::ThreadRolledBackEvent;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::WarningEvent;
+    use codex_protocol::protocol::WorkspaceOverlayDecision;
     use codex_protocol::request_user_input::RequestUserInputResponse;
 
     use crate::context_manager::is_user_turn_boundary;
@@ -3904,6 +4108,7 @@ mod handlers {
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
@@ -4208,6 +4413,43 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
+        let prompts = collect_mcp_prompts_from_manager(&mcp_connection_manager).await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::McpListPromptsResponse(prompts),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_mcp_prompt(
+        sess: &Session,
+        sub_id: String,
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) {
+        let result = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(&server, &name, arguments)
+            .await
+            .map(|result| prompt_result_text(&result))
+            .map_err(|err| format!("{err:#}"));
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::McpGetPromptResponse(McpGetPromptResponseEvent {
+                server,
+                name,
+                result,
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -4547,8 +4789,74 @@ mod handlers {
         .await;
     }
 
+    pub async fn resolve_workspace_overlay(
+        sess: &Arc<Session>,
+        sub_id: String,
+        decision: WorkspaceOverlayDecision,
+    ) {
+        let Some(overlay) = sess.services.workspace_overlay.clone() else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Copy-on-write sandboxing is not enabled for this session."
+                        .to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        };
+
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        if has_active_turn {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Cannot merge sandbox changes while a turn is in progress."
+                        .to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        }
+
+        let result = tokio::task::spawn_blocking(move || overlay.resolve(&decision)).await;
+        let msg = match result {
+            Ok(Ok(resolved)) => EventMsg::WorkspaceOverlayResolved(resolved),
+            Ok(Err(err)) => EventMsg::Error(ErrorEvent {
+                message: format!("Failed to merge sandbox changes: {err}"),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("Failed to merge sandbox changes: {err}"),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
+        let (cwd, client) = {
+            let state = sess.state.lock().await;
+            (
+                state.session_configuration.cwd.clone(),
+                state.session_configuration.app_server_client_name.clone(),
+            )
+        };
+        // Shutdown cannot be refused; failures are logged by the dispatcher.
+        let _ = sess
+            .run_lifecycle_hooks(
+                cwd,
+                client,
+                HookEvent::SessionShutdown {
+                    event: HookEventSessionShutdown {
+                        thread_id: sess.conversation_id,
+                    },
+                },
+            )
+            .await;
         let _ = sess.conversation.shutdown().await;
         sess.services
             .unified_exec_manager
@@ -4853,6 +5161,8 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+
+    let user_turn_context = before_user_turn_context(&sess, &turn_context, &input).await?;
     // TODO(ccunningham): Pre-turn compaction runs before context updates and the
     // new user message are recorded. Estimate pending incoming items (context
     // diffs/full reinjection + user input) and trigger compaction preemptively
@@ -4984,6 +5294,11 @@ pub(crate) async fn run_turn(
     sess.set_previous_model(Some(turn_context.model_info.slug.clone()))
         .await;
 
+    if !user_turn_context.is_empty() {
+        sess.record_conversation_items(&turn_context, &user_turn_context)
+            .await;
+    }
+
     if !skill_items.is_empty() {
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
@@ -5012,6 +5327,21 @@ pub(crate) async fn run_turn(
             .into_iter()
             .map(ResponseItem::from)
             .collect::<Vec<ResponseItem>>();
+        let pending_user_input = pending_response_items
+            .iter()
+            .filter_map(|item| match parse_turn_item(item) {
+                Some(TurnItem::UserMessage(user_message)) => Some(user_message.content),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<UserInput>>();
+        // Mid-turn input is a user turn of its own for the hooks, so a
+        // blocked message ends the turn before it reaches the model.
+        let pending_turn_context = if pending_user_input.is_empty() {
+            Vec::new()
+        } else {
+            before_user_turn_context(&sess, &turn_context, &pending_user_input).await?
+        };
 
         if !pending_response_items.is_empty() {
             for response_item in pending_response_items {
@@ -5032,6 +5362,10 @@ pub(crate) async fn run_turn(
                 }
             }
         }
+        if !pending_turn_context.is_empty() {
+            sess.record_conversation_items(&turn_context, &pending_turn_context)
+                .await;
+        }
 
         // Construct the input that we will send to the model.
         let sampling_request_input: Vec<ResponseItem> = {
@@ -5071,8 +5405,7 @@ pub(crate) async fn run_turn(
                 let total_usage_tokens = sess.get_total_token_usage().await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
-                let estimated_token_count =
-                    sess.get_estimated_token_count(turn_context.as_ref()).await;
+                let estimated_token_count = sess.get_estimated_token_count(&turn_context).await;
 
                 trace!(
                     turn_id = %turn_context.sub_id,
@@ -5124,7 +5457,9 @@ pub(crate) async fn run_turn(
                     for hook_outcome in hook_outcomes {
                         let hook_name = hook_outcome.hook_name;
                         match hook_outcome.result {
-                            HookResult::Success => {}
+                            HookResult::Success
+                            | HookResult::Decision(_)
+                            | HookResult::Context(_) => {}
                             HookResult::FailedContinue(error) => {
                                 warn!(
                                     turn_id = %turn_context.sub_id,
@@ -5278,24 +5613,77 @@ async fn run_auto_compact(
     initial_context_injection: InitialContextInjection,
     previous_user_turn_model: Option<&str>,
 ) -> CodexResult<()> {
-    if should_use_remote_compact_task(&turn_context.provider) {
-        run_inline_remote_auto_compact_task(
-            Arc::clone(sess),
-            Arc::clone(turn_context),
-            initial_context_injection,
-            previous_user_turn_model,
-        )
-        .await?;
-    } else {
-        run_inline_auto_compact_task(
-            Arc::clone(sess),
-            Arc::clone(turn_context),
-            initial_context_injection,
-            previous_user_turn_model,
+    run_compaction_with_hooks(sess, turn_context, HookCompactionTrigger::Auto, async {
+        if should_use_remote_compact_task(&turn_context.provider) {
+            run_inline_remote_auto_compact_task(
+                Arc::clone(sess),
+                Arc::clone(turn_context),
+                initial_context_injection,
+                previous_user_turn_model,
+            )
+            .await
+        } else {
+            run_inline_auto_compact_task(
+                Arc::clone(sess),
+                Arc::clone(turn_context),
+                initial_context_injection,
+                previous_user_turn_model,
+            )
+            .await
+        }
+    })
+    .await
+}
+
+/// Runs the `before_user_turn` hooks for `input`, returning the context they
+/// add. When a hook blocks the turn, reports it and returns `None`.
+async fn before_user_turn_context(
+    sess: &Session,
+    turn_context: &TurnContext,
+    input: &[UserInput],
+) -> Option<Vec<ResponseItem>> {
+    let result = sess
+        .run_lifecycle_hooks(
+            turn_context.cwd.clone(),
+            turn_context.app_server_client_name.clone(),
+            HookEvent::BeforeUserTurn {
+                event: HookEventBeforeUserTurn {
+                    thread_id: sess.conversation_id,
+                    turn_id: turn_context.sub_id.clone(),
+                    input_messages: input
+                        .iter()
+                        .filter_map(|item| match item {
+                            UserInput::Text { text, .. } => Some(text.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                },
+            },
         )
-        .await?;
+        .await;
+    match result {
+        Ok(additional_context) => Some(hook_context_items(additional_context)),
+        Err(message) => {
+            sess.send_event(
+                turn_context,
+                EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: None,
+                }),
+            )
+            .await;
+            None
+        }
     }
-    Ok(())
+}
+
+/// Wraps context returned by `session_start` and `before_user_turn` hooks as
+/// developer messages.
+fn hook_context_items(additional_context: Vec<String>) -> Vec<ResponseItem> {
+    additional_context
+        .into_iter()
+        .map(|text| DeveloperInstructions::new(text).into())
+        .collect()
 }
 
 fn collect_explicit_app_ids_from_skill_items(
@@ -5467,6 +5855,7 @@ fn build_prompt(
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     }
 }
 #[allow(clippy::too_many_arguments)]
@@ -5878,8 +6267,10 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::ThreadNameUpdated(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
+        | EventMsg::McpToolsChanged(_)
         | EventMsg::McpToolCallBegin(_)
         | EventMsg::McpToolCallEnd(_)
+        | EventMsg::McpSamplingEnd(_)
         | EventMsg::WebSearchBegin(_)
         | EventMsg::WebSearchEnd(_)
         | EventMsg::ExecCommandBegin(_)
@@ -5901,9 +6292,13 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::UndoCompleted(_)
         | EventMsg::StreamError(_)
         | EventMsg::TurnDiff(_)
+        | EventMsg::WorkspaceOverlayChanges(_)
+        | EventMsg::WorkspaceOverlayResolved(_)
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::McpListPromptsResponse(_)
+        | EventMsg::McpGetPromptResponse(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
         | EventMsg::RemoteSkillDownloaded(_)
@@ -7591,24 +7986,28 @@ mod tests {
             TokenCountEvent {
                 info: Some(info1),
                 rate_limits: None,
+                estimated_tokens: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                estimated_tokens: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: Some(info2.clone()),
                 rate_limits: None,
+                estimated_tokens: None,
             },
         )));
         rollout_items.push(RolloutItem::EventMsg(EventMsg::TokenCount(
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                estimated_tokens: None,
             },
         )));
 
@@ -7644,7 +8043,10 @@ mod tests {
             text: override_instructions,
         };
         let expected_tokens = history
-            .estimate_token_count_with_base_instructions(&session_base_instructions)
+            .estimate_token_count_with_base_instructions(
+                &session_base_instructions,
+                turn_context.tokenizer(),
+            )
             .expect("estimate with session base instructions");
         let model_estimated_tokens = history
             .estimate_token_count(&turn_context)
@@ -8103,6 +8505,7 @@ mod tests {
             aggregated_output: StreamOutput::new("Command output".to_string()),
             duration: StdDuration::from_secs(1),
             timed_out: true,
+            resource_limit_exceeded: None,
         };
         let (_, turn_context) = make_session_and_context().await;
 
@@ -8498,6 +8901,7 @@ mod tests {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
@@ -8526,6 +8930,8 @@ mod tests {
                 config.features.enabled(Feature::RuntimeMetrics),
                 Session::build_model_client_beta_features_header(config.as_ref()),
             ),
+            tool_output_store: ToolOutputStore::disabled(),
+            workspace_overlay: None,
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
@@ -8658,6 +9064,7 @@ mod tests {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
@@ -8686,6 +9093,8 @@ mod tests {
                 config.features.enabled(Feature::RuntimeMetrics),
                 Session::build_model_client_beta_features_header(config.as_ref()),
             ),
+            tool_output_store: ToolOutputStore::disabled(),
+            workspace_overlay: None,
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
@@ -9627,6 +10036,7 @@ mod tests {
                     })
                     .to_string(),
                 },
+                hook_approval: None,
             })
             .await;
 
@@ -9666,6 +10076,7 @@ mod tests {
                     })
                     .to_string(),
                 },
+                hook_approval: None,
             })
             .await;
 
@@ -9725,6 +10136,7 @@ mod tests {
                     })
                     .to_string(),
                 },
+                hook_approval: None,
             })
             .await;
 
@@ -14,9 +14,12 @@ use crate::protocol::EventMsg;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
+use crate::truncate::policy_token_count;
 use crate::truncate::truncate_text;
 use crate::util::backoff;
+use codex_hooks::HookCompactionTrigger;
+use codex_hooks::HookEvent;
+use codex_hooks::HookEventCompaction;
 use codex_protocol::items::ContextCompactionItem;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
@@ -50,6 +53,46 @@ pub(crate) fn should_use_remote_compact_task(provider: &ModelProviderInfo) -> bo
     provider.is_openai()
 }
 
+/// Runs `compact` between the `before_compaction` and `after_compaction` hooks.
+///
+/// Compaction hooks only observe: a failing hook is logged and never stops
+/// compaction. `after_compaction` runs only when compaction succeeded.
+pub(crate) async fn run_compaction_with_hooks<F>(
+    sess: &Session,
+    turn_context: &TurnContext,
+    trigger: HookCompactionTrigger,
+    compact: F,
+) -> CodexResult<()>
+where
+    F: Future<Output = CodexResult<()>>,
+{
+    let compaction_event = HookEventCompaction {
+        thread_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        trigger,
+    };
+    let _ = sess
+        .run_lifecycle_hooks(
+            turn_context.cwd.clone(),
+            turn_context.app_server_client_name.clone(),
+            HookEvent::BeforeCompaction {
+                event: compaction_event.clone(),
+            },
+        )
+        .await;
+    compact.await?;
+    let _ = sess
+        .run_lifecycle_hooks(
+            turn_context.cwd.clone(),
+            turn_context.app_server_client_name.clone(),
+            HookEvent::AfterCompaction {
+                event: compaction_event,
+            },
+        )
+        .await;
+    Ok(())
+}
+
 pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
@@ -359,7 +402,7 @@ fn build_compacted_history_with_limit(
             if remaining == 0 {
                 break;
             }
-            let tokens = approx_token_count(message);
+            let tokens = policy_token_count(message);
             if tokens <= remaining {
                 selected_messages.push(message.clone());
                 remaining = remaining.saturating_sub(tokens);
@@ -120,6 +120,7 @@ async fn run_remote_compact_task_inner_impl(
         base_instructions,
         personality: turn_context.personality,
         output_schema: None,
+        max_output_tokens: None,
     };
 
     let mut new_history = sess
@@ -289,8 +290,10 @@ fn trim_function_call_history_to_fit_context_window(
         return deleted_items;
     };
 
+    // The byte estimate is cheap enough to redo after every removal; running the
+    // tokenizer over the whole history each time is not.
     while history
-        .estimate_token_count_with_base_instructions(base_instructions)
+        .estimate_token_count_with_base_instructions(base_instructions, None)
         .is_some_and(|estimated_tokens| estimated_tokens > context_window)
     {
         let Some(last_item) = history.raw_items().last() else {
@@ -200,6 +200,9 @@ mod document_helpers {
         {
             entry["oauth_resource"] = value(resource.clone());
         }
+        if let Some(limit) = config.sampling_token_limit {
+            entry["sampling_token_limit"] = value(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
 
         entry
     }
@@ -1471,6 +1474,7 @@ gpt-5 = "gpt-5.1"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -1496,6 +1500,7 @@ gpt-5 = "gpt-5.1"
                 disabled_tools: Some(vec!["forbidden".to_string()]),
                 scopes: None,
                 oauth_resource: Some("https://resource.example.com".to_string()),
+                sampling_token_limit: None,
             },
         );
 
@@ -1565,6 +1570,7 @@ foo = { command = "cmd" }
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -1612,6 +1618,7 @@ foo = { command = "cmd" } # keep me
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -1658,6 +1665,7 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -1705,6 +1713,7 @@ foo = { command = "cmd" }
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -4,6 +4,7 @@ use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HookToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
@@ -25,6 +26,7 @@ use crate::config::types::WindowsSandboxModeToml;
 use crate::config::types::WindowsToml;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::ConstrainedWithSource;
 use crate::config_loader::LoaderOverrides;
@@ -55,11 +57,14 @@ use crate::windows_sandbox::WindowsSandboxLevelExt;
 use crate::windows_sandbox::resolve_windows_sandbox_mode;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::SandboxResourceLimits;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WebSearchMode;
@@ -79,6 +84,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -168,6 +174,13 @@ pub struct Permissions {
     /// Optional macOS seatbelt extension profile used to extend default
     /// seatbelt permissions when running under seatbelt.
     pub macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
+    /// Resource limits applied by the Linux sandbox helper from
+    /// `[sandbox_resources]`.
+    pub sandbox_resource_limits: SandboxResourceLimits,
+    /// Whether sandboxed commands write to a copy-on-write overlay of the
+    /// workspace instead of the workspace itself, from `copy_on_write` under
+    /// `[sandbox_workspace_write]`.
+    pub sandbox_copy_on_write: bool,
 }
 
 /// Application configuration loaded from disk and merged with overrides.
@@ -263,6 +276,10 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Command hooks declared in `[[hooks]]` tables, collected from every
+    /// enabled config layer (lowest precedence first).
+    pub hooks: Vec<CommandHookConfig>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -1031,6 +1048,15 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Paths (globs allowed) that commands cannot read when `sandbox` is
+    /// `ReadOnly` or `WorkspaceWrite`. The `read_file` and `grep_files` tools
+    /// honor the same list.
+    pub sandbox_denied_read_paths: Option<Vec<String>>,
+
+    /// Limits on memory, CPU time, processes, open files, file size and
+    /// wall-clock time for commands run under the Linux sandbox.
+    pub sandbox_resources: Option<SandboxResourceLimits>,
+
     /// Nested permissions settings.
     #[serde(default)]
     pub permissions: Option<PermissionsToml>,
@@ -1039,6 +1065,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Command hooks to run at lifecycle events. Project config layers add to
+    /// the hooks declared in lower-precedence layers rather than replacing them.
+    pub hooks: Option<Vec<HookToml>>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -1450,12 +1480,14 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    copy_on_write: _,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     read_only_access: ReadOnlyAccess::FullAccess,
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    denied_read_paths: Vec::new(),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -1485,6 +1517,17 @@ impl ConfigToml {
             sandbox_policy = constraint.get().clone();
             downgrade_workspace_write_if_unsupported(&mut sandbox_policy);
         }
+        if let Some(paths) = &self.sandbox_denied_read_paths {
+            match &mut sandbox_policy {
+                SandboxPolicy::ReadOnly {
+                    denied_read_paths, ..
+                }
+                | SandboxPolicy::WorkspaceWrite {
+                    denied_read_paths, ..
+                } => denied_read_paths.clone_from(paths),
+                SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {}
+            }
+        }
         sandbox_policy
     }
 
@@ -1660,6 +1703,7 @@ impl Config {
         config_layer_stack: ConfigLayerStack,
     ) -> std::io::Result<Self> {
         let requirements = config_layer_stack.requirements().clone();
+        let hooks = hooks_from_config_layers(&config_layer_stack, cfg.hooks.as_deref())?;
         let user_instructions = Self::load_instructions(Some(&codex_home));
         let mut startup_warnings = Vec::new();
 
@@ -2075,6 +2119,17 @@ impl Config {
             network.enabled().then_some(network)
         };
 
+        let copy_on_write_requested = cfg
+            .sandbox_workspace_write
+            .as_ref()
+            .is_some_and(|settings| settings.copy_on_write);
+        if copy_on_write_requested && !cfg!(target_os = "linux") {
+            startup_warnings.push(
+                "`copy_on_write` under `[sandbox_workspace_write]` is only supported on Linux; sandboxed commands write to the workspace directly."
+                    .to_string(),
+            );
+        }
+
         let config = Self {
             model,
             review_model,
@@ -2092,10 +2147,13 @@ impl Config {
                 shell_environment_policy,
                 windows_sandbox_mode,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: cfg.sandbox_resources.unwrap_or_default(),
+                sandbox_copy_on_write: copy_on_write_requested && cfg!(target_os = "linux"),
             },
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             notify: cfg.notify,
+            hooks,
             user_instructions,
             base_instructions,
             personality,
@@ -2349,6 +2407,63 @@ impl Config {
     }
 }
 
+/// Collects `[[hooks]]` from every enabled config layer, lowest precedence
+/// first. Unlike most keys, hooks accumulate across layers instead of the
+/// highest-precedence array replacing the others, so a trusted project can add
+/// hooks on top of the user's. Disabled (e.g. untrusted project) layers
+/// contribute nothing.
+fn hooks_from_config_layers(
+    config_layer_stack: &ConfigLayerStack,
+    merged_hooks: Option<&[HookToml]>,
+) -> std::io::Result<Vec<CommandHookConfig>> {
+    let layers =
+        config_layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false);
+    let hook_tables = if layers.is_empty() {
+        merged_hooks.map(<[HookToml]>::to_vec).unwrap_or_default()
+    } else {
+        let mut hook_tables = Vec::new();
+        for layer in layers {
+            let Some(value) = layer.config.get("hooks") else {
+                continue;
+            };
+            let layer_hooks: Vec<HookToml> = value.clone().try_into().map_err(|err| {
+                std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid [[hooks]] in {:?}: {err}", layer.name),
+                )
+            })?;
+            hook_tables.extend(layer_hooks);
+        }
+        hook_tables
+    };
+
+    hook_tables
+        .into_iter()
+        .map(|hook| {
+            let name = hook
+                .name
+                .or_else(|| hook.command.first().cloned())
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        "[[hooks]] entries must set a non-empty `command`",
+                    )
+                })?;
+            Ok(CommandHookConfig {
+                name,
+                event: hook.event,
+                matcher: hook.matcher,
+                argv: hook.command,
+                timeout: hook
+                    .timeout_sec
+                    .map_or(DEFAULT_COMMAND_HOOK_TIMEOUT, Duration::from_secs),
+                required: hook.required,
+            })
+        })
+        .collect()
+}
+
 pub(crate) fn uses_deprecated_instructions_file(config_layer_stack: &ConfigLayerStack) -> bool {
     config_layer_stack
         .layers_high_to_low()
@@ -2408,6 +2523,8 @@ mod tests {
     use codex_config::CONFIG_TOML_FILE;
 
     use super::*;
+    use crate::config_loader::ConfigLayerEntry;
+    use codex_app_server_protocol::ConfigLayerSource;
     use core_test_support::test_absolute_path;
     use pretty_assertions::assert_eq;
 
@@ -2434,6 +2551,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         }
     }
 
@@ -2454,6 +2572,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         }
     }
 
@@ -2566,6 +2685,12 @@ allowed_domains = ["openai.com"]
                 denied_domains: None,
                 allow_unix_sockets: None,
                 allow_local_binding: None,
+                mitm: None,
+                request_rules: None,
+                credentials: None,
+                mitm_log_body_excerpts: None,
+                har_record_dir: None,
+                har_replay_file: None,
             }
         );
     }
@@ -2746,6 +2871,7 @@ exclude_slash_tmp = true
                     network_access: false,
                     exclude_tmpdir_env_var: true,
                     exclude_slash_tmp: true,
+                    denied_read_paths: Vec::new(),
                 }
             );
         }
@@ -2788,6 +2914,7 @@ trust_level = "trusted"
                     network_access: false,
                     exclude_tmpdir_env_var: true,
                     exclude_slash_tmp: true,
+                    denied_read_paths: Vec::new(),
                 }
             );
         }
@@ -3492,6 +3619,7 @@ profile = "project"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
 
@@ -3650,6 +3778,7 @@ bearer_token = "secret"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -3722,6 +3851,7 @@ ZIG_VAR = "3"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -3774,6 +3904,7 @@ ZIG_VAR = "3"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -3824,6 +3955,7 @@ ZIG_VAR = "3"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -3890,6 +4022,7 @@ startup_timeout_sec = 2.0
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
         apply_blocking(
@@ -3968,6 +4101,7 @@ X-Auth = "DOCS_AUTH"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -3999,6 +4133,7 @@ X-Auth = "DOCS_AUTH"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         );
         apply_blocking(
@@ -4068,6 +4203,7 @@ url = "https://example.com/mcp"
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             ),
             (
@@ -4089,6 +4225,7 @@ url = "https://example.com/mcp"
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             ),
         ]);
@@ -4173,6 +4310,7 @@ url = "https://example.com/mcp"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -4219,6 +4357,7 @@ url = "https://example.com/mcp"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -4265,6 +4404,7 @@ url = "https://example.com/mcp"
                 disabled_tools: Some(vec!["blocked".to_string()]),
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -4315,6 +4455,7 @@ url = "https://example.com/mcp"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: Some("https://resource.example.com".to_string()),
+                sampling_token_limit: None,
             },
         )]);
 
@@ -4743,6 +4884,7 @@ model_verbosity = "high"
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: false,
         };
@@ -4809,11 +4951,14 @@ model_verbosity = "high"
                     shell_environment_policy: ShellEnvironmentPolicy::default(),
                     windows_sandbox_mode: None,
                     macos_seatbelt_profile_extensions: None,
+                    sandbox_resource_limits: SandboxResourceLimits::default(),
+                    sandbox_copy_on_write: false,
                 },
                 enforce_residency: Constrained::allow_any(None),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 user_instructions: None,
                 notify: None,
+                hooks: Vec::new(),
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4936,11 +5081,14 @@ model_verbosity = "high"
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             },
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -5061,11 +5209,14 @@ model_verbosity = "high"
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             },
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -5172,11 +5323,14 @@ model_verbosity = "high"
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             },
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -5339,6 +5493,57 @@ model_verbosity = "high"
         Ok(())
     }
 
+    #[test]
+    fn hooks_accumulate_across_enabled_config_layers() -> std::io::Result<()> {
+        let hooks_layer = |command: &str| {
+            toml::from_str::<TomlValue>(&format!(
+                r#"
+[[hooks]]
+event = "before_tool_use"
+command = ["{command}"]
+"#
+            ))
+            .expect("hooks layer")
+        };
+        let config_layer_stack = ConfigLayerStack::new(
+            vec![
+                ConfigLayerEntry::new(
+                    ConfigLayerSource::User {
+                        file: test_absolute_path("/home/user/.codex/config.toml"),
+                    },
+                    hooks_layer("user-hook"),
+                ),
+                ConfigLayerEntry::new(
+                    ConfigLayerSource::Project {
+                        dot_codex_folder: test_absolute_path("/repo/.codex"),
+                    },
+                    hooks_layer("project-hook"),
+                ),
+                ConfigLayerEntry::new_disabled(
+                    ConfigLayerSource::Project {
+                        dot_codex_folder: test_absolute_path("/repo/untrusted/.codex"),
+                    },
+                    hooks_layer("untrusted-hook"),
+                    "untrusted project",
+                ),
+            ],
+            ConfigRequirements::default(),
+            crate::config_loader::ConfigRequirementsToml::default(),
+        )?;
+
+        let hooks = hooks_from_config_layers(&config_layer_stack, None)?;
+
+        assert_eq!(
+            hooks
+                .iter()
+                .map(|hook| hook.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["user-hook", "project-hook"]
+        );
+        assert_eq!(hooks[0].timeout, DEFAULT_COMMAND_HOOK_TIMEOUT);
+        Ok(())
+    }
+
     #[test]
     fn test_set_project_trusted_writes_explicit_tables() -> anyhow::Result<()> {
         let project_dir = Path::new("/some/path");
@@ -1,5 +1,8 @@
+use codex_network_proxy::NetworkCredential;
 use codex_network_proxy::NetworkMode;
 use codex_network_proxy::NetworkProxyConfig;
+use codex_network_proxy::NetworkRequestRule;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -31,6 +34,23 @@ pub struct NetworkToml {
     pub denied_domains: Option<Vec<String>>,
     pub allow_unix_sockets: Option<Vec<String>>,
     pub allow_local_binding: Option<bool>,
+    /// Terminate HTTPS CONNECT tunnels so limited mode and `request_rules` apply to the inner
+    /// requests.
+    pub mitm: Option<bool>,
+    /// Path- and method-level rules for hosts that already pass the domain lists.
+    #[schemars(with = "Option<Vec<NetworkRequestRuleSchema>>")]
+    pub request_rules: Option<Vec<NetworkRequestRule>>,
+    /// Headers the proxy adds to intercepted HTTPS requests, with values read from the secrets
+    /// store. Requires `mitm`.
+    #[schemars(with = "Option<Vec<NetworkCredentialSchema>>")]
+    pub credentials: Option<Vec<NetworkCredential>>,
+    /// Debug option: log redacted excerpts of intercepted request and response bodies instead of
+    /// only their lengths.
+    pub mitm_log_body_excerpts: Option<bool>,
+    /// Directory to record proxied requests to, one HAR file per session.
+    pub har_record_dir: Option<AbsolutePathBuf>,
+    /// HAR file to answer requests from instead of the network.
+    pub har_replay_file: Option<AbsolutePathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -40,6 +60,40 @@ enum NetworkModeSchema {
     Full,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct NetworkRequestRuleSchema {
+    /// Host pattern, using the same syntax as `allowed_domains`.
+    host: String,
+    /// Path the rule applies to, along with everything below it. Defaults to `/`.
+    path_prefix: Option<String>,
+    /// HTTP methods the rule applies to. Empty or unset means every method.
+    methods: Option<Vec<String>>,
+    action: NetworkRuleActionSchema,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum NetworkRuleActionSchema {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct NetworkCredentialSchema {
+    /// Host pattern, using the same syntax as `allowed_domains`.
+    host: String,
+    /// Header to set, e.g. `Authorization`. Replaces any value sent by the client.
+    header: String,
+    /// Name of the secret holding the header value.
+    secret: String,
+    /// Text placed before the secret value, e.g. `Bearer `.
+    prefix: Option<String>,
+    /// Environment the secret is scoped to. Global secrets are used when unset.
+    environment: Option<String>,
+}
+
 impl NetworkToml {
     pub(crate) fn apply_to_network_proxy_config(&self, config: &mut NetworkProxyConfig) {
         if let Some(enabled) = self.enabled {
@@ -93,6 +147,24 @@ impl NetworkToml {
         if let Some(allow_local_binding) = self.allow_local_binding {
             config.network.allow_local_binding = allow_local_binding;
         }
+        if let Some(mitm) = self.mitm {
+            config.network.mitm = mitm;
+        }
+        if let Some(request_rules) = self.request_rules.as_ref() {
+            config.network.request_rules = request_rules.clone();
+        }
+        if let Some(credentials) = self.credentials.as_ref() {
+            config.network.credentials = credentials.clone();
+        }
+        if let Some(mitm_log_body_excerpts) = self.mitm_log_body_excerpts {
+            config.network.mitm_log_body_excerpts = mitm_log_body_excerpts;
+        }
+        if let Some(har_record_dir) = self.har_record_dir.as_ref() {
+            config.network.har_record_dir = Some(har_record_dir.to_path_buf());
+        }
+        if let Some(har_replay_file) = self.har_replay_file.as_ref() {
+            config.network.har_replay_file = Some(har_replay_file.to_path_buf());
+        }
     }
 
     pub(crate) fn to_network_proxy_config(&self) -> NetworkProxyConfig {
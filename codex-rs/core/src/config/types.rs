@@ -4,6 +4,8 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use codex_hooks::HookEventKind;
+use codex_hooks::HookMatcher;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
@@ -103,6 +105,12 @@ pub struct McpServerConfig {
     /// Optional OAuth resource parameter to include during MCP login (RFC 8707).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub oauth_resource: Option<String>,
+
+    /// Total output tokens this server may consume through
+    /// `sampling/createMessage` requests in one session. `0` disables sampling
+    /// for the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_token_limit: Option<u64>,
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -149,6 +157,8 @@ pub(crate) struct RawMcpServerConfig {
     pub scopes: Option<Vec<String>>,
     #[serde(default)]
     pub oauth_resource: Option<String>,
+    #[serde(default)]
+    pub sampling_token_limit: Option<u64>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -173,6 +183,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
         let oauth_resource = raw.oauth_resource.clone();
+        let sampling_token_limit = raw.sampling_token_limit;
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -231,6 +242,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             disabled_tools,
             scopes,
             oauth_resource,
+            sampling_token_limit,
         })
     }
 }
@@ -328,6 +340,35 @@ impl UriBasedFileOpener {
     }
 }
 
+/// A command hook declared in a `[[hooks]]` table.
+///
+/// The command receives the serialized hook payload on stdin and may answer
+/// with a JSON decision on stdout.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookToml {
+    /// Name used in logs and error messages. Defaults to the program name.
+    pub name: Option<String>,
+
+    /// Lifecycle event that triggers the hook.
+    pub event: HookEventKind,
+
+    /// Restricts tool events to matching tool calls.
+    #[serde(default)]
+    pub matcher: HookMatcher,
+
+    /// Program and arguments to run.
+    pub command: Vec<String>,
+
+    /// Maximum time to wait for the command. Defaults to 30 seconds.
+    pub timeout_sec: Option<u64>,
+
+    /// When true, a failing or timed-out hook aborts the operation instead of
+    /// only being logged.
+    #[serde(default)]
+    pub required: bool,
+}
+
 /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -763,6 +804,10 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Linux only: send sandboxed writes to the workspace into a per-session
+    /// overlay and merge them into the real workspace only after review.
+    #[serde(default)]
+    pub copy_on_write: bool,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
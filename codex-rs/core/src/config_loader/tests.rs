@@ -431,6 +431,7 @@ allowed_sandbox_modes = ["read-only"]
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             })
             .is_err()
     );
@@ -2,8 +2,8 @@ use crate::codex::TurnContext;
 use crate::context_manager::normalize;
 use crate::event_mapping::is_contextual_user_message_content;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count_i64;
+use crate::truncate::token_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
 use codex_protocol::models::BaseInstructions;
@@ -16,7 +16,15 @@ use codex_protocol::openai_models::InputModality;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use codex_protocol::protocol::TurnContextItem;
+use codex_utils_tokenizer::EncodingKind;
+use codex_utils_tokenizer::Tokenizer;
+use std::collections::HashMap;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -33,8 +41,14 @@ pub(crate) struct ContextManager {
     /// When this is `None`, settings diffing treats the next turn as having no
     /// baseline and emits a full reinjection of context state.
     reference_context_item: Option<TurnContextItem>,
+    /// Token counts from the last tokenizer estimate, shared with clones so
+    /// that re-estimating only tokenizes the items added since then.
+    token_counts: Arc<Mutex<TokenCounts>>,
 }
 
+/// Token counts keyed by the encoding and a hash of the counted text.
+type TokenCounts = HashMap<(EncodingKind, u64), usize>;
+
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct TotalTokenUsageBreakdown {
     pub last_api_response_total_tokens: i64,
@@ -49,6 +63,7 @@ impl ContextManager {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
             reference_context_item: None,
+            token_counts: Arc::default(),
         }
     }
 
@@ -111,28 +126,60 @@ impl ContextManager {
         &self.items
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
+    // Estimate token usage with the model's tokenizer, falling back to the
+    // byte-based heuristics from the truncation helpers for unknown models.
+    // Items are counted in their serialized form, so this is an estimate of the
+    // prompt size rather than the exact count the server reports.
     pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let model_info = &turn_context.model_info;
         let personality = turn_context.personality.or(turn_context.config.personality);
         let base_instructions = BaseInstructions {
             text: model_info.get_model_instructions(personality),
         };
-        self.estimate_token_count_with_base_instructions(&base_instructions)
+        self.estimate_token_count_with_base_instructions(
+            &base_instructions,
+            turn_context.tokenizer(),
+        )
     }
 
     pub(crate) fn estimate_token_count_with_base_instructions(
         &self,
         base_instructions: &BaseInstructions,
+        tokenizer: Option<Tokenizer>,
     ) -> Option<i64> {
-        let base_tokens =
-            i64::try_from(approx_token_count(&base_instructions.text)).unwrap_or(i64::MAX);
+        let Some(tokenizer) = tokenizer else {
+            let base_tokens =
+                i64::try_from(token_count(&base_instructions.text, None)).unwrap_or(i64::MAX);
+            let items_tokens = self
+                .items
+                .iter()
+                .map(estimate_item_token_count)
+                .fold(0i64, i64::saturating_add);
+            return Some(base_tokens.saturating_add(items_tokens));
+        };
+
+        // Counts are only kept for the texts of this estimate, so items that
+        // left the history do not accumulate.
+        let mut token_counts = self
+            .token_counts
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let previous = std::mem::take(&mut *token_counts);
+        let mut count = |text: &str| {
+            let key = (tokenizer.kind(), text_hash(text));
+            let tokens = previous
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| tokenizer.count(text));
+            token_counts.insert(key, tokens);
+            i64::try_from(tokens).unwrap_or(i64::MAX)
+        };
 
+        let base_tokens = count(&base_instructions.text);
         let items_tokens = self
             .items
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| count_item_tokens(item, &mut count))
             .fold(0i64, i64::saturating_add);
 
         Some(base_tokens.saturating_add(items_tokens))
@@ -424,6 +471,70 @@ fn estimate_item_token_count(item: &ResponseItem) -> i64 {
     approx_tokens_from_byte_count_i64(model_visible_bytes)
 }
 
+/// Token count of one item, with `count` counting the tokens of its
+/// serialized text. Encrypted reasoning and images keep their byte-based
+/// estimates since their text is not what the model reads.
+fn count_item_tokens(item: &ResponseItem, count: &mut impl FnMut(&str) -> i64) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Reasoning {
+            encrypted_content: Some(_),
+            ..
+        }
+        | ResponseItem::Compaction { .. } => estimate_item_token_count(item),
+        item => {
+            let (_, image_count) = image_data_url_estimate_adjustment(item);
+            let serialized = if image_count == 0 {
+                serde_json::to_string(item)
+            } else {
+                serde_json::to_string(&without_image_payloads(item))
+            }
+            .unwrap_or_default();
+            count(&serialized).saturating_add(
+                image_count.saturating_mul(approx_tokens_from_byte_count_i64(IMAGE_BYTES_ESTIMATE)),
+            )
+        }
+    }
+}
+
+fn text_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Copy of `item` with the base64 payloads counted by
+/// [`image_data_url_estimate_adjustment`] removed, keeping the data URL prefix.
+fn without_image_payloads(item: &ResponseItem) -> ResponseItem {
+    let strip = |image_url: &mut String| {
+        if let Some(payload_len) = base64_data_url_payload_len(image_url) {
+            image_url.truncate(image_url.len() - payload_len);
+        }
+    };
+    let mut item = item.clone();
+    match &mut item {
+        ResponseItem::Message { content, .. } => {
+            for content_item in content {
+                if let ContentItem::InputImage { image_url } = content_item {
+                    strip(image_url);
+                }
+            }
+        }
+        ResponseItem::FunctionCallOutput { output, .. }
+        | ResponseItem::CustomToolCallOutput { output, .. } => {
+            if let FunctionCallOutputBody::ContentItems(items) = &mut output.body {
+                for content_item in items {
+                    if let FunctionCallOutputContentItem::InputImage { image_url } = content_item {
+                        strip(image_url);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    item
+}
+
 /// Approximate model-visible byte cost for one image input.
 ///
 /// The estimator later converts bytes to tokens using a 4-bytes/token heuristic
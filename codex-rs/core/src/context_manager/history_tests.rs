@@ -14,6 +14,7 @@ use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ReasoningItemReasoningSummary;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::openai_models::default_input_modalities;
+use codex_utils_tokenizer::EncodingKind;
 use pretty_assertions::assert_eq;
 use regex_lite::Regex;
 
@@ -409,10 +410,10 @@ fn estimate_token_count_with_base_instructions_uses_provided_text() {
     };
 
     let short_estimate = history
-        .estimate_token_count_with_base_instructions(&short_base)
+        .estimate_token_count_with_base_instructions(&short_base, None)
         .expect("token estimate");
     let long_estimate = history
-        .estimate_token_count_with_base_instructions(&long_base)
+        .estimate_token_count_with_base_instructions(&long_base, None)
         .expect("token estimate");
 
     let expected_delta = approx_token_count_for_text(&long_base.text)
@@ -420,6 +421,101 @@ fn estimate_token_count_with_base_instructions_uses_provided_text() {
     assert_eq!(long_estimate - short_estimate, expected_delta);
 }
 
+#[test]
+fn estimate_token_count_uses_the_model_tokenizer() {
+    let tokenizer = Tokenizer::new(EncodingKind::O200kBase);
+    let message = assistant_msg("hello from history");
+    let history = create_history_with_items(vec![message.clone()]);
+    let base = BaseInstructions {
+        text: "x".repeat(1_000),
+    };
+
+    let estimate = history
+        .estimate_token_count_with_base_instructions(&base, Some(tokenizer))
+        .expect("token estimate");
+
+    let expected = tokenizer.count(&base.text)
+        + tokenizer.count(&serde_json::to_string(&message).expect("serialize"));
+    assert_eq!(estimate, expected as i64);
+    // Repeated characters compress far better than the 4-bytes/token estimate.
+    assert!(
+        estimate
+            < history
+                .estimate_token_count_with_base_instructions(&base, None)
+                .unwrap()
+    );
+}
+
+#[test]
+fn tokenizer_estimates_reuse_counts_of_unchanged_items() {
+    let tokenizer = Tokenizer::new(EncodingKind::O200kBase);
+    let first = assistant_msg("first");
+    let second = assistant_msg("second");
+    let mut history = create_history_with_items(vec![first.clone(), second]);
+    let base = BaseInstructions {
+        text: "base".to_string(),
+    };
+    let estimate = |history: &ContextManager| {
+        history
+            .estimate_token_count_with_base_instructions(&base, Some(tokenizer))
+            .expect("token estimate")
+    };
+    let full_estimate = estimate(&history);
+    assert_eq!(history.token_counts.lock().expect("counts").len(), 3);
+
+    // A stale count proves the cached value is used instead of re-tokenizing.
+    let first_key = (
+        EncodingKind::O200kBase,
+        text_hash(&serde_json::to_string(&first).expect("serialize")),
+    );
+    let first_tokens = history.token_counts.lock().expect("counts")[&first_key];
+    history
+        .token_counts
+        .lock()
+        .expect("counts")
+        .insert(first_key, first_tokens + 100);
+    assert_eq!(estimate(&history.clone()), full_estimate + 100);
+
+    history.remove_first_item();
+    estimate(&history);
+    assert_eq!(history.token_counts.lock().expect("counts").len(), 2);
+    assert!(
+        !history
+            .token_counts
+            .lock()
+            .expect("counts")
+            .contains_key(&first_key)
+    );
+}
+
+#[test]
+fn tokenizer_estimate_keeps_fixed_image_cost() {
+    let image_item = |payload: &str| ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![
+            ContentItem::InputText {
+                text: "Here is the screenshot".to_string(),
+            },
+            ContentItem::InputImage {
+                image_url: format!("data:image/png;base64,{payload}"),
+            },
+        ],
+        end_turn: None,
+        phase: None,
+    };
+    let tokenizer = Tokenizer::new(EncodingKind::O200kBase);
+    let without_payload = serde_json::to_string(&image_item("")).expect("serialize");
+
+    assert_eq!(
+        count_item_tokens(&image_item(&"A".repeat(100_000)), &mut |text| {
+            tokenizer.count(text) as i64
+        }),
+        tokenizer.count(&without_payload) as i64
+            + approx_tokens_from_byte_count_i64(IMAGE_BYTES_ESTIMATE)
+    );
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -874,16 +970,17 @@ fn format_exec_output_truncates_large_error() {
 
     let truncated = truncate_exec_output(&large_error);
 
-    assert_truncated_message_matches(&truncated, line, 36250);
+    assert_truncated_message_matches(&truncated, line, 25_000);
     assert_ne!(truncated, large_error);
 }
 
 #[test]
 fn format_exec_output_marks_byte_truncation_without_omitted_lines() {
-    let long_line = "a".repeat(EXEC_FORMAT_MAX_BYTES + 10000);
+    // Runs of one letter tokenize into long tokens, so alternate with spaces.
+    let long_line = "a ".repeat(EXEC_FORMAT_MAX_BYTES);
     let truncated = truncate_exec_output(&long_line);
     assert_ne!(truncated, long_line);
-    assert_truncated_message_matches(&truncated, "a", 2500);
+    assert_truncated_message_matches(&truncated, "a", 7_501);
     assert!(
         !truncated.contains("omitted"),
         "line omission marker should not appear when no lines were dropped: {truncated}"
@@ -905,7 +1002,7 @@ fn format_exec_output_reports_omitted_lines_and_keeps_head_and_tail() {
         .collect();
 
     let truncated = truncate_exec_output(&content);
-    assert_truncated_message_matches(&truncated, "line-0-", 34_723);
+    assert_truncated_message_matches(&truncated, "line-0-", 24_500);
     assert!(
         truncated.contains("line-0-"),
         "expected head line to remain: {truncated}"
@@ -928,7 +1025,7 @@ fn format_exec_output_prefers_line_marker_when_both_limits_exceeded() {
 
     let truncated = truncate_exec_output(&content);
 
-    assert_truncated_message_matches(&truncated, "line-0-", 17_423);
+    assert_truncated_message_matches(&truncated, "line-0-", 8_600);
 }
 
 #[cfg(not(debug_assertions))]
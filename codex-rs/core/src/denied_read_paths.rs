@@ -0,0 +1,353 @@
+//! Resolution of [`SandboxPolicy::denied_read_paths`] shared by the platform
+//! sandboxes and the built-in file tools.
+//!
+//! Seatbelt enforces the resolved globs directly, bubblewrap overmounts the
+//! paths that exist when the command starts, and `read_file`, `grep_files`
+//! and `code_symbols` check each path they touch.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::SandboxPolicy;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+use tracing::warn;
+
+/// Upper bound on directory entries visited by
+/// [`DeniedReadPaths::existing_paths`], so a `**` pattern over a large
+/// workspace cannot stall command startup. Running out is an error: a
+/// partial scan would leave later matches readable.
+const MAX_SCANNED_ENTRIES: usize = 50_000;
+
+const GLOB_META_CHARS: &[char] = &['*', '?', '[', ']', '{', '}'];
+
+/// A denied pattern split into the literal directory it starts from and the
+/// glob components below it.
+#[derive(Debug, Clone)]
+struct ResolvedPattern {
+    prefix: PathBuf,
+    rest: Option<String>,
+    matcher: GlobMatcher,
+}
+
+impl ResolvedPattern {
+    fn glob(&self) -> &str {
+        self.matcher.glob().glob()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeniedReadPaths {
+    patterns: Vec<ResolvedPattern>,
+    globs: GlobSet,
+}
+
+impl DeniedReadPaths {
+    pub fn from_policy(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Self {
+        Self::new(sandbox_policy.denied_read_paths(), cwd)
+    }
+
+    /// Resolve `patterns` against `cwd` and the home directory. Invalid
+    /// patterns are logged and skipped.
+    pub fn new(patterns: &[String], cwd: &Path) -> Self {
+        let home = dirs::home_dir();
+        let mut resolved = Vec::new();
+        for pattern in patterns {
+            let Some((prefix, rest)) = split_pattern(pattern, cwd, home.as_deref()) else {
+                warn!("ignoring denied read path {pattern:?}: cannot resolve it");
+                continue;
+            };
+            let canonical_prefix = prefix
+                .canonicalize()
+                .ok()
+                .filter(|canonical| canonical != &prefix);
+            for prefix in std::iter::once(prefix).chain(canonical_prefix) {
+                match compile_pattern(prefix, rest.clone()) {
+                    Ok(pattern) => resolved.push(pattern),
+                    Err(err) => warn!("ignoring denied read path {pattern:?}: {err}"),
+                }
+            }
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &resolved {
+            builder.add(pattern.matcher.glob().clone());
+        }
+        let globs = builder.build().unwrap_or_else(|err| {
+            warn!("ignoring denied read paths: {err}");
+            resolved.clear();
+            GlobSet::empty()
+        });
+
+        Self {
+            patterns: resolved,
+            globs,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Absolute globs for every denied pattern, including variants rooted at
+    /// the canonical form of their literal prefix (`/var` vs `/private/var`).
+    pub fn globs(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(ResolvedPattern::glob)
+    }
+
+    /// Whether reading the absolute `path` is denied, either because it
+    /// matches a pattern or because one of its ancestors does. Symlinks are
+    /// resolved so a link cannot be used to reach a denied target.
+    pub fn is_denied(&self, path: &Path) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        if self.matches_self_or_ancestor(path) {
+            return true;
+        }
+        path.canonicalize()
+            .is_ok_and(|canonical| canonical != path && self.matches_self_or_ancestor(&canonical))
+    }
+
+    fn matches_self_or_ancestor(&self, path: &Path) -> bool {
+        path.ancestors()
+            .any(|ancestor| self.globs.is_match(ancestor))
+    }
+
+    /// Paths that currently exist and match a denied pattern, outermost
+    /// first. Matched directories are not descended into, so the result
+    /// never lists a path below another one.
+    ///
+    /// Fails when the patterns need more than [`MAX_SCANNED_ENTRIES`]
+    /// directory entries to resolve.
+    pub fn existing_paths(&self) -> std::io::Result<Vec<PathBuf>> {
+        self.existing_paths_within(MAX_SCANNED_ENTRIES)
+    }
+
+    fn existing_paths_within(&self, max_entries: usize) -> std::io::Result<Vec<PathBuf>> {
+        let mut found = BTreeSet::new();
+        let mut budget = max_entries;
+        for pattern in &self.patterns {
+            collect_existing_matches(pattern, &mut found, &mut budget, max_entries)?;
+        }
+
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for path in found {
+            if !paths.iter().any(|parent| path.starts_with(parent)) {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+}
+
+/// Turn a user pattern into an absolute literal prefix plus the glob
+/// components below it. A leading `~/` is relative to `home`, a relative
+/// pattern is relative to `cwd`, and a bare name such as `.env*` (without a
+/// `./` prefix) matches at any depth below `cwd`.
+fn split_pattern(
+    pattern: &str,
+    cwd: &Path,
+    home: Option<&Path>,
+) -> Option<(PathBuf, Option<String>)> {
+    let pattern = pattern.trim().trim_end_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let (base, relative) = if pattern == "~" {
+        (home?.to_path_buf(), "")
+    } else if let Some(rest) = pattern.strip_prefix("~/") {
+        (home?.to_path_buf(), rest)
+    } else if let Some(rest) = pattern.strip_prefix('/') {
+        (PathBuf::from("/"), rest)
+    } else if let Some(rest) = pattern.strip_prefix("./") {
+        (cwd.to_path_buf(), rest)
+    } else if pattern.contains('/') {
+        (cwd.to_path_buf(), pattern)
+    } else {
+        return Some((cwd.to_path_buf(), Some(format!("**/{pattern}"))));
+    };
+
+    let mut prefix = base;
+    let mut components = relative
+        .split('/')
+        .filter(|component| !component.is_empty());
+    let mut rest = Vec::new();
+    for component in components.by_ref() {
+        if component.contains(GLOB_META_CHARS) {
+            rest.push(component);
+            break;
+        }
+        prefix.push(component);
+    }
+    rest.extend(components);
+
+    Some((prefix, (!rest.is_empty()).then(|| rest.join("/"))))
+}
+
+fn compile_pattern(
+    prefix: PathBuf,
+    rest: Option<String>,
+) -> Result<ResolvedPattern, globset::Error> {
+    let escaped_prefix = globset::escape(&prefix.to_string_lossy());
+    let glob = match &rest {
+        Some(rest) => format!("{}/{rest}", escaped_prefix.trim_end_matches('/')),
+        None => escaped_prefix,
+    };
+    let matcher = GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher();
+    Ok(ResolvedPattern {
+        prefix,
+        rest,
+        matcher,
+    })
+}
+
+fn collect_existing_matches(
+    pattern: &ResolvedPattern,
+    found: &mut BTreeSet<PathBuf>,
+    budget: &mut usize,
+    max_entries: usize,
+) -> std::io::Result<()> {
+    let Some(rest) = &pattern.rest else {
+        if pattern.prefix.symlink_metadata().is_ok() {
+            found.insert(pattern.prefix.clone());
+        }
+        return Ok(());
+    };
+
+    // Without `**` a match can sit no deeper than the number of components.
+    let max_depth = if rest.contains("**") {
+        usize::MAX
+    } else {
+        rest.split('/').count()
+    };
+    let mut pending = vec![(pattern.prefix.clone(), 1)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if *budget == 0 {
+                return Err(std::io::Error::other(format!(
+                    "denied read path {} needs more than {max_entries} directory entries to resolve; use a narrower pattern",
+                    pattern.glob()
+                )));
+            }
+            *budget -= 1;
+
+            let path = entry.path();
+            if pattern.matcher.is_match(&path) {
+                found.insert(path);
+            } else if depth < max_depth && entry.file_type().is_ok_and(|ty| ty.is_dir()) {
+                pending.push((path, depth + 1));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn matches_bare_names_at_any_depth_and_everything_below_a_denied_dir() {
+        let cwd = Path::new("/work/repo");
+        let denied = DeniedReadPaths::new(
+            &[
+                ".env*".to_string(),
+                "./secrets/".to_string(),
+                "/etc/shadow".to_string(),
+            ],
+            cwd,
+        );
+
+        assert!(denied.is_denied(Path::new("/work/repo/.env")));
+        assert!(denied.is_denied(Path::new("/work/repo/app/.env.local")));
+        assert!(denied.is_denied(Path::new("/work/repo/secrets/prod/key.pem")));
+        assert!(denied.is_denied(Path::new("/etc/shadow")));
+        assert!(!denied.is_denied(Path::new("/work/repo/app/secrets/key.pem")));
+        assert!(!denied.is_denied(Path::new("/work/repo/src/env.rs")));
+        assert!(!denied.is_denied(Path::new("/work/other/.env")));
+    }
+
+    #[test]
+    fn existing_paths_lists_outermost_matches() {
+        let tmp = TempDir::new().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        std::fs::create_dir_all(root.join("app/.aws/nested")).expect("create dirs");
+        std::fs::write(root.join("app/.aws/nested/credentials"), "x").expect("write");
+        std::fs::write(root.join(".env"), "x").expect("write");
+        std::fs::write(root.join("app/.env.local"), "x").expect("write");
+        std::fs::write(root.join("app/main.rs"), "x").expect("write");
+
+        let denied = DeniedReadPaths::new(
+            &[
+                ".env*".to_string(),
+                "app/.aws".to_string(),
+                "app/.aws/nested/credentials".to_string(),
+                "missing/file".to_string(),
+            ],
+            &root,
+        );
+
+        assert_eq!(
+            denied.existing_paths().expect("scan denied paths"),
+            vec![
+                root.join(".env"),
+                root.join("app/.aws"),
+                root.join("app/.env.local"),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_policy_denies_nothing() {
+        let denied = DeniedReadPaths::from_policy(
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/work"),
+        );
+
+        assert!(denied.is_empty());
+        assert!(!denied.is_denied(Path::new("/work/.env")));
+        assert_eq!(
+            denied.existing_paths().expect("scan denied paths"),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn existing_paths_fails_when_the_scan_budget_runs_out() {
+        let tmp = TempDir::new().expect("tempdir");
+        let root = tmp.path().canonicalize().expect("canonical tempdir");
+        let crowded = root.join("crowded");
+        std::fs::create_dir(&crowded).expect("create dir");
+        for index in 0..10 {
+            std::fs::write(crowded.join(index.to_string()), "").expect("write");
+        }
+        std::fs::write(root.join("zz.env"), "x").expect("write");
+
+        let denied = DeniedReadPaths::new(&["*.env".to_string()], &root);
+
+        assert_eq!(
+            denied.existing_paths_within(20).expect("scan fits"),
+            vec![root.join("zz.env")]
+        );
+        let err = denied
+            .existing_paths_within(5)
+            .expect_err("partial scans must not succeed");
+        assert!(
+            err.to_string().contains("use a narrower pattern"),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -737,6 +737,7 @@ mod tests {
             aggregated_output: StreamOutput::new("aggregate detail".to_string()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -754,6 +755,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -771,6 +773,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(8),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -815,6 +818,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(5),
             timed_out: false,
+            resource_limit_exceeded: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
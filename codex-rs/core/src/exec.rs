@@ -20,10 +20,12 @@ use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
 use crate::get_platform_sandbox;
+use crate::landlock::ResourceLimitReport;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
+use crate::protocol::ResourceLimitKind;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecRequest;
@@ -34,6 +36,7 @@ use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::config_types::SandboxResourceLimits;
 use codex_utils_pty::process_group::kill_child_process_group;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
@@ -223,6 +226,8 @@ pub async fn process_exec_tool_call(
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
+            resource_limits: &SandboxResourceLimits::default(),
+            workspace_overlay: None,
             windows_sandbox_level,
         })
         .map_err(CodexErr::from)?;
@@ -248,6 +253,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_policy: _sandbox_policy_from_env,
         justification,
         arg0,
+        resource_limit_report,
     } = env;
 
     let params = ExecParams {
@@ -265,7 +271,15 @@ pub(crate) async fn execute_exec_env(
     let start = Instant::now();
     let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    let resource_limit_exceeded = resource_limit_report
+        .as_ref()
+        .and_then(ResourceLimitReport::exceeded);
+    finalize_exec_result(
+        raw_output_result,
+        sandbox,
+        duration,
+        resource_limit_exceeded,
+    )
 }
 
 #[cfg(target_os = "windows")]
@@ -451,6 +465,7 @@ fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
     duration: Duration,
+    resource_limit_exceeded: Option<ResourceLimitKind>,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
@@ -483,6 +498,7 @@ fn finalize_exec_result(
                 aggregated_output,
                 duration,
                 timed_out,
+                resource_limit_exceeded,
             };
 
             if timed_out {
@@ -524,6 +540,11 @@ pub(crate) mod errors {
                 SandboxTransformError::InvalidAdditionalPermissionsPath(path) => {
                     CodexErr::InvalidRequest(format!("invalid additional_permissions path: {path}"))
                 }
+                SandboxTransformError::DeniedReadPathsUnsupported => {
+                    CodexErr::UnsupportedOperation(
+                        "denied_read_paths cannot be enforced by the Windows sandbox".to_string(),
+                    )
+                }
             }
         }
     }
@@ -538,7 +559,10 @@ pub(crate) fn is_likely_sandbox_denied(
     sandbox_type: SandboxType,
     exec_output: &ExecToolCallOutput,
 ) -> bool {
-    if sandbox_type == SandboxType::None || exec_output.exit_code == 0 {
+    if sandbox_type == SandboxType::None
+        || exec_output.exit_code == 0
+        || exec_output.resource_limit_exceeded.is_some()
+    {
         return false;
     }
 
@@ -675,6 +699,9 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// Resource limit reported by the Linux sandbox helper as the reason the
+    /// command stopped.
+    pub resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 impl Default for ExecToolCallOutput {
@@ -686,6 +713,7 @@ impl Default for ExecToolCallOutput {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::ZERO,
             timed_out: false,
+            resource_limit_exceeded: None,
         }
     }
 }
@@ -932,6 +960,7 @@ mod tests {
             aggregated_output: StreamOutput::new(aggregated.to_string()),
             duration: Duration::from_millis(1),
             timed_out: false,
+            resource_limit_exceeded: None,
         }
     }
 
@@ -959,6 +988,64 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn sandbox_detection_ignores_resource_limit_kills() {
+        let stderr = "Killed\ncodex-linux-sandbox: resource limit exceeded: memory\n";
+        let mut output = make_exec_output(137, "", stderr, stderr);
+        output.resource_limit_exceeded = Some(ResourceLimitKind::Memory);
+
+        assert!(!is_likely_sandbox_denied(
+            SandboxType::LinuxSeccomp,
+            &output
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resource_limit_comes_from_the_report_not_the_output() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let stderr = b"codex-linux-sandbox: resource limit exceeded: memory\n";
+        let stream = |text: &[u8]| StreamOutput {
+            text: text.to_vec(),
+            truncated_after_lines: None,
+        };
+        let raw_output = || RawExecToolCallOutput {
+            exit_status: ExitStatus::from_raw(137 << 8),
+            stdout: stream(b""),
+            stderr: stream(stderr),
+            aggregated_output: stream(stderr),
+            timed_out: false,
+        };
+
+        let forged = finalize_exec_result(
+            Ok(raw_output()),
+            SandboxType::LinuxSeccomp,
+            Duration::from_millis(1),
+            None,
+        );
+        assert!(
+            matches!(
+                forged,
+                Err(CodexErr::Sandbox(SandboxErr::Denied { ref output, .. }))
+                    if output.resource_limit_exceeded.is_none()
+            ),
+            "unexpected result: {forged:?}"
+        );
+
+        let reported = finalize_exec_result(
+            Ok(raw_output()),
+            SandboxType::LinuxSeccomp,
+            Duration::from_millis(1),
+            Some(ResourceLimitKind::Memory),
+        )
+        .expect("resource limit kills are not sandbox denials");
+        assert_eq!(
+            reported.resource_limit_exceeded,
+            Some(ResourceLimitKind::Memory)
+        );
+    }
+
     #[test]
     fn sandbox_detection_ignores_non_sandbox_mode() {
         let output = make_exec_output(1, "", "Operation not permitted", "");
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
@@ -10,13 +11,16 @@ use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
 use crate::is_safe_command::is_known_safe_command;
 use codex_execpolicy::AmendError;
+use codex_execpolicy::ApprovalPolicyKind;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::NetworkRuleProtocol;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
+use codex_execpolicy::SandboxKind;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_execpolicy::blocking_append_network_rule;
 use codex_protocol::approvals::ExecPolicyAmendment;
@@ -170,6 +174,8 @@ pub(crate) struct ExecPolicyManager {
 
 pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) command: &'a [String],
+    pub(crate) cwd: &'a Path,
+    pub(crate) env: &'a HashMap<String, String>,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
@@ -201,6 +207,8 @@ impl ExecPolicyManager {
     ) -> ExecApprovalRequirement {
         let ExecApprovalRequest {
             command,
+            cwd,
+            env,
             approval_policy,
             sandbox_policy,
             sandbox_permissions,
@@ -221,13 +229,25 @@ impl ExecPolicyManager {
                 used_complex_parsing,
             )
         };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
+        let match_context = exec_policy_match_context(
+            cwd,
+            approval_policy,
+            sandbox_policy,
+            sandbox_permissions,
+            Some(env),
+        );
+        let evaluation = exec_policy.check_multiple_with_context(
+            commands.iter(),
+            &match_context,
+            &exec_policy_fallback,
+        );
 
         let requested_amendment = derive_requested_execpolicy_amendment_from_prefix_rule(
             prefix_rule.as_ref(),
             &evaluation.matched_rules,
             exec_policy.as_ref(),
             &commands,
+            &match_context,
             &exec_policy_fallback,
         );
 
@@ -564,6 +584,39 @@ pub fn render_decision_for_unmatched_command(
     }
 }
 
+/// Describes where and how a command runs so `when` conditions on rules can be evaluated.
+/// Commands that request escalated permissions run outside the sandbox, so they are matched
+/// as `full-access`. Environment conditions are checked against `env` when the command's
+/// environment is known and against the Codex process environment otherwise.
+pub(crate) fn exec_policy_match_context(
+    cwd: &Path,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+    env: Option<&HashMap<String, String>>,
+) -> MatchContext {
+    let sandbox = match sandbox_policy {
+        _ if sandbox_permissions.requires_escalated_permissions() => SandboxKind::FullAccess,
+        SandboxPolicy::DangerFullAccess => SandboxKind::FullAccess,
+        SandboxPolicy::ReadOnly { .. } => SandboxKind::ReadOnly,
+        SandboxPolicy::ExternalSandbox { .. } => SandboxKind::ExternalSandbox,
+        SandboxPolicy::WorkspaceWrite { .. } => SandboxKind::WorkspaceWrite,
+    };
+    let approval_policy = match approval_policy {
+        AskForApproval::UnlessTrusted => ApprovalPolicyKind::Untrusted,
+        AskForApproval::OnFailure => ApprovalPolicyKind::OnFailure,
+        AskForApproval::OnRequest => ApprovalPolicyKind::OnRequest,
+        AskForApproval::Reject(_) => ApprovalPolicyKind::Reject,
+        AskForApproval::Never => ApprovalPolicyKind::Never,
+    };
+    MatchContext {
+        cwd: Some(cwd.to_path_buf()),
+        sandbox: Some(sandbox),
+        approval_policy: Some(approval_policy),
+        env: env.cloned(),
+    }
+}
+
 fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
@@ -639,6 +692,7 @@ fn derive_requested_execpolicy_amendment_from_prefix_rule(
     matched_rules: &[RuleMatch],
     exec_policy: &Policy,
     commands: &[Vec<String>],
+    match_context: &MatchContext,
     exec_policy_fallback: &impl Fn(&[String]) -> Decision,
 ) -> Option<ExecPolicyAmendment> {
     let prefix_rule = prefix_rule?;
@@ -665,6 +719,7 @@ fn derive_requested_execpolicy_amendment_from_prefix_rule(
         exec_policy,
         &amendment.command,
         commands,
+        match_context,
         exec_policy_fallback,
     ) {
         Some(amendment)
@@ -677,6 +732,7 @@ fn prefix_rule_would_approve_all_commands(
     exec_policy: &Policy,
     prefix_rule: &[String],
     commands: &[Vec<String>],
+    match_context: &MatchContext,
     exec_policy_fallback: &impl Fn(&[String]) -> Decision,
 ) -> bool {
     let mut policy_with_prefix_rule = exec_policy.clone();
@@ -689,7 +745,7 @@ fn prefix_rule_would_approve_all_commands(
 
     commands.iter().all(|command| {
         policy_with_prefix_rule
-            .check(command, exec_policy_fallback)
+            .check_with_context(command, match_context, exec_policy_fallback)
             .decision
             == Decision::Allow
     })
@@ -1146,6 +1202,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &forbidden_script,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1196,6 +1254,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::new(policy)
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1223,6 +1283,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1251,6 +1313,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1290,6 +1354,8 @@ prefix_rule(
                     "-rf".to_string(),
                     "/some/important/folder".to_string(),
                 ],
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1305,6 +1371,135 @@ prefix_rule(
         );
     }
 
+    #[tokio::test]
+    async fn when_conditions_use_command_cwd_and_sandbox() {
+        let policy_src = r#"
+prefix_rule(
+    pattern=["docker"],
+    decision="forbidden",
+    justification="docker needs the sandbox",
+    when={"sandbox": "full-access", "cwd_outside": "/workspace/ci"},
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let command = vec!["docker".to_string(), "ps".to_string()];
+
+        let requirement_for = |cwd: &'static str, sandbox_policy: SandboxPolicy| {
+            let manager = &manager;
+            let command = &command;
+            async move {
+                manager
+                    .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                        command,
+                        cwd: Path::new(cwd),
+                        env: &HashMap::new(),
+                        approval_policy: AskForApproval::Never,
+                        sandbox_policy: &sandbox_policy,
+                        sandbox_permissions: SandboxPermissions::UseDefault,
+                        prefix_rule: None,
+                    })
+                    .await
+            }
+        };
+
+        assert_eq!(
+            requirement_for("/workspace", SandboxPolicy::DangerFullAccess).await,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`docker ps` rejected: docker needs the sandbox".to_string()
+            }
+        );
+        assert!(matches!(
+            requirement_for("/workspace/ci", SandboxPolicy::DangerFullAccess).await,
+            ExecApprovalRequirement::Skip { .. }
+        ));
+        assert!(matches!(
+            requirement_for("/workspace", SandboxPolicy::new_read_only_policy()).await,
+            ExecApprovalRequirement::Skip { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn when_conditions_use_escalation_and_command_env() {
+        let policy_src = r#"
+prefix_rule(
+    pattern=["docker"],
+    decision="forbidden",
+    justification="docker needs the sandbox",
+    when={"sandbox": "full-access"},
+)
+prefix_rule(
+    pattern=["terraform"],
+    decision="forbidden",
+    justification="no production applies",
+    when={"env_set": ["CODEX_TEST_PRODUCTION"]},
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let sandbox_policy = SandboxPolicy::new_read_only_policy();
+
+        let requirement_for = |command: Vec<String>,
+                               sandbox_permissions: SandboxPermissions,
+                               env: HashMap<String, String>| {
+            let manager = &manager;
+            let sandbox_policy = &sandbox_policy;
+            async move {
+                manager
+                    .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                        command: &command,
+                        cwd: Path::new("/workspace"),
+                        env: &env,
+                        approval_policy: AskForApproval::Never,
+                        sandbox_policy,
+                        sandbox_permissions,
+                        prefix_rule: None,
+                    })
+                    .await
+            }
+        };
+        let docker = vec!["docker".to_string(), "ps".to_string()];
+        let terraform = vec!["terraform".to_string(), "apply".to_string()];
+        let production = HashMap::from([("CODEX_TEST_PRODUCTION".to_string(), "1".to_string())]);
+
+        assert_eq!(
+            requirement_for(
+                docker.clone(),
+                SandboxPermissions::RequireEscalated,
+                HashMap::new()
+            )
+            .await,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`docker ps` rejected: docker needs the sandbox".to_string()
+            }
+        );
+        assert!(matches!(
+            requirement_for(docker, SandboxPermissions::UseDefault, HashMap::new()).await,
+            ExecApprovalRequirement::Skip { .. }
+        ));
+        assert_eq!(
+            requirement_for(
+                terraform.clone(),
+                SandboxPermissions::UseDefault,
+                production
+            )
+            .await,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`terraform apply` rejected: no production applies".to_string()
+            }
+        );
+        assert!(matches!(
+            requirement_for(terraform, SandboxPermissions::UseDefault, HashMap::new()).await,
+            ExecApprovalRequirement::Skip { .. }
+        ));
+    }
+
     #[tokio::test]
     async fn exec_approval_requirement_prefers_execpolicy_match() {
         let policy_src = r#"prefix_rule(pattern=["rm"], decision="prompt")"#;
@@ -1319,6 +1514,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1349,6 +1546,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1391,6 +1590,8 @@ prefix_rule(
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::Reject(RejectConfig {
                     sandbox_approval: true,
                     rules: false,
@@ -1427,6 +1628,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::Reject(RejectConfig {
                     sandbox_approval: true,
                     rules: false,
@@ -1461,6 +1664,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::Reject(RejectConfig {
                     sandbox_approval: false,
                     rules: true,
@@ -1488,6 +1693,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1512,6 +1719,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1540,6 +1749,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1568,6 +1779,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
@@ -1599,6 +1812,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
@@ -1637,6 +1852,8 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: Path::new("/workspace"),
+                    env: &HashMap::new(),
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1711,6 +1928,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1741,6 +1960,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1768,6 +1989,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1806,6 +2029,8 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: Path::new("/workspace"),
+                    env: &HashMap::new(),
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1829,6 +2054,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1859,6 +2086,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1888,6 +2117,7 @@ prefix_rule(
             matched_rules,
             &Policy::empty(),
             &commands,
+            &MatchContext::default(),
             &|_: &[String]| Decision::Allow,
         )
     }
@@ -2028,6 +2258,8 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: Path::new("/workspace"),
+                env: &HashMap::new(),
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -2094,6 +2326,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &sneaky_command,
+                    cwd: Path::new("/workspace"),
+                    env: &HashMap::new(),
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -2117,6 +2351,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: Path::new("/workspace"),
+                    env: &HashMap::new(),
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -2136,6 +2372,8 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: Path::new("/workspace"),
+                    env: &HashMap::new(),
                     approval_policy: AskForApproval::Never,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -153,6 +153,10 @@ pub enum Feature {
     ResponsesWebsockets,
     /// Enable Responses API websocket v2 mode.
     ResponsesWebsocketsV2,
+    /// Save truncated tool output to disk and expose `read_tool_output`.
+    ToolOutputSpill,
+    /// Expose the tree-sitter backed `code_symbols` outline and lookup tool.
+    CodeSymbols,
 }
 
 impl Feature {
@@ -694,6 +698,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::ToolOutputSpill,
+        key: "tool_output_spill",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::CodeSymbols,
+        key: "code_symbols",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
 ];
 
 /// Push a warning event if any under-development features are enabled.
@@ -1,11 +1,16 @@
+use crate::config::find_codex_home;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::config_types::SandboxResourceLimits;
+use codex_protocol::protocol::ResourceLimitKind;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempPath;
 use tokio::process::Child;
 
 /// Spawn a shell tool command under the Linux sandbox helper
@@ -37,6 +42,9 @@ where
         sandbox_policy_cwd,
         use_bwrap_sandbox,
         allow_network_for_proxy(false),
+        &SandboxResourceLimits::default(),
+        None,
+        None,
     );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(SpawnChildRequest {
@@ -52,6 +60,72 @@ where
     .await
 }
 
+/// Directory under `CODEX_HOME` holding [`ResourceLimitReport`] files.
+const RESOURCE_LIMIT_REPORT_DIR: &str = "tmp/resource-limits";
+
+/// File `codex-linux-sandbox` writes the [`ResourceLimitKind`] to when one of
+/// the `--resource-limits` stopped the command.
+///
+/// Unlike the note the helper also prints, the file cannot be forged by the
+/// command: it is only used when it sits outside every writable root of the
+/// sandbox policy. The file is deleted once the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct ResourceLimitReport {
+    path: Arc<TempPath>,
+}
+
+impl ResourceLimitReport {
+    /// Creates an empty report under `CODEX_HOME`, or returns `None` when the
+    /// sandboxed command could write to it.
+    pub(crate) fn create(
+        sandbox_policy: &SandboxPolicy,
+        sandbox_policy_cwd: &Path,
+    ) -> Option<Self> {
+        let dir = find_codex_home().ok()?.join(RESOURCE_LIMIT_REPORT_DIR);
+        Self::create_in(&dir, sandbox_policy, sandbox_policy_cwd)
+    }
+
+    fn create_in(
+        dir: &Path,
+        sandbox_policy: &SandboxPolicy,
+        sandbox_policy_cwd: &Path,
+    ) -> Option<Self> {
+        if sandbox_policy.has_full_disk_write_access() {
+            return None;
+        }
+        std::fs::create_dir_all(dir).ok()?;
+        let canonical_dir = dir.canonicalize().ok()?;
+        if sandbox_policy
+            .get_writable_roots_with_cwd(sandbox_policy_cwd)
+            .iter()
+            .any(|root| root.is_path_writable(dir) || root.is_path_writable(&canonical_dir))
+        {
+            return None;
+        }
+        let path = tempfile::Builder::new()
+            .prefix("report-")
+            .tempfile_in(&canonical_dir)
+            .ok()?
+            .into_temp_path();
+        Some(Self {
+            path: Arc::new(path),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The limit the helper reported, if it has written one.
+    pub fn exceeded(&self) -> Option<ResourceLimitKind> {
+        std::fs::read_to_string(self.path())
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
+
 pub(crate) fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
     // When managed network requirements are active, request proxy-only
     // networking from the Linux sandbox helper. Without managed requirements,
@@ -63,12 +137,16 @@ pub(crate) fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
 ///
 /// The helper performs the actual sandboxing (bubblewrap + seccomp) after
 /// parsing these arguments. See `docs/linux_sandbox.md` for the Linux semantics.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
+    resource_limits: &SandboxResourceLimits,
+    resource_limit_report: Option<&ResourceLimitReport>,
+    workspace_overlay: Option<&Path>,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -86,12 +164,32 @@ pub(crate) fn create_linux_sandbox_command_args(
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
     ];
-    if use_bwrap_sandbox {
+    // The overlay and denied read paths are only available through
+    // bubblewrap; Landlock alone would leave denied files readable.
+    if use_bwrap_sandbox
+        || workspace_overlay.is_some()
+        || !sandbox_policy.denied_read_paths().is_empty()
+    {
         linux_cmd.push("--use-bwrap-sandbox".to_string());
     }
     if allow_network_for_proxy {
         linux_cmd.push("--allow-network-for-proxy".to_string());
     }
+    if !resource_limits.is_empty() {
+        #[expect(clippy::expect_used)]
+        let resource_limits_json = serde_json::to_string(resource_limits)
+            .expect("Failed to serialize SandboxResourceLimits to JSON");
+        linux_cmd.push("--resource-limits".to_string());
+        linux_cmd.push(resource_limits_json);
+        if let Some(report) = resource_limit_report {
+            linux_cmd.push("--resource-limit-report".to_string());
+            linux_cmd.push(report.path().to_string_lossy().to_string());
+        }
+    }
+    if let Some(workspace_overlay) = workspace_overlay {
+        linux_cmd.push("--workspace-overlay".to_string());
+        linux_cmd.push(workspace_overlay.to_string_lossy().to_string());
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
@@ -114,36 +212,210 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let with_bwrap =
-            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, false);
+        let limits = SandboxResourceLimits::default();
+
+        let with_bwrap = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            true,
+            false,
+            &limits,
+            None,
+            None,
+        );
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false, false);
+        let without_bwrap = create_linux_sandbox_command_args(
+            command, &policy, cwd, false, false, &limits, None, None,
+        );
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
         );
     }
 
+    #[test]
+    fn denied_read_paths_force_bwrap() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::ReadOnly {
+            access: Default::default(),
+            denied_read_paths: vec![".env*".to_string()],
+        };
+
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            false,
+            false,
+            &SandboxResourceLimits::default(),
+            None,
+            None,
+        );
+        assert_eq!(args.contains(&"--use-bwrap-sandbox".to_string()), true);
+    }
+
     #[test]
     fn proxy_flag_is_included_when_requested() {
         let command = vec!["/bin/true".to_string()];
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let args = create_linux_sandbox_command_args(command, &policy, cwd, true, true);
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            true,
+            true,
+            &SandboxResourceLimits::default(),
+            None,
+            None,
+        );
         assert_eq!(
             args.contains(&"--allow-network-for-proxy".to_string()),
             true
         );
     }
 
+    #[test]
+    fn resource_limits_are_passed_only_when_set() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        let unlimited = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            true,
+            false,
+            &SandboxResourceLimits::default(),
+            None,
+            None,
+        );
+        assert_eq!(unlimited.contains(&"--resource-limits".to_string()), false);
+
+        let limits = SandboxResourceLimits {
+            max_memory_bytes: Some(1 << 30),
+            max_wall_clock_seconds: Some(600),
+            ..Default::default()
+        };
+        let args = create_linux_sandbox_command_args(
+            command, &policy, cwd, true, false, &limits, None, None,
+        );
+        let flag_index = args
+            .iter()
+            .position(|arg| arg == "--resource-limits")
+            .expect("resource limits flag");
+        assert_eq!(
+            args[flag_index + 1].parse::<SandboxResourceLimits>().ok(),
+            Some(limits)
+        );
+        let separator_index = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        assert!(flag_index < separator_index);
+    }
+
+    #[test]
+    fn resource_limit_report_is_only_created_outside_writable_roots() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
+        };
+
+        let inside_workspace = workspace.path().join("reports");
+        assert_eq!(
+            ResourceLimitReport::create_in(&inside_workspace, &policy, workspace.path()).is_none(),
+            true
+        );
+        assert_eq!(
+            ResourceLimitReport::create_in(
+                codex_home.path(),
+                &SandboxPolicy::DangerFullAccess,
+                workspace.path()
+            )
+            .is_none(),
+            true
+        );
+
+        let report = ResourceLimitReport::create_in(codex_home.path(), &policy, workspace.path())
+            .expect("report outside the writable roots");
+        assert_eq!(report.exceeded(), None);
+        std::fs::write(report.path(), "memory").expect("write report");
+        assert_eq!(report.exceeded(), Some(ResourceLimitKind::Memory));
+
+        let limits = SandboxResourceLimits {
+            max_memory_bytes: Some(1 << 30),
+            ..Default::default()
+        };
+        let args = create_linux_sandbox_command_args(
+            vec!["/bin/true".to_string()],
+            &policy,
+            workspace.path(),
+            true,
+            false,
+            &limits,
+            Some(&report),
+            None,
+        );
+        let flag_index = args
+            .iter()
+            .position(|arg| arg == "--resource-limit-report")
+            .expect("report flag");
+        assert_eq!(
+            args[flag_index + 1],
+            report.path().to_string_lossy().to_string()
+        );
+
+        let path = report.path().to_path_buf();
+        drop(report);
+        assert_eq!(path.exists(), false);
+    }
+
     #[test]
     fn proxy_network_requires_managed_requirements() {
         assert_eq!(allow_network_for_proxy(false), false);
         assert_eq!(allow_network_for_proxy(true), true);
     }
+
+    #[test]
+    fn workspace_overlay_requires_bwrap_and_is_passed_through() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_workspace_write_policy();
+        let overlay = Path::new("/codex-home/sessions/thread/workspace_overlay/abc");
+
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            false,
+            false,
+            &SandboxResourceLimits::default(),
+            None,
+            Some(overlay),
+        );
+        assert_eq!(args.contains(&"--use-bwrap-sandbox".to_string()), true);
+        let flag_index = args
+            .iter()
+            .position(|arg| arg == "--workspace-overlay")
+            .expect("--workspace-overlay flag");
+        assert_eq!(
+            args[flag_index + 1],
+            "/codex-home/sessions/thread/workspace_overlay/abc"
+        );
+    }
 }
@@ -29,6 +29,7 @@ pub mod connectors;
 mod context_manager;
 mod contextual_user_message;
 pub mod custom_prompts;
+pub mod denied_read_paths;
 pub mod env;
 mod environment_context;
 pub mod error;
@@ -106,9 +107,11 @@ pub mod skills;
 pub mod spawn;
 pub mod state_db;
 pub mod terminal;
+mod tool_output_store;
 mod tools;
 pub mod turn_diff_tracker;
 mod turn_metadata;
+mod workspace_overlay;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
@@ -123,6 +126,7 @@ pub use rollout::find_thread_path_by_id_str;
 pub use rollout::find_thread_path_by_name_str;
 pub use rollout::list::Cursor;
 pub use rollout::list::ThreadItem;
+pub use rollout::list::ThreadSearchItem;
 pub use rollout::list::ThreadSortKey;
 pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
@@ -1,5 +1,8 @@
 pub mod auth;
+mod sampling;
 mod skill_dependencies;
+pub(crate) use sampling::make_sampler;
+pub(crate) use sampling::sampling_message_text;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 
 use std::collections::HashMap;
@@ -8,11 +11,16 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use async_channel::unbounded;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::mcp::Resource;
 use codex_protocol::mcp::ResourceTemplate;
 use codex_protocol::mcp::Tool;
+use codex_protocol::protocol::McpListPromptsResponseEvent;
 use codex_protocol::protocol::McpListToolsResponseEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use rmcp::model::GetPromptResult;
+use rmcp::model::PromptMessageContent;
+use rmcp::model::ResourceContents;
 use serde_json::Value;
 
 use crate::AuthManager;
@@ -140,6 +148,7 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_limit: None,
     }
 }
 
@@ -212,6 +221,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
         sandbox_state,
         config.codex_home.clone(),
         codex_apps_tools_cache_key(auth.as_ref()),
+        None,
     )
     .await;
 
@@ -363,10 +373,60 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
     }
 }
 
+pub(crate) async fn collect_mcp_prompts_from_manager(
+    mcp_connection_manager: &McpConnectionManager,
+) -> McpListPromptsResponseEvent {
+    let prompts = mcp_connection_manager
+        .list_all_prompts()
+        .await
+        .into_iter()
+        .map(|(server, prompts)| {
+            let prompts = prompts
+                .into_iter()
+                .filter_map(|prompt| {
+                    let name = prompt.name.clone();
+                    match serde_json::to_value(prompt).and_then(Prompt::from_mcp_value) {
+                        Ok(prompt) => Some(prompt),
+                        Err(err) => {
+                            tracing::warn!("Failed to convert MCP prompt '{server}/{name}': {err}");
+                            None
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            (server, prompts)
+        })
+        .collect();
+
+    McpListPromptsResponseEvent { prompts }
+}
+
+/// Flattens the messages of a `prompts/get` result into the text of a single
+/// user turn. Embedded text resources are inlined and resource links are
+/// reduced to their URI; images are dropped.
+pub(crate) fn prompt_result_text(result: &GetPromptResult) -> String {
+    result
+        .messages
+        .iter()
+        .filter_map(|message| match &message.content {
+            PromptMessageContent::Text { text } => Some(text.clone()),
+            PromptMessageContent::Resource { resource } => match &resource.resource {
+                ResourceContents::TextResourceContents { text, .. } => Some(text.clone()),
+                ResourceContents::BlobResourceContents { .. } => None,
+            },
+            PromptMessageContent::ResourceLink { link } => Some(link.uri.clone()),
+            PromptMessageContent::Image { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use rmcp::model::PromptMessage;
+    use rmcp::model::PromptMessageRole;
 
     fn make_tool(name: &str) -> Tool {
         Tool {
@@ -542,4 +602,37 @@ mod tests {
         let expected_url = format!("{OPENAI_CONNECTORS_MCP_BASE_URL}{OPENAI_CONNECTORS_MCP_PATH}");
         assert_eq!(url, &expected_url);
     }
+
+    #[test]
+    fn prompt_result_text_joins_text_and_embedded_resources() {
+        let result = GetPromptResult {
+            description: None,
+            messages: vec![
+                PromptMessage::new_text(PromptMessageRole::User, "Review this file:"),
+                PromptMessage::new_resource(
+                    PromptMessageRole::User,
+                    "file:///src/main.rs".to_string(),
+                    Some("text/x-rust".to_string()),
+                    Some("fn main() {}".to_string()),
+                    None,
+                    None,
+                    None,
+                ),
+                PromptMessage::new_resource(
+                    PromptMessageRole::User,
+                    "file:///logo.png".to_string(),
+                    Some("image/png".to_string()),
+                    None,
+                    None,
+                    None,
+                    None,
+                ),
+            ],
+        };
+
+        assert_eq!(
+            prompt_result_text(&result),
+            "Review this file:\n\nfn main() {}"
+        );
+    }
 }
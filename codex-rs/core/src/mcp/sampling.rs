@@ -0,0 +1,372 @@
+//! Runs `sampling/createMessage` requests from MCP servers against the
+//! session's model.
+//!
+//! Approval and the per-server token limit are enforced by the connection
+//! manager before a request reaches [`make_sampler`]. Each request runs as a
+//! one-off prompt on the current provider and is recorded in the rollout as
+//! an [`McpSamplingEndEvent`]; nothing is added to the conversation history.
+
+use std::sync::Arc;
+use std::sync::Weak;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_api::ResponseEvent;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpSamplingEndEvent;
+use codex_protocol::protocol::TokenUsage;
+use codex_rmcp_client::SamplingRequest;
+use codex_rmcp_client::SamplingResponse;
+use futures::FutureExt;
+use futures::StreamExt;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
+
+use crate::Prompt;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::mcp_connection_manager::McpSampler;
+use crate::mcp_connection_manager::McpSamplingOutput;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::exceeds_budget;
+use crate::truncate::truncate_text;
+
+/// Error `codex-api` reports when a Responses stream ends with
+/// `response.incomplete` because it reached `max_output_tokens`.
+const MAX_OUTPUT_TOKENS_INCOMPLETE: &str =
+    "Incomplete response returned, reason: max_output_tokens";
+
+/// Builds the sampler handed to MCP servers. It holds the session weakly so
+/// running servers do not keep a closed session alive.
+pub(crate) fn make_sampler(session: Weak<Session>) -> McpSampler {
+    Arc::new(move |server, request| {
+        let session = session.clone();
+        async move {
+            let session = session
+                .upgrade()
+                .ok_or_else(|| anyhow!("session is no longer running"))?;
+            run_sampling_request(&session, server, request).await
+        }
+        .boxed()
+    })
+}
+
+/// Text of a sampling message. Images and audio are described rather than
+/// inlined so the text can be shown in approval prompts and the rollout.
+pub(crate) fn sampling_message_text(message: &SamplingMessage) -> String {
+    message
+        .content
+        .clone()
+        .into_vec()
+        .into_iter()
+        .map(|content| match content {
+            SamplingMessageContent::Text(text) => text.text,
+            SamplingMessageContent::Image(image) => format!("[image: {}]", image.mime_type),
+            SamplingMessageContent::Audio(audio) => format!("[audio: {}]", audio.mime_type),
+            SamplingMessageContent::ToolUse(tool_use) => format!("[tool use: {}]", tool_use.name),
+            SamplingMessageContent::ToolResult(_) => "[tool result]".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn run_sampling_request(
+    sess: &Session,
+    server: String,
+    request: SamplingRequest,
+) -> Result<McpSamplingOutput> {
+    let turn_context = sess.new_default_turn().await;
+    let model = turn_context.model_info.slug.clone();
+    let messages = request.messages.iter().map(sampling_message_text).collect();
+
+    let outcome = match sampling_prompt(&request) {
+        Ok(prompt) => stream_text(sess, &turn_context, &prompt).await,
+        Err(err) => Err(err),
+    };
+    let outcome = outcome.map(|(text, token_usage, reached_limit)| {
+        // The request carries `max_tokens`, but a provider that ignores it is
+        // still held to it here.
+        let policy =
+            TruncationPolicy::Tokens(usize::try_from(request.max_tokens).unwrap_or(usize::MAX));
+        if exceeds_budget(&text, policy) {
+            (
+                truncate_text(&text, policy),
+                token_usage,
+                SamplingResponse::STOP_REASON_END_MAX_TOKEN,
+            )
+        } else if reached_limit {
+            (
+                text,
+                token_usage,
+                SamplingResponse::STOP_REASON_END_MAX_TOKEN,
+            )
+        } else {
+            (text, token_usage, SamplingResponse::STOP_REASON_END_TURN)
+        }
+    });
+
+    sess.send_event(
+        &turn_context,
+        EventMsg::McpSamplingEnd(McpSamplingEndEvent {
+            server,
+            model: model.clone(),
+            messages,
+            result: match &outcome {
+                Ok((text, _, _)) => Ok(text.clone()),
+                Err(err) => Err(format!("{err:#}")),
+            },
+            token_usage: outcome
+                .as_ref()
+                .ok()
+                .and_then(|(_, token_usage, _)| token_usage.clone()),
+        }),
+    )
+    .await;
+
+    let (text, token_usage, stop_reason) = outcome?;
+    // The limit is reserved in output tokens (`max_tokens`), so settle in output tokens too.
+    // Without usage from the provider, charge the full amount the server asked for.
+    let tokens_used = token_usage
+        .and_then(|usage| u64::try_from(usage.output_tokens).ok())
+        .unwrap_or_else(|| u64::from(request.max_tokens));
+    Ok(McpSamplingOutput {
+        response: SamplingResponse {
+            model,
+            stop_reason: Some(stop_reason.to_string()),
+            message: SamplingMessage {
+                role: Role::Assistant,
+                content: SamplingMessageContent::text(text).into(),
+                meta: None,
+            },
+        },
+        tokens_used,
+    })
+}
+
+fn sampling_prompt(request: &SamplingRequest) -> Result<Prompt> {
+    let input = request
+        .messages
+        .iter()
+        .map(sampling_message_to_response_item)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Prompt {
+        input,
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions: BaseInstructions {
+            text: request.system_prompt.clone().unwrap_or_default(),
+        },
+        personality: None,
+        output_schema: None,
+        max_output_tokens: Some(u64::from(request.max_tokens)),
+    })
+}
+
+fn sampling_message_to_response_item(message: &SamplingMessage) -> Result<ResponseItem> {
+    let (role, content) = match message.role {
+        Role::User => (
+            "user",
+            message
+                .content
+                .clone()
+                .into_vec()
+                .into_iter()
+                .map(|content| match content {
+                    SamplingMessageContent::Text(text) => {
+                        Ok(ContentItem::InputText { text: text.text })
+                    }
+                    SamplingMessageContent::Image(image) => Ok(ContentItem::InputImage {
+                        image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+                    }),
+                    _ => Err(anyhow!(
+                        "unsupported content in sampling request: only text and images are accepted"
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        Role::Assistant => (
+            "assistant",
+            message
+                .content
+                .clone()
+                .into_vec()
+                .into_iter()
+                .map(|content| match content {
+                    SamplingMessageContent::Text(text) => {
+                        Ok(ContentItem::OutputText { text: text.text })
+                    }
+                    _ => Err(anyhow!(
+                        "unsupported content in sampling request: assistant messages must be text"
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        ),
+    };
+    Ok(ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content,
+        end_turn: None,
+        phase: None,
+    })
+}
+
+/// Streams the reply to `prompt`. The flag is set when the model stopped at the
+/// prompt's `max_output_tokens`.
+async fn stream_text(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+) -> Result<(String, Option<TokenUsage>, bool)> {
+    let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
+    let mut client_session = sess.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            prompt,
+            &turn_context.model_info,
+            &turn_context.otel_manager,
+            turn_context.reasoning_effort,
+            turn_context.reasoning_summary,
+            turn_metadata_header.as_deref(),
+        )
+        .await?;
+
+    let mut text = String::new();
+    let mut token_usage = None;
+    let mut reached_limit = false;
+    while let Some(event) = stream.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(CodexErr::Stream(message, _)) if message == MAX_OUTPUT_TOKENS_INCOMPLETE => {
+                reached_limit = true;
+                break;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => text.push_str(&delta),
+            ResponseEvent::OutputItemDone(item) => {
+                if text.is_empty()
+                    && let ResponseItem::Message { content, .. } = item
+                    && let Some(item_text) = content_items_to_text(&content)
+                {
+                    text.push_str(&item_text);
+                }
+            }
+            ResponseEvent::Completed {
+                token_usage: usage, ..
+            } => {
+                token_usage = usage;
+                break;
+            }
+            _ => {}
+        }
+    }
+    Ok((text, token_usage, reached_limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rmcp::model::RawImageContent;
+
+    fn message(role: Role, content: Vec<SamplingMessageContent>) -> SamplingMessage {
+        SamplingMessage {
+            role,
+            content: content.into(),
+            meta: None,
+        }
+    }
+
+    fn request(messages: Vec<SamplingMessage>) -> SamplingRequest {
+        SamplingRequest {
+            meta: None,
+            task: None,
+            messages,
+            model_preferences: None,
+            system_prompt: Some("Be brief.".to_string()),
+            include_context: None,
+            temperature: None,
+            max_tokens: 32,
+            stop_sequences: None,
+            metadata: None,
+            tools: None,
+            tool_choice: None,
+        }
+    }
+
+    #[test]
+    fn sampling_prompt_maps_roles_and_images() {
+        let prompt = sampling_prompt(&request(vec![
+            message(
+                Role::User,
+                vec![
+                    SamplingMessageContent::text("describe this"),
+                    SamplingMessageContent::Image(RawImageContent {
+                        data: "AAAA".to_string(),
+                        mime_type: "image/png".to_string(),
+                        meta: None,
+                    }),
+                ],
+            ),
+            message(Role::Assistant, vec![SamplingMessageContent::text("a dot")]),
+        ]))
+        .expect("prompt");
+
+        assert_eq!(prompt.base_instructions.text, "Be brief.");
+        assert_eq!(prompt.max_output_tokens, Some(32));
+        assert_eq!(
+            prompt.input,
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![
+                        ContentItem::InputText {
+                            text: "describe this".to_string(),
+                        },
+                        ContentItem::InputImage {
+                            image_url: "data:image/png;base64,AAAA".to_string(),
+                        },
+                    ],
+                    end_turn: None,
+                    phase: None,
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "a dot".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sampling_prompt_rejects_tool_use() {
+        let err = sampling_prompt(&request(vec![message(
+            Role::Assistant,
+            vec![SamplingMessageContent::tool_use(
+                "call-1",
+                "search",
+                Default::default(),
+            )],
+        )]))
+        .expect_err("tool use is not supported");
+
+        assert_eq!(
+            err.to_string(),
+            "unsupported content in sampling request: assistant messages must be text"
+        );
+    }
+}
@@ -389,6 +389,7 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         });
     }
 
@@ -414,6 +415,7 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         });
     }
 
@@ -472,6 +474,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
@@ -521,6 +524,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
         )]);
 
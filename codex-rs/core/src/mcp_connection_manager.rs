@@ -14,12 +14,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::mcp::sampling_message_text;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -37,11 +39,17 @@ use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
+use codex_protocol::protocol::McpToolsChangedEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::ListRoots;
+use codex_rmcp_client::McpListChanged;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SamplingRequest;
+use codex_rmcp_client::SamplingResponse;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
@@ -50,17 +58,24 @@ use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::ElicitationAction;
 use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
+use rmcp::model::ListRootsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
 use rmcp::model::Resource;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SamplingCapability;
 use rmcp::model::Tool;
 
 use serde::Deserialize;
@@ -68,6 +83,8 @@ use serde::Serialize;
 use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
@@ -94,6 +111,9 @@ pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 /// Default timeout for individual tool calls.
 const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Default for [`McpServerConfig::sampling_token_limit`].
+const DEFAULT_SAMPLING_TOKEN_LIMIT: u64 = 100_000;
+
 const CODEX_APPS_TOOLS_CACHE_SCHEMA_VERSION: u8 = 1;
 const CODEX_APPS_TOOLS_CACHE_DIR: &str = "cache/codex_apps_tools";
 const MCP_TOOLS_LIST_DURATION_METRIC: &str = "codex.mcp.tools.list.duration_ms";
@@ -236,6 +256,21 @@ enum CachedCodexAppsToolsLoad {
 
 type ResponderMap = HashMap<(String, RequestId), oneshot::Sender<ElicitationResponse>>;
 
+/// Result of a sampling request run by an [`McpSampler`].
+pub(crate) struct McpSamplingOutput {
+    pub(crate) response: SamplingResponse,
+    /// Output tokens the request consumed, counted against the server's
+    /// `sampling_token_limit` in the same unit as the `max_tokens` reserved
+    /// for it.
+    pub(crate) tokens_used: u64,
+}
+
+/// Runs an approved `sampling/createMessage` request from the named server
+/// against the session's model.
+pub(crate) type McpSampler = Arc<
+    dyn Fn(String, SamplingRequest) -> BoxFuture<'static, Result<McpSamplingOutput>> + Send + Sync,
+>;
+
 fn elicitation_is_rejected_by_policy(approval_policy: AskForApproval) -> bool {
     match approval_policy {
         AskForApproval::Never => true,
@@ -275,68 +310,193 @@ impl ElicitationRequestManager {
             .map_err(|e| anyhow!("failed to send elicitation response: {e:?}"))
     }
 
+    fn is_rejected_by_policy(&self) -> bool {
+        self.approval_policy
+            .lock()
+            .is_ok_and(|policy| elicitation_is_rejected_by_policy(*policy))
+    }
+
+    /// Shows `message` to the user and waits for their answer.
+    async fn ask(
+        &self,
+        server_name: String,
+        id: RequestId,
+        message: String,
+        tx_event: &Sender<Event>,
+    ) -> Result<ElicitationResponse> {
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut lock = self.requests.lock().await;
+            lock.insert((server_name.clone(), id.clone()), tx);
+        }
+        let _ = tx_event
+            .send(Event {
+                id: "mcp_elicitation_request".to_string(),
+                msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                    server_name,
+                    id: match id {
+                        rmcp::model::NumberOrString::String(value) => {
+                            ProtocolRequestId::String(value.to_string())
+                        }
+                        rmcp::model::NumberOrString::Number(value) => {
+                            ProtocolRequestId::Integer(value)
+                        }
+                    },
+                    message,
+                }),
+            })
+            .await;
+        rx.await
+            .context("elicitation request channel closed unexpectedly")
+    }
+
     fn make_sender(&self, server_name: String, tx_event: Sender<Event>) -> SendElicitation {
-        let elicitation_requests = self.requests.clone();
-        let approval_policy = self.approval_policy.clone();
+        let manager = self.clone();
         Box::new(move |id, elicitation| {
-            let elicitation_requests = elicitation_requests.clone();
+            let manager = manager.clone();
             let tx_event = tx_event.clone();
             let server_name = server_name.clone();
-            let approval_policy = approval_policy.clone();
             async move {
-                if approval_policy
-                    .lock()
-                    .is_ok_and(|policy| elicitation_is_rejected_by_policy(*policy))
-                {
+                if manager.is_rejected_by_policy() {
                     return Ok(ElicitationResponse {
                         action: ElicitationAction::Decline,
                         content: None,
                     });
                 }
 
-                let (tx, rx) = oneshot::channel();
-                {
-                    let mut lock = elicitation_requests.lock().await;
-                    lock.insert((server_name.clone(), id.clone()), tx);
+                let message = match elicitation {
+                    CreateElicitationRequestParams::FormElicitationParams { message, .. }
+                    | CreateElicitationRequestParams::UrlElicitationParams { message, .. } => {
+                        message
+                    }
+                };
+                manager.ask(server_name, id, message, &tx_event).await
+            }
+            .boxed()
+        })
+    }
+
+    /// Sampling requests are approved through the same prompt as
+    /// elicitations, then run with `sampler` as long as the server stays
+    /// within `token_limit` for the session.
+    fn make_sampling_sender(
+        &self,
+        server_name: String,
+        tx_event: Sender<Event>,
+        sampler: McpSampler,
+        token_limit: u64,
+    ) -> SendSampling {
+        let manager = self.clone();
+        let tokens_used = Arc::new(AtomicU64::new(0));
+        Box::new(move |id, request| {
+            let manager = manager.clone();
+            let tx_event = tx_event.clone();
+            let server_name = server_name.clone();
+            let sampler = Arc::clone(&sampler);
+            let tokens_used = Arc::clone(&tokens_used);
+            async move {
+                if manager.is_rejected_by_policy() {
+                    return Err(anyhow!(
+                        "sampling requests are not allowed by the approval policy"
+                    ));
                 }
-                let _ = tx_event
-                    .send(Event {
-                        id: "mcp_elicitation_request".to_string(),
-                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
-                            server_name,
-                            id: match id.clone() {
-                                rmcp::model::NumberOrString::String(value) => {
-                                    ProtocolRequestId::String(value.to_string())
-                                }
-                                rmcp::model::NumberOrString::Number(value) => {
-                                    ProtocolRequestId::Integer(value)
-                                }
-                            },
-                            message: match elicitation {
-                                CreateElicitationRequestParams::FormElicitationParams {
-                                    message,
-                                    ..
-                                }
-                                | CreateElicitationRequestParams::UrlElicitationParams {
-                                    message,
-                                    ..
-                                } => message,
-                            },
-                        }),
+                // Reserve the request's budget before running it so that
+                // concurrent requests cannot overshoot the limit together.
+                let reserved = u64::from(request.max_tokens);
+                if let Err(used) =
+                    tokens_used.fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                        let total = used.saturating_add(reserved);
+                        (total <= token_limit).then_some(total)
                     })
-                    .await;
-                rx.await
-                    .context("elicitation request channel closed unexpectedly")
+                {
+                    return Err(anyhow!(
+                        "sampling token limit reached for MCP server `{server_name}` ({used} of {token_limit} tokens used)"
+                    ));
+                }
+
+                let result = async {
+                    let message = sampling_approval_message(&request);
+                    let response = manager
+                        .ask(server_name.clone(), id, message, &tx_event)
+                        .await?;
+                    if response.action != ElicitationAction::Accept {
+                        return Err(anyhow!("user declined the sampling request"));
+                    }
+                    sampler(server_name, request).await
+                }
+                .await;
+
+                // Settle the reservation with what the request actually used.
+                let used = result.as_ref().map_or(0, |output| output.tokens_used);
+                if used >= reserved {
+                    tokens_used.fetch_add(used - reserved, Ordering::AcqRel);
+                } else {
+                    tokens_used.fetch_sub(reserved - used, Ordering::AcqRel);
+                }
+                result.map(|output| output.response)
             }
             .boxed()
         })
     }
 }
 
+fn sampling_approval_message(request: &SamplingRequest) -> String {
+    let prompt = request
+        .messages
+        .last()
+        .map(sampling_message_text)
+        .unwrap_or_default();
+    format!(
+        "Allow this server to run a prompt on your model (up to {} tokens)?\n\n{prompt}",
+        request.max_tokens
+    )
+}
+
+/// Roots reported to servers: the session cwd followed by the additional
+/// writable roots of a workspace-write sandbox.
+fn roots_for_sandbox_state(sandbox_state: &SandboxState) -> Vec<Root> {
+    let mut paths = vec![sandbox_state.sandbox_cwd.clone()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &sandbox_state.sandbox_policy {
+        for root in writable_roots {
+            let path = root.to_path_buf();
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let uri = Url::from_file_path(&path).ok()?;
+            Some(Root {
+                uri: uri.to_string(),
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+            })
+        })
+        .collect()
+}
+
+fn make_list_roots(sandbox_state: Arc<StdMutex<Option<SandboxState>>>) -> ListRoots {
+    Box::new(move || {
+        let roots = match sandbox_state.lock() {
+            Ok(state) => state.as_ref().map(roots_for_sandbox_state),
+            Err(err) => err.into_inner().as_ref().map(roots_for_sandbox_state),
+        }
+        .unwrap_or_default();
+        async move { Ok(ListRootsResult { roots }) }.boxed()
+    })
+}
+
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
-    tools: Vec<ToolInfo>,
+    /// Shared with the list-changed listener, which replaces the contents
+    /// whenever the server announces a new tool list.
+    tools: Arc<StdMutex<Vec<ToolInfo>>>,
+    /// Prompts listed at startup, refreshed the same way as `tools`.
+    prompts: Arc<StdMutex<Vec<Prompt>>>,
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
@@ -366,7 +526,17 @@ impl ManagedClient {
             );
         }
 
-        self.tools.clone()
+        match self.tools.lock() {
+            Ok(tools) => tools.clone(),
+            Err(err) => err.into_inner().clone(),
+        }
+    }
+
+    fn listed_prompts(&self) -> Vec<Prompt> {
+        match self.prompts.lock() {
+            Ok(prompts) => prompts.clone(),
+            Err(err) => err.into_inner().clone(),
+        }
     }
 
     /// Returns once the server has ack'd the sandbox state update.
@@ -401,6 +571,8 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampler: Option<McpSampler>,
+        sandbox_state: Arc<StdMutex<Option<SandboxState>>>,
         codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
     ) -> Self {
         let tool_filter = ToolFilter::from_config(&config);
@@ -431,6 +603,11 @@ impl AsyncManagedClient {
                         tool_filter: startup_tool_filter,
                         tx_event,
                         elicitation_requests,
+                        sampler,
+                        sampling_token_limit: config
+                            .sampling_token_limit
+                            .unwrap_or(DEFAULT_SAMPLING_TOKEN_LIMIT),
+                        sandbox_state,
                         codex_apps_tools_cache_context,
                     },
                 )
@@ -487,6 +664,11 @@ impl AsyncManagedClient {
         let managed = self.client().await?;
         managed.notify_sandbox_state_change(sandbox_state).await
     }
+
+    async fn notify_roots_list_changed(&self) -> Result<()> {
+        let managed = self.client().await?;
+        managed.client.notify_roots_list_changed().await
+    }
 }
 
 pub const MCP_SANDBOX_STATE_CAPABILITY: &str = "codex/sandbox-state";
@@ -510,6 +692,9 @@ pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     server_origins: HashMap<String, String>,
     elicitation_requests: ElicitationRequestManager,
+    sampler: Option<McpSampler>,
+    /// Latest sandbox state, used to answer `roots/list`.
+    sandbox_state: Arc<StdMutex<Option<SandboxState>>>,
 }
 
 impl McpConnectionManager {
@@ -518,6 +703,8 @@ impl McpConnectionManager {
             clients: HashMap::new(),
             server_origins: HashMap::new(),
             elicitation_requests: ElicitationRequestManager::new(approval_policy.value()),
+            sampler: None,
+            sandbox_state: Arc::new(StdMutex::new(None)),
         }
     }
 
@@ -536,6 +723,12 @@ impl McpConnectionManager {
         self.server_origins.get(server_name).map(String::as_str)
     }
 
+    /// Sampler handed to servers started by this manager, reused when the
+    /// servers are restarted.
+    pub(crate) fn sampler(&self) -> Option<McpSampler> {
+        self.sampler.clone()
+    }
+
     pub fn set_approval_policy(&self, approval_policy: &Constrained<AskForApproval>) {
         if let Ok(mut policy) = self.elicitation_requests.approval_policy.lock() {
             *policy = approval_policy.value();
@@ -552,12 +745,14 @@ impl McpConnectionManager {
         initial_sandbox_state: SandboxState,
         codex_home: PathBuf,
         codex_apps_tools_cache_key: CodexAppsToolsCacheKey,
+        sampler: Option<McpSampler>,
     ) -> (Self, CancellationToken) {
         let cancel_token = CancellationToken::new();
         let mut clients = HashMap::new();
         let mut server_origins = HashMap::new();
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::new(approval_policy.value());
+        let sandbox_state = Arc::new(StdMutex::new(Some(initial_sandbox_state.clone())));
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             if let Some(origin) = transport_origin(&cfg.transport) {
@@ -587,6 +782,8 @@ impl McpConnectionManager {
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                sampler.clone(),
+                Arc::clone(&sandbox_state),
                 codex_apps_tools_cache_context,
             );
             clients.insert(server_name.clone(), async_managed_client.clone());
@@ -637,6 +834,8 @@ impl McpConnectionManager {
             clients,
             server_origins,
             elicitation_requests: elicitation_requests.clone(),
+            sampler,
+            sandbox_state,
         };
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
@@ -912,6 +1111,22 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns the prompts discovered at startup, keyed by server name. Servers
+    /// that do not offer prompts are omitted.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut aggregated = HashMap::new();
+        for (server_name, async_managed_client) in &self.clients {
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            let prompts = managed_client.listed_prompts();
+            if !prompts.is_empty() {
+                aggregated.insert(server_name.clone(), prompts);
+            }
+        }
+        aggregated
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -998,6 +1213,33 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Fetch a prompt from the specified server with the given arguments.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let params = GetPromptRequestParams {
+            meta: None,
+            name: name.to_string(),
+            arguments: (!arguments.is_empty()).then(|| {
+                arguments
+                    .into_iter()
+                    .map(|(key, value)| (key, serde_json::Value::String(value)))
+                    .collect()
+            }),
+        };
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}` ({name})"))
+    }
+
     pub async fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.list_all_tools()
             .await
@@ -1006,6 +1248,16 @@ impl McpConnectionManager {
     }
 
     pub async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        let roots_changed = {
+            let mut guard = match self.sandbox_state.lock() {
+                Ok(guard) => guard,
+                Err(err) => err.into_inner(),
+            };
+            let previous_roots = guard.as_ref().map(roots_for_sandbox_state);
+            *guard = Some(sandbox_state.clone());
+            previous_roots != Some(roots_for_sandbox_state(sandbox_state))
+        };
+
         let mut join_set = JoinSet::new();
 
         for async_managed_client in self.clients.values() {
@@ -1014,7 +1266,11 @@ impl McpConnectionManager {
             join_set.spawn(async move {
                 async_managed_client
                     .notify_sandbox_state_change(&sandbox_state)
-                    .await
+                    .await?;
+                if roots_changed {
+                    async_managed_client.notify_roots_list_changed().await?;
+                }
+                Ok(())
             });
         }
 
@@ -1232,16 +1488,23 @@ async fn start_server_task(
         tool_filter,
         tx_event,
         elicitation_requests,
+        sampler,
+        sampling_token_limit,
+        sandbox_state,
         codex_apps_tools_cache_context,
     } = params;
     let elicitation = elicitation_capability_for_server(&server_name);
+    // A zero limit turns sampling off for the server.
+    let sampler = sampler.filter(|_| sampling_token_limit > 0);
     let params = InitializeRequestParams {
         meta: None,
         capabilities: ClientCapabilities {
             experimental: None,
             extensions: None,
-            roots: None,
-            sampling: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: sampler.as_ref().map(|_| SamplingCapability::default()),
             elicitation,
             tasks: None,
         },
@@ -1256,10 +1519,27 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
-    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event.clone());
+    let send_sampling = sampler.map(|sampler| {
+        elicitation_requests.make_sampling_sender(
+            server_name.clone(),
+            tx_event.clone(),
+            sampler,
+            sampling_token_limit,
+        )
+    });
 
+    // Subscribe before the initial listing so a `list_changed` sent while it is
+    // in flight triggers a re-list instead of being dropped.
+    let list_changed = client.subscribe_list_changed();
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            send_sampling,
+            Some(make_list_roots(sandbox_state)),
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
@@ -1287,6 +1567,18 @@ async fn start_server_task(
     }
     let tools = filter_tools(tools, &tool_filter);
 
+    // Prompts are optional, so a failure here should not fail startup.
+    let prompts = if initialize_result.capabilities.prompts.is_some() {
+        list_prompts_for_client(&client, startup_timeout)
+            .await
+            .unwrap_or_else(|err| {
+                warn!("Failed to list prompts for MCP server {server_name}: {err:#}");
+                Vec::new()
+            })
+    } else {
+        Vec::new()
+    };
+
     let server_supports_sandbox_state_capability = initialize_result
         .capabilities
         .experimental
@@ -1296,22 +1588,125 @@ async fn start_server_task(
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
-        tools,
+        tools: Arc::new(StdMutex::new(tools)),
+        prompts: Arc::new(StdMutex::new(prompts)),
         tool_timeout: Some(tool_timeout),
         tool_filter,
         server_supports_sandbox_state_capability,
         codex_apps_tools_cache_context,
     };
+    spawn_list_changed_listener(server_name, &managed, list_changed, tx_event);
 
     Ok(managed)
 }
 
+/// Re-lists tools or prompts whenever the server sends the matching
+/// `list_changed` notification. The tool router is rebuilt from
+/// [`McpConnectionManager::list_all_tools`] on every turn, so replacing the
+/// cached list is enough for the next turn to see the change. Resources are
+/// always listed on demand and need no refresh.
+fn spawn_list_changed_listener(
+    server_name: String,
+    managed: &ManagedClient,
+    mut list_changed: broadcast::Receiver<McpListChanged>,
+    tx_event: Sender<Event>,
+) {
+    // Hold a weak reference so the listener does not keep the server alive.
+    let client = Arc::downgrade(&managed.client);
+    let tools = Arc::clone(&managed.tools);
+    let prompts = Arc::clone(&managed.prompts);
+    let tool_filter = managed.tool_filter.clone();
+    let tool_timeout = managed.tool_timeout;
+    let codex_apps_tools_cache_context = managed.codex_apps_tools_cache_context.clone();
+    tokio::spawn(async move {
+        loop {
+            let (refresh_tools, refresh_prompts) = match list_changed.recv().await {
+                Ok(McpListChanged::Tools) => (true, false),
+                Ok(McpListChanged::Prompts) => (false, true),
+                Ok(McpListChanged::Resources) => continue,
+                // A missed notification may have been for either list, so re-list both.
+                Err(RecvError::Lagged(_)) => (true, true),
+                Err(RecvError::Closed) => break,
+            };
+            let Some(client) = client.upgrade() else {
+                break;
+            };
+            if refresh_prompts {
+                match list_prompts_for_client(&client, tool_timeout).await {
+                    Ok(fetched) => match prompts.lock() {
+                        Ok(mut prompts) => *prompts = fetched,
+                        Err(err) => *err.into_inner() = fetched,
+                    },
+                    Err(err) => {
+                        warn!("Failed to refresh prompts for MCP server {server_name}: {err:#}");
+                    }
+                }
+            }
+            if !refresh_tools {
+                continue;
+            }
+            let fetched =
+                match list_tools_for_client_uncached(&server_name, &client, tool_timeout).await {
+                    Ok(fetched) => fetched,
+                    Err(err) => {
+                        warn!("Failed to refresh tools for MCP server {server_name}: {err:#}");
+                        continue;
+                    }
+                };
+            drop(client);
+            write_cached_codex_apps_tools_if_needed(
+                &server_name,
+                codex_apps_tools_cache_context.as_ref(),
+                &fetched,
+            );
+            let fetched = filter_tools(fetched, &tool_filter);
+            let (added, removed) = {
+                let mut tools = match tools.lock() {
+                    Ok(tools) => tools,
+                    Err(err) => err.into_inner(),
+                };
+                let diff = diff_tool_names(&tools, &fetched);
+                *tools = fetched;
+                diff
+            };
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+            let _ = tx_event
+                .send(Event {
+                    id: "mcp_tools_changed".to_string(),
+                    msg: EventMsg::McpToolsChanged(McpToolsChangedEvent {
+                        server: server_name.clone(),
+                        added,
+                        removed,
+                    }),
+                })
+                .await;
+        }
+    });
+}
+
+/// Returns the sorted tool names that were `(added, removed)` going from
+/// `before` to `after`.
+fn diff_tool_names(before: &[ToolInfo], after: &[ToolInfo]) -> (Vec<String>, Vec<String>) {
+    let before: HashSet<&str> = before.iter().map(|tool| tool.tool_name.as_str()).collect();
+    let after: HashSet<&str> = after.iter().map(|tool| tool.tool_name.as_str()).collect();
+    let mut added: Vec<String> = after.difference(&before).map(ToString::to_string).collect();
+    let mut removed: Vec<String> = before.difference(&after).map(ToString::to_string).collect();
+    added.sort();
+    removed.sort();
+    (added, removed)
+}
+
 struct StartServerTaskParams {
     startup_timeout: Option<Duration>, // TODO: cancel_token should handle this.
     tool_timeout: Duration,
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampler: Option<McpSampler>,
+    sampling_token_limit: u64,
+    sandbox_state: Arc<StdMutex<Option<SandboxState>>>,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
@@ -1504,6 +1899,31 @@ async fn list_tools_for_client_uncached(
     Ok(tools)
 }
 
+async fn list_prompts_for_client(
+    client: &RmcpClient,
+    timeout: Option<Duration>,
+) -> Result<Vec<Prompt>> {
+    let mut collected = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = cursor.as_ref().map(|next| PaginatedRequestParams {
+            meta: None,
+            cursor: Some(next.clone()),
+        });
+        let response = client.list_prompts(params, timeout).await?;
+        collected.extend(response.prompts);
+        match response.next_cursor {
+            Some(next) => {
+                if cursor.as_ref() == Some(&next) {
+                    return Err(anyhow!("prompts/list returned duplicate cursor"));
+                }
+                cursor = Some(next);
+            }
+            None => return Ok(collected),
+        }
+    }
+}
+
 fn validate_mcp_server_name(server_name: &str) -> Result<()> {
     let re = regex_lite::Regex::new(r"^[a-zA-Z0-9_-]+$")?;
     if !re.is_match(server_name) {
@@ -1586,6 +2006,7 @@ mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
     use codex_protocol::protocol::RejectConfig;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use rmcp::model::JsonObject;
     use std::collections::HashSet;
     use std::sync::Arc;
@@ -1822,6 +2243,27 @@ mod tests {
         assert_eq!(filtered[0].tool_name, "tool_a");
     }
 
+    #[test]
+    fn diff_tool_names_reports_added_and_removed_tools() {
+        let before = vec![
+            create_test_tool("server1", "kept"),
+            create_test_tool("server1", "dropped"),
+        ];
+        let after = vec![
+            create_test_tool("server1", "kept"),
+            create_test_tool("server1", "new_b"),
+            create_test_tool("server1", "new_a"),
+        ];
+
+        assert_eq!(
+            diff_tool_names(&before, &after),
+            (
+                vec!["new_a".to_string(), "new_b".to_string()],
+                vec!["dropped".to_string()],
+            )
+        );
+    }
+
     #[test]
     fn codex_apps_tools_cache_is_overwritten_by_last_write() {
         let codex_home = tempdir().expect("tempdir");
@@ -2116,6 +2558,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -2164,6 +2607,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_limit: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -2216,4 +2660,43 @@ mod tests {
 
         assert_eq!(transport_origin(&transport), Some("stdio".to_string()));
     }
+
+    #[test]
+    fn roots_include_cwd_and_deduplicated_writable_roots() {
+        let cwd = tempdir().expect("tempdir");
+        let extra = tempdir().expect("tempdir");
+        let sandbox_state = SandboxState {
+            sandbox_policy: SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path(cwd.path()).expect("absolute cwd"),
+                    AbsolutePathBuf::from_absolute_path(extra.path()).expect("absolute root"),
+                ],
+                read_only_access: Default::default(),
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
+            },
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: cwd.path().to_path_buf(),
+            use_linux_sandbox_bwrap: false,
+        };
+
+        let uris: Vec<String> = roots_for_sandbox_state(&sandbox_state)
+            .into_iter()
+            .map(|root| root.uri)
+            .collect();
+
+        assert_eq!(
+            uris,
+            vec![
+                Url::from_file_path(cwd.path())
+                    .expect("cwd uri")
+                    .to_string(),
+                Url::from_file_path(extra.path())
+                    .expect("root uri")
+                    .to_string(),
+            ]
+        );
+    }
 }
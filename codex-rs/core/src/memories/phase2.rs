@@ -284,6 +284,7 @@ mod agent {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         };
         agent_config
             .permissions
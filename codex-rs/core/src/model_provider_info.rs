@@ -27,7 +27,6 @@ const MAX_STREAM_MAX_RETRIES: u64 = 100;
 const MAX_REQUEST_MAX_RETRIES: u64 = 100;
 
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
-const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
 pub(crate) const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub(crate) const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
 
@@ -38,6 +37,12 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// The Chat Completions API at `/v1/chat/completions`, for OpenAI-compatible
+    /// third-party providers that do not implement the Responses API.
+    Chat,
+    /// The Anthropic Messages API at `/v1/messages`.
+    #[serde(rename = "anthropic_messages")]
+    AnthropicMessages,
 }
 
 impl<'de> Deserialize<'de> for WireApi {
@@ -48,8 +53,12 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
-            "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            "chat" => Ok(Self::Chat),
+            "anthropic_messages" => Ok(Self::AnthropicMessages),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "chat", "anthropic_messages"],
+            )),
         }
     }
 }
@@ -101,6 +110,10 @@ pub struct ModelProviderInfo {
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
 
+    /// Output token cap (`max_tokens`) sent with Anthropic Messages requests. Defaults to
+    /// 32,000 when unset; the thinking budget is kept below this cap.
+    pub max_output_tokens: Option<u64>,
+
     /// Does this provider require an OpenAI API Key or ChatGPT login token? If true,
     /// user is presented with login screen on first run, and login preference and token/key
     /// are stored in auth.json. If false (which is the default), login screen is skipped,
@@ -251,6 +264,7 @@ impl ModelProviderInfo {
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: true,
             supports_websockets: true,
         }
@@ -324,6 +338,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     }
@@ -353,6 +368,7 @@ base_url = "http://localhost:11434/v1"
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: false,
         };
@@ -384,6 +400,7 @@ query_params = { api-version = "2025-04-01-preview" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: false,
         };
@@ -418,6 +435,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: false,
         };
@@ -427,7 +445,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
     }
 
     #[test]
-    fn test_deserialize_chat_wire_api_shows_helpful_error() {
+    fn test_deserialize_chat_wire_api() {
         let provider_toml = r#"
 name = "OpenAI using Chat Completions"
 base_url = "https://api.openai.com/v1"
@@ -435,7 +453,27 @@ env_key = "OPENAI_API_KEY"
 wire_api = "chat"
         "#;
 
-        let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
-        assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(provider.wire_api, WireApi::Chat);
+
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic_messages"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(provider.wire_api, WireApi::AnthropicMessages);
+
+        let err = toml::from_str::<ModelProviderInfo>(
+            r#"
+name = "Unknown"
+wire_api = "completions"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown variant `completions`"));
     }
 }
@@ -7,13 +7,16 @@ use crate::config_loader::ConfigLayerStackOrdering;
 use crate::config_loader::LoaderOverrides;
 use crate::config_loader::load_config_layers_state;
 use crate::exec_policy::ExecPolicyError;
+use crate::exec_policy::exec_policy_match_context;
 use crate::exec_policy::format_exec_policy_error_with_source;
 use crate::exec_policy::load_exec_policy;
+use crate::sandboxing::SandboxPermissions;
 use anyhow::Context;
 use anyhow::Result;
 use async_trait::async_trait;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_config::CONFIG_TOML_FILE;
+use codex_execpolicy::MatchContext;
 use codex_network_proxy::ConfigReloader;
 use codex_network_proxy::ConfigState;
 use codex_network_proxy::NetworkProxyConfig;
@@ -23,23 +26,50 @@ use codex_network_proxy::NetworkProxyState;
 use codex_network_proxy::build_config_state;
 use codex_network_proxy::normalize_host;
 use codex_network_proxy::validate_policy_against_constraints;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
 use serde::Deserialize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use tokio::sync::RwLock;
 
-pub async fn build_network_proxy_state() -> Result<NetworkProxyState> {
-    let (state, reloader) = build_network_proxy_state_and_reloader().await?;
+/// Builds the proxy state for a session. `when` conditions on execpolicy
+/// network rules are evaluated against the session's cwd, approval policy and
+/// sandbox policy. Callers that let those change mid-session should build with
+/// [`build_network_proxy_state_and_reloader`] and keep the reloader so they can
+/// call [`MtimeConfigReloader::update_match_context`].
+pub async fn build_network_proxy_state(
+    cwd: &Path,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+) -> Result<NetworkProxyState> {
+    let (state, reloader) =
+        build_network_proxy_state_and_reloader(cwd, approval_policy, sandbox_policy).await?;
     Ok(NetworkProxyState::with_reloader(state, Arc::new(reloader)))
 }
 
-pub async fn build_network_proxy_state_and_reloader() -> Result<(ConfigState, MtimeConfigReloader)>
-{
-    let (state, layer_mtimes) = build_config_state_with_mtimes().await?;
-    Ok((state, MtimeConfigReloader::new(layer_mtimes)))
+pub async fn build_network_proxy_state_and_reloader(
+    cwd: &Path,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+) -> Result<(ConfigState, MtimeConfigReloader)> {
+    let match_context = exec_policy_match_context(
+        cwd,
+        approval_policy,
+        sandbox_policy,
+        SandboxPermissions::UseDefault,
+        None,
+    );
+    let (state, layer_mtimes) = build_config_state_with_mtimes(&match_context).await?;
+    Ok((state, MtimeConfigReloader::new(layer_mtimes, match_context)))
 }
 
-async fn build_config_state_with_mtimes() -> Result<(ConfigState, Vec<LayerMtime>)> {
+async fn build_config_state_with_mtimes(
+    match_context: &MatchContext,
+) -> Result<(ConfigState, Vec<LayerMtime>)> {
     let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
     let cli_overrides = Vec::new();
     let overrides = LoaderOverrides::default();
@@ -67,7 +97,7 @@ async fn build_config_state_with_mtimes() -> Result<(ConfigState, Vec<LayerMtime
         );
     }
 
-    let config = config_from_layers(&config_layer_stack, &exec_policy)?;
+    let config = config_from_layers(&config_layer_stack, &exec_policy, match_context)?;
 
     let constraints = enforce_trusted_constraints(&config_layer_stack, &config)?;
     let layer_mtimes = collect_layer_mtimes(&config_layer_stack);
@@ -196,21 +226,24 @@ fn apply_network_tables(config: &mut NetworkProxyConfig, parsed: NetworkTablesTo
 fn config_from_layers(
     layers: &ConfigLayerStack,
     exec_policy: &codex_execpolicy::Policy,
+    match_context: &MatchContext,
 ) -> Result<NetworkProxyConfig> {
     let mut config = NetworkProxyConfig::default();
     for layer in layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false) {
         let parsed = network_tables_from_toml(&layer.config)?;
         apply_network_tables(&mut config, parsed);
     }
-    apply_exec_policy_network_rules(&mut config, exec_policy);
+    apply_exec_policy_network_rules(&mut config, exec_policy, match_context);
     Ok(config)
 }
 
 fn apply_exec_policy_network_rules(
     config: &mut NetworkProxyConfig,
     exec_policy: &codex_execpolicy::Policy,
+    match_context: &MatchContext,
 ) {
-    let (allowed_domains, denied_domains) = exec_policy.compiled_network_domains();
+    let (allowed_domains, denied_domains) =
+        exec_policy.compiled_network_domains_with_context(match_context);
     for host in allowed_domains {
         upsert_network_domain(
             &mut config.network.allowed_domains,
@@ -257,16 +290,46 @@ impl LayerMtime {
 
 pub struct MtimeConfigReloader {
     layer_mtimes: RwLock<Vec<LayerMtime>>,
+    match_context: RwLock<MatchContext>,
+    context_changed: AtomicBool,
 }
 
 impl MtimeConfigReloader {
-    fn new(layer_mtimes: Vec<LayerMtime>) -> Self {
+    fn new(layer_mtimes: Vec<LayerMtime>, match_context: MatchContext) -> Self {
         Self {
             layer_mtimes: RwLock::new(layer_mtimes),
+            match_context: RwLock::new(match_context),
+            context_changed: AtomicBool::new(false),
+        }
+    }
+
+    /// Re-targets `when` conditions on execpolicy network rules at the session's
+    /// current cwd, approval policy and sandbox policy. The next reload check
+    /// rebuilds the proxy state if the context changed.
+    pub async fn update_match_context(
+        &self,
+        cwd: &Path,
+        approval_policy: AskForApproval,
+        sandbox_policy: &SandboxPolicy,
+    ) {
+        let match_context = exec_policy_match_context(
+            cwd,
+            approval_policy,
+            sandbox_policy,
+            SandboxPermissions::UseDefault,
+            None,
+        );
+        let mut guard = self.match_context.write().await;
+        if *guard != match_context {
+            *guard = match_context;
+            self.context_changed.store(true, Ordering::SeqCst);
         }
     }
 
     async fn needs_reload(&self) -> bool {
+        if self.context_changed.load(Ordering::SeqCst) {
+            return true;
+        }
         let guard = self.layer_mtimes.read().await;
         guard.iter().any(|layer| {
             let metadata = std::fs::metadata(&layer.path).ok();
@@ -291,14 +354,19 @@ impl ConfigReloader for MtimeConfigReloader {
             return Ok(None);
         }
 
-        let (state, layer_mtimes) = build_config_state_with_mtimes().await?;
-        let mut guard = self.layer_mtimes.write().await;
-        *guard = layer_mtimes;
-        Ok(Some(state))
+        self.reload_now().await.map(Some)
     }
 
     async fn reload_now(&self) -> Result<ConfigState> {
-        let (state, layer_mtimes) = build_config_state_with_mtimes().await?;
+        let match_context = self.match_context.read().await.clone();
+        self.context_changed.store(false, Ordering::SeqCst);
+        let (state, layer_mtimes) = match build_config_state_with_mtimes(&match_context).await {
+            Ok(built) => built,
+            Err(err) => {
+                self.context_changed.store(true, Ordering::SeqCst);
+                return Err(err);
+            }
+        };
         let mut guard = self.layer_mtimes.write().await;
         *guard = layer_mtimes;
         Ok(state)
@@ -312,6 +380,7 @@ mod tests {
     use codex_execpolicy::Decision;
     use codex_execpolicy::NetworkRuleProtocol;
     use codex_execpolicy::Policy;
+    use codex_execpolicy::PolicyParser;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -368,7 +437,7 @@ allowed_domains = ["higher.example.com"]
             )
             .expect("deny rule should be valid");
 
-        apply_exec_policy_network_rules(&mut config, &exec_policy);
+        apply_exec_policy_network_rules(&mut config, &exec_policy, &MatchContext::default());
 
         assert_eq!(
             config.network.allowed_domains,
@@ -383,6 +452,81 @@ allowed_domains = ["higher.example.com"]
         );
     }
 
+    #[test]
+    fn conditional_execpolicy_network_rules_use_the_session_context() {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "network.rules",
+                r#"
+network_rule(
+    host = "registry.npmjs.org",
+    protocol = "https",
+    decision = "allow",
+    when = {"sandbox": ["workspace-write"], "approval_policy": ["on-request"]},
+)
+"#,
+            )
+            .expect("policy should parse");
+        let exec_policy = parser.build();
+        let cwd = std::env::temp_dir();
+
+        let mut config = NetworkProxyConfig::default();
+        let workspace_write = exec_policy_match_context(
+            &cwd,
+            AskForApproval::OnRequest,
+            &SandboxPolicy::new_workspace_write_policy(),
+            SandboxPermissions::UseDefault,
+            None,
+        );
+        apply_exec_policy_network_rules(&mut config, &exec_policy, &workspace_write);
+        assert_eq!(
+            config.network.allowed_domains,
+            vec!["registry.npmjs.org".to_string()]
+        );
+
+        let mut config = NetworkProxyConfig::default();
+        let read_only = exec_policy_match_context(
+            &cwd,
+            AskForApproval::OnRequest,
+            &SandboxPolicy::new_read_only_policy(),
+            SandboxPermissions::UseDefault,
+            None,
+        );
+        apply_exec_policy_network_rules(&mut config, &exec_policy, &read_only);
+        assert_eq!(config.network.allowed_domains, Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn match_context_changes_force_a_reload() {
+        let cwd = std::env::temp_dir();
+        let workspace_write = SandboxPolicy::new_workspace_write_policy();
+        let reloader = MtimeConfigReloader::new(
+            Vec::new(),
+            exec_policy_match_context(
+                &cwd,
+                AskForApproval::OnRequest,
+                &workspace_write,
+                SandboxPermissions::UseDefault,
+                None,
+            ),
+        );
+
+        reloader
+            .update_match_context(&cwd, AskForApproval::OnRequest, &workspace_write)
+            .await;
+        assert!(!reloader.needs_reload().await);
+
+        reloader
+            .update_match_context(
+                &cwd,
+                AskForApproval::OnRequest,
+                &SandboxPolicy::new_read_only_policy(),
+            )
+            .await;
+        assert!(reloader.needs_reload().await);
+    }
+
     #[test]
     fn apply_network_constraints_includes_allow_all_unix_sockets_flag() {
         let config: toml::Value = toml::from_str(
@@ -73,6 +73,17 @@ pub struct ThreadItem {
     pub updated_at: Option<String>,
 }
 
+/// A thread matching a full-text search over its contents.
+#[derive(Debug, PartialEq)]
+pub struct ThreadSearchItem {
+    /// Summary of the matching thread.
+    pub thread: ThreadItem,
+    /// Kind of content (user message, agent message or command) that matched best.
+    pub kind: codex_state::ThreadSearchKind,
+    /// Excerpt of the best match with matched terms wrapped in `[` and `]`.
+    pub snippet: String,
+}
+
 #[allow(dead_code)]
 #[deprecated(note = "use ThreadItem")]
 pub type ConversationItem = ThreadItem;
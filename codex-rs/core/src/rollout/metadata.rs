@@ -22,6 +22,7 @@ use codex_state::DB_METRIC_BACKFILL_DURATION_MS;
 use codex_state::ExtractionOutcome;
 use codex_state::ThreadMetadataBuilder;
 use codex_state::apply_rollout_item;
+use codex_state::search_entries_from_items;
 use std::path::Path;
 use std::path::PathBuf;
 use tracing::info;
@@ -130,6 +131,7 @@ pub(crate) async fn extract_metadata_from_rollout(
     Ok(ExtractionOutcome {
         metadata,
         parse_errors,
+        search_entries: search_entries_from_items(items.as_slice()),
     })
 }
 
@@ -283,6 +285,22 @@ pub(crate) async fn backfill_sessions(
                         warn!("failed to upsert rollout {}: {err}", rollout.path.display());
                     } else {
                         stats.upserted = stats.upserted.saturating_add(1);
+                        if let Err(err) = runtime
+                            .replace_thread_search_entries(
+                                metadata.id,
+                                outcome.search_entries.as_slice(),
+                            )
+                            .await
+                        {
+                            if let Some(otel) = otel {
+                                otel.counter(
+                                    DB_ERROR_METRIC,
+                                    1,
+                                    &[("stage", "backfill_thread_search")],
+                                );
+                            }
+                            warn!("failed to index rollout {}: {err}", rollout.path.display());
+                        }
                         if let Ok(meta_line) =
                             rollout::list::read_session_meta_line(&rollout.path).await
                         {
@@ -29,6 +29,7 @@ use super::list::Cursor;
 use super::list::ThreadItem;
 use super::list::ThreadListConfig;
 use super::list::ThreadListLayout;
+use super::list::ThreadSearchItem;
 use super::list::ThreadSortKey;
 use super::list::ThreadsPage;
 use super::list::get_threads;
@@ -294,6 +295,44 @@ impl RolloutRecorder {
         Ok(truncate_fs_page(fs_page, page_size, sort_key))
     }
 
+    /// Search thread contents (user messages, agent messages and commands) using the
+    /// SQLite full-text index, best match first. Fails when the state DB is unavailable.
+    pub async fn search_threads(
+        config: &Config,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[SessionSource],
+        model_providers: Option<&[String]>,
+        archived: bool,
+        cwd: Option<&Path>,
+    ) -> std::io::Result<Vec<ThreadSearchItem>> {
+        let state_db_ctx = state_db::get_state_db(config, None).await;
+        let hits = state_db::search_threads_db(
+            state_db_ctx.as_deref(),
+            query,
+            limit,
+            allowed_sources,
+            model_providers,
+            archived,
+            cwd,
+        )
+        .await
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "thread search requires the state database",
+            )
+        })?;
+        Ok(hits
+            .into_iter()
+            .map(|hit| ThreadSearchItem {
+                thread: thread_item_from_metadata(hit.thread),
+                kind: hit.kind,
+                snippet: hit.snippet,
+            })
+            .collect())
+    }
+
     /// Find the newest recorded thread path, optionally filtering to a matching cwd.
     #[allow(clippy::too_many_arguments)]
     pub async fn find_latest_thread_path(
@@ -933,26 +972,7 @@ impl From<codex_state::ThreadsPage> for ThreadsPage {
         let items = db_page
             .items
             .into_iter()
-            .map(|item| ThreadItem {
-                path: item.rollout_path,
-                thread_id: Some(item.id),
-                first_user_message: item.first_user_message,
-                cwd: Some(item.cwd),
-                git_branch: item.git_branch,
-                git_sha: item.git_sha,
-                git_origin_url: item.git_origin_url,
-                source: Some(
-                    serde_json::from_str(item.source.as_str())
-                        .or_else(|_| serde_json::from_value(Value::String(item.source)))
-                        .unwrap_or(SessionSource::Unknown),
-                ),
-                agent_nickname: item.agent_nickname,
-                agent_role: item.agent_role,
-                model_provider: Some(item.model_provider),
-                cli_version: Some(item.cli_version),
-                created_at: Some(item.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
-                updated_at: Some(item.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
-            })
+            .map(thread_item_from_metadata)
             .collect();
         Self {
             items,
@@ -963,6 +983,29 @@ impl From<codex_state::ThreadsPage> for ThreadsPage {
     }
 }
 
+fn thread_item_from_metadata(item: codex_state::ThreadMetadata) -> ThreadItem {
+    ThreadItem {
+        path: item.rollout_path,
+        thread_id: Some(item.id),
+        first_user_message: item.first_user_message,
+        cwd: Some(item.cwd),
+        git_branch: item.git_branch,
+        git_sha: item.git_sha,
+        git_origin_url: item.git_origin_url,
+        source: Some(
+            serde_json::from_str(item.source.as_str())
+                .or_else(|_| serde_json::from_value(Value::String(item.source)))
+                .unwrap_or(SessionSource::Unknown),
+        ),
+        agent_nickname: item.agent_nickname,
+        agent_role: item.agent_role,
+        model_provider: Some(item.model_provider),
+        cli_version: Some(item.cli_version),
+        created_at: Some(item.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        updated_at: Some(item.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+    }
+}
+
 async fn select_resume_path(
     page: &ThreadsPage,
     filter_cwd: Option<&Path>,
@@ -169,11 +169,23 @@ fn is_write_patch_constrained_to_writable_paths(
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::Delete { .. }
+            | ApplyPatchFileChange::Binary { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
             }
+            // Later writes through the link land on its target, so that must be writable too.
+            ApplyPatchFileChange::Symlink { target } => {
+                let resolved_target = path
+                    .parent()
+                    .map(|parent| parent.join(target))
+                    .unwrap_or_else(|| target.clone());
+                if !is_path_writable(path) || !is_path_writable(&resolved_target) {
+                    return false;
+                }
+            }
             ApplyPatchFileChange::Update { move_path, .. } => {
                 if !is_path_writable(path) {
                     return false;
@@ -219,6 +231,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -241,6 +254,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
@@ -287,6 +301,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
         assert_eq!(
@@ -328,6 +343,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
         assert_eq!(
@@ -11,6 +11,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
+use crate::landlock::ResourceLimitReport;
 use crate::landlock::allow_network_for_proxy;
 use crate::landlock::create_linux_sandbox_command_args;
 use crate::protocol::SandboxPolicy;
@@ -23,6 +24,7 @@ use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::config_types::SandboxResourceLimits;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::FileSystemPermissions;
 #[cfg(target_os = "macos")]
@@ -61,6 +63,9 @@ pub struct ExecRequest {
     pub sandbox_policy: SandboxPolicy,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Where the Linux sandbox reports which resource limit, if any, stopped
+    /// the command.
+    pub resource_limit_report: Option<ResourceLimitReport>,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -79,6 +84,10 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub macos_seatbelt_profile_extensions: Option<&'a MacOsSeatbeltProfileExtensions>,
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
+    pub resource_limits: &'a SandboxResourceLimits,
+    /// Copy-on-write overlay directory mounted over `sandbox_policy_cwd` by
+    /// the Linux sandbox.
+    pub workspace_overlay: Option<&'a Path>,
     pub windows_sandbox_level: WindowsSandboxLevel,
 }
 
@@ -97,6 +106,25 @@ pub(crate) enum SandboxTransformError {
     #[cfg(not(target_os = "macos"))]
     #[error("seatbelt sandbox is only available on macOS")]
     SeatbeltUnavailable,
+    #[error("denied_read_paths cannot be enforced by the Windows sandbox")]
+    DeniedReadPathsUnsupported,
+}
+
+/// Fails closed when `sandbox` cannot hide the policy's
+/// `denied_read_paths`, rather than running with the files readable.
+fn ensure_denied_read_paths_enforceable(
+    sandbox: SandboxType,
+    policy: &SandboxPolicy,
+) -> Result<(), SandboxTransformError> {
+    if policy.denied_read_paths().is_empty() {
+        return Ok(());
+    }
+    match sandbox {
+        SandboxType::WindowsRestrictedToken => {
+            Err(SandboxTransformError::DeniedReadPathsUnsupported)
+        }
+        SandboxType::None | SandboxType::MacosSeatbelt | SandboxType::LinuxSeccomp => Ok(()),
+    }
 }
 
 pub(crate) fn normalize_additional_permissions(
@@ -251,6 +279,7 @@ fn sandbox_policy_with_additional_permissions(
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            denied_read_paths,
         } => {
             let mut merged_writes = writable_roots.clone();
             merged_writes.extend(extra_writes);
@@ -263,12 +292,17 @@ fn sandbox_policy_with_additional_permissions(
                 network_access: *network_access,
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                denied_read_paths: denied_read_paths.clone(),
             }
         }
-        SandboxPolicy::ReadOnly { access } => {
+        SandboxPolicy::ReadOnly {
+            access,
+            denied_read_paths,
+        } => {
             if extra_writes.is_empty() {
                 SandboxPolicy::ReadOnly {
                     access: merge_read_only_access_with_additional_reads(access, extra_reads),
+                    denied_read_paths: denied_read_paths.clone(),
                 }
             } else {
                 // todo(dylan) - for now, this grants more access than the request. We should restrict this,
@@ -283,6 +317,7 @@ fn sandbox_policy_with_additional_permissions(
                     network_access: false,
                     exclude_tmpdir_env_var: false,
                     exclude_slash_tmp: false,
+                    denied_read_paths: denied_read_paths.clone(),
                 }
             }
         }
@@ -350,6 +385,8 @@ impl SandboxManager {
             macos_seatbelt_profile_extensions,
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
+            resource_limits,
+            workspace_overlay,
             windows_sandbox_level,
         } = request;
         let effective_policy =
@@ -358,6 +395,7 @@ impl SandboxManager {
             } else {
                 policy.clone()
             };
+        ensure_denied_read_paths_enforceable(sandbox, &effective_policy)?;
         let mut env = spec.env;
         if !effective_policy.has_full_network_access() {
             env.insert(
@@ -370,6 +408,7 @@ impl SandboxManager {
         command.push(spec.program);
         command.append(&mut spec.args);
 
+        let mut resource_limit_report = None;
         let (command, sandbox_env, arg0_override) = match sandbox {
             SandboxType::None => (command, HashMap::new(), None),
             #[cfg(target_os = "macos")]
@@ -395,12 +434,19 @@ impl SandboxManager {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
                 let allow_proxy_network = allow_network_for_proxy(enforce_managed_network);
+                if !resource_limits.is_empty() {
+                    resource_limit_report =
+                        ResourceLimitReport::create(&effective_policy, sandbox_policy_cwd);
+                }
                 let mut args = create_linux_sandbox_command_args(
                     command.clone(),
                     &effective_policy,
                     sandbox_policy_cwd,
                     use_linux_sandbox_bwrap,
                     allow_proxy_network,
+                    resource_limits,
+                    resource_limit_report.as_ref(),
+                    workspace_overlay,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
@@ -435,6 +481,7 @@ impl SandboxManager {
             sandbox_policy: effective_policy,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limit_report,
         })
     }
 
@@ -454,6 +501,8 @@ pub async fn execute_env(
 #[cfg(test)]
 mod tests {
     use super::SandboxManager;
+    use super::SandboxTransformError;
+    use super::ensure_denied_read_paths_enforceable;
     use crate::exec::SandboxType;
     use crate::protocol::SandboxPolicy;
     use crate::tools::sandboxing::SandboxablePreference;
@@ -484,4 +533,27 @@ mod tests {
         );
         assert_eq!(sandbox, expected);
     }
+
+    #[test]
+    fn windows_sandbox_refuses_policies_with_denied_read_paths() {
+        let mut policy = SandboxPolicy::new_read_only_policy();
+        assert!(
+            ensure_denied_read_paths_enforceable(SandboxType::WindowsRestrictedToken, &policy)
+                .is_ok()
+        );
+
+        if let SandboxPolicy::ReadOnly {
+            denied_read_paths, ..
+        } = &mut policy
+        {
+            denied_read_paths.push(".env*".to_string());
+        }
+
+        assert!(matches!(
+            ensure_denied_read_paths_enforceable(SandboxType::WindowsRestrictedToken, &policy),
+            Err(SandboxTransformError::DeniedReadPathsUnsupported)
+        ));
+        assert!(ensure_denied_read_paths_enforceable(SandboxType::LinuxSeccomp, &policy).is_ok());
+        assert!(ensure_denied_read_paths_enforceable(SandboxType::MacosSeatbelt, &policy).is_ok());
+    }
 }
@@ -15,6 +15,7 @@ use tokio::process::Child;
 use tracing::warn;
 use url::Url;
 
+use crate::denied_read_paths::DeniedReadPaths;
 use crate::protocol::SandboxPolicy;
 use crate::seatbelt_permissions::MacOsSeatbeltProfileExtensions;
 use crate::seatbelt_permissions::build_seatbelt_extensions;
@@ -421,6 +422,15 @@ pub(crate) fn create_seatbelt_command_args_with_extensions(
     if !seatbelt_extensions.policy.is_empty() {
         policy_sections.push(seatbelt_extensions.policy.clone());
     }
+    // Seatbelt applies the last matching rule, so deny rules go after every
+    // allow section.
+    let denied_read_policy = denied_read_policy(&DeniedReadPaths::from_policy(
+        sandbox_policy,
+        sandbox_policy_cwd,
+    ));
+    if !denied_read_policy.is_empty() {
+        policy_sections.push(denied_read_policy);
+    }
 
     let full_policy = policy_sections.join("\n");
 
@@ -443,6 +453,89 @@ pub(crate) fn create_seatbelt_command_args_with_extensions(
     seatbelt_args
 }
 
+/// `(deny file-read* ...)` rules for the denied read paths of the policy, or
+/// an empty string when there are none.
+fn denied_read_policy(denied_read_paths: &DeniedReadPaths) -> String {
+    let rules: Vec<String> = denied_read_paths
+        .globs()
+        .filter_map(|glob| {
+            if glob.contains('"') {
+                warn!("skipping denied read path {glob:?}: seatbelt regexes cannot contain quotes");
+                return None;
+            }
+            let regex = glob_to_seatbelt_regex(glob);
+            Some(format!("(regex #\"^{regex}(/.*)?$\")"))
+        })
+        .collect();
+    if rules.is_empty() {
+        return String::new();
+    }
+    format!(
+        "; deny reads of configured paths\n(deny file-read*\n{}\n)",
+        rules.join(" ")
+    )
+}
+
+/// Translate an absolute glob (`*` and `?` stop at `/`, `**/` spans
+/// directories) into the POSIX extended regex syntax used by Seatbelt.
+fn glob_to_seatbelt_regex(glob: &str) -> String {
+    let mut regex = String::new();
+    let mut chars = glob.chars().peekable();
+    let mut alternation_depth = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.next_if_eq(&'/').is_some() {
+                    regex.push_str("(.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                regex.push('[');
+                if chars.next_if_eq(&'!').is_some() {
+                    regex.push('^');
+                }
+                // A `]` right after the opening bracket is a literal member.
+                let mut first = true;
+                for c in chars.by_ref() {
+                    regex.push(c);
+                    if c == ']' && !first {
+                        break;
+                    }
+                    first = false;
+                }
+            }
+            '{' => {
+                alternation_depth += 1;
+                regex.push('(');
+            }
+            '}' if alternation_depth > 0 => {
+                alternation_depth -= 1;
+                regex.push(')');
+            }
+            ',' if alternation_depth > 0 => regex.push('|'),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    push_regex_literal(&mut regex, escaped);
+                }
+            }
+            c => push_regex_literal(&mut regex, c),
+        }
+    }
+    regex
+}
+
+fn push_regex_literal(regex: &mut String, c: char) {
+    if ".^$|()[]{}*+?\\".contains(c) {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
 /// Wraps libc::confstr to return a String.
 fn confstr(name: libc::c_int) -> Option<String> {
     let mut buf = vec![0_i8; (libc::PATH_MAX as usize) + 1];
@@ -477,6 +570,7 @@ mod tests {
     use super::create_seatbelt_command_args;
     use super::create_seatbelt_command_args_with_extensions;
     use super::dynamic_network_policy;
+    use super::glob_to_seatbelt_regex;
     use super::macos_dir_params;
     use super::normalize_path_for_sandbox;
     use super::unix_socket_dir_params;
@@ -655,6 +749,7 @@ mod tests {
                 network_access: true,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             },
             false,
             &ProxyPolicyInputs {
@@ -684,6 +779,7 @@ mod tests {
                 network_access: true,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             },
             true,
             &ProxyPolicyInputs {
@@ -793,6 +889,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_seatbelt_args_denies_reads_of_denied_paths_last() {
+        let policy = SandboxPolicy::ReadOnly {
+            access: Default::default(),
+            denied_read_paths: vec![
+                "/nonexistent-codex-test/secrets".to_string(),
+                ".env*".to_string(),
+            ],
+        };
+        let cwd = Path::new("/nonexistent-codex-test/repo");
+        let args =
+            create_seatbelt_command_args(vec!["/bin/true".to_string()], &policy, cwd, false, None);
+
+        let policy_text = &args[1];
+        let deny_section = concat!(
+            "; deny reads of configured paths\n(deny file-read*\n",
+            r#"(regex #"^/nonexistent-codex-test/secrets(/.*)?$") "#,
+            r#"(regex #"^/nonexistent-codex-test/repo/(.*/)?\.env[^/]*(/.*)?$")"#,
+            "\n)",
+        );
+        assert!(
+            policy_text.ends_with(deny_section),
+            "deny rules must be the last section:\n{policy_text}"
+        );
+    }
+
+    #[test]
+    fn glob_to_seatbelt_regex_handles_classes_and_alternations() {
+        assert_eq!(
+            glob_to_seatbelt_regex("/a/[[]b[]]/{x,y}.?/**"),
+            r"/a/[[]b[]]/(x|y)\.[^/]/.*"
+        );
+    }
+
     #[test]
     fn create_seatbelt_args_full_network_with_proxy_is_still_proxy_only() {
         let policy = dynamic_network_policy(
@@ -802,6 +932,7 @@ mod tests {
                 network_access: true,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             },
             false,
             &ProxyPolicyInputs {
@@ -853,6 +984,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
         // Create the Seatbelt command to wrap a shell command that tries to
@@ -1047,6 +1179,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
         let shell_command: Vec<String> = [
@@ -1132,6 +1265,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         };
 
         let shell_command: Vec<String> = [
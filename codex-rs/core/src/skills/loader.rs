@@ -839,6 +839,7 @@ mod tests {
     use crate::config_loader::ConfigRequirements;
     use crate::config_loader::ConfigRequirementsToml;
     use codex_config::CONFIG_TOML_FILE;
+    use codex_protocol::config_types::SandboxResourceLimits;
     use codex_protocol::config_types::TrustLevel;
     use codex_protocol::models::FileSystemPermissions;
     use codex_protocol::models::PermissionProfile;
@@ -1411,6 +1412,7 @@ permissions:
                         network_access: true,
                         exclude_tmpdir_env_var: false,
                         exclude_slash_tmp: false,
+                        denied_read_paths: Vec::new(),
                     }
                 ),
                 network: None,
@@ -1418,6 +1420,8 @@ permissions:
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             })
         );
     }
@@ -1457,6 +1461,8 @@ permissions: {}
             macos_seatbelt_profile_extensions: Some(
                 crate::seatbelt_permissions::MacOsSeatbeltProfileExtensions::default(),
             ),
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            sandbox_copy_on_write: false,
         });
         #[cfg(not(target_os = "macos"))]
         let expected = Some(Permissions {
@@ -1469,6 +1475,8 @@ permissions: {}
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            sandbox_copy_on_write: false,
         });
         assert_eq!(outcome.skills[0].permission_profile, None);
         assert_eq!(outcome.skills[0].permissions, expected);
@@ -1565,6 +1573,8 @@ permissions:
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             })
         );
     }
@@ -3,6 +3,7 @@ use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 
+use codex_protocol::config_types::SandboxResourceLimits;
 #[cfg(target_os = "macos")]
 use codex_protocol::models::MacOsAutomationValue;
 use codex_protocol::models::MacOsPermissions;
@@ -56,6 +57,7 @@ pub(crate) fn compile_permission_profile(
             network_access: network.unwrap_or_default(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         }
     } else if !fs_read.is_empty() {
         SandboxPolicy::ReadOnly {
@@ -63,6 +65,7 @@ pub(crate) fn compile_permission_profile(
                 include_platform_defaults: true,
                 readable_roots: fs_read,
             },
+            denied_read_paths: Vec::new(),
         }
     } else {
         // Default sandbox policy
@@ -80,6 +83,8 @@ pub(crate) fn compile_permission_profile(
         shell_environment_policy: ShellEnvironmentPolicy::default(),
         windows_sandbox_mode: None,
         macos_seatbelt_profile_extensions,
+        sandbox_resource_limits: SandboxResourceLimits::default(),
+        sandbox_copy_on_write: false,
     })
 }
 
@@ -258,6 +263,7 @@ mod tests {
     use crate::protocol::AskForApproval;
     use crate::protocol::ReadOnlyAccess;
     use crate::protocol::SandboxPolicy;
+    use codex_protocol::config_types::SandboxResourceLimits;
     use codex_protocol::models::FileSystemPermissions;
     #[cfg(target_os = "macos")]
     use codex_protocol::models::MacOsAutomationValue;
@@ -317,6 +323,7 @@ mod tests {
                     network_access: true,
                     exclude_tmpdir_env_var: false,
                     exclude_slash_tmp: false,
+                    denied_read_paths: Vec::new(),
                 }),
                 network: None,
                 allow_login_shell: true,
@@ -328,6 +335,8 @@ mod tests {
                 ),
                 #[cfg(not(target_os = "macos"))]
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             }
         );
     }
@@ -373,6 +382,8 @@ mod tests {
                 ),
                 #[cfg(not(target_os = "macos"))]
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             }
         );
     }
@@ -411,6 +422,7 @@ mod tests {
                             .expect("absolute read path")
                         ],
                     },
+                    denied_read_paths: Vec::new(),
                 }),
                 network: None,
                 allow_login_shell: true,
@@ -422,6 +434,8 @@ mod tests {
                 ),
                 #[cfg(not(target_os = "macos"))]
                 macos_seatbelt_profile_extensions: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                sandbox_copy_on_write: false,
             }
         );
     }
@@ -13,10 +13,12 @@ use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
+use crate::tool_output_store::ToolOutputStore;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::runtimes::ExecveSessionApproval;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::workspace_overlay::WorkspaceOverlay;
 use codex_hooks::Hooks;
 use codex_otel::OtelManager;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -55,4 +57,9 @@ pub(crate) struct SessionServices {
     pub(crate) state_db: Option<StateDbHandle>,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
+    /// Full text of truncated tool outputs, read back by `read_tool_output`.
+    pub(crate) tool_output_store: ToolOutputStore,
+    /// Copy-on-write overlay that sandboxed commands write to, when
+    /// `copy_on_write` is enabled.
+    pub(crate) workspace_overlay: Option<Arc<WorkspaceOverlay>>,
 }
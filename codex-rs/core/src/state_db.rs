@@ -215,6 +215,58 @@ pub async fn list_thread_ids_db(
     }
 }
 
+/// Full-text search over thread contents in SQLite, best match first.
+pub async fn search_threads_db(
+    context: Option<&codex_state::StateRuntime>,
+    query: &str,
+    limit: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    archived: bool,
+    cwd: Option<&Path>,
+) -> Option<Vec<codex_state::ThreadSearchHit>> {
+    let ctx = context?;
+    let allowed_sources: Vec<String> = allowed_sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect();
+    match ctx
+        .search_threads(
+            query,
+            limit,
+            allowed_sources.as_slice(),
+            model_providers,
+            archived,
+            cwd,
+        )
+        .await
+    {
+        Ok(hits) => {
+            let mut valid_hits = Vec::with_capacity(hits.len());
+            for hit in hits {
+                if tokio::fs::try_exists(&hit.thread.rollout_path)
+                    .await
+                    .unwrap_or(false)
+                {
+                    valid_hits.push(hit);
+                } else {
+                    record_discrepancy("search_threads_db", "stale_db_path_dropped");
+                    let _ = ctx.delete_thread(hit.thread.id).await;
+                }
+            }
+            Some(valid_hits)
+        }
+        Err(err) => {
+            warn!("state db search_threads failed: {err}");
+            None
+        }
+    }
+}
+
 /// List thread metadata from SQLite without rollout directory traversal.
 #[allow(clippy::too_many_arguments)]
 pub async fn list_threads_db(
@@ -390,6 +442,15 @@ pub async fn reconcile_rollout(
         );
         return;
     }
+    if let Err(err) = ctx
+        .replace_thread_search_entries(metadata.id, outcome.search_entries.as_slice())
+        .await
+    {
+        warn!(
+            "state db reconcile_rollout search index failed {}: {err}",
+            rollout_path.display()
+        );
+    }
     if let Ok(meta_line) = crate::rollout::list::read_session_meta_line(rollout_path).await {
         persist_dynamic_tools(
             Some(ctx),
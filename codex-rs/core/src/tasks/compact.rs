@@ -5,6 +5,7 @@ use super::SessionTaskContext;
 use crate::codex::TurnContext;
 use crate::state::TaskKind;
 use async_trait::async_trait;
+use codex_hooks::HookCompactionTrigger;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
 
@@ -25,21 +26,31 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
-        let _ = if crate::compact::should_use_remote_compact_task(&ctx.provider) {
-            let _ = session.services.otel_manager.counter(
-                "codex.task.compact",
-                1,
-                &[("type", "remote")],
-            );
-            crate::compact_remote::run_remote_compact_task(session.clone(), ctx).await
-        } else {
-            let _ = session.services.otel_manager.counter(
-                "codex.task.compact",
-                1,
-                &[("type", "local")],
-            );
-            crate::compact::run_compact_task(session.clone(), ctx, input).await
+        let compact = async {
+            if crate::compact::should_use_remote_compact_task(&ctx.provider) {
+                let _ = session.services.otel_manager.counter(
+                    "codex.task.compact",
+                    1,
+                    &[("type", "remote")],
+                );
+                crate::compact_remote::run_remote_compact_task(session.clone(), Arc::clone(&ctx))
+                    .await
+            } else {
+                let _ = session.services.otel_manager.counter(
+                    "codex.task.compact",
+                    1,
+                    &[("type", "local")],
+                );
+                crate::compact::run_compact_task(session.clone(), Arc::clone(&ctx), input).await
+            }
         };
+        let _ = crate::compact::run_compaction_with_hooks(
+            &session,
+            &ctx,
+            HookCompactionTrigger::Manual,
+            compact,
+        )
+        .await;
         None
     }
 }
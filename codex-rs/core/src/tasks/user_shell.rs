@@ -165,6 +165,7 @@ pub(crate) async fn execute_user_shell_command(
         sandbox_policy: sandbox_policy.clone(),
         justification: None,
         arg0: None,
+        resource_limit_report: None,
     };
 
     let stdout_stream = Some(StdoutStream {
@@ -187,6 +188,7 @@ pub(crate) async fn execute_user_shell_command(
                 aggregated_output: StreamOutput::new(aborted_message.clone()),
                 duration: Duration::ZERO,
                 timed_out: false,
+                resource_limit_exceeded: None,
             };
             persist_user_shell_output(
                 &session,
@@ -215,6 +217,7 @@ pub(crate) async fn execute_user_shell_command(
                         duration: Duration::ZERO,
                         formatted_output: aborted_message,
                         status: ExecCommandStatus::Failed,
+                        resource_limit_exceeded: None,
                     }),
                 )
                 .await;
@@ -246,6 +249,7 @@ pub(crate) async fn execute_user_shell_command(
                         } else {
                             ExecCommandStatus::Failed
                         },
+                        resource_limit_exceeded: None,
                     }),
                 )
                 .await;
@@ -263,6 +267,7 @@ pub(crate) async fn execute_user_shell_command(
                 aggregated_output: StreamOutput::new(message.clone()),
                 duration: Duration::ZERO,
                 timed_out: false,
+                resource_limit_exceeded: None,
             };
             session
                 .send_event(
@@ -286,6 +291,7 @@ pub(crate) async fn execute_user_shell_command(
                             turn_context.truncation_policy,
                         ),
                         status: ExecCommandStatus::Failed,
+                        resource_limit_exceeded: None,
                     }),
                 )
                 .await;
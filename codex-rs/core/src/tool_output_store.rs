@@ -0,0 +1,310 @@
+//! Full text of tool outputs that were truncated before reaching the model.
+//!
+//! Outputs are saved per call id under
+//! `$CODEX_HOME/sessions/<thread id>/tool_outputs/` so the model can page
+//! through or grep them with the `read_tool_output` tool instead of re-running
+//! the command. [`cleanup_stale_tool_outputs`] removes them once their
+//! session has not been used for [`TOOL_OUTPUT_RETENTION`].
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::ThreadId;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::fs;
+use tracing::warn;
+
+use crate::rollout::SESSIONS_SUBDIR;
+use crate::rollout::list::find_thread_path_by_id_str;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::exceeds_budget;
+
+const TOOL_OUTPUTS_SUBDIR: &str = "tool_outputs";
+const TOOL_OUTPUT_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 7); // 7 days retention.
+
+#[derive(Debug)]
+pub(crate) struct ToolOutputStore {
+    /// `None` when saving is disabled for this session.
+    dir: Option<PathBuf>,
+    saved: Mutex<HashSet<String>>,
+}
+
+impl ToolOutputStore {
+    pub(crate) fn new(codex_home: &Path, thread_id: ThreadId) -> Self {
+        Self {
+            dir: Some(
+                codex_home
+                    .join(SESSIONS_SUBDIR)
+                    .join(thread_id.to_string())
+                    .join(TOOL_OUTPUTS_SUBDIR),
+            ),
+            saved: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub(crate) fn disabled() -> Self {
+        Self {
+            dir: None,
+            saved: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Returns true if the output of `call_id` is already on disk.
+    pub(crate) fn is_saved(&self, call_id: &str) -> bool {
+        self.saved_call_ids().contains(call_id)
+    }
+
+    /// Save `content` for `call_id` if it does not fit in `policy`. Returns
+    /// true when the full text is on disk, in which case the caller should
+    /// truncate it with a marker that points at `read_tool_output`.
+    pub(crate) async fn save_if_truncated(
+        &self,
+        call_id: &str,
+        content: &str,
+        policy: TruncationPolicy,
+    ) -> bool {
+        let Some(dir) = self.dir.as_ref() else {
+            return false;
+        };
+        if !exceeds_budget(content, policy) {
+            return false;
+        }
+        if self.is_saved(call_id) {
+            // Keep the first, complete output; later saves for the same call
+            // only see text that was already truncated.
+            return true;
+        }
+        let path = output_path(dir, call_id);
+        let result = async {
+            tokio::fs::create_dir_all(dir).await?;
+            tokio::fs::write(&path, content).await
+        }
+        .await;
+        match result {
+            Ok(()) => {
+                self.saved_call_ids().insert(call_id.to_string());
+                true
+            }
+            Err(err) => {
+                warn!("failed to save tool output to {}: {err}", path.display());
+                false
+            }
+        }
+    }
+
+    /// Read back the full output saved for `call_id`.
+    pub(crate) async fn read(&self, call_id: &str) -> io::Result<String> {
+        let Some(dir) = self.dir.as_ref() else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "tool output saving is disabled",
+            ));
+        };
+        // Read from disk rather than `saved` so outputs from before a resume
+        // are still available.
+        tokio::fs::read_to_string(output_path(dir, call_id)).await
+    }
+
+    fn saved_call_ids(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.saved
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Call ids come from the model and may contain any character, so files are
+/// named after a hash of the id: distinct ids never share a file and every
+/// name is safe and short.
+fn output_path(dir: &Path, call_id: &str) -> PathBuf {
+    let file_name: String = Sha256::digest(call_id.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    dir.join(format!("{file_name}.txt"))
+}
+
+/// Removes the saved tool outputs of sessions that either lack a rollout file
+/// or whose rollouts have not been updated within the retention window.
+/// The active session is exempt from cleanup.
+pub(crate) async fn cleanup_stale_tool_outputs(
+    codex_home: &Path,
+    active_thread_id: ThreadId,
+) -> io::Result<()> {
+    let sessions_dir = codex_home.join(SESSIONS_SUBDIR);
+    let mut entries = match fs::read_dir(&sessions_dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    let now = SystemTime::now();
+    let active_thread_id = active_thread_id.to_string();
+
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        // Rollouts share the sessions directory under date folders; only
+        // per-thread folders hold tool outputs.
+        if ThreadId::from_string(&file_name).is_err() || file_name == active_thread_id {
+            continue;
+        }
+        let outputs_dir = entry.path().join(TOOL_OUTPUTS_SUBDIR);
+        if !fs::try_exists(&outputs_dir).await.unwrap_or(false) {
+            continue;
+        }
+
+        let stale = match find_thread_path_by_id_str(codex_home, &file_name).await? {
+            Some(rollout_path) => {
+                match fs::metadata(&rollout_path).await.and_then(|m| m.modified()) {
+                    Ok(modified) => now
+                        .duration_since(modified)
+                        .ok()
+                        .is_some_and(|age| age >= TOOL_OUTPUT_RETENTION),
+                    Err(err) => {
+                        warn!(
+                            "failed to check rollout age for tool outputs in {}: {err}",
+                            outputs_dir.display()
+                        );
+                        false
+                    }
+                }
+            }
+            None => true,
+        };
+        if !stale {
+            continue;
+        }
+        if let Err(err) = fs::remove_dir_all(&outputs_dir).await {
+            warn!(
+                "failed to delete tool outputs in {}: {err}",
+                outputs_dir.display()
+            );
+            continue;
+        }
+        // Drop the thread folder too unless other session data lives there.
+        let _ = fs::remove_dir(entry.path()).await;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn saves_only_outputs_that_do_not_fit() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let thread_id = ThreadId::new();
+        let store = ToolOutputStore::new(codex_home.path(), thread_id);
+        let policy = TruncationPolicy::Bytes(8);
+
+        assert!(!store.save_if_truncated("call-1", "short", policy).await);
+        assert!(!store.is_saved("call-1"));
+
+        let long = "0123456789abcdef";
+        assert!(store.save_if_truncated("call/2", long, policy).await);
+        assert!(store.is_saved("call/2"));
+        assert_eq!(store.read("call/2").await.expect("read"), long);
+        let outputs_dir = codex_home
+            .path()
+            .join(SESSIONS_SUBDIR)
+            .join(thread_id.to_string())
+            .join(TOOL_OUTPUTS_SUBDIR);
+        assert!(output_path(&outputs_dir, "call/2").exists());
+
+        // Ids that only differ in characters a file name cannot hold are kept
+        // apart.
+        let other = "fedcba9876543210";
+        assert!(store.save_if_truncated("call_2", other, policy).await);
+        assert_eq!(store.read("call_2").await.expect("read"), other);
+        assert_eq!(store.read("call/2").await.expect("read"), long);
+
+        // A second, already truncated copy does not replace the full output.
+        assert!(
+            store
+                .save_if_truncated("call/2", "0123…truncated…cdef", policy)
+                .await
+        );
+        assert_eq!(store.read("call/2").await.expect("read"), long);
+
+        let err = store.read("call-1").await.expect_err("not saved");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn cleanup_removes_outputs_of_stale_and_missing_sessions() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let codex_home = codex_home.path();
+        let policy = TruncationPolicy::Bytes(4);
+        let active = ThreadId::new();
+        let live = ThreadId::new();
+        let stale = ThreadId::new();
+        let orphan = ThreadId::new();
+        for thread_id in [active, live, stale, orphan] {
+            let store = ToolOutputStore::new(codex_home, thread_id);
+            assert!(
+                store
+                    .save_if_truncated("call-1", "0123456789", policy)
+                    .await
+            );
+        }
+        write_rollout_stub(codex_home, live).await;
+        let stale_rollout = write_rollout_stub(codex_home, stale).await;
+        std::fs::File::options()
+            .write(true)
+            .open(&stale_rollout)
+            .expect("open rollout")
+            .set_modified(SystemTime::now() - TOOL_OUTPUT_RETENTION - Duration::from_secs(60))
+            .expect("set rollout mtime");
+
+        cleanup_stale_tool_outputs(codex_home, active)
+            .await
+            .expect("cleanup");
+
+        let sessions_dir = codex_home.join(SESSIONS_SUBDIR);
+        let has_outputs = |thread_id: ThreadId| {
+            sessions_dir
+                .join(thread_id.to_string())
+                .join(TOOL_OUTPUTS_SUBDIR)
+                .exists()
+        };
+        assert!(has_outputs(active));
+        assert!(has_outputs(live));
+        assert!(!has_outputs(stale));
+        assert!(!has_outputs(orphan));
+        assert!(!sessions_dir.join(orphan.to_string()).exists());
+        assert!(stale_rollout.exists());
+    }
+
+    async fn write_rollout_stub(codex_home: &Path, thread_id: ThreadId) -> PathBuf {
+        let dir = codex_home
+            .join(SESSIONS_SUBDIR)
+            .join("2025")
+            .join("01")
+            .join("01");
+        fs::create_dir_all(&dir).await.expect("create rollout dir");
+        let path = dir.join(format!("rollout-2025-01-01T00-00-00-{thread_id}.jsonl"));
+        fs::write(&path, "").await.expect("write rollout");
+        path
+    }
+
+    #[tokio::test]
+    async fn disabled_store_saves_nothing() {
+        let store = ToolOutputStore::disabled();
+        assert!(
+            !store
+                .save_if_truncated("call-1", "0123456789", TruncationPolicy::Bytes(4))
+                .await
+        );
+        let err = store.read("call-1").await.expect_err("disabled");
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
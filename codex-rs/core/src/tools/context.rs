@@ -30,6 +30,16 @@ pub struct ToolInvocation {
     pub call_id: String,
     pub tool_name: String,
     pub payload: ToolPayload,
+    /// Set by the registry when a `before_tool_use` hook asked for the call to be approved
+    /// by the user.
+    pub hook_approval: Option<HookApprovalRequest>,
+}
+
+/// Approval prompt requested by a `before_tool_use` hook.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HookApprovalRequest {
+    /// Reason to show in the prompt, if the hook gave one.
+    pub reason: Option<String>,
 }
 
 #[derive(Clone, Debug)]
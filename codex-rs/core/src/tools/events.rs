@@ -14,6 +14,7 @@ use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::PatchApplyStatus;
+use crate::protocol::ResourceLimitKind;
 use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
@@ -283,16 +284,28 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
     ) -> String {
+        let policy = ctx.turn.truncation_policy;
+        let saved = ctx
+            .session
+            .services
+            .tool_output_store
+            .save_if_truncated(
+                ctx.call_id,
+                &super::build_content_with_timeout(output),
+                policy,
+            )
+            .await;
+        let saved_call_id = saved.then_some(ctx.call_id);
         match self {
             Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
+                super::format_exec_output_for_model_freeform(output, policy, saved_call_id)
             }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            _ => super::format_exec_output_for_model_structured(output, policy, saved_call_id),
         }
     }
 
@@ -303,7 +316,7 @@ impl ToolEmitter {
     ) -> Result<String, FunctionCallError> {
         let (event, result) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let content = self.format_exec_output_for_model(&output, ctx).await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -315,7 +328,7 @@ impl ToolEmitter {
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+                let response = self.format_exec_output_for_model(&output, ctx).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
@@ -393,6 +406,7 @@ struct ExecCommandResult {
     duration: Duration,
     formatted_output: String,
     status: ExecCommandStatus,
+    resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 async fn emit_exec_stage(
@@ -427,6 +441,7 @@ async fn emit_exec_stage(
                 } else {
                     ExecCommandStatus::Failed
                 },
+                resource_limit_exceeded: output.resource_limit_exceeded,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -440,6 +455,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Failed,
+                resource_limit_exceeded: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -453,6 +469,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Declined,
+                resource_limit_exceeded: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -483,6 +500,7 @@ async fn emit_exec_end(
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
                 status: exec_result.status,
+                resource_limit_exceeded: exec_result.resource_limit_exceeded,
             }),
         )
         .await;
@@ -12,6 +12,7 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::tools::context::HookApprovalRequest;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -27,9 +28,12 @@ use crate::tools::runtimes::apply_patch::ApplyPatchRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::tools::spec::ApplyPatchToolArgs;
 use crate::tools::spec::JsonSchema;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::overlay_content_path;
 use async_trait::async_trait;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::sync::Arc;
 
@@ -61,6 +65,24 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Verifies the patch in `command` against the files it will be applied to.
+/// Patches run in the sandbox, so with a copy-on-write overlay they see what
+/// earlier sandboxed commands wrote there rather than the workspace.
+fn verify_apply_patch(
+    command: &[String],
+    cwd: &Path,
+    overlay: Option<&OverlayRoot>,
+) -> MaybeApplyPatchVerified {
+    match overlay {
+        Some(overlay) => {
+            codex_apply_patch::maybe_parse_apply_patch_verified_with_view(command, cwd, &|path| {
+                overlay_content_path(overlay, path)
+            })
+        }
+        None => codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd),
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -74,6 +96,10 @@ impl ToolHandler for ApplyPatchHandler {
         )
     }
 
+    fn supports_hook_approval(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
     async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
         true
     }
@@ -86,7 +112,7 @@ impl ToolHandler for ApplyPatchHandler {
             call_id,
             tool_name,
             payload,
-            ..
+            hook_approval,
         } = invocation;
 
         let patch_input = match payload {
@@ -106,7 +132,8 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
+        let overlay = session.workspace_overlay_root(turn.as_ref());
+        match verify_apply_patch(&command, &cwd, overlay.as_ref()) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
                 match apply_patch::apply_patch(turn.as_ref(), changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
@@ -145,6 +172,7 @@ impl ToolHandler for ApplyPatchHandler {
                             turn: turn.clone(),
                             call_id: call_id.clone(),
                             tool_name: tool_name.to_string(),
+                            hook_approval,
                         };
                         let out = orchestrator
                             .run(
@@ -200,8 +228,10 @@ pub(crate) async fn intercept_apply_patch(
     tracker: Option<&SharedTurnDiffTracker>,
     call_id: &str,
     tool_name: &str,
+    hook_approval: Option<HookApprovalRequest>,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    let overlay = session.workspace_overlay_root(turn.as_ref());
+    match verify_apply_patch(command, cwd, overlay.as_ref()) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
@@ -247,6 +277,7 @@ pub(crate) async fn intercept_apply_patch(
                         turn: turn.clone(),
                         call_id: call_id.to_string(),
                         tool_name: tool_name.to_string(),
+                        hook_approval,
                     };
                     let out = orchestrator
                         .run(
@@ -352,14 +383,24 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
-AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
+FileOp := AddFile | DeleteFile | UpdateFile | AddBinaryFile | ReplaceBinaryFile | AddSymlink
+AddFile := "*** Add File: " path NEWLINE [ SetMode ] { "+" line NEWLINE }
 DeleteFile := "*** Delete File: " path NEWLINE
-UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
+UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] [ SetMode ] { Hunk }
+AddBinaryFile := "*** Add Binary File: " path NEWLINE [ SetMode ] { "+" base64 NEWLINE }
+ReplaceBinaryFile := "*** Replace Binary File: " path NEWLINE [ SetMode ] { "+" base64 NEWLINE }
+AddSymlink := "*** Add Symlink: " path NEWLINE "*** Link to: " target NEWLINE
 MoveTo := "*** Move to: " newPath NEWLINE
+SetMode := "*** Set Mode: " octalMode NEWLINE
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
+Less common operations use their own headers:
+
+*** Set Mode: <octal> - set permission bits such as 755 or 644. Goes right after an Add File, Add Binary File or Replace Binary File header, or after Update File (and its Move to). An Update File section may contain only this line, e.g. to make a script executable.
+*** Add Binary File: <path> / *** Replace Binary File: <path> - write binary contents, given as base64 on one or more + lines. Replace requires the file to exist.
+*** Add Symlink: <path> - create a symlink; the next line must be *** Link to: <target>, stored as written.
+
 A full patch can combine several operations:
 
 *** Begin Patch
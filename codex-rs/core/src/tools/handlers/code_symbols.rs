@@ -0,0 +1,842 @@
+use codex_protocol::models::FunctionCallOutputBody;
+use std::path::Path;
+
+use async_trait::async_trait;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+use crate::denied_read_paths::DeniedReadPaths;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::overlay_content_path;
+use crate::workspace_overlay::overlay_view_path;
+use crate::workspace_overlay::upper_path;
+
+pub struct CodeSymbolsHandler;
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 200;
+/// Larger files are skipped when searching a directory.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_FILES_SCANNED: usize = 20_000;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+/// JSON arguments accepted by the `code_symbols` tool handler.
+#[derive(Deserialize)]
+struct CodeSymbolsArgs {
+    /// File to outline, or file or directory to search; defaults to the working directory.
+    #[serde(default)]
+    path: Option<String>,
+    /// Name whose definitions to find, optionally qualified as `Type::name` or `Type.name`.
+    #[serde(default)]
+    symbol: Option<String>,
+    /// Maximum number of definitions to return when searching.
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for CodeSymbolsHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_symbols handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: CodeSymbolsArgs = parse_arguments(&arguments)?;
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let symbol = args
+            .symbol
+            .as_deref()
+            .map(str::trim)
+            .filter(|symbol| !symbol.is_empty())
+            .map(SymbolQuery::parse);
+        let limit = args.limit.min(MAX_LIMIT);
+        let path = turn.resolve_path(args.path);
+        let cwd = turn.cwd.clone();
+        let denied_read_paths = DeniedReadPaths::from_policy(turn.sandbox_policy.get(), &cwd);
+        if denied_read_paths.is_denied(&path) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "reading `{}` is denied by the sandbox policy",
+                path.display()
+            )));
+        }
+
+        let overlay = session.workspace_overlay_root(turn.as_ref());
+
+        // Parsing is CPU bound, and a directory search may read thousands of files.
+        let output = tokio::task::spawn_blocking(move || match symbol {
+            Some(query) => find_definitions(
+                &path,
+                &cwd,
+                &query,
+                limit,
+                &denied_read_paths,
+                overlay.as_ref(),
+            ),
+            None => outline_file(&path, &cwd, overlay.as_ref()),
+        })
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("code_symbols task failed: {err}"))
+        })??;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success: Some(true),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    Python,
+    Go,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+}
+
+/// One definition found in a source file. Lines are 1-indexed and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CodeSymbol {
+    /// What the outline shows, e.g. `fn new` or `impl Display for Config`.
+    label: String,
+    /// Bare name matched by lookups.
+    name: String,
+    /// Type, class, trait or module the definition belongs to.
+    container: Option<String>,
+    /// Nesting level inside impls, classes, traits and modules.
+    depth: usize,
+    start_line: usize,
+    end_line: usize,
+}
+
+/// A definition recognised at one node, before it is placed in the outline.
+struct Definition<'tree> {
+    kind: &'static str,
+    name: String,
+    /// Overrides `{kind} {name}` in the outline.
+    label: Option<String>,
+    container: Option<String>,
+    /// Node spanning the whole definition, including decorators.
+    range: Node<'tree>,
+    /// Node whose children are listed one level deeper.
+    body: Option<Node<'tree>>,
+}
+
+fn parse_symbols(source: &str, language: SourceLanguage) -> Result<Vec<CodeSymbol>, String> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.grammar())
+        .map_err(|err| format!("failed to load grammar: {err}"))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| "failed to parse file".to_string())?;
+    let mut symbols = Vec::new();
+    collect_symbols(
+        tree.root_node(),
+        source.as_bytes(),
+        language,
+        None,
+        0,
+        &mut symbols,
+    );
+    Ok(symbols)
+}
+
+/// Walk the named children of `node`. Definitions are recorded without looking
+/// inside function bodies; other nodes (exports, declarations lists,
+/// conditionals) are searched at the same depth.
+fn collect_symbols(
+    node: Node<'_>,
+    source: &[u8],
+    language: SourceLanguage,
+    container: Option<&str>,
+    depth: usize,
+    out: &mut Vec<CodeSymbol>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let definition = match language {
+            SourceLanguage::Rust => rust_definition(child, source),
+            SourceLanguage::TypeScript | SourceLanguage::Tsx => {
+                typescript_definition(child, source)
+            }
+            SourceLanguage::Python => python_definition(child, source),
+            SourceLanguage::Go => go_definition(child, source),
+        };
+        let Some(definition) = definition else {
+            collect_symbols(child, source, language, container, depth, out);
+            continue;
+        };
+        let container_name = definition
+            .container
+            .clone()
+            .or_else(|| container.map(ToString::to_string));
+        out.push(CodeSymbol {
+            label: definition
+                .label
+                .unwrap_or_else(|| format!("{} {}", definition.kind, definition.name)),
+            name: definition.name.clone(),
+            container: container_name,
+            depth,
+            start_line: definition.range.start_position().row + 1,
+            end_line: definition.range.end_position().row + 1,
+        });
+        if let Some(body) = definition.body {
+            collect_symbols(
+                body,
+                source,
+                language,
+                Some(&definition.name),
+                depth + 1,
+                out,
+            );
+        }
+    }
+}
+
+fn node_text(node: Node<'_>, source: &[u8]) -> Option<String> {
+    node.utf8_text(source).ok().map(ToString::to_string)
+}
+
+fn field_text(node: Node<'_>, field: &str, source: &[u8]) -> Option<String> {
+    node_text(node.child_by_field_name(field)?, source)
+}
+
+fn definition<'tree>(
+    kind: &'static str,
+    node: Node<'tree>,
+    source: &[u8],
+    body_field: Option<&str>,
+) -> Option<Definition<'tree>> {
+    Some(Definition {
+        kind,
+        name: field_text(node, "name", source)?,
+        label: None,
+        container: None,
+        range: node,
+        body: body_field.and_then(|field| node.child_by_field_name(field)),
+    })
+}
+
+/// Drop generic parameters and pointer markers from a type, e.g. `*Server[T]`
+/// or `Config<'a>` become `Server` and `Config`.
+fn base_type_name(text: &str) -> String {
+    let text = text.trim_start_matches(['*', '&']);
+    text.split(['<', '['])
+        .next()
+        .unwrap_or(text)
+        .trim()
+        .to_string()
+}
+
+fn rust_definition<'tree>(node: Node<'tree>, source: &[u8]) -> Option<Definition<'tree>> {
+    match node.kind() {
+        "function_item" | "function_signature_item" => definition("fn", node, source, None),
+        "struct_item" => definition("struct", node, source, None),
+        "enum_item" => definition("enum", node, source, None),
+        "union_item" => definition("union", node, source, None),
+        "type_item" => definition("type", node, source, None),
+        "const_item" => definition("const", node, source, None),
+        "static_item" => definition("static", node, source, None),
+        "macro_definition" => definition("macro_rules!", node, source, None),
+        "trait_item" => definition("trait", node, source, Some("body")),
+        // `mod foo;` only points at another file.
+        "mod_item" if node.child_by_field_name("body").is_some() => {
+            definition("mod", node, source, Some("body"))
+        }
+        "impl_item" => {
+            let self_type = field_text(node, "type", source)?;
+            let label = match field_text(node, "trait", source) {
+                Some(trait_name) => format!("impl {trait_name} for {self_type}"),
+                None => format!("impl {self_type}"),
+            };
+            Some(Definition {
+                kind: "impl",
+                name: base_type_name(&self_type),
+                label: Some(label),
+                container: None,
+                range: node,
+                body: node.child_by_field_name("body"),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn typescript_definition<'tree>(node: Node<'tree>, source: &[u8]) -> Option<Definition<'tree>> {
+    match node.kind() {
+        "function_declaration" | "generator_function_declaration" | "function_signature" => {
+            definition("function", node, source, None)
+        }
+        "class_declaration" | "abstract_class_declaration" => {
+            definition("class", node, source, Some("body"))
+        }
+        "interface_declaration" => definition("interface", node, source, Some("body")),
+        "type_alias_declaration" => definition("type", node, source, None),
+        "enum_declaration" => definition("enum", node, source, None),
+        "internal_module" | "module" => definition("namespace", node, source, Some("body")),
+        "method_definition" | "method_signature" | "abstract_method_signature" => {
+            definition("method", node, source, None)
+        }
+        // `const handler = () => {}` and `const handler = function () {}`.
+        "variable_declarator"
+            if node.child_by_field_name("value").is_some_and(|value| {
+                matches!(
+                    value.kind(),
+                    "arrow_function" | "function_expression" | "generator_function"
+                )
+            }) =>
+        {
+            definition("function", node, source, None)
+        }
+        _ => None,
+    }
+}
+
+fn python_definition<'tree>(node: Node<'tree>, source: &[u8]) -> Option<Definition<'tree>> {
+    match node.kind() {
+        "function_definition" => definition("def", node, source, None),
+        "class_definition" => definition("class", node, source, Some("body")),
+        "decorated_definition" => {
+            let inner = python_definition(node.child_by_field_name("definition")?, source)?;
+            // Include the decorators in the line range.
+            Some(Definition {
+                range: node,
+                ..inner
+            })
+        }
+        _ => None,
+    }
+}
+
+fn go_definition<'tree>(node: Node<'tree>, source: &[u8]) -> Option<Definition<'tree>> {
+    match node.kind() {
+        "function_declaration" => definition("func", node, source, None),
+        "method_declaration" => {
+            let name = field_text(node, "name", source)?;
+            let receiver = node
+                .child_by_field_name("receiver")
+                .and_then(|receiver| receiver.named_child(0))
+                .and_then(|parameter| field_text(parameter, "type", source))
+                .map(|receiver_type| base_type_name(&receiver_type));
+            let label = match &receiver {
+                Some(receiver) => format!("func ({receiver}) {name}"),
+                None => format!("func {name}"),
+            };
+            Some(Definition {
+                kind: "func",
+                name,
+                label: Some(label),
+                container: receiver,
+                range: node,
+                body: None,
+            })
+        }
+        "type_spec" | "type_alias" => {
+            let kind = match node.child_by_field_name("type").map(|ty| ty.kind()) {
+                Some("struct_type") => "struct",
+                Some("interface_type") => "interface",
+                _ => "type",
+            };
+            definition(kind, node, source, None)
+        }
+        _ => None,
+    }
+}
+
+/// A symbol name, optionally qualified by its container.
+#[derive(Debug, PartialEq, Eq)]
+struct SymbolQuery {
+    container: Option<String>,
+    name: String,
+}
+
+impl SymbolQuery {
+    fn parse(query: &str) -> Self {
+        let split = query
+            .rsplit_once("::")
+            .or_else(|| query.rsplit_once('.'))
+            .filter(|(container, name)| !container.is_empty() && !name.is_empty());
+        match split {
+            Some((container, name)) => Self {
+                container: Some(base_type_name(
+                    container.rsplit("::").next().unwrap_or(container),
+                )),
+                name: name.to_string(),
+            },
+            None => Self {
+                container: None,
+                name: query.to_string(),
+            },
+        }
+    }
+
+    fn matches(&self, symbol: &CodeSymbol) -> bool {
+        symbol.name == self.name
+            && self
+                .container
+                .as_ref()
+                .is_none_or(|container| symbol.container.as_ref() == Some(container))
+    }
+}
+
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+fn read_source(path: &Path) -> Result<(String, SourceLanguage), FunctionCallError> {
+    let language = SourceLanguage::from_path(path).ok_or_else(|| {
+        FunctionCallError::RespondToModel(format!(
+            "unsupported file type for `{}`; code_symbols reads Rust, TypeScript, Python and Go",
+            path.display()
+        ))
+    })?;
+    let source = std::fs::read_to_string(path).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("unable to read `{}`: {err}", path.display()))
+    })?;
+    Ok((source, language))
+}
+
+fn outline_file(
+    path: &Path,
+    cwd: &Path,
+    overlay: Option<&OverlayRoot>,
+) -> Result<String, FunctionCallError> {
+    // Sandboxed commands write to the copy-on-write overlay, so read what
+    // they see.
+    let content_path = match overlay {
+        Some(overlay) => overlay_content_path(overlay, path).ok_or_else(|| {
+            FunctionCallError::RespondToModel(format!(
+                "unable to read `{}`: it was deleted in the workspace overlay",
+                path.display()
+            ))
+        })?,
+        None => path.to_path_buf(),
+    };
+    if content_path.is_dir() {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "`{}` is a directory; pass a file to outline, or a `symbol` to search for",
+            path.display()
+        )));
+    }
+    let (source, language) = read_source(&content_path)?;
+    let symbols = parse_symbols(&source, language).map_err(FunctionCallError::RespondToModel)?;
+    let path = display_path(path, cwd);
+    if symbols.is_empty() {
+        return Ok(format!("No symbols found in {path}."));
+    }
+    let mut lines = vec![path];
+    lines.extend(symbols.iter().map(|symbol| {
+        format!(
+            "{}L{}-{} {}",
+            "  ".repeat(symbol.depth),
+            symbol.start_line,
+            symbol.end_line,
+            symbol.label
+        )
+    }));
+    Ok(lines.join("\n"))
+}
+
+fn find_definitions(
+    root: &Path,
+    cwd: &Path,
+    query: &SymbolQuery,
+    limit: usize,
+    denied_read_paths: &DeniedReadPaths,
+    overlay: Option<&OverlayRoot>,
+) -> Result<String, FunctionCallError> {
+    std::fs::metadata(root).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("unable to access `{}`: {err}", root.display()))
+    })?;
+
+    let mut results = Vec::new();
+    let mut more = false;
+    let mut walk = WalkBuilder::new(root);
+    // Files written by sandboxed commands live in the overlay's upper
+    // directory, so walk it too and report its files at their workspace paths.
+    if let Some(upper) = overlay
+        .and_then(|overlay| upper_path(overlay, root))
+        .filter(|upper| upper.exists())
+    {
+        walk.add(upper);
+    }
+    let denied = denied_read_paths.clone();
+    let view = overlay.cloned();
+    let files = walk
+        .sort_by_file_name(std::cmp::Ord::cmp)
+        .filter_entry(move |entry| {
+            let path = match &view {
+                Some(view) => overlay_view_path(view, entry.path()),
+                None => Some(entry.path().to_path_buf()),
+            };
+            // Directories replaced in the overlay are still walked for the
+            // files they do not shadow.
+            path.is_none_or(|path| !denied.is_denied(&path))
+        })
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(ignore::DirEntry::into_path)
+        .filter(|path| SourceLanguage::from_path(path).is_some())
+        .filter_map(|content_path| match overlay {
+            Some(overlay) => {
+                overlay_view_path(overlay, &content_path).map(|path| (path, content_path))
+            }
+            None => Some((content_path.clone(), content_path)),
+        })
+        .take(MAX_FILES_SCANNED);
+    'files: for (path, content_path) in files {
+        if std::fs::metadata(&content_path).is_ok_and(|metadata| metadata.len() > MAX_FILE_BYTES) {
+            continue;
+        }
+        let Ok((source, language)) = read_source(&content_path) else {
+            continue;
+        };
+        if !source.contains(&query.name) {
+            continue;
+        }
+        let Ok(symbols) = parse_symbols(&source, language) else {
+            continue;
+        };
+        for symbol in symbols.iter().filter(|symbol| query.matches(symbol)) {
+            if results.len() == limit {
+                more = true;
+                break 'files;
+            }
+            results.push(format_definition(&display_path(&path, cwd), symbol));
+        }
+    }
+
+    if results.is_empty() {
+        return Ok("No definitions found.".to_string());
+    }
+    if more {
+        results.push(format!(
+            "More than {limit} definitions found; pass a narrower `path` or a qualified `symbol`."
+        ));
+    }
+    Ok(results.join("\n"))
+}
+
+fn format_definition(path: &str, symbol: &CodeSymbol) -> String {
+    let location = format!(
+        "{path}:L{}-{} {}",
+        symbol.start_line, symbol.end_line, symbol.label
+    );
+    match &symbol.container {
+        Some(container) if symbol.depth > 0 || !symbol.label.contains(container.as_str()) => {
+            format!("{location} (in {container})")
+        }
+        _ => location,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn outline(source: &str, language: SourceLanguage) -> Vec<String> {
+        parse_symbols(source, language)
+            .expect("parse")
+            .iter()
+            .map(|symbol| {
+                format!(
+                    "{}L{}-{} {}",
+                    "  ".repeat(symbol.depth),
+                    symbol.start_line,
+                    symbol.end_line,
+                    symbol.label
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn outlines_rust() {
+        let source = r#"use std::fmt;
+
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    pub fn new(name: String) -> Self {
+        fn helper() {}
+        Self { name }
+    }
+}
+
+impl<'a> fmt::Display for Wrapper<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+mod inner {
+    pub enum Mode { A, B }
+}
+
+mod elsewhere;
+
+macro_rules! noop { () => {}; }
+"#;
+        assert_eq!(
+            outline(source, SourceLanguage::Rust),
+            vec![
+                "L3-5 struct Config",
+                "L7-12 impl Config",
+                "  L8-11 fn new",
+                "L14-18 impl fmt::Display for Wrapper<'a>",
+                "  L15-17 fn fmt",
+                "L20-22 trait Named",
+                "  L21-21 fn name",
+                "L24-26 mod inner",
+                "  L25-25 enum Mode",
+                "L30-30 macro_rules! noop",
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_typescript() {
+        let source = r#"export interface Options {
+  verbose: boolean;
+  run(): void;
+}
+
+export class Runner {
+  constructor(private options: Options) {}
+
+  run(): void {
+    const inner = () => {};
+  }
+}
+
+export const handler = async (event: string) => {
+  return event;
+};
+
+export function main(): void {}
+
+type Id = string;
+"#;
+        assert_eq!(
+            outline(source, SourceLanguage::TypeScript),
+            vec![
+                "L1-4 interface Options",
+                "  L3-3 method run",
+                "L6-12 class Runner",
+                "  L7-7 method constructor",
+                "  L9-11 method run",
+                "L14-16 function handler",
+                "L18-18 function main",
+                "L20-20 type Id",
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_python_and_go() {
+        let python = r#"import os
+
+@dataclass
+class Config:
+    name: str
+
+    @property
+    def label(self):
+        def inner():
+            pass
+        return self.name
+
+def main():
+    pass
+"#;
+        assert_eq!(
+            outline(python, SourceLanguage::Python),
+            vec!["L3-11 class Config", "  L7-11 def label", "L13-14 def main",]
+        );
+
+        let go = r#"package server
+
+type Server struct {
+	addr string
+}
+
+type Handler interface {
+	Serve()
+}
+
+func (s *Server) Start() error {
+	return nil
+}
+
+func New(addr string) *Server {
+	return &Server{addr: addr}
+}
+"#;
+        assert_eq!(
+            outline(go, SourceLanguage::Go),
+            vec![
+                "L3-5 struct Server",
+                "L7-9 interface Handler",
+                "L11-13 func (Server) Start",
+                "L15-17 func New",
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_definitions_across_a_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("src")).expect("mkdir");
+        std::fs::write(
+            dir.path().join("src/config.rs"),
+            "pub struct Config;\n\nimpl Config {\n    pub fn new() -> Self {\n        Config\n    }\n}\n",
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("src/server.go"),
+            "package server\n\ntype Server struct{}\n\nfunc (s *Server) new() {}\n",
+        )
+        .expect("write");
+        std::fs::write(dir.path().join("notes.txt"), "fn new() {}\n").expect("write");
+
+        let output = find_definitions(
+            dir.path(),
+            dir.path(),
+            &SymbolQuery::parse("new"),
+            20,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .expect("search");
+        assert_eq!(
+            output,
+            "src/config.rs:L4-6 fn new (in Config)\nsrc/server.go:L5-5 func (Server) new"
+        );
+
+        let output = find_definitions(
+            dir.path(),
+            dir.path(),
+            &SymbolQuery::parse("Config::new"),
+            20,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .expect("search");
+        assert_eq!(output, "src/config.rs:L4-6 fn new (in Config)");
+
+        let output = find_definitions(
+            dir.path(),
+            dir.path(),
+            &SymbolQuery::parse("new"),
+            1,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .expect("search");
+        assert_eq!(
+            output,
+            "src/config.rs:L4-6 fn new (in Config)\nMore than 1 definitions found; pass a narrower `path` or a qualified `symbol`."
+        );
+
+        let output = find_definitions(
+            dir.path(),
+            dir.path(),
+            &SymbolQuery::parse("missing"),
+            20,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .expect("search");
+        assert_eq!(output, "No definitions found.");
+    }
+
+    #[test]
+    fn skips_denied_files_when_searching() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("secrets")).expect("mkdir");
+        std::fs::write(dir.path().join("lib.rs"), "fn load() {}\n").expect("write");
+        std::fs::write(dir.path().join("secrets/keys.rs"), "fn load() {}\n").expect("write");
+        let denied = DeniedReadPaths::new(&["./secrets".to_string()], dir.path());
+
+        let output = find_definitions(
+            dir.path(),
+            dir.path(),
+            &SymbolQuery::parse("load"),
+            20,
+            &denied,
+            None,
+        )
+        .expect("search");
+        assert_eq!(output, "lib.rs:L1-1 fn load");
+    }
+}
@@ -7,6 +7,7 @@ use serde::Deserialize;
 use tokio::process::Command;
 use tokio::time::timeout;
 
+use crate::denied_read_paths::DeniedReadPaths;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -14,6 +15,10 @@ use crate::tools::context::ToolPayload;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::overlay_content_path;
+use crate::workspace_overlay::overlay_view_path;
+use crate::workspace_overlay::upper_path;
 
 pub struct GrepFilesHandler;
 
@@ -43,7 +48,12 @@ impl ToolHandler for GrepFilesHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, turn, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -71,8 +81,17 @@ impl ToolHandler for GrepFilesHandler {
 
         let limit = args.limit.min(MAX_LIMIT);
         let search_path = turn.resolve_path(args.path.clone());
+        let overlay = session.workspace_overlay_root(turn.as_ref());
 
-        verify_path_exists(&search_path).await?;
+        verify_path_exists(&search_path, overlay.as_ref()).await?;
+
+        let denied_read_paths = DeniedReadPaths::from_policy(turn.sandbox_policy.get(), &turn.cwd);
+        if denied_read_paths.is_denied(&search_path) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "searching `{}` is denied by the sandbox policy",
+                search_path.display()
+            )));
+        }
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -82,8 +101,16 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        let search_results = run_rg_search(
+            pattern,
+            include.as_deref(),
+            &search_path,
+            limit,
+            &turn.cwd,
+            &denied_read_paths,
+            overlay.as_ref(),
+        )
+        .await?;
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -99,8 +126,20 @@ impl ToolHandler for GrepFilesHandler {
     }
 }
 
-async fn verify_path_exists(path: &Path) -> Result<(), FunctionCallError> {
-    tokio::fs::metadata(path).await.map_err(|err| {
+async fn verify_path_exists(
+    path: &Path,
+    overlay: Option<&OverlayRoot>,
+) -> Result<(), FunctionCallError> {
+    let content_path = match overlay {
+        Some(overlay) => overlay_content_path(overlay, path).ok_or_else(|| {
+            FunctionCallError::RespondToModel(format!(
+                "unable to access `{}`: it was deleted in the workspace overlay",
+                path.display()
+            ))
+        })?,
+        None => path.to_path_buf(),
+    };
+    tokio::fs::metadata(content_path).await.map_err(|err| {
         FunctionCallError::RespondToModel(format!("unable to access `{}`: {err}", path.display()))
     })?;
     Ok(())
@@ -112,6 +151,8 @@ async fn run_rg_search(
     search_path: &Path,
     limit: usize,
     cwd: &Path,
+    denied_read_paths: &DeniedReadPaths,
+    overlay: Option<&OverlayRoot>,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut command = Command::new("rg");
     command
@@ -127,6 +168,14 @@ async fn run_rg_search(
     }
 
     command.arg("--").arg(search_path);
+    // Files written by sandboxed commands live in the overlay's upper
+    // directory; `parse_results` maps them back to the workspace.
+    if let Some(upper) = overlay
+        .and_then(|overlay| upper_path(overlay, search_path))
+        .filter(|upper| upper.exists())
+    {
+        command.arg(upper);
+    }
 
     let output = timeout(COMMAND_TIMEOUT, command.output())
         .await
@@ -140,7 +189,13 @@ async fn run_rg_search(
         })?;
 
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(
+            &output.stdout,
+            limit,
+            cwd,
+            denied_read_paths,
+            overlay,
+        )),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -151,7 +206,15 @@ async fn run_rg_search(
     }
 }
 
-fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
+/// Collect up to `limit` matching files, dropping any the sandbox policy
+/// denies reading or the copy-on-write overlay hides.
+fn parse_results(
+    stdout: &[u8],
+    limit: usize,
+    cwd: &Path,
+    denied_read_paths: &DeniedReadPaths,
+    overlay: Option<&OverlayRoot>,
+) -> Vec<String> {
     let mut results = Vec::new();
     for line in stdout.split(|byte| *byte == b'\n') {
         if line.is_empty() {
@@ -161,7 +224,17 @@ fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
             if text.is_empty() {
                 continue;
             }
-            results.push(text.to_string());
+            let text = match overlay {
+                Some(overlay) => match overlay_view_path(overlay, &cwd.join(text)) {
+                    Some(path) => path.display().to_string(),
+                    None => continue,
+                },
+                None => text.to_string(),
+            };
+            if denied_read_paths.is_denied(&cwd.join(&text)) {
+                continue;
+            }
+            results.push(text);
             if results.len() == limit {
                 break;
             }
@@ -179,7 +252,13 @@ mod tests {
     #[test]
     fn parses_basic_results() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n";
-        let parsed = parse_results(stdout, 10);
+        let parsed = parse_results(
+            stdout,
+            10,
+            Path::new("/tmp"),
+            &DeniedReadPaths::default(),
+            None,
+        );
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -189,7 +268,13 @@ mod tests {
     #[test]
     fn parse_truncates_after_limit() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n/tmp/file_c.rs\n";
-        let parsed = parse_results(stdout, 2);
+        let parsed = parse_results(
+            stdout,
+            2,
+            Path::new("/tmp"),
+            &DeniedReadPaths::default(),
+            None,
+        );
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -207,7 +292,16 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 10, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            None,
+            dir,
+            10,
+            dir,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
         assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -224,7 +318,16 @@ mod tests {
         std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-        let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            Some("*.rs"),
+            dir,
+            10,
+            dir,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .await?;
         assert_eq!(results.len(), 1);
         assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
         Ok(())
@@ -241,7 +344,16 @@ mod tests {
         std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
         std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 2, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            None,
+            dir,
+            2,
+            dir,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .await?;
         assert_eq!(results.len(), 2);
         Ok(())
     }
@@ -255,11 +367,39 @@ mod tests {
         let dir = temp.path();
         std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 5, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            None,
+            dir,
+            5,
+            dir,
+            &DeniedReadPaths::default(),
+            None,
+        )
+        .await?;
         assert!(results.is_empty());
         Ok(())
     }
 
+    #[tokio::test]
+    async fn run_search_skips_denied_files() -> anyhow::Result<()> {
+        if !rg_available() {
+            return Ok(());
+        }
+        let temp = tempdir().expect("create temp dir");
+        let dir = temp.path();
+        std::fs::create_dir(dir.join("secrets")).unwrap();
+        std::fs::write(dir.join(".env"), "alpha=1").unwrap();
+        std::fs::write(dir.join("secrets/key.txt"), "alpha").unwrap();
+        std::fs::write(dir.join("main.txt"), "alpha").unwrap();
+        let denied = DeniedReadPaths::new(&[".env".to_string(), "./secrets".to_string()], dir);
+
+        let results = run_rg_search("alpha", None, dir, 10, dir, &denied, None).await?;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].ends_with("main.txt"));
+        Ok(())
+    }
+
     fn rg_available() -> bool {
         StdCommand::new("rg")
             .arg("--version")
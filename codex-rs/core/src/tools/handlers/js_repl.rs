@@ -52,6 +52,7 @@ fn build_js_repl_exec_output(
         aggregated_output: StreamOutput::new(aggregated_output),
         duration,
         timed_out: false,
+        resource_limit_exceeded: None,
     }
 }
 
@@ -1,6 +1,7 @@
 use codex_protocol::models::FunctionCallOutputBody;
 use std::collections::VecDeque;
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fs::FileType;
 use std::path::Path;
 use std::path::PathBuf;
@@ -17,6 +18,8 @@ use crate::tools::context::ToolPayload;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::overlay_read_dir;
 
 pub struct ListDirHandler;
 
@@ -53,7 +56,12 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -98,7 +106,8 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        let overlay = session.workspace_overlay_root(turn.as_ref());
+        let entries = list_dir_slice(&path, overlay.as_ref(), offset, limit, depth).await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -111,12 +120,13 @@ impl ToolHandler for ListDirHandler {
 
 async fn list_dir_slice(
     path: &Path,
+    overlay: Option<&OverlayRoot>,
     offset: usize,
     limit: usize,
     depth: usize,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(path, overlay, Path::new(""), depth, &mut entries).await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -150,6 +160,7 @@ async fn list_dir_slice(
 
 async fn collect_entries(
     dir_path: &Path,
+    overlay: Option<&OverlayRoot>,
     relative_prefix: &Path,
     depth: usize,
     entries: &mut Vec<DirEntry>,
@@ -158,20 +169,9 @@ async fn collect_entries(
     queue.push_back((dir_path.to_path_buf(), relative_prefix.to_path_buf(), depth));
 
     while let Some((current_dir, prefix, remaining_depth)) = queue.pop_front() {
-        let mut read_dir = fs::read_dir(&current_dir).await.map_err(|err| {
-            FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
-        })?;
-
         let mut dir_entries = Vec::new();
 
-        while let Some(entry) = read_dir.next_entry().await.map_err(|err| {
-            FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
-        })? {
-            let file_type = entry.file_type().await.map_err(|err| {
-                FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
-            })?;
-
-            let file_name = entry.file_name();
+        for (entry_path, file_name, file_type) in read_dir_entries(&current_dir, overlay).await? {
             let relative_path = if prefix.as_os_str().is_empty() {
                 PathBuf::from(&file_name)
             } else {
@@ -183,7 +183,7 @@ async fn collect_entries(
             let sort_key = format_entry_name(&relative_path);
             let kind = DirEntryKind::from(&file_type);
             dir_entries.push((
-                entry.path(),
+                entry_path,
                 relative_path,
                 kind,
                 DirEntry {
@@ -208,6 +208,52 @@ async fn collect_entries(
     Ok(())
 }
 
+/// Path, name and type of each entry in `dir`, as seen through the
+/// copy-on-write overlay when one is active.
+async fn read_dir_entries(
+    dir: &Path,
+    overlay: Option<&OverlayRoot>,
+) -> Result<Vec<(PathBuf, OsString, FileType)>, FunctionCallError> {
+    let read_error = |err: std::io::Error| {
+        FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
+    };
+    let inspect_error = |err: std::io::Error| {
+        FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
+    };
+
+    if let Some(overlay) = overlay {
+        let overlay = overlay.clone();
+        let dir = dir.to_path_buf();
+        let listed = tokio::task::spawn_blocking(move || overlay_read_dir(&overlay, &dir))
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
+            })?
+            .map_err(read_error)?;
+        let mut entries = Vec::with_capacity(listed.len());
+        for (path, content_path) in listed {
+            let file_type = fs::symlink_metadata(&content_path)
+                .await
+                .map_err(inspect_error)?
+                .file_type();
+            let file_name = path
+                .file_name()
+                .map(OsStr::to_os_string)
+                .unwrap_or_default();
+            entries.push((path, file_name, file_type));
+        }
+        return Ok(entries);
+    }
+
+    let mut read_dir = fs::read_dir(dir).await.map_err(read_error)?;
+    let mut entries = Vec::new();
+    while let Some(entry) = read_dir.next_entry().await.map_err(read_error)? {
+        let file_type = entry.file_type().await.map_err(inspect_error)?;
+        entries.push((entry.path(), entry.file_name(), file_type));
+    }
+    Ok(entries)
+}
+
 fn format_entry_name(path: &Path) -> String {
     let normalized = path.to_string_lossy().replace("\\", "/");
     if normalized.len() > MAX_ENTRY_LENGTH {
@@ -306,7 +352,7 @@ mod tests {
             symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 20, 3)
+        let entries = list_dir_slice(dir_path, None, 1, 20, 3)
             .await
             .expect("list directory");
 
@@ -340,7 +386,7 @@ mod tests {
             .await
             .expect("create sub dir");
 
-        let err = list_dir_slice(dir_path, 10, 1, 2)
+        let err = list_dir_slice(dir_path, None, 10, 1, 2)
             .await
             .expect_err("offset exceeds entries");
         assert_eq!(
@@ -367,7 +413,7 @@ mod tests {
             .await
             .expect("write deeper");
 
-        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
+        let entries_depth_one = list_dir_slice(dir_path, None, 1, 10, 1)
             .await
             .expect("list depth 1");
         assert_eq!(
@@ -375,7 +421,7 @@ mod tests {
             vec!["nested/".to_string(), "root.txt".to_string(),]
         );
 
-        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
+        let entries_depth_two = list_dir_slice(dir_path, None, 1, 20, 2)
             .await
             .expect("list depth 2");
         assert_eq!(
@@ -388,7 +434,7 @@ mod tests {
             ]
         );
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
+        let entries_depth_three = list_dir_slice(dir_path, None, 1, 30, 3)
             .await
             .expect("list depth 3");
         assert_eq!(
@@ -420,7 +466,7 @@ mod tests {
             .await
             .expect("write b child");
 
-        let first_page = list_dir_slice(dir_path, 1, 2, 2)
+        let first_page = list_dir_slice(dir_path, None, 1, 2, 2)
             .await
             .expect("list page one");
         assert_eq!(
@@ -432,7 +478,7 @@ mod tests {
             ]
         );
 
-        let second_page = list_dir_slice(dir_path, 3, 2, 2)
+        let second_page = list_dir_slice(dir_path, None, 3, 2, 2)
             .await
             .expect("list page two");
         assert_eq!(
@@ -455,7 +501,7 @@ mod tests {
             .await
             .expect("write gamma");
 
-        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
+        let entries = list_dir_slice(dir_path, None, 2, usize::MAX, 1)
             .await
             .expect("list without overflow");
         assert_eq!(
@@ -476,7 +522,7 @@ mod tests {
                 .expect("write file");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 25, 1)
+        let entries = list_dir_slice(dir_path, None, 1, 25, 1)
             .await
             .expect("list directory");
         assert_eq!(entries.len(), 26);
@@ -498,7 +544,7 @@ mod tests {
         tokio::fs::write(nested.join("child.txt"), b"child").await?;
         tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+        let entries_depth_three = list_dir_slice(dir_path, None, 1, 3, 3).await?;
         assert_eq!(
             entries_depth_three,
             vec![
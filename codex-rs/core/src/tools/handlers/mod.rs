@@ -1,5 +1,6 @@
 pub(crate) mod agent_jobs;
 pub mod apply_patch;
+mod code_symbols;
 mod dynamic;
 mod grep_files;
 mod js_repl;
@@ -9,6 +10,7 @@ mod mcp_resource;
 pub(crate) mod multi_agents;
 mod plan;
 mod read_file;
+mod read_tool_output;
 mod request_user_input;
 mod search_tool_bm25;
 mod shell;
@@ -24,6 +26,7 @@ use crate::function_tool::FunctionCallError;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::normalize_additional_permissions;
 pub use apply_patch::ApplyPatchHandler;
+pub use code_symbols::CodeSymbolsHandler;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::protocol::AskForApproval;
 pub use dynamic::DynamicToolHandler;
@@ -36,6 +39,7 @@ pub use mcp_resource::McpResourceHandler;
 pub use multi_agents::MultiAgentHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use read_tool_output::ReadToolOutputHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
 pub(crate) use search_tool_bm25::DEFAULT_LIMIT as SEARCH_TOOL_BM25_DEFAULT_LIMIT;
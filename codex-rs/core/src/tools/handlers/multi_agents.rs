@@ -1003,6 +1003,7 @@ mod tests {
             call_id: "call-1".to_string(),
             tool_name: tool_name.to_string(),
             payload,
+            hook_approval: None,
         }
     }
 
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 
+use crate::denied_read_paths::DeniedReadPaths;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -13,6 +14,7 @@ use crate::tools::context::ToolPayload;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_overlay::overlay_content_path;
 
 pub struct ReadFileHandler;
 
@@ -99,7 +101,12 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -139,6 +146,23 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
+        if DeniedReadPaths::from_policy(turn.sandbox_policy.get(), &turn.cwd).is_denied(&path) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "reading `{file_path}` is denied by the sandbox policy"
+            )));
+        }
+
+        // Sandboxed commands write to the copy-on-write overlay, so read what
+        // they see.
+        let path = match session.workspace_overlay_root(turn.as_ref()) {
+            Some(overlay) => overlay_content_path(&overlay, &path).ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!(
+                    "failed to read file: `{file_path}` was deleted in the workspace overlay"
+                ))
+            })?,
+            None => path,
+        };
+
         let collected = match mode {
             ReadMode::Slice => slice::read(&path, offset, limit).await?,
             ReadMode::Indentation => {
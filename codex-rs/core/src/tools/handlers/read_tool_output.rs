@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_utils_string::take_bytes_at_char_boundary;
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::truncate_text;
+
+pub struct ReadToolOutputHandler;
+
+const DEFAULT_LINE_LIMIT: usize = 200;
+const DEFAULT_BYTE_LIMIT: usize = 16 * 1024;
+const MAX_LINE_LENGTH: usize = 500;
+
+/// JSON arguments accepted by the `read_tool_output` tool handler.
+#[derive(Deserialize)]
+struct ReadToolOutputArgs {
+    /// Call id named in the truncation marker.
+    call_id: String,
+    /// 1-indexed line to start from; defaults to 1.
+    #[serde(default)]
+    offset: Option<usize>,
+    /// Maximum number of lines (or matches, with `pattern`) to return.
+    #[serde(default)]
+    limit: Option<usize>,
+    /// 0-indexed byte to start from. Selects a byte range instead of lines.
+    #[serde(default)]
+    byte_offset: Option<usize>,
+    /// Maximum number of bytes to return from `byte_offset`.
+    #[serde(default)]
+    byte_limit: Option<usize>,
+    /// Regular expression; returns the matching lines instead of a range.
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for ReadToolOutputHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_tool_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: ReadToolOutputArgs = parse_arguments(&arguments)?;
+        let content = session
+            .services
+            .tool_output_store
+            .read(&args.call_id)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "no saved output for call_id {}: {err}",
+                    args.call_id
+                ))
+            })?;
+
+        let output = select_output(&content, &args)?;
+        // The selection is cut to the turn budget here so it is never saved
+        // again as a truncated output of its own.
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(truncate_text(&output, turn.truncation_policy)),
+            success: Some(true),
+        })
+    }
+}
+
+fn select_output(content: &str, args: &ReadToolOutputArgs) -> Result<String, FunctionCallError> {
+    if args.offset == Some(0) {
+        return Err(FunctionCallError::RespondToModel(
+            "offset must be a 1-indexed line number".to_string(),
+        ));
+    }
+    if args.limit == Some(0) || args.byte_limit == Some(0) {
+        return Err(FunctionCallError::RespondToModel(
+            "limit must be greater than zero".to_string(),
+        ));
+    }
+
+    if let Some(pattern) = args.pattern.as_deref() {
+        if args.byte_offset.is_some() || args.byte_limit.is_some() {
+            return Err(FunctionCallError::RespondToModel(
+                "pattern cannot be combined with byte_offset or byte_limit".to_string(),
+            ));
+        }
+        let regex = Regex::new(pattern)
+            .map_err(|err| FunctionCallError::RespondToModel(format!("invalid pattern: {err}")))?;
+        return Ok(grep_lines(
+            content,
+            &regex,
+            args.offset.unwrap_or(1),
+            args.limit.unwrap_or(DEFAULT_LINE_LIMIT),
+        ));
+    }
+
+    if args.byte_offset.is_some() || args.byte_limit.is_some() {
+        if args.offset.is_some() || args.limit.is_some() {
+            return Err(FunctionCallError::RespondToModel(
+                "use either offset/limit or byte_offset/byte_limit, not both".to_string(),
+            ));
+        }
+        return byte_range(
+            content,
+            args.byte_offset.unwrap_or(0),
+            args.byte_limit.unwrap_or(DEFAULT_BYTE_LIMIT),
+        );
+    }
+
+    line_range(
+        content,
+        args.offset.unwrap_or(1),
+        args.limit.unwrap_or(DEFAULT_LINE_LIMIT),
+    )
+}
+
+fn line_range(content: &str, offset: usize, limit: usize) -> Result<String, FunctionCallError> {
+    let total_lines = content.lines().count();
+    if offset > total_lines {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "offset {offset} exceeds output length of {total_lines} lines"
+        )));
+    }
+    let lines = content
+        .lines()
+        .enumerate()
+        .skip(offset - 1)
+        .take(limit)
+        .map(|(index, line)| format_line(index + 1, line))
+        .collect::<Vec<_>>();
+    let last = offset - 1 + lines.len();
+    Ok(format!(
+        "Lines {offset}-{last} of {total_lines}:\n{}",
+        lines.join("\n")
+    ))
+}
+
+fn byte_range(content: &str, offset: usize, limit: usize) -> Result<String, FunctionCallError> {
+    let total_bytes = content.len();
+    if offset >= total_bytes {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "byte_offset {offset} exceeds output length of {total_bytes} bytes"
+        )));
+    }
+    // Move the start forward to the next character boundary.
+    let start = (offset..total_bytes)
+        .find(|index| content.is_char_boundary(*index))
+        .unwrap_or(total_bytes);
+    let slice = take_bytes_at_char_boundary(&content[start..], limit);
+    let end = start + slice.len();
+    Ok(format!("Bytes {start}-{end} of {total_bytes}:\n{slice}"))
+}
+
+fn grep_lines(content: &str, regex: &Regex, offset: usize, limit: usize) -> String {
+    let matches = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| regex.is_match(line))
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        return "No matches found.".to_string();
+    }
+    let total = matches.len();
+    let shown = matches
+        .into_iter()
+        .skip(offset - 1)
+        .take(limit)
+        .map(|(index, line)| format_line(index + 1, line))
+        .collect::<Vec<_>>();
+    if shown.is_empty() {
+        return format!("offset {offset} exceeds the {total} matching lines");
+    }
+    format!(
+        "{total} matching lines; showing {} from match {offset}:\n{}",
+        shown.len(),
+        shown.join("\n")
+    )
+}
+
+fn format_line(number: usize, line: &str) -> String {
+    let line = take_bytes_at_char_boundary(line, MAX_LINE_LENGTH);
+    format!("L{number}: {line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn args(json: &str) -> ReadToolOutputArgs {
+        serde_json::from_str(json).expect("valid args")
+    }
+
+    const OUTPUT: &str =
+        "compiling a\ncompiling b\nerror: b failed\ncompiling c\nerror: c failed\n";
+
+    #[test]
+    fn reads_line_ranges() {
+        let output =
+            select_output(OUTPUT, &args(r#"{"call_id":"c","offset":2,"limit":2}"#)).expect("lines");
+        assert_eq!(
+            output,
+            "Lines 2-3 of 5:\nL2: compiling b\nL3: error: b failed"
+        );
+
+        let err = select_output(OUTPUT, &args(r#"{"call_id":"c","offset":9}"#))
+            .expect_err("offset past the end");
+        assert_eq!(err.to_string(), "offset 9 exceeds output length of 5 lines");
+    }
+
+    #[test]
+    fn reads_byte_ranges_on_char_boundaries() {
+        let output = select_output(
+            OUTPUT,
+            &args(r#"{"call_id":"c","byte_offset":12,"byte_limit":15}"#),
+        )
+        .expect("bytes");
+        assert_eq!(output, "Bytes 12-27 of 68:\ncompiling b\nerr");
+
+        let output = select_output(
+            "héllo",
+            &args(r#"{"call_id":"c","byte_offset":2,"byte_limit":2}"#),
+        )
+        .expect("bytes");
+        assert_eq!(output, "Bytes 3-5 of 6:\nll");
+    }
+
+    #[test]
+    fn greps_matching_lines() {
+        let output =
+            select_output(OUTPUT, &args(r#"{"call_id":"c","pattern":"^error"}"#)).expect("grep");
+        assert_eq!(
+            output,
+            "2 matching lines; showing 2 from match 1:\nL3: error: b failed\nL5: error: c failed"
+        );
+
+        let output =
+            select_output(OUTPUT, &args(r#"{"call_id":"c","pattern":"warning"}"#)).expect("grep");
+        assert_eq!(output, "No matches found.");
+
+        let err = select_output(
+            OUTPUT,
+            &args(r#"{"call_id":"c","pattern":"error","byte_limit":10}"#),
+        )
+        .expect_err("mixed modes");
+        assert_eq!(
+            err.to_string(),
+            "pattern cannot be combined with byte_offset or byte_limit"
+        );
+    }
+}
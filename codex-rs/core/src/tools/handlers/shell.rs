@@ -15,6 +15,7 @@ use crate::is_safe_command::is_known_safe_command;
 use crate::protocol::ExecCommandSource;
 use crate::shell::Shell;
 use crate::skills::maybe_emit_implicit_skill_invocation;
+use crate::tools::context::HookApprovalRequest;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -56,6 +57,7 @@ struct RunExecLikeArgs {
     call_id: String,
     freeform: bool,
     shell_runtime_backend: ShellRuntimeBackend,
+    hook_approval: Option<HookApprovalRequest>,
 }
 
 impl ShellHandler {
@@ -151,6 +153,10 @@ impl ToolHandler for ShellHandler {
         )
     }
 
+    fn supports_hook_approval(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
     async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
         match &invocation.payload {
             ToolPayload::Function { arguments } => {
@@ -171,7 +177,7 @@ impl ToolHandler for ShellHandler {
             call_id,
             tool_name,
             payload,
-            ..
+            hook_approval,
         } = invocation;
 
         match payload {
@@ -191,6 +197,7 @@ impl ToolHandler for ShellHandler {
                     call_id,
                     freeform: false,
                     shell_runtime_backend: ShellRuntimeBackend::Generic,
+                    hook_approval,
                 })
                 .await
             }
@@ -208,6 +215,7 @@ impl ToolHandler for ShellHandler {
                     call_id,
                     freeform: false,
                     shell_runtime_backend: ShellRuntimeBackend::Generic,
+                    hook_approval,
                 })
                 .await
             }
@@ -228,6 +236,10 @@ impl ToolHandler for ShellCommandHandler {
         matches!(payload, ToolPayload::Function { .. })
     }
 
+    fn supports_hook_approval(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
     async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
         let ToolPayload::Function { arguments } = &invocation.payload else {
             return true;
@@ -257,7 +269,7 @@ impl ToolHandler for ShellCommandHandler {
             call_id,
             tool_name,
             payload,
-            ..
+            hook_approval,
         } = invocation;
 
         let ToolPayload::Function { arguments } = payload else {
@@ -293,6 +305,7 @@ impl ToolHandler for ShellCommandHandler {
             call_id,
             freeform: true,
             shell_runtime_backend: self.shell_runtime_backend(),
+            hook_approval,
         })
         .await
     }
@@ -311,6 +324,7 @@ impl ShellHandler {
             call_id,
             freeform,
             shell_runtime_backend,
+            hook_approval,
         } = args;
 
         let mut exec_params = exec_params;
@@ -361,6 +375,7 @@ impl ShellHandler {
             Some(&tracker),
             &call_id,
             tool_name.as_str(),
+            hook_approval.clone(),
         )
         .await?
         {
@@ -382,6 +397,8 @@ impl ShellHandler {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &exec_params.command,
+                cwd: &exec_params.cwd,
+                env: &exec_params.env,
                 approval_policy: turn.approval_policy.value(),
                 sandbox_policy: turn.sandbox_policy.get(),
                 sandbox_permissions: exec_params.sandbox_permissions,
@@ -416,6 +433,7 @@ impl ShellHandler {
             turn: turn.clone(),
             call_id: call_id.clone(),
             tool_name,
+            hook_approval,
         };
         let out = orchestrator
             .run(
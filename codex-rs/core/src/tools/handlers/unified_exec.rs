@@ -15,14 +15,19 @@ use crate::tools::handlers::normalize_and_validate_additional_permissions;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_saved_text;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::unified_exec::UnifiedExecResponse;
+use crate::unified_exec::WaitUntil;
 use crate::unified_exec::WriteStdinRequest;
+use crate::unified_exec::resolve_max_tokens;
 use async_trait::async_trait;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::PermissionProfile;
+use regex_lite::Regex;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -52,6 +57,10 @@ pub(crate) struct ExecCommandArgs {
     justification: Option<String>,
     #[serde(default)]
     prefix_rule: Option<Vec<String>>,
+    #[serde(default)]
+    until_pattern: Option<String>,
+    #[serde(default)]
+    until_exit: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,6 +73,10 @@ struct WriteStdinArgs {
     yield_time_ms: u64,
     #[serde(default)]
     max_output_tokens: Option<usize>,
+    #[serde(default)]
+    until_pattern: Option<String>,
+    #[serde(default)]
+    until_exit: bool,
 }
 
 fn default_exec_yield_time_ms() -> u64 {
@@ -78,6 +91,23 @@ fn default_tty() -> bool {
     false
 }
 
+fn parse_wait_until(
+    until_pattern: Option<&str>,
+    until_exit: bool,
+) -> Result<WaitUntil, FunctionCallError> {
+    let pattern = until_pattern
+        .filter(|pattern| !pattern.is_empty())
+        .map(Regex::new)
+        .transpose()
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("invalid until_pattern: {err}"))
+        })?;
+    Ok(WaitUntil {
+        pattern,
+        exit: until_exit,
+    })
+}
+
 #[async_trait]
 impl ToolHandler for UnifiedExecHandler {
     fn kind(&self) -> ToolKind {
@@ -88,6 +118,11 @@ impl ToolHandler for UnifiedExecHandler {
         matches!(payload, ToolPayload::Function { .. })
     }
 
+    fn supports_hook_approval(&self, invocation: &ToolInvocation) -> bool {
+        // `write_stdin` feeds a process that was already approved.
+        invocation.tool_name == "exec_command"
+    }
+
     async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
         let ToolPayload::Function { arguments } = &invocation.payload else {
             tracing::error!(
@@ -119,7 +154,7 @@ impl ToolHandler for UnifiedExecHandler {
             call_id,
             tool_name,
             payload,
-            ..
+            hook_approval,
         } = invocation;
 
         let arguments = match payload {
@@ -134,7 +169,7 @@ impl ToolHandler for UnifiedExecHandler {
         let manager: &UnifiedExecProcessManager = &session.services.unified_exec_manager;
         let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id.clone());
 
-        let response = match tool_name.as_str() {
+        let (mut response, max_output_tokens) = match tool_name.as_str() {
             "exec_command" => {
                 let args: ExecCommandArgs = parse_arguments(&arguments)?;
                 maybe_emit_implicit_skill_invocation(
@@ -144,6 +179,7 @@ impl ToolHandler for UnifiedExecHandler {
                     args.workdir.as_deref(),
                 )
                 .await;
+                let wait_until = parse_wait_until(args.until_pattern.as_deref(), args.until_exit)?;
                 let process_id = manager.allocate_process_id().await;
                 let command = get_command(
                     &args,
@@ -208,6 +244,7 @@ impl ToolHandler for UnifiedExecHandler {
                     Some(&tracker),
                     &context.call_id,
                     tool_name.as_str(),
+                    hook_approval.clone(),
                 )
                 .await?
                 {
@@ -215,7 +252,7 @@ impl ToolHandler for UnifiedExecHandler {
                     return Ok(output);
                 }
 
-                manager
+                let response = manager
                     .exec_command(
                         ExecCommandRequest {
                             command,
@@ -229,22 +266,27 @@ impl ToolHandler for UnifiedExecHandler {
                             additional_permissions: normalized_additional_permissions,
                             justification,
                             prefix_rule,
+                            wait_until,
+                            hook_approval,
                         },
                         &context,
                     )
                     .await
                     .map_err(|err| {
                         FunctionCallError::RespondToModel(format!("exec_command failed: {err:?}"))
-                    })?
+                    })?;
+                (response, max_output_tokens)
             }
             "write_stdin" => {
                 let args: WriteStdinArgs = parse_arguments(&arguments)?;
+                let wait_until = parse_wait_until(args.until_pattern.as_deref(), args.until_exit)?;
                 let response = manager
                     .write_stdin(WriteStdinRequest {
                         process_id: &args.session_id.to_string(),
                         input: &args.chars,
                         yield_time_ms: args.yield_time_ms,
                         max_output_tokens: args.max_output_tokens,
+                        wait_until,
                     })
                     .await
                     .map_err(|err| {
@@ -260,7 +302,7 @@ impl ToolHandler for UnifiedExecHandler {
                     .send_event(turn.as_ref(), EventMsg::TerminalInteraction(interaction))
                     .await;
 
-                response
+                (response, args.max_output_tokens)
             }
             other => {
                 return Err(FunctionCallError::RespondToModel(format!(
@@ -269,6 +311,17 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
+        let text = String::from_utf8_lossy(&response.raw_output);
+        let policy = TruncationPolicy::Tokens(resolve_max_tokens(max_output_tokens));
+        if session
+            .services
+            .tool_output_store
+            .save_if_truncated(&call_id, &text, policy)
+            .await
+        {
+            response.output = formatted_truncate_saved_text(&text, policy, &call_id);
+        }
+
         let content = format_response(&response);
 
         Ok(ToolOutput::Function {
@@ -313,6 +366,10 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     let wall_time_seconds = response.wall_time.as_secs_f64();
     sections.push(format!("Wall time: {wall_time_seconds:.4} seconds"));
 
+    if let Some(wait_outcome) = response.wait_outcome {
+        sections.push(format!("Wait ended: {}", wait_outcome.as_str()));
+    }
+
     if let Some(exit_code) = response.exit_code {
         sections.push(format!("Process exited with code {exit_code}"));
     }
@@ -336,6 +393,7 @@ fn format_response(response: &UnifiedExecResponse) -> String {
 mod tests {
     use super::*;
     use crate::shell::default_user_shell;
+    use crate::unified_exec::WaitOutcome;
     use pretty_assertions::assert_eq;
     use std::sync::Arc;
 
@@ -406,6 +464,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_wait_until_rejects_invalid_pattern() {
+        let err = parse_wait_until(Some("listening on ("), false)
+            .expect_err("unbalanced group should be rejected");
+
+        match err {
+            FunctionCallError::RespondToModel(message) => assert!(
+                message.starts_with("invalid until_pattern:"),
+                "unexpected error: {message}"
+            ),
+            other => panic!("expected RespondToModel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_wait_until_ignores_empty_pattern() -> anyhow::Result<()> {
+        let args: WriteStdinArgs =
+            parse_arguments(r#"{"session_id": 1, "until_pattern": "", "until_exit": true}"#)?;
+
+        let wait_until = parse_wait_until(args.until_pattern.as_deref(), args.until_exit)?;
+
+        assert!(wait_until.pattern.is_none());
+        assert!(wait_until.exit);
+        Ok(())
+    }
+
+    #[test]
+    fn format_response_reports_wait_outcome() {
+        let response = UnifiedExecResponse {
+            event_call_id: "call".to_string(),
+            chunk_id: String::new(),
+            wall_time: std::time::Duration::from_millis(1500),
+            output: "ready".to_string(),
+            raw_output: b"ready".to_vec(),
+            process_id: Some("1000".to_string()),
+            exit_code: None,
+            original_token_count: None,
+            session_command: None,
+            wait_outcome: Some(WaitOutcome::PatternMatched),
+        };
+
+        assert_eq!(
+            format_response(&response),
+            "Wall time: 1.5000 seconds\nWait ended: pattern matched\nProcess running with session ID 1000\nOutput:\nready"
+        );
+    }
+
     #[test]
     fn test_get_command_rejects_explicit_login_when_disallowed() -> anyhow::Result<()> {
         let json = r#"{"cmd": "echo hello", "login": true}"#;
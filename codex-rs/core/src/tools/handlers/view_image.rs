@@ -14,6 +14,7 @@ use crate::tools::context::ToolPayload;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_overlay::overlay_content_path;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::local_image_content_items_with_label_number;
 
@@ -65,8 +66,19 @@ impl ToolHandler for ViewImageHandler {
         let args: ViewImageArgs = parse_arguments(&arguments)?;
 
         let abs_path = turn.resolve_path(Some(args.path));
+        // Sandboxed commands write to the copy-on-write overlay, so show the
+        // image they see.
+        let content_path = match session.workspace_overlay_root(turn.as_ref()) {
+            Some(overlay) => overlay_content_path(&overlay, &abs_path).ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!(
+                    "unable to locate image at `{}`: it was deleted in the workspace overlay",
+                    abs_path.display()
+                ))
+            })?,
+            None => abs_path.clone(),
+        };
 
-        let metadata = fs::metadata(&abs_path).await.map_err(|error| {
+        let metadata = fs::metadata(&content_path).await.map_err(|error| {
             FunctionCallError::RespondToModel(format!(
                 "unable to locate image at `{}`: {error}",
                 abs_path.display()
@@ -81,7 +93,7 @@ impl ToolHandler for ViewImageHandler {
         }
         let event_path = abs_path.clone();
 
-        let content = local_image_content_items_with_label_number(&abs_path, None);
+        let content = local_image_content_items_with_label_number(&content_path, None);
         let content = content
             .into_iter()
             .map(|item| match item {
@@ -686,8 +686,15 @@ impl JsReplManager {
         let (stdin, pending_execs, exec_contexts, child, recent_stderr) = {
             let mut kernel = self.kernel.lock().await;
             if kernel.is_none() {
+                let workspace_overlay = session
+                    .workspace_overlay_dir(&turn.cwd)
+                    .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
                 let state = self
-                    .start_kernel(Arc::clone(&turn), Some(session.conversation_id))
+                    .start_kernel(
+                        Arc::clone(&turn),
+                        Some(session.conversation_id),
+                        workspace_overlay,
+                    )
                     .await
                     .map_err(FunctionCallError::RespondToModel)?;
                 *kernel = Some(state);
@@ -809,6 +816,7 @@ impl JsReplManager {
         &self,
         turn: Arc<TurnContext>,
         thread_id: Option<ThreadId>,
+        workspace_overlay: Option<PathBuf>,
     ) -> Result<KernelState, String> {
         let node_path = resolve_compatible_node(self.node_path.as_deref()).await?;
 
@@ -873,6 +881,8 @@ impl JsReplManager {
                 use_linux_sandbox_bwrap: turn
                     .features
                     .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
+                resource_limits: &turn.config.permissions.sandbox_resource_limits,
+                workspace_overlay: workspace_overlay.as_deref(),
                 windows_sandbox_level: turn.windows_sandbox_level,
             })
             .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
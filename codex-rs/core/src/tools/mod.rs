@@ -13,7 +13,9 @@ pub mod spec;
 
 use crate::exec::ExecToolCallOutput;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_saved_text;
 use crate::truncate::formatted_truncate_text;
+use crate::truncate::truncate_saved_text;
 use crate::truncate::truncate_text;
 pub use router::ToolRouter;
 use serde::Serialize;
@@ -26,9 +28,11 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
+/// `saved_call_id` is set when the full output was saved for `read_tool_output`.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    saved_call_id: Option<&str>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -51,7 +55,11 @@ pub fn format_exec_output_for_model_structured(
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let content = build_content_with_timeout(exec_output);
+    let formatted_output = match saved_call_id {
+        Some(call_id) => formatted_truncate_saved_text(&content, truncation_policy, call_id),
+        None => formatted_truncate_text(&content, truncation_policy),
+    };
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -68,6 +76,7 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    saved_call_id: Option<&str>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -76,7 +85,10 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = match saved_call_id {
+        Some(call_id) => truncate_saved_text(&content, truncation_policy, call_id),
+        None => truncate_text(&content, truncation_policy),
+    };
 
     let mut sections = Vec::new();
 
@@ -103,7 +115,7 @@ pub fn format_exec_output_str(
 }
 
 /// Extracts exec output content and prepends a timeout message if the command timed out.
-fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
+pub(crate) fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
     if exec_output.timed_out {
         format!(
             "command timed out after {} milliseconds\n{}",
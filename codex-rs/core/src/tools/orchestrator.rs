@@ -70,6 +70,7 @@ impl ToolOrchestrator {
             turn: tool_ctx.turn.clone(),
             call_id: tool_ctx.call_id.clone(),
             tool_name: tool_ctx.tool_name.clone(),
+            hook_approval: tool_ctx.hook_approval.clone(),
         };
         let run_result = tool.run(req, attempt, &attempt_tool_ctx).await;
 
@@ -117,9 +118,16 @@ impl ToolOrchestrator {
         // 1) Approval
         let mut already_approved = false;
 
-        let requirement = tool.exec_approval_requirement(req).unwrap_or_else(|| {
+        let mut requirement = tool.exec_approval_requirement(req).unwrap_or_else(|| {
             default_exec_approval_requirement(approval_policy, &turn_ctx.sandbox_policy)
         });
+        if let Some(hook_approval) = tool_ctx.hook_approval.as_ref() {
+            requirement = force_approval_requirement(
+                requirement,
+                approval_policy,
+                hook_approval.reason.clone(),
+            );
+        }
         match requirement {
             ExecApprovalRequirement::Skip { .. } => {
                 otel.tool_decision(otel_tn, otel_ci, &ReviewDecision::Approved, otel_cfg);
@@ -179,6 +187,16 @@ impl ToolOrchestrator {
         // Platform-specific flag gating is handled by SandboxManager::select_initial
         // via crate::safety::get_platform_sandbox(..).
         let use_linux_sandbox_bwrap = turn_ctx.features.enabled(Feature::UseLinuxSandboxBwrap);
+        // Only the Linux sandbox mounts the copy-on-write overlay; unsandboxed
+        // attempts (including escalated retries) write to the workspace.
+        let workspace_overlay = if initial_sandbox == crate::exec::SandboxType::LinuxSeccomp {
+            tool_ctx
+                .session
+                .workspace_overlay_dir(&turn_ctx.cwd)
+                .map_err(|err| ToolError::Codex(CodexErr::Io(err)))?
+        } else {
+            None
+        };
         let initial_attempt = SandboxAttempt {
             sandbox: initial_sandbox,
             policy: &turn_ctx.sandbox_policy,
@@ -187,6 +205,8 @@ impl ToolOrchestrator {
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
+            resource_limits: &turn_ctx.config.permissions.sandbox_resource_limits,
+            workspace_overlay: workspace_overlay.as_deref(),
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
         };
 
@@ -301,6 +321,8 @@ impl ToolOrchestrator {
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
                     use_linux_sandbox_bwrap,
+                    resource_limits: &turn_ctx.config.permissions.sandbox_resource_limits,
+                    workspace_overlay: None,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
                 };
 
@@ -323,8 +345,112 @@ impl ToolOrchestrator {
     }
 }
 
+/// Upgrades `requirement` so the user is prompted, as requested by a
+/// `before_tool_use` hook. Forbidden calls stay forbidden, and a prompt that
+/// can never be shown under `AskForApproval::Never` becomes a rejection.
+fn force_approval_requirement(
+    requirement: ExecApprovalRequirement,
+    approval_policy: AskForApproval,
+    hook_reason: Option<String>,
+) -> ExecApprovalRequirement {
+    if matches!(approval_policy, AskForApproval::Never) {
+        return match requirement {
+            ExecApprovalRequirement::Forbidden { reason } => {
+                ExecApprovalRequirement::Forbidden { reason }
+            }
+            _ => ExecApprovalRequirement::Forbidden {
+                reason: hook_reason.unwrap_or_else(|| {
+                    "before_tool_use hook requested approval but approval policy is never"
+                        .to_string()
+                }),
+            },
+        };
+    }
+    match requirement {
+        ExecApprovalRequirement::Skip {
+            proposed_execpolicy_amendment,
+            ..
+        } => ExecApprovalRequirement::NeedsApproval {
+            reason: hook_reason,
+            proposed_execpolicy_amendment,
+        },
+        ExecApprovalRequirement::NeedsApproval {
+            reason,
+            proposed_execpolicy_amendment,
+        } => ExecApprovalRequirement::NeedsApproval {
+            reason: hook_reason.or(reason),
+            proposed_execpolicy_amendment,
+        },
+        forbidden @ ExecApprovalRequirement::Forbidden { .. } => forbidden,
+    }
+}
+
 fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
     // Keep approval reason terse and stable for UX/tests, but accept the
     // output so we can evolve heuristics later without touching call sites.
     "command failed; retry without sandbox?".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn force_approval_requirement_upgrades_skip_to_prompt() {
+        let requirement = force_approval_requirement(
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: true,
+                proposed_execpolicy_amendment: None,
+            },
+            AskForApproval::OnRequest,
+            Some("touches prod kubeconfig".to_string()),
+        );
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some("touches prod kubeconfig".to_string()),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
+    #[test]
+    fn force_approval_requirement_keeps_forbidden() {
+        let requirement = force_approval_requirement(
+            ExecApprovalRequirement::Forbidden {
+                reason: "blocked by policy".to_string(),
+            },
+            AskForApproval::UnlessTrusted,
+            None,
+        );
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason: "blocked by policy".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn force_approval_requirement_rejects_when_approval_policy_is_never() {
+        let requirement = force_approval_requirement(
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            },
+            AskForApproval::Never,
+            None,
+        );
+
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason: "before_tool_use hook requested approval but approval policy is never"
+                    .to_string(),
+            }
+        );
+    }
+}
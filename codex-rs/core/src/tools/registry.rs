@@ -9,18 +9,23 @@ use crate::function_tool::FunctionCallError;
 use crate::memories::usage::emit_metric_for_tool_read;
 use crate::protocol::SandboxPolicy;
 use crate::sandbox_tags::sandbox_tag;
+use crate::tools::context::HookApprovalRequest;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::truncate::truncate_saved_text;
 use async_trait::async_trait;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterToolUse;
+use codex_hooks::HookEventBeforeToolUse;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
+use codex_hooks::HookToolDecision;
 use codex_hooks::HookToolInput;
 use codex_hooks::HookToolInputLocalShell;
-use codex_hooks::HookToolKind;
+use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ShellToolCallParams;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
 
@@ -50,6 +55,13 @@ pub trait ToolHandler: Send + Sync {
         false
     }
 
+    /// Returns `true` if the [ToolInvocation] can stop for a user approval prompt
+    /// when a `before_tool_use` hook asks for one. Calls that cannot are rejected
+    /// rather than run without the prompt.
+    fn supports_hook_approval(&self, _invocation: &ToolInvocation) -> bool {
+        false
+    }
+
     /// Perform the actual [ToolInvocation] and returns a [ToolOutput] containing
     /// the final output to return to the model.
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError>;
@@ -78,7 +90,7 @@ impl ToolRegistry {
 
     pub async fn dispatch(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<ResponseInputItem, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let call_id_owned = invocation.call_id.clone();
@@ -154,6 +166,49 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
+        // Pre-execution hooks see every call and may deny it, ask for approval, or
+        // rewrite its input. Handlers then parse the rewritten input and assess it
+        // like any other call.
+        let before_tool_use = match dispatch_before_tool_use_hook(&invocation).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                let message = err.to_string();
+                otel.tool_result_with_tags(
+                    tool_name.as_ref(),
+                    &call_id_owned,
+                    log_payload.as_ref(),
+                    Duration::ZERO,
+                    false,
+                    &message,
+                    &metric_tags,
+                    mcp_server_ref,
+                    mcp_server_origin_ref,
+                );
+                let hook_abort_error = dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
+                    invocation: &invocation,
+                    output_preview: message,
+                    success: false,
+                    executed: false,
+                    duration: Duration::ZERO,
+                    mutating: false,
+                })
+                .await;
+                return Err(hook_abort_error.unwrap_or(err));
+            }
+        };
+        if let Some(tool_input) = before_tool_use.rewritten_input {
+            invocation.payload = rewritten_payload(&invocation.payload, tool_input)
+                .map_err(FunctionCallError::RespondToModel)?;
+        }
+        if let Some(hook_approval) = before_tool_use.approval {
+            if !handler.supports_hook_approval(&invocation) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "before_tool_use hook requested approval, but {tool_name} cannot prompt for approval"
+                )));
+            }
+            invocation.hook_approval = Some(hook_approval);
+        }
+
         let is_mutating = handler.is_mutating(&invocation).await;
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
@@ -216,6 +271,7 @@ impl ToolRegistry {
                 let output = guard.take().ok_or_else(|| {
                     FunctionCallError::Fatal("tool produced no output".to_string())
                 })?;
+                let output = save_truncated_output(&invocation, output).await;
                 Ok(output.into_response(&call_id_owned, &payload_for_response))
             }
             Err(err) => Err(err),
@@ -223,6 +279,32 @@ impl ToolRegistry {
     }
 }
 
+/// Text output that will not fit the turn's truncation policy is saved for
+/// `read_tool_output` and truncated here, so the marker can point at it. Exec
+/// tools save and truncate their own output before formatting it.
+async fn save_truncated_output(invocation: &ToolInvocation, output: ToolOutput) -> ToolOutput {
+    let ToolOutput::Function {
+        body: FunctionCallOutputBody::Text(text),
+        success,
+    } = &output
+    else {
+        return output;
+    };
+    let store = &invocation.session.services.tool_output_store;
+    let policy = invocation.turn.truncation_policy;
+    if store.is_saved(&invocation.call_id)
+        || !store
+            .save_if_truncated(&invocation.call_id, text, policy)
+            .await
+    {
+        return output;
+    }
+    ToolOutput::Function {
+        body: FunctionCallOutputBody::Text(truncate_saved_text(text, policy, &invocation.call_id)),
+        success: *success,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfiguredToolSpec {
     pub spec: ToolSpec,
@@ -306,7 +388,7 @@ fn unsupported_tool_call_message(payload: &ToolPayload, tool_name: &str) -> Stri
     }
 }
 
-fn sandbox_policy_tag(policy: &SandboxPolicy) -> &'static str {
+pub(crate) fn sandbox_policy_tag(policy: &SandboxPolicy) -> &'static str {
     match policy {
         SandboxPolicy::ReadOnly { .. } => "read-only",
         SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
@@ -349,12 +431,140 @@ impl From<&ToolPayload> for HookToolInput {
     }
 }
 
-fn hook_tool_kind(tool_input: &HookToolInput) -> HookToolKind {
-    match tool_input {
-        HookToolInput::Function { .. } => HookToolKind::Function,
-        HookToolInput::Custom { .. } => HookToolKind::Custom,
-        HookToolInput::LocalShell { .. } => HookToolKind::LocalShell,
-        HookToolInput::Mcp { .. } => HookToolKind::Mcp,
+/// Combined effect of every `before_tool_use` hook that did not deny the call.
+#[derive(Default)]
+struct BeforeToolUseOutcome {
+    rewritten_input: Option<HookToolInput>,
+    approval: Option<HookApprovalRequest>,
+}
+
+async fn dispatch_before_tool_use_hook(
+    invocation: &ToolInvocation,
+) -> Result<BeforeToolUseOutcome, FunctionCallError> {
+    let session = invocation.session.as_ref();
+    let turn = invocation.turn.as_ref();
+    let tool_input = HookToolInput::from(&invocation.payload);
+    let hook_outcomes = session
+        .hooks()
+        .dispatch(HookPayload {
+            session_id: session.conversation_id,
+            cwd: turn.cwd.clone(),
+            client: turn.app_server_client_name.clone(),
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: turn.sub_id.clone(),
+                    call_id: invocation.call_id.clone(),
+                    tool_name: invocation.tool_name.clone(),
+                    tool_kind: tool_input.tool_kind(),
+                    tool_input,
+                    sandbox: sandbox_tag(
+                        &turn.sandbox_policy,
+                        turn.windows_sandbox_level,
+                        turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+                    )
+                    .to_string(),
+                    sandbox_policy: sandbox_policy_tag(&turn.sandbox_policy).to_string(),
+                },
+            },
+        })
+        .await;
+
+    let mut outcome = BeforeToolUseOutcome::default();
+    for hook_outcome in hook_outcomes {
+        let hook_name = hook_outcome.hook_name;
+        match hook_outcome.result {
+            HookResult::Success
+            | HookResult::Decision(HookToolDecision::Allow)
+            | HookResult::Context(_) => {}
+            HookResult::Decision(HookToolDecision::Deny { reason }) => {
+                return Err(FunctionCallError::RespondToModel(reason));
+            }
+            HookResult::Decision(HookToolDecision::Ask { reason }) => {
+                let approval = outcome.approval.get_or_insert_default();
+                if reason.is_some() {
+                    approval.reason = reason;
+                }
+            }
+            HookResult::Decision(HookToolDecision::Rewrite { tool_input }) => {
+                outcome.rewritten_input = Some(tool_input);
+            }
+            HookResult::FailedContinue(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; continuing"
+                );
+            }
+            HookResult::FailedAbort(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; aborting operation"
+                );
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "before_tool_use hook '{hook_name}' failed and aborted operation: {error}"
+                )));
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Builds the payload for input rewritten by a `before_tool_use` hook. The
+/// rewrite must keep the input type of the original call, and MCP rewrites may
+/// only change the arguments.
+fn rewritten_payload(
+    payload: &ToolPayload,
+    tool_input: HookToolInput,
+) -> Result<ToolPayload, String> {
+    match (payload, tool_input) {
+        (ToolPayload::Function { .. }, HookToolInput::Function { arguments }) => {
+            Ok(ToolPayload::Function { arguments })
+        }
+        (ToolPayload::Custom { .. }, HookToolInput::Custom { input }) => {
+            Ok(ToolPayload::Custom { input })
+        }
+        (ToolPayload::LocalShell { params }, HookToolInput::LocalShell { params: rewritten }) => {
+            if rewritten.command.first().is_none_or(String::is_empty) {
+                return Err("before_tool_use hook rewrite produced an empty command".to_string());
+            }
+            Ok(ToolPayload::LocalShell {
+                params: ShellToolCallParams {
+                    command: rewritten.command,
+                    workdir: rewritten.workdir,
+                    timeout_ms: rewritten.timeout_ms,
+                    sandbox_permissions: rewritten.sandbox_permissions,
+                    prefix_rule: rewritten.prefix_rule,
+                    additional_permissions: params.additional_permissions.clone(),
+                    justification: rewritten.justification,
+                },
+            })
+        }
+        (
+            ToolPayload::Mcp { server, tool, .. },
+            HookToolInput::Mcp {
+                server: rewritten_server,
+                tool: rewritten_tool,
+                arguments,
+            },
+        ) if *server == rewritten_server && *tool == rewritten_tool => Ok(ToolPayload::Mcp {
+            server: rewritten_server,
+            tool: rewritten_tool,
+            raw_arguments: arguments,
+        }),
+        (ToolPayload::Mcp { .. }, HookToolInput::Mcp { .. }) => {
+            Err("before_tool_use hook rewrite cannot change the MCP server or tool".to_string())
+        }
+        (_, tool_input) => Err(format!(
+            "before_tool_use hook rewrite must keep the call's input type, got {:?} input",
+            tool_input.tool_kind()
+        )),
     }
 }
 
@@ -386,7 +596,7 @@ async fn dispatch_after_tool_use_hook(
                     turn_id: turn.sub_id.clone(),
                     call_id: invocation.call_id.clone(),
                     tool_name: invocation.tool_name.clone(),
-                    tool_kind: hook_tool_kind(&tool_input),
+                    tool_kind: tool_input.tool_kind(),
                     tool_input,
                     executed: dispatch.executed,
                     success: dispatch.success,
@@ -408,7 +618,7 @@ async fn dispatch_after_tool_use_hook(
     for hook_outcome in hook_outcomes {
         let hook_name = hook_outcome.hook_name;
         match hook_outcome.result {
-            HookResult::Success => {}
+            HookResult::Success | HookResult::Decision(_) | HookResult::Context(_) => {}
             HookResult::FailedContinue(error) => {
                 warn!(
                     call_id = %invocation.call_id,
@@ -435,3 +645,96 @@ async fn dispatch_after_tool_use_hook(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_hooks::HookMatcher;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rewritten_payload_keeps_the_input_type() {
+        let payload = ToolPayload::Function {
+            arguments: r#"{"cmd":"rm -rf build"}"#.to_string(),
+        };
+
+        let rewritten = rewritten_payload(
+            &payload,
+            HookToolInput::Function {
+                arguments: r#"{"cmd":"rm -rf build/cache"}"#.to_string(),
+            },
+        )
+        .expect("same input type");
+        assert_eq!(rewritten.log_payload(), r#"{"cmd":"rm -rf build/cache"}"#);
+
+        let err = rewritten_payload(
+            &payload,
+            HookToolInput::Custom {
+                input: "rm -rf /".to_string(),
+            },
+        )
+        .expect_err("input type changed");
+        assert!(err.contains("input type"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn rewritten_payload_cannot_redirect_mcp_calls() {
+        let payload = ToolPayload::Mcp {
+            server: "kube".to_string(),
+            tool: "get_pods".to_string(),
+            raw_arguments: "{}".to_string(),
+        };
+
+        let rewritten = rewritten_payload(
+            &payload,
+            HookToolInput::Mcp {
+                server: "kube".to_string(),
+                tool: "get_pods".to_string(),
+                arguments: r#"{"namespace":"dev"}"#.to_string(),
+            },
+        )
+        .expect("arguments only");
+        assert_eq!(rewritten.log_payload(), r#"{"namespace":"dev"}"#);
+
+        let err = rewritten_payload(
+            &payload,
+            HookToolInput::Mcp {
+                server: "kube".to_string(),
+                tool: "delete_pod".to_string(),
+                arguments: "{}".to_string(),
+            },
+        )
+        .expect_err("tool changed");
+        assert!(
+            err.contains("MCP server or tool"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn mcp_calls_reach_hooks_matching_their_server() {
+        let payload = ToolPayload::Mcp {
+            server: "kube".to_string(),
+            tool: "get_pods".to_string(),
+            raw_arguments: "{}".to_string(),
+        };
+        let tool_input = HookToolInput::from(&payload);
+        let event = HookEvent::BeforeToolUse {
+            event: HookEventBeforeToolUse {
+                turn_id: "turn-1".to_string(),
+                call_id: "call-1".to_string(),
+                tool_name: "mcp__kube__get_pods".to_string(),
+                tool_kind: tool_input.tool_kind(),
+                tool_input,
+                sandbox: "none".to_string(),
+                sandbox_policy: "read-only".to_string(),
+            },
+        };
+
+        let matcher = HookMatcher {
+            mcp_server: Some("kube".to_string()),
+            ..HookMatcher::default()
+        };
+        assert!(matcher.matches(&event));
+    }
+}
@@ -175,6 +175,7 @@ impl ToolRouter {
             call_id,
             tool_name,
             payload,
+            hook_approval: None,
         };
 
         match self.registry.dispatch(invocation).await {
@@ -6,6 +6,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::is_likely_sandbox_denied;
 use crate::features::Feature;
+use crate::landlock::ResourceLimitReport;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
 use crate::skills::SkillMetadata;
@@ -18,6 +19,7 @@ use crate::tools::sandboxing::ToolError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Policy;
 use codex_execpolicy::RuleMatch;
+use codex_protocol::config_types::SandboxResourceLimits;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::MacOsSeatbeltProfileExtensions;
 use codex_protocol::models::PermissionProfile;
@@ -41,6 +43,7 @@ use codex_shell_escalation::ShellCommandExecutor;
 use codex_shell_escalation::Stopwatch;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -91,6 +94,7 @@ pub(super) async fn try_run_zsh_fork(
         sandbox_policy,
         justification,
         arg0,
+        resource_limit_report,
     } = sandbox_exec_request;
     let ParsedShellCommand { script, login } = extract_shell_script(&command)?;
     let effective_timeout = Duration::from_millis(
@@ -111,6 +115,7 @@ pub(super) async fn try_run_zsh_fork(
         sandbox_permissions,
         justification,
         arg0,
+        resource_limit_report,
         sandbox_policy_cwd: ctx.turn.cwd.clone(),
         macos_seatbelt_profile_extensions: ctx
             .turn
@@ -120,6 +125,8 @@ pub(super) async fn try_run_zsh_fork(
             .clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_linux_sandbox_bwrap: ctx.turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+        sandbox_resource_limits: ctx.turn.config.permissions.sandbox_resource_limits,
+        workspace_overlay: attempt.workspace_overlay.map(Path::to_path_buf),
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -151,6 +158,7 @@ pub(super) async fn try_run_zsh_fork(
         approval_policy: ctx.turn.approval_policy.value(),
         sandbox_policy: attempt.policy.clone(),
         sandbox_permissions: req.sandbox_permissions,
+        env: req.env.clone(),
         prompt_permissions: req.additional_permissions.clone(),
         stopwatch: stopwatch.clone(),
     };
@@ -177,6 +185,7 @@ struct CoreShellActionProvider {
     approval_policy: AskForApproval,
     sandbox_policy: SandboxPolicy,
     sandbox_permissions: SandboxPermissions,
+    env: HashMap<String, String>,
     prompt_permissions: Option<PermissionProfile>,
     stopwatch: Stopwatch,
 }
@@ -512,9 +521,16 @@ impl EscalationPolicy for CoreShellActionProvider {
                 used_complex_parsing,
             )
         };
+        let match_context = crate::exec_policy::exec_policy_match_context(
+            workdir.as_path(),
+            self.approval_policy,
+            &self.sandbox_policy,
+            self.sandbox_permissions,
+            Some(&self.env),
+        );
         let evaluation = {
             let policy = self.policy.read().await;
-            policy.check_multiple(commands.iter(), &fallback)
+            policy.check_multiple_with_context(commands.iter(), &match_context, &fallback)
         };
         // When true, means the Evaluation was due to *.rules, not the
         // fallback function.
@@ -563,10 +579,13 @@ struct CoreShellCommandExecutor {
     sandbox_permissions: SandboxPermissions,
     justification: Option<String>,
     arg0: Option<String>,
+    resource_limit_report: Option<ResourceLimitReport>,
     sandbox_policy_cwd: PathBuf,
     macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
     codex_linux_sandbox_exe: Option<PathBuf>,
     use_linux_sandbox_bwrap: bool,
+    sandbox_resource_limits: SandboxResourceLimits,
+    workspace_overlay: Option<PathBuf>,
 }
 
 #[async_trait::async_trait]
@@ -598,6 +617,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                 sandbox_policy: self.sandbox_policy.clone(),
                 justification: self.justification.clone(),
                 arg0: self.arg0.clone(),
+                resource_limit_report: self.resource_limit_report.clone(),
             },
             None,
         )
@@ -719,6 +739,8 @@ impl CoreShellCommandExecutor {
                 macos_seatbelt_profile_extensions,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.as_ref(),
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
+                resource_limits: &self.sandbox_resource_limits,
+                workspace_overlay: self.workspace_overlay.as_deref(),
                 windows_sandbox_level: self.windows_sandbox_level,
             })?;
         if let Some(network) = exec_request.network.as_ref() {
@@ -768,6 +790,7 @@ fn map_exec_result(
         aggregated_output: crate::exec::StreamOutput::new(result.output.clone()),
         duration: result.duration,
         timed_out: result.timed_out,
+        resource_limit_exceeded: None,
     };
 
     if result.timed_out {
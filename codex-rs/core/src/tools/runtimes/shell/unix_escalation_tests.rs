@@ -21,6 +21,8 @@ use crate::sandboxing::SandboxPermissions;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
+use codex_protocol::config_types::SandboxResourceLimits;
+#[cfg(target_os = "macos")]
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::FileSystemPermissions;
 use codex_protocol::models::MacOsPreferencesPermission;
@@ -162,6 +164,7 @@ fn shell_request_escalation_execution_is_explicit() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        denied_read_paths: Vec::new(),
     };
     let macos_seatbelt_profile_extensions = MacOsSeatbeltProfileExtensions {
         macos_preferences: MacOsPreferencesPermission::ReadWrite,
@@ -217,6 +220,7 @@ async fn prepare_escalated_exec_turn_default_preserves_macos_seatbelt_extensions
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        resource_limit_report: None,
         sandbox_policy_cwd: cwd.to_path_buf(),
         macos_seatbelt_profile_extensions: Some(MacOsSeatbeltProfileExtensions {
             macos_preferences: MacOsPreferencesPermission::ReadWrite,
@@ -224,6 +228,8 @@ async fn prepare_escalated_exec_turn_default_preserves_macos_seatbelt_extensions
         }),
         codex_linux_sandbox_exe: None,
         use_linux_sandbox_bwrap: false,
+        sandbox_resource_limits: SandboxResourceLimits::default(),
+        workspace_overlay: None,
     };
 
     let prepared = executor
@@ -267,10 +273,13 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        resource_limit_report: None,
         sandbox_policy_cwd: cwd.to_path_buf(),
         macos_seatbelt_profile_extensions: None,
         codex_linux_sandbox_exe: None,
         use_linux_sandbox_bwrap: false,
+        sandbox_resource_limits: SandboxResourceLimits::default(),
+        workspace_overlay: None,
     };
 
     let permissions = Permissions {
@@ -284,6 +293,8 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
             macos_preferences: MacOsPreferencesPermission::ReadWrite,
             ..Default::default()
         }),
+        sandbox_resource_limits: SandboxResourceLimits::default(),
+        sandbox_copy_on_write: false,
     };
 
     let prepared = executor
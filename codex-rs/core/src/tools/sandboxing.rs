@@ -13,6 +13,7 @@ use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::SandboxTransformError;
 use crate::state::SessionServices;
+use crate::tools::context::HookApprovalRequest;
 use crate::tools::network_approval::NetworkApprovalSpec;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::approvals::ExecPolicyAmendment;
@@ -294,6 +295,8 @@ pub(crate) struct ToolCtx {
     pub turn: Arc<TurnContext>,
     pub call_id: String,
     pub tool_name: String,
+    /// Set when a `before_tool_use` hook asked for the call to be approved by the user.
+    pub hook_approval: Option<HookApprovalRequest>,
 }
 
 #[derive(Debug)]
@@ -323,6 +326,8 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
+    pub resource_limits: &'a codex_protocol::config_types::SandboxResourceLimits,
+    pub workspace_overlay: Option<&'a Path>,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
 }
 
@@ -344,6 +349,8 @@ impl<'a> SandboxAttempt<'a> {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
+                resource_limits: self.resource_limits,
+                workspace_overlay: self.workspace_overlay,
                 windows_sandbox_level: self.windows_sandbox_level,
             })
     }
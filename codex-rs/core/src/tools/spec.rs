@@ -59,6 +59,8 @@ pub(crate) struct ToolsConfig {
     pub experimental_supported_tools: Vec<String>,
     pub agent_jobs_tools: bool,
     pub agent_jobs_worker_tools: bool,
+    pub read_tool_output: bool,
+    pub code_symbols: bool,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -86,6 +88,8 @@ impl ToolsConfig {
         let include_search_tool = features.enabled(Feature::Apps);
         let include_agent_jobs = include_collab_tools && features.enabled(Feature::Sqlite);
         let request_permission_enabled = features.enabled(Feature::RequestPermissions);
+        let include_read_tool_output = features.enabled(Feature::ToolOutputSpill);
+        let include_code_symbols = features.enabled(Feature::CodeSymbols);
         let shell_command_backend =
             if features.enabled(Feature::ShellTool) && features.enabled(Feature::ShellZshFork) {
                 ShellCommandBackendConfig::ZshFork
@@ -143,6 +147,8 @@ impl ToolsConfig {
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
             agent_jobs_tools: include_agent_jobs,
             agent_jobs_worker_tools,
+            read_tool_output: include_read_tool_output,
+            code_symbols: include_code_symbols,
         }
     }
 
@@ -336,6 +342,24 @@ fn create_exec_command_tool(allow_login_shell: bool, request_permission_enabled:
                 ),
             },
         ),
+        (
+            "until_pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional regex; return as soon as the output from this call matches it (e.g. a server's ready line). `yield_time_ms` then acts as a timeout. Output is searched in a window of roughly the last 4 KiB that starts at a line boundary when it can, so a match must fit in that window; use `(?m)^` to anchor at the start of a line."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "until_exit".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Wait for the process to exit, up to `yield_time_ms`. The result says whether the pattern matched, the process exited, or the wait timed out."
+                        .to_string(),
+                ),
+            },
+        ),
         (
             "max_output_tokens".to_string(),
             JsonSchema::Number {
@@ -394,6 +418,24 @@ fn create_write_stdin_tool() -> ToolSpec {
                 ),
             },
         ),
+        (
+            "until_pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional regex; return as soon as the output from this call matches it (e.g. a server's ready line). `yield_time_ms` then acts as a timeout. Output is searched in a window of roughly the last 4 KiB that starts at a line boundary when it can, so a match must fit in that window; use `(?m)^` to anchor at the start of a line."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "until_exit".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Wait for the process to exit, up to `yield_time_ms`. The result says whether the pattern matched, the process exited, or the wait timed out."
+                        .to_string(),
+                ),
+            },
+        ),
         (
             "max_output_tokens".to_string(),
             JsonSchema::Number {
@@ -1251,6 +1293,125 @@ fn create_read_file_tool() -> ToolSpec {
     })
 }
 
+fn create_read_tool_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "call_id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Call id named in the truncation marker of the output to read.".to_string(),
+                ),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The line number to start reading from, or the first match to return with \
+                     `pattern`. Must be 1 or greater."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The maximum number of lines (or matching lines) to return (defaults to 200)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "byte_offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Byte to start reading from. Reads a byte range instead of lines.".to_string(),
+                ),
+            },
+        ),
+        (
+            "byte_limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The maximum number of bytes to return (defaults to 16384).".to_string(),
+                ),
+            },
+        ),
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Regular expression; returns the matching lines with their line numbers."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_tool_output".to_string(),
+        description: "Reads the full output of an earlier tool call whose output was truncated. \
+                      Returns a line range by default, a byte range with `byte_offset`/`byte_limit`, \
+                      or the lines matching `pattern`."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["call_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_code_symbols_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File to outline, or file or directory to search for `symbol`. Defaults to \
+                     the session's working directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "symbol".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Name to find the definitions of, optionally qualified by its type \
+                     (e.g. \"Config::new\" or \"Runner.run\"). Omit to outline `path`."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of definitions to return (defaults to 20).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_symbols".to_string(),
+        description: "Parses Rust, TypeScript, Python and Go source. Without `symbol`, lists the \
+                      functions, types, impls and classes in the file at `path` with their line \
+                      ranges. With `symbol`, finds where it is defined under `path`. Use the line \
+                      ranges to read only the code you need."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_dir_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1649,6 +1810,7 @@ pub(crate) fn build_specs(
     dynamic_tools: &[DynamicToolSpec],
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::CodeSymbolsHandler;
     use crate::tools::handlers::DynamicToolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
@@ -1659,6 +1821,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::MultiAgentHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadToolOutputHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
     use crate::tools::handlers::ShellCommandHandler;
@@ -1796,6 +1959,18 @@ pub(crate) fn build_specs(
         builder.register_handler("list_dir", list_dir_handler);
     }
 
+    if config.read_tool_output {
+        let read_tool_output_handler = Arc::new(ReadToolOutputHandler);
+        builder.push_spec_with_parallel_support(create_read_tool_output_tool(), true);
+        builder.register_handler("read_tool_output", read_tool_output_handler);
+    }
+
+    if config.code_symbols {
+        let code_symbols_handler = Arc::new(CodeSymbolsHandler);
+        builder.push_spec_with_parallel_support(create_code_symbols_tool(), true);
+        builder.register_handler("code_symbols", code_symbols_handler);
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"test_sync_tool".to_string())
@@ -2122,6 +2297,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_specs_read_tool_output_enabled() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ToolOutputSpill);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["read_tool_output"]);
+        assert!(find_tool(&tools, "read_tool_output").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_build_specs_code_symbols_enabled() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::CodeSymbols);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["code_symbols"]);
+        assert!(find_tool(&tools, "code_symbols").supports_parallel_tool_calls);
+    }
+
     #[test]
     fn test_build_specs_agent_job_worker_tools_enabled() {
         let config = test_config();
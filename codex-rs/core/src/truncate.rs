@@ -6,6 +6,8 @@ use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_protocol::protocol::TruncationPolicy as ProtocolTruncationPolicy;
+use codex_utils_tokenizer::EncodingKind;
+use codex_utils_tokenizer::Tokenizer;
 
 const APPROX_BYTES_PER_TOKEN: usize = 4;
 
@@ -77,23 +79,68 @@ impl std::ops::Mul<f64> for TruncationPolicy {
 }
 
 pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
-    if content.len() <= policy.byte_budget() {
-        return content.to_string();
+    let result = truncate_text(content, policy);
+    if result == content {
+        return result;
     }
     let total_lines = content.lines().count();
-    let result = truncate_text(content, policy);
     format!("Total output lines: {total_lines}\n\n{result}")
 }
 
 pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
+    truncate_text_with_saved_output(content, policy, None)
+}
+
+/// Like [`formatted_truncate_text`], for output whose full text was saved for
+/// `call_id`. The marker tells the model it can read the rest back with
+/// `read_tool_output`.
+pub(crate) fn formatted_truncate_saved_text(
+    content: &str,
+    policy: TruncationPolicy,
+    call_id: &str,
+) -> String {
+    let result = truncate_text_with_saved_output(content, policy, Some(call_id));
+    if result == content {
+        return result;
+    }
+    let total_lines = content.lines().count();
+    format!("Total output lines: {total_lines}\n\n{result}")
+}
+
+/// Whether truncating `content` with `policy` would change it.
+pub(crate) fn exceeds_budget(content: &str, policy: TruncationPolicy) -> bool {
     match policy {
-        TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
+        TruncationPolicy::Bytes(bytes) => content.len() > bytes,
+        // Every token covers at least one byte.
+        TruncationPolicy::Tokens(tokens) => {
+            content.len() > tokens && policy_tokenizer().count_up_to(content, tokens).is_none()
+        }
+    }
+}
+
+/// Like [`truncate_text`], for output whose full text was saved for `call_id`.
+pub(crate) fn truncate_saved_text(
+    content: &str,
+    policy: TruncationPolicy,
+    call_id: &str,
+) -> String {
+    truncate_text_with_saved_output(content, policy, Some(call_id))
+}
+
+fn truncate_text_with_saved_output(
+    content: &str,
+    policy: TruncationPolicy,
+    saved_call_id: Option<&str>,
+) -> String {
+    match policy {
+        TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy, saved_call_id),
         TruncationPolicy::Tokens(_) => {
-            let (truncated, _) = truncate_with_token_budget(content, policy);
+            let (truncated, _) = truncate_with_token_budget(content, policy, saved_call_id);
             truncated
         }
     }
 }
+
 /// Globally truncate function output items to fit within the given
 /// truncation policy's budget, preserving as many text/image items as
 /// possible and appending a summary for any omitted text items.
@@ -117,11 +164,14 @@ pub(crate) fn truncate_function_output_items_with_policy(
                 }
 
                 let cost = match policy {
-                    TruncationPolicy::Bytes(_) => text.len(),
-                    TruncationPolicy::Tokens(_) => approx_token_count(text),
-                };
+                    TruncationPolicy::Bytes(_) => Some(text.len()),
+                    TruncationPolicy::Tokens(_) => {
+                        policy_tokenizer().count_up_to(text, remaining_budget)
+                    }
+                }
+                .filter(|cost| *cost <= remaining_budget);
 
-                if cost <= remaining_budget {
+                if let Some(cost) = cost {
                     out.push(FunctionCallOutputContentItem::InputText { text: text.clone() });
                     remaining_budget = remaining_budget.saturating_sub(cost);
                 } else {
@@ -155,35 +205,52 @@ pub(crate) fn truncate_function_output_items_with_policy(
     out
 }
 
-/// Truncate the middle of a UTF-8 string to at most `max_tokens` tokens,
-/// preserving the beginning and the end. Returns the possibly truncated string
-/// and `Some(original_token_count)` if truncation occurred; otherwise returns
-/// the original string and `None`.
-fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+/// Truncate the middle of a UTF-8 string to at most `max_tokens` tokens as
+/// counted by [`policy_token_count`], preserving the beginning and the end.
+/// The string is encoded once, plus the parts around the two cuts.
+/// Returns the possibly truncated string and `Some(original_token_count)` if
+/// truncation occurred; otherwise returns the original string and `None`.
+fn truncate_with_token_budget(
+    s: &str,
+    policy: TruncationPolicy,
+    saved_call_id: Option<&str>,
+) -> (String, Option<u64>) {
     if s.is_empty() {
         return (String::new(), None);
     }
     let max_tokens = policy.token_budget();
 
-    let byte_len = s.len();
-    if max_tokens > 0 && byte_len <= approx_bytes_for_tokens(max_tokens) {
+    // Every token covers at least one byte.
+    if max_tokens > 0 && s.len() <= max_tokens {
         return (s.to_string(), None);
     }
 
-    let truncated = truncate_with_byte_estimate(s, policy);
-    let approx_total_usize = approx_token_count(s);
-    let approx_total = u64::try_from(approx_total_usize).unwrap_or(u64::MAX);
-    if truncated == s {
-        (truncated, None)
-    } else {
-        (truncated, Some(approx_total))
+    let (left_tokens, right_tokens) = split_budget(max_tokens);
+    let kept = policy_tokenizer().head_and_tail(s, left_tokens, right_tokens);
+    let total_tokens = kept.total_tokens;
+    if total_tokens <= max_tokens {
+        return (s.to_string(), None);
     }
+
+    let marker = format_truncation_marker(
+        policy,
+        u64::try_from(total_tokens - max_tokens).unwrap_or(u64::MAX),
+        saved_call_id,
+    );
+    (
+        assemble_truncated_output(kept.head, kept.tail, &marker),
+        Some(u64::try_from(total_tokens).unwrap_or(u64::MAX)),
+    )
 }
 
 /// Truncate a string using a byte budget derived from the token budget, without
 /// performing any real tokenization. This keeps the logic purely byte-based and
 /// uses a bytes placeholder in the truncated output.
-fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
+fn truncate_with_byte_estimate(
+    s: &str,
+    policy: TruncationPolicy,
+    saved_call_id: Option<&str>,
+) -> String {
     if s.is_empty() {
         return String::new();
     }
@@ -196,6 +263,7 @@ fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
         let marker = format_truncation_marker(
             policy,
             removed_units_for_source(policy, s.len(), total_chars),
+            saved_call_id,
         );
         return marker;
     }
@@ -213,6 +281,7 @@ fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
     let marker = format_truncation_marker(
         policy,
         removed_units_for_source(policy, total_bytes.saturating_sub(max_bytes), removed_chars),
+        saved_call_id,
     );
 
     assemble_truncated_output(left, right, &marker)
@@ -258,10 +327,20 @@ fn split_string(s: &str, beginning_bytes: usize, end_bytes: usize) -> (usize, &s
     (removed_chars, before, after)
 }
 
-fn format_truncation_marker(policy: TruncationPolicy, removed_count: u64) -> String {
-    match policy {
-        TruncationPolicy::Tokens(_) => format!("…{removed_count} tokens truncated…"),
-        TruncationPolicy::Bytes(_) => format!("…{removed_count} chars truncated…"),
+fn format_truncation_marker(
+    policy: TruncationPolicy,
+    removed_count: u64,
+    saved_call_id: Option<&str>,
+) -> String {
+    let removed = match policy {
+        TruncationPolicy::Tokens(_) => format!("{removed_count} tokens truncated"),
+        TruncationPolicy::Bytes(_) => format!("{removed_count} chars truncated"),
+    };
+    match saved_call_id {
+        Some(call_id) => format!(
+            "…{removed}; full output saved, use read_tool_output with call_id \"{call_id}\" to read it…"
+        ),
+        None => format!("…{removed}…"),
     }
 }
 
@@ -294,6 +373,26 @@ pub(crate) fn approx_token_count(text: &str) -> usize {
     len.saturating_add(APPROX_BYTES_PER_TOKEN.saturating_sub(1)) / APPROX_BYTES_PER_TOKEN
 }
 
+/// Tokenizer that [`TruncationPolicy::Tokens`] budgets are counted with. Every
+/// model whose truncation policy is in tokens uses o200k_base.
+fn policy_tokenizer() -> Tokenizer {
+    Tokenizer::new(EncodingKind::O200kBase)
+}
+
+/// Tokens in `text` as counted against a [`TruncationPolicy::Tokens`] budget.
+pub(crate) fn policy_token_count(text: &str) -> usize {
+    policy_tokenizer().count(text)
+}
+
+/// Tokens in `text` counted with `tokenizer`, or estimated from the byte length
+/// when the model has no known tokenizer.
+pub(crate) fn token_count(text: &str, tokenizer: Option<Tokenizer>) -> usize {
+    match tokenizer {
+        Some(tokenizer) => tokenizer.count(text),
+        None => approx_token_count(text),
+    }
+}
+
 pub(crate) fn approx_bytes_for_tokens(tokens: usize) -> usize {
     tokens.saturating_mul(APPROX_BYTES_PER_TOKEN)
 }
@@ -316,8 +415,10 @@ pub(crate) fn approx_tokens_from_byte_count_i64(bytes: i64) -> i64 {
 mod tests {
 
     use super::TruncationPolicy;
-    use super::approx_token_count;
+    use super::exceeds_budget;
+    use super::formatted_truncate_saved_text;
     use super::formatted_truncate_text;
+    use super::policy_token_count;
     use super::split_string;
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
@@ -375,7 +476,7 @@ mod tests {
         let content = "example output";
 
         assert_eq!(
-            "Total output lines: 1\n\nex…3 tokens truncated…ut",
+            "Total output lines: 1\n\n…1 tokens truncated… output",
             formatted_truncate_text(content, TruncationPolicy::Tokens(1)),
         );
     }
@@ -405,7 +506,7 @@ mod tests {
         let content = "this is an example of a long output that should be truncated";
 
         assert_eq!(
-            "Total output lines: 1\n\nthis is an…10 tokens truncated… truncated",
+            "Total output lines: 1\n\nthis is…7 tokens truncated… should be truncated",
             formatted_truncate_text(content, TruncationPolicy::Tokens(5)),
         );
     }
@@ -437,16 +538,44 @@ mod tests {
             "this is an example of a long output that should be truncated\nalso some other line";
 
         assert_eq!(
-            "Total output lines: 2\n\nthis is an example o…11 tokens truncated…also some other line",
+            "Total output lines: 2\n\nthis is an example of…7 tokens truncated…\nalso some other line",
             formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
         );
     }
 
+    #[test]
+    fn truncate_saved_text_points_at_read_tool_output() {
+        let content =
+            "this is an example of a long output that will be truncated\nalso some other line";
+        assert_eq!(
+            "Total output lines: 2\n\nthis is an example of…7 tokens truncated; full output saved, use read_tool_output with call_id \"call-1\" to read it…\nalso some other line",
+            formatted_truncate_saved_text(content, TruncationPolicy::Tokens(10), "call-1"),
+        );
+        assert_eq!(
+            "short",
+            formatted_truncate_saved_text("short", TruncationPolicy::Tokens(10), "call-1"),
+        );
+    }
+
+    #[test]
+    fn token_budgets_count_real_tokens() {
+        // Three bytes per character but one token each: the old 4-bytes/token
+        // estimate let this through a 100 token budget.
+        let content = "漢".repeat(120);
+
+        assert!(exceeds_budget(&content, TruncationPolicy::Tokens(100)));
+        assert!(!exceeds_budget(&content, TruncationPolicy::Tokens(120)));
+        assert!(
+            formatted_truncate_text(&content, TruncationPolicy::Tokens(100))
+                .contains("tokens truncated")
+        );
+    }
+
     #[test]
     fn truncate_with_token_budget_returns_original_when_under_limit() {
         let s = "short output";
         let limit = 100;
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit));
+        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit), None);
         assert_eq!(out, s);
         assert_eq!(original, None);
     }
@@ -454,15 +583,15 @@ mod tests {
     #[test]
     fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
         let s = "abcdef";
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0));
-        assert_eq!(out, "…2 tokens truncated…");
-        assert_eq!(original, Some(2));
+        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0), None);
+        assert_eq!(out, "…1 tokens truncated…");
+        assert_eq!(original, Some(1));
     }
 
     #[test]
     fn truncate_middle_tokens_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8));
+        let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8), None);
         assert_eq!(out, "😀😀😀😀…8 tokens truncated… line with text\n");
         assert_eq!(tokens, Some(16));
     }
@@ -477,7 +606,7 @@ mod tests {
     #[test]
     fn truncates_across_multiple_under_limit_texts_and_reports_omitted() {
         let chunk = "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu nu xi omicron pi rho sigma tau upsilon phi chi psi omega.\n";
-        let chunk_tokens = approx_token_count(chunk);
+        let chunk_tokens = policy_token_count(chunk);
         assert!(chunk_tokens > 0, "chunk must consume tokens");
         let limit = chunk_tokens * 3;
         let t1 = chunk.to_string();
@@ -11,6 +11,8 @@ use sha1::digest::Output;
 use uuid::Uuid;
 
 use crate::protocol::FileChange;
+use crate::workspace_overlay::OverlayRoot;
+use crate::workspace_overlay::overlay_content_path;
 
 const ZERO_OID: &str = "0000000000000000000000000000000000000000";
 const DEV_NULL: &str = "/dev/null";
@@ -40,6 +42,9 @@ pub struct TurnDiffTracker {
     temp_name_to_current_path: HashMap<String, PathBuf>,
     /// Cache of known git worktree roots to avoid repeated filesystem walks.
     git_root_cache: Vec<PathBuf>,
+    /// When set, current file contents are read through this copy-on-write
+    /// overlay instead of from the workspace.
+    overlay: Option<OverlayRoot>,
 }
 
 impl TurnDiffTracker {
@@ -47,6 +52,25 @@ impl TurnDiffTracker {
         Self::default()
     }
 
+    /// Tracker that diffs the workspace against the view of it through
+    /// `overlay`, so baselines come from the workspace and current contents
+    /// from the overlay's upper directory.
+    pub(crate) fn for_overlay(overlay: OverlayRoot) -> Self {
+        Self {
+            overlay: Some(overlay),
+            ..Self::default()
+        }
+    }
+
+    /// Path holding the current contents of `path`, or `None` when the
+    /// overlay deleted it.
+    fn current_content_path(&self, path: &Path) -> Option<PathBuf> {
+        match &self.overlay {
+            Some(overlay) => overlay_content_path(overlay, path),
+            None => Some(path.to_path_buf()),
+        }
+    }
+
     /// Front-run apply patch calls to track the starting contents of any modified files.
     /// - Creates an in-memory baseline snapshot for files that already exist on disk when first seen.
     /// - For additions, we intentionally do not create a baseline snapshot so that diffs are proper additions.
@@ -265,8 +289,14 @@ impl TurnDiffTracker {
             None => return aggregated,
         };
 
-        let current_mode = file_mode_for_path(&current_external_path).unwrap_or(FileMode::Regular);
-        let right_bytes = blob_bytes(&current_external_path, current_mode);
+        let current_content_path = self.current_content_path(&current_external_path);
+        let current_mode = current_content_path
+            .as_deref()
+            .and_then(file_mode_for_path)
+            .unwrap_or(FileMode::Regular);
+        let right_bytes = current_content_path
+            .as_deref()
+            .and_then(|path| blob_bytes(path, current_mode));
 
         // Compute displays with &mut self before borrowing any baseline content.
         let left_display = self.relative_to_git_root_str(&baseline_external_path);
@@ -274,7 +304,7 @@ impl TurnDiffTracker {
 
         // Compute right oid before borrowing baseline content.
         let right_oid = if let Some(b) = right_bytes.as_ref() {
-            if current_mode == FileMode::Symlink {
+            if current_mode == FileMode::Symlink || self.overlay.is_some() {
                 format!("{:x}", git_blob_sha1_hex_bytes(b))
             } else {
                 self.git_blob_oid_for_path(&current_external_path)
@@ -515,6 +545,7 @@ mod tests {
             file.clone(),
             FileChange::Add {
                 content: "foo\n".to_string(),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&add_changes);
@@ -545,6 +576,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -620,6 +652,7 @@ index {left_oid}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -660,6 +693,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv_changes);
@@ -682,6 +716,7 @@ index {left_oid}..{right_oid}
             FileChange::Update {
                 unified_diff: "".into(),
                 move_path: Some(dest.clone()),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&mv);
@@ -722,6 +757,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_a);
@@ -802,6 +838,7 @@ index {left_oid_b}..{ZERO_OID}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
@@ -838,6 +875,7 @@ Binary files differ
             file.clone(),
             FileChange::Add {
                 content: "foo\n".to_string(),
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&add_changes);
@@ -868,6 +906,7 @@ index {ZERO_OID}..{right_oid}
             FileChange::Update {
                 unified_diff: "".to_owned(),
                 move_path: None,
+                mode: None,
             },
         )]);
         acc.on_patch_begin(&update_changes);
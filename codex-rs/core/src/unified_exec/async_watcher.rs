@@ -18,6 +18,7 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecCommandSource;
 use crate::protocol::ExecOutputStream;
+use crate::protocol::ResourceLimitKind;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
@@ -122,6 +123,7 @@ pub(crate) fn spawn_exit_watcher(
         output_drained.notified().await;
 
         let exit_code = process.exit_code().unwrap_or(-1);
+        let resource_limit_exceeded = process.resource_limit_exceeded();
         let duration = Instant::now().saturating_duration_since(started_at);
         emit_exec_end_for_unified_exec(
             session_ref,
@@ -133,6 +135,7 @@ pub(crate) fn spawn_exit_watcher(
             transcript,
             String::new(),
             exit_code,
+            resource_limit_exceeded,
             duration,
         )
         .await;
@@ -185,6 +188,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
     transcript: Arc<Mutex<HeadTailBuffer>>,
     fallback_output: String,
     exit_code: i32,
+    resource_limit_exceeded: Option<ResourceLimitKind>,
     duration: Duration,
 ) {
     let aggregated_output = resolve_aggregated_output(&transcript, fallback_output).await;
@@ -195,6 +199,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
         aggregated_output: StreamOutput::new(aggregated_output),
         duration,
         timed_out: false,
+        resource_limit_exceeded,
     };
     let event_ctx = ToolEventCtx::new(session_ref.as_ref(), turn_ref.as_ref(), &call_id, None);
     let emitter = ToolEmitter::unified_exec(
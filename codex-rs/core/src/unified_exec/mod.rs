@@ -32,11 +32,13 @@ use codex_network_proxy::NetworkProxy;
 use codex_protocol::models::PermissionProfile;
 use rand::Rng;
 use rand::rng;
+use regex_lite::Regex;
 use tokio::sync::Mutex;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::HookApprovalRequest;
 
 mod async_watcher;
 mod errors;
@@ -93,6 +95,8 @@ pub(crate) struct ExecCommandRequest {
     pub additional_permissions: Option<PermissionProfile>,
     pub justification: Option<String>,
     pub prefix_rule: Option<Vec<String>>,
+    pub wait_until: WaitUntil,
+    pub hook_approval: Option<HookApprovalRequest>,
 }
 
 #[derive(Debug)]
@@ -101,6 +105,43 @@ pub(crate) struct WriteStdinRequest<'a> {
     pub input: &'a str,
     pub yield_time_ms: u64,
     pub max_output_tokens: Option<usize>,
+    pub wait_until: WaitUntil,
+}
+
+/// Conditions that let a unified exec call return before its yield time.
+///
+/// When any condition is set, the yield time acts as a timeout and may extend
+/// up to the background terminal bound instead of the interactive cap.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WaitUntil {
+    /// Return once the output collected by this call matches.
+    pub pattern: Option<Regex>,
+    /// Wait for the process to exit.
+    pub exit: bool,
+}
+
+impl WaitUntil {
+    pub(crate) fn is_set(&self) -> bool {
+        self.pattern.is_some() || self.exit
+    }
+}
+
+/// Which condition ended a unified exec wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WaitOutcome {
+    PatternMatched,
+    ProcessExited,
+    TimedOut,
+}
+
+impl WaitOutcome {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::PatternMatched => "pattern matched",
+            Self::ProcessExited => "process exited",
+            Self::TimedOut => "timed out",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -115,6 +156,8 @@ pub(crate) struct UnifiedExecResponse {
     pub exit_code: Option<i32>,
     pub original_token_count: Option<usize>,
     pub session_command: Option<Vec<String>>,
+    /// Set when the call asked to wait for a pattern or for exit.
+    pub wait_outcome: Option<WaitOutcome>,
 }
 
 #[derive(Default)]
@@ -209,6 +252,16 @@ mod tests {
         turn: &Arc<TurnContext>,
         cmd: &str,
         yield_time_ms: u64,
+    ) -> Result<UnifiedExecResponse, UnifiedExecError> {
+        exec_command_until(session, turn, cmd, yield_time_ms, WaitUntil::default()).await
+    }
+
+    async fn exec_command_until(
+        session: &Arc<Session>,
+        turn: &Arc<TurnContext>,
+        cmd: &str,
+        yield_time_ms: u64,
+        wait_until: WaitUntil,
     ) -> Result<UnifiedExecResponse, UnifiedExecError> {
         let context =
             UnifiedExecContext::new(Arc::clone(session), Arc::clone(turn), "call".to_string());
@@ -234,6 +287,8 @@ mod tests {
                     additional_permissions: None,
                     justification: None,
                     prefix_rule: None,
+                    wait_until,
+                    hook_approval: None,
                 },
                 &context,
             )
@@ -245,6 +300,23 @@ mod tests {
         process_id: &str,
         input: &str,
         yield_time_ms: u64,
+    ) -> Result<UnifiedExecResponse, UnifiedExecError> {
+        write_stdin_until(
+            session,
+            process_id,
+            input,
+            yield_time_ms,
+            WaitUntil::default(),
+        )
+        .await
+    }
+
+    async fn write_stdin_until(
+        session: &Arc<Session>,
+        process_id: &str,
+        input: &str,
+        yield_time_ms: u64,
+        wait_until: WaitUntil,
     ) -> Result<UnifiedExecResponse, UnifiedExecError> {
         session
             .services
@@ -254,6 +326,7 @@ mod tests {
                 input,
                 yield_time_ms,
                 max_output_tokens: None,
+                wait_until,
             })
             .await
     }
@@ -426,6 +499,78 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn exec_command_returns_when_output_matches_until_pattern() -> anyhow::Result<()> {
+        skip_if_sandbox!(Ok(()));
+
+        let (session, turn) = test_session_and_turn().await;
+
+        let response = exec_command_until(
+            &session,
+            &turn,
+            "echo booting; sleep 0.2; echo 'listening on 8080'; sleep 30",
+            20_000,
+            WaitUntil {
+                pattern: Some(Regex::new(r"listening on \d+")?),
+                exit: false,
+            },
+        )
+        .await?;
+
+        assert_eq!(response.wait_outcome, Some(WaitOutcome::PatternMatched));
+        assert!(response.output.contains("listening on 8080"));
+        assert!(response.wall_time < Duration::from_secs(10));
+        let process_id = response.process_id.expect("expected process id");
+
+        let followup = write_stdin_until(
+            &session,
+            &process_id,
+            "",
+            250,
+            WaitUntil {
+                pattern: Some(Regex::new("never printed")?),
+                exit: false,
+            },
+        )
+        .await?;
+        assert_eq!(followup.wait_outcome, Some(WaitOutcome::TimedOut));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn write_stdin_until_exit_reports_process_exit() -> anyhow::Result<()> {
+        skip_if_sandbox!(Ok(()));
+
+        let (session, turn) = test_session_and_turn().await;
+
+        let open_shell = exec_command(&session, &turn, "bash -i", 2_500).await?;
+        assert_eq!(open_shell.wait_outcome, None);
+        let process_id = open_shell
+            .process_id
+            .as_ref()
+            .expect("expected process id")
+            .as_str();
+
+        let response = write_stdin_until(
+            &session,
+            process_id,
+            "sleep 0.5; echo finished; exit\n",
+            60_000,
+            WaitUntil {
+                pattern: None,
+                exit: true,
+            },
+        )
+        .await?;
+
+        assert_eq!(response.wait_outcome, Some(WaitOutcome::ProcessExited));
+        assert!(response.output.contains("finished"));
+        assert!(response.process_id.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore] // Ignored while we have a better way to test this.
     async fn requests_with_large_timeout_are_capped() -> anyhow::Result<()> {
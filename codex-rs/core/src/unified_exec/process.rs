@@ -15,6 +15,8 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::landlock::ResourceLimitReport;
+use crate::protocol::ResourceLimitKind;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
@@ -44,6 +46,7 @@ pub(crate) struct UnifiedExecProcess {
     output_drained: Arc<Notify>,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    resource_limit_report: Option<ResourceLimitReport>,
 }
 
 impl UnifiedExecProcess {
@@ -51,6 +54,7 @@ impl UnifiedExecProcess {
         process_handle: ExecCommandSession,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
         sandbox_type: SandboxType,
+        resource_limit_report: Option<ResourceLimitReport>,
     ) -> Self {
         let output_buffer = Arc::new(Mutex::new(HeadTailBuffer::default()));
         let output_notify = Arc::new(Notify::new());
@@ -92,6 +96,7 @@ impl UnifiedExecProcess {
             output_drained,
             output_task,
             sandbox_type,
+            resource_limit_report,
         }
     }
 
@@ -146,6 +151,13 @@ impl UnifiedExecProcess {
         self.sandbox_type
     }
 
+    /// The resource limit the Linux sandbox reported for the exited process.
+    pub(crate) fn resource_limit_exceeded(&self) -> Option<ResourceLimitKind> {
+        self.resource_limit_report
+            .as_ref()
+            .and_then(ResourceLimitReport::exceeded)
+    }
+
     pub(super) async fn check_for_sandbox_denial(&self) -> Result<(), UnifiedExecError> {
         let _ =
             tokio::time::timeout(Duration::from_millis(20), self.output_notify.notified()).await;
@@ -176,6 +188,7 @@ impl UnifiedExecProcess {
             exit_code,
             stderr: StreamOutput::new(text.to_string()),
             aggregated_output: StreamOutput::new(text.to_string()),
+            resource_limit_exceeded: self.resource_limit_exceeded(),
             ..Default::default()
         };
         if is_likely_sandbox_denied(sandbox_type, &exec_output) {
@@ -196,13 +209,19 @@ impl UnifiedExecProcess {
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
+        resource_limit_report: Option<ResourceLimitReport>,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
             session: process_handle,
             output_rx,
             mut exit_rx,
         } = spawned;
-        let managed = Self::new(process_handle, output_rx, sandbox_type);
+        let managed = Self::new(
+            process_handle,
+            output_rx,
+            sandbox_type,
+            resource_limit_report,
+        );
 
         let exit_ready = matches!(exit_rx.try_recv(), Ok(_) | Err(TryRecvError::Closed));
 
@@ -1,4 +1,5 @@
 use rand::Rng;
+use regex_lite::Regex;
 use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -26,8 +27,8 @@ use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolR
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
+use crate::truncate::policy_token_count;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
 use crate::unified_exec::MAX_YIELD_TIME_MS;
@@ -40,6 +41,7 @@ use crate::unified_exec::UnifiedExecError;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::unified_exec::UnifiedExecResponse;
 use crate::unified_exec::WARNING_UNIFIED_EXEC_PROCESSES;
+use crate::unified_exec::WaitOutcome;
 use crate::unified_exec::WriteStdinRequest;
 use crate::unified_exec::async_watcher::emit_exec_end_for_unified_exec;
 use crate::unified_exec::async_watcher::spawn_exit_watcher;
@@ -52,6 +54,11 @@ use crate::unified_exec::process::OutputHandles;
 use crate::unified_exec::process::UnifiedExecProcess;
 use crate::unified_exec::resolve_max_tokens;
 
+/// Bytes of already-scanned output kept around so `until_pattern` still matches text that
+/// straddles two chunks. The kept text is trimmed forward to a line start when one falls inside
+/// it.
+const UNTIL_PATTERN_OVERLAP_BYTES: usize = 4 * 1024;
+
 const UNIFIED_EXEC_ENV: [(&str, &str); 10] = [
     ("NO_COLOR", "1"),
     ("TERM", "dumb"),
@@ -194,7 +201,13 @@ impl UnifiedExecProcessManager {
 
         start_streaming_output(&process, context, Arc::clone(&transcript));
         let max_tokens = resolve_max_tokens(request.max_output_tokens);
-        let yield_time_ms = clamp_yield_time(request.yield_time_ms);
+        let yield_time_ms = if request.wait_until.is_set() {
+            request
+                .yield_time_ms
+                .clamp(MIN_YIELD_TIME_MS, self.max_write_stdin_yield_time_ms)
+        } else {
+            clamp_yield_time(request.yield_time_ms)
+        };
 
         let start = Instant::now();
         // For the initial exec_command call, we both stream output to events
@@ -208,13 +221,14 @@ impl UnifiedExecProcessManager {
             cancellation_token,
         } = process.output_handles();
         let deadline = start + Duration::from_millis(yield_time_ms);
-        let collected = Self::collect_output_until_deadline(
+        let (collected, wait_outcome) = Self::collect_output_until_deadline(
             &output_buffer,
             &output_notify,
             &output_closed,
             &output_closed_notify,
             &cancellation_token,
             deadline,
+            request.wait_until.pattern.as_ref(),
         )
         .await;
         let wall_time = Instant::now().saturating_duration_since(start);
@@ -241,6 +255,7 @@ impl UnifiedExecProcessManager {
                 Arc::clone(&transcript),
                 output.clone(),
                 exit,
+                process.resource_limit_exceeded(),
                 wall_time,
             )
             .await;
@@ -274,7 +289,7 @@ impl UnifiedExecProcessManager {
             .await;
         };
 
-        let original_token_count = approx_token_count(&text);
+        let original_token_count = policy_token_count(&text);
         let response = UnifiedExecResponse {
             event_call_id: context.call_id.clone(),
             chunk_id,
@@ -289,6 +304,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(request.command.clone()),
+            wait_outcome: request.wait_until.is_set().then_some(wait_outcome),
         };
 
         Ok(response)
@@ -326,30 +342,34 @@ impl UnifiedExecProcessManager {
         let max_tokens = resolve_max_tokens(request.max_output_tokens);
         let yield_time_ms = {
             // Empty polls use configurable background timeout bounds. Non-empty
-            // writes keep a fixed max cap so interactive stdin remains responsive.
+            // writes keep a fixed max cap so interactive stdin remains responsive,
+            // unless the caller waits on a condition that can end the call early.
             let time_ms = request.yield_time_ms.max(MIN_YIELD_TIME_MS);
             if request.input.is_empty() {
                 time_ms.clamp(MIN_EMPTY_YIELD_TIME_MS, self.max_write_stdin_yield_time_ms)
+            } else if request.wait_until.is_set() {
+                time_ms.min(self.max_write_stdin_yield_time_ms)
             } else {
                 time_ms.min(MAX_YIELD_TIME_MS)
             }
         };
         let start = Instant::now();
         let deadline = start + Duration::from_millis(yield_time_ms);
-        let collected = Self::collect_output_until_deadline(
+        let (collected, wait_outcome) = Self::collect_output_until_deadline(
             &output_buffer,
             &output_notify,
             &output_closed,
             &output_closed_notify,
             &cancellation_token,
             deadline,
+            request.wait_until.pattern.as_ref(),
         )
         .await;
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
         let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
-        let original_token_count = approx_token_count(&text);
+        let original_token_count = policy_token_count(&text);
         let chunk_id = generate_chunk_id();
 
         // After polling, refresh_process_state tells us whether the PTY is
@@ -384,6 +404,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(session_command.clone()),
+            wait_outcome: request.wait_until.is_set().then_some(wait_outcome),
         };
 
         Ok(response)
@@ -555,7 +576,8 @@ impl UnifiedExecProcessManager {
         };
         let spawned =
             spawn_result.map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
-        UnifiedExecProcess::from_spawned(spawned, env.sandbox).await
+        UnifiedExecProcess::from_spawned(spawned, env.sandbox, env.resource_limit_report.clone())
+            .await
     }
 
     pub(super) async fn open_session_with_sandbox(
@@ -576,6 +598,8 @@ impl UnifiedExecProcessManager {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &request.command,
+                cwd: &cwd,
+                env: &env,
                 approval_policy: context.turn.approval_policy.value(),
                 sandbox_policy: context.turn.sandbox_policy.get(),
                 sandbox_permissions: request.sandbox_permissions,
@@ -599,6 +623,7 @@ impl UnifiedExecProcessManager {
             turn: context.turn.clone(),
             call_id: context.call_id.clone(),
             tool_name: "exec_command".to_string(),
+            hook_approval: request.hook_approval.clone(),
         };
         orchestrator
             .run(
@@ -620,10 +645,13 @@ impl UnifiedExecProcessManager {
         output_closed_notify: &Arc<Notify>,
         cancellation_token: &CancellationToken,
         deadline: Instant,
-    ) -> Vec<u8> {
+        until_pattern: Option<&Regex>,
+    ) -> (Vec<u8>, WaitOutcome) {
         const POST_EXIT_CLOSE_WAIT_CAP: Duration = Duration::from_millis(50);
 
         let mut collected: Vec<u8> = Vec::with_capacity(4096);
+        let mut pattern_matcher = until_pattern.map(UntilPatternMatcher::new);
+        let mut pattern_matched = false;
         let mut exit_signal_received = cancellation_token.is_cancelled();
         let mut post_exit_deadline: Option<Instant> = None;
         loop {
@@ -682,6 +710,13 @@ impl UnifiedExecProcessManager {
 
             for chunk in drained_chunks {
                 collected.extend_from_slice(&chunk);
+                if !pattern_matched && let Some(matcher) = pattern_matcher.as_mut() {
+                    pattern_matched = matcher.push(&chunk);
+                }
+            }
+
+            if pattern_matched {
+                break;
             }
 
             exit_signal_received |= cancellation_token.is_cancelled();
@@ -690,7 +725,14 @@ impl UnifiedExecProcessManager {
             }
         }
 
-        collected
+        let outcome = if pattern_matched {
+            WaitOutcome::PatternMatched
+        } else if exit_signal_received || cancellation_token.is_cancelled() {
+            WaitOutcome::ProcessExited
+        } else {
+            WaitOutcome::TimedOut
+        };
+        (collected, outcome)
     }
 
     fn prune_processes_if_needed(store: &mut ProcessStore) -> Option<ProcessEntry> {
@@ -772,6 +814,83 @@ enum ProcessStatus {
     Unknown,
 }
 
+/// Matches `until_pattern` against output as it arrives.
+///
+/// Each chunk is decoded once, holding back a trailing incomplete UTF-8 sequence until the
+/// rest of it arrives, and only the new text plus at most the last
+/// [`UNTIL_PATTERN_OVERLAP_BYTES`] of earlier text is searched. The earlier text starts at a
+/// line boundary whenever one falls inside that budget, so `^` keeps meaning the start of a line
+/// rather than an arbitrary cut.
+struct UntilPatternMatcher<'a> {
+    pattern: &'a Regex,
+    pending: Vec<u8>,
+    overlap: String,
+}
+
+impl<'a> UntilPatternMatcher<'a> {
+    fn new(pattern: &'a Regex) -> Self {
+        Self {
+            pattern,
+            pending: Vec::new(),
+            overlap: String::new(),
+        }
+    }
+
+    /// Feeds one chunk of output and reports whether the pattern has matched.
+    fn push(&mut self, chunk: &[u8]) -> bool {
+        self.pending.extend_from_slice(chunk);
+        let pending = std::mem::take(&mut self.pending);
+        let mut window = std::mem::take(&mut self.overlap);
+        let new_text_start = window.len();
+        let mut rest = pending.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    window.push_str(text);
+                    rest = &[];
+                    break;
+                }
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    window.push_str(&String::from_utf8_lossy(valid));
+                    match err.error_len() {
+                        Some(len) => {
+                            window.push(char::REPLACEMENT_CHARACTER);
+                            rest = &invalid[len..];
+                        }
+                        None => {
+                            rest = invalid;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending = rest.to_vec();
+
+        if window.len() == new_text_start {
+            self.overlap = window;
+            return false;
+        }
+        if self.pattern.is_match(&window) {
+            return true;
+        }
+
+        let mut keep_from = window.len().saturating_sub(UNTIL_PATTERN_OVERLAP_BYTES);
+        while !window.is_char_boundary(keep_from) {
+            keep_from += 1;
+        }
+        if keep_from > 0
+            && !window[..keep_from].ends_with('\n')
+            && let Some(newline) = window[keep_from..].find('\n')
+        {
+            keep_from += newline + 1;
+        }
+        self.overlap = window.split_off(keep_from);
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -876,4 +995,43 @@ mod tests {
         // (10) is exited but among the last 8; we should drop the LRU outside that set.
         assert_eq!(candidate, Some(id(1)));
     }
+
+    #[test]
+    fn until_pattern_matches_across_chunks_and_split_characters() {
+        let pattern = Regex::new("ready: ✓").expect("valid regex");
+        let mut matcher = UntilPatternMatcher::new(&pattern);
+        let check = "✓".as_bytes();
+
+        assert!(!matcher.push(b"server rea"));
+        assert!(!matcher.push(b"dy: "));
+        assert!(!matcher.push(&check[..1]));
+        assert!(matcher.push(&check[1..]));
+    }
+
+    #[test]
+    fn until_pattern_trims_the_overlap_at_a_line_start() {
+        let pattern = Regex::new("^start.*end").expect("valid regex");
+        let mut matcher = UntilPatternMatcher::new(&pattern);
+
+        assert!(!matcher.push(b"noise\n"));
+        assert!(!matcher.push(b"start"));
+        // Pushes the window just past the budget, so the cut lands inside "noise".
+        assert!(!matcher.push(&vec![b'x'; UNTIL_PATTERN_OVERLAP_BYTES - 10]));
+        assert!(matcher.overlap.starts_with("start"));
+        assert!(matcher.push(b"end"));
+    }
+
+    #[test]
+    fn until_pattern_only_keeps_a_bounded_overlap() {
+        let pattern = Regex::new("never printed").expect("valid regex");
+        let mut matcher = UntilPatternMatcher::new(&pattern);
+
+        assert!(!matcher.push(&vec![b'x'; 3 * UNTIL_PATTERN_OVERLAP_BYTES]));
+        assert_eq!(matcher.overlap.len(), UNTIL_PATTERN_OVERLAP_BYTES);
+        let mut lines = "line\n".repeat(UNTIL_PATTERN_OVERLAP_BYTES);
+        lines.push_str("partial");
+        assert!(!matcher.push(lines.as_bytes()));
+        assert!(matcher.overlap.len() <= UNTIL_PATTERN_OVERLAP_BYTES);
+        assert!(matcher.overlap.starts_with("line\n"));
+    }
 }
@@ -0,0 +1,683 @@
+//! Copy-on-write workspace overlays for `copy_on_write = true` under
+//! `[sandbox_workspace_write]`.
+//!
+//! On Linux the sandbox helper mounts an overlayfs over the sandbox cwd, so
+//! sandboxed commands write to an upper directory under
+//! `$CODEX_HOME/sessions/<thread id>/workspace_overlay/<workspace hash>/`
+//! instead of to the workspace. The pending changes are reported at the end of
+//! each turn and only reach the workspace once the user accepts them with
+//! `Op::ResolveWorkspaceOverlay`. Accepted changes whose workspace or overlay
+//! file changed after they were reported are not merged and are reported as
+//! conflicts instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::WorkspaceOverlayChangesEvent;
+use codex_protocol::protocol::WorkspaceOverlayDecision;
+use codex_protocol::protocol::WorkspaceOverlayResolvedEvent;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::rollout::SESSIONS_SUBDIR;
+use crate::turn_diff_tracker::TurnDiffTracker;
+
+const WORKSPACE_OVERLAY_SUBDIR: &str = "workspace_overlay";
+/// File in each overlay directory that records the workspace it covers.
+const WORKSPACE_FILE: &str = "workspace";
+/// File in each overlay directory that records the state of every change as
+/// of the last time it was reported.
+const REVIEWED_FILE: &str = "reviewed.json";
+/// Overlay upper and work directories. The names are shared with the
+/// `--workspace-overlay` flag of `codex-linux-sandbox`.
+const UPPER_DIR: &str = "upper";
+const WORK_DIR: &str = "work";
+
+#[derive(Debug)]
+pub(crate) struct WorkspaceOverlay {
+    dir: PathBuf,
+}
+
+/// The overlay of a single workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OverlayRoot {
+    pub(crate) workspace: PathBuf,
+    pub(crate) upper: PathBuf,
+    pub(crate) work: PathBuf,
+    reviewed: PathBuf,
+}
+
+/// Fingerprints of both sides of a change at the time it was reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ReviewedChange {
+    path: PathBuf,
+    workspace: Option<String>,
+    overlay: Option<String>,
+}
+
+impl ReviewedChange {
+    fn capture(root: &OverlayRoot, path: &Path) -> io::Result<Self> {
+        let overlay = match overlay_content_path(root, path) {
+            Some(content_path) => fingerprint(&content_path)?,
+            None => None,
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            workspace: fingerprint(path)?,
+            overlay,
+        })
+    }
+}
+
+impl WorkspaceOverlay {
+    pub(crate) fn new(codex_home: &Path, thread_id: ThreadId) -> Self {
+        Self {
+            dir: codex_home
+                .join(SESSIONS_SUBDIR)
+                .join(thread_id.to_string())
+                .join(WORKSPACE_OVERLAY_SUBDIR),
+        }
+    }
+
+    /// Returns the overlay directory for `workspace`, creating it on first
+    /// use. It holds the `upper` and `work` directories that the sandbox
+    /// helper mounts over `workspace`.
+    pub(crate) fn prepare(&self, workspace: &Path) -> io::Result<PathBuf> {
+        let dir = self.dir.join(workspace_key(workspace));
+        fs::create_dir_all(dir.join(UPPER_DIR))?;
+        fs::create_dir_all(dir.join(WORK_DIR))?;
+        let workspace_file = dir.join(WORKSPACE_FILE);
+        if !workspace_file.exists() {
+            fs::write(&workspace_file, workspace.to_string_lossy().as_bytes())?;
+        }
+        Ok(dir)
+    }
+
+    /// The overlay of `workspace`, whether or not it has been prepared yet.
+    pub(crate) fn root(&self, workspace: &Path) -> OverlayRoot {
+        let dir = self.dir.join(workspace_key(workspace));
+        OverlayRoot {
+            workspace: workspace.to_path_buf(),
+            upper: dir.join(UPPER_DIR),
+            work: dir.join(WORK_DIR),
+            reviewed: dir.join(REVIEWED_FILE),
+        }
+    }
+
+    /// Overlays that have been prepared for this session.
+    fn roots(&self) -> io::Result<Vec<OverlayRoot>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut roots = Vec::new();
+        for entry in entries {
+            let dir = entry?.path();
+            let Ok(workspace) = fs::read_to_string(dir.join(WORKSPACE_FILE)) else {
+                continue;
+            };
+            roots.push(OverlayRoot {
+                workspace: PathBuf::from(workspace),
+                upper: dir.join(UPPER_DIR),
+                work: dir.join(WORK_DIR),
+                reviewed: dir.join(REVIEWED_FILE),
+            });
+        }
+        roots.sort_by(|a, b| a.workspace.cmp(&b.workspace));
+        Ok(roots)
+    }
+
+    /// Changes waiting in the overlays, or `None` when there are none. The
+    /// reported state of each change is recorded so that [`Self::resolve`]
+    /// only merges what the user has seen.
+    pub(crate) fn pending_changes(&self) -> io::Result<Option<WorkspaceOverlayChangesEvent>> {
+        let mut changes = HashMap::new();
+        let mut unified_diff = String::new();
+        for root in self.roots()? {
+            let root_changes = collect_changes(&root)?;
+            record_reviewed(&root, &root_changes)?;
+            if root_changes.is_empty() {
+                continue;
+            }
+            let mut tracker = TurnDiffTracker::for_overlay(root.clone());
+            tracker.on_patch_begin(&root_changes);
+            match tracker.get_unified_diff() {
+                Ok(Some(diff)) => unified_diff.push_str(&diff),
+                Ok(None) => {}
+                Err(err) => warn!("failed to diff workspace overlay: {err:#}"),
+            }
+            changes.extend(root_changes);
+        }
+        if changes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(WorkspaceOverlayChangesEvent {
+            changes,
+            unified_diff,
+        }))
+    }
+
+    /// Merges the accepted changes into the workspaces and discards everything
+    /// else in the overlays. An accepted change is only merged when neither
+    /// side of it changed since it was last reported by
+    /// [`Self::pending_changes`].
+    pub(crate) fn resolve(
+        &self,
+        decision: &WorkspaceOverlayDecision,
+    ) -> io::Result<WorkspaceOverlayResolvedEvent> {
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+        let mut conflicts = Vec::new();
+        for root in self.roots()? {
+            let changes = collect_changes(&root)?;
+            let reviewed = read_reviewed(&root)?;
+            let mut paths: Vec<&PathBuf> = changes.keys().collect();
+            paths.sort();
+            for path in paths {
+                let is_accepted = match decision {
+                    WorkspaceOverlayDecision::AcceptAll => true,
+                    WorkspaceOverlayDecision::RejectAll => false,
+                    WorkspaceOverlayDecision::AcceptPaths { paths } => paths.contains(path),
+                };
+                if !is_accepted {
+                    rejected.push(path.clone());
+                    continue;
+                }
+                let Some(change) = changes.get(path) else {
+                    continue;
+                };
+                if reviewed.get(path) != Some(&ReviewedChange::capture(&root, path)?) {
+                    conflicts.push(path.clone());
+                    continue;
+                }
+                merge_change(&root, path, change)?;
+                accepted.push(path.clone());
+            }
+            clear_dir(&root.upper)?;
+            clear_dir(&root.work)?;
+            remove_path(&root.reviewed)?;
+        }
+        Ok(WorkspaceOverlayResolvedEvent {
+            accepted,
+            rejected,
+            conflicts,
+        })
+    }
+}
+
+fn workspace_key(workspace: &Path) -> String {
+    let digest = Sha256::digest(workspace.to_string_lossy().as_bytes());
+    hex(&digest[..8])
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hash of the file, symlink or directory at `path`, or `None` when nothing
+/// is there.
+fn fingerprint(path: &Path) -> io::Result<Option<String>> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut hasher = Sha256::new();
+    if metadata.file_type().is_symlink() {
+        hasher.update(b"symlink\0");
+        hasher.update(fs::read_link(path)?.to_string_lossy().as_bytes());
+    } else if metadata.is_file() {
+        hasher.update(b"file\0");
+        hasher.update(file_mode(&metadata).unwrap_or_default().to_le_bytes());
+        hasher.update(fs::read(path)?);
+    } else {
+        hasher.update(b"other\0");
+    }
+    Ok(Some(hex(&hasher.finalize())))
+}
+
+/// Records the current state of `changes` as what the user reviewed.
+fn record_reviewed(root: &OverlayRoot, changes: &HashMap<PathBuf, FileChange>) -> io::Result<()> {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+    let reviewed = paths
+        .into_iter()
+        .map(|path| ReviewedChange::capture(root, path))
+        .collect::<io::Result<Vec<_>>>()?;
+    let json = serde_json::to_vec(&reviewed).map_err(io::Error::other)?;
+    fs::write(&root.reviewed, json)
+}
+
+/// The changes recorded by the last [`record_reviewed`], keyed by path.
+fn read_reviewed(root: &OverlayRoot) -> io::Result<HashMap<PathBuf, ReviewedChange>> {
+    let json = match fs::read(&root.reviewed) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(err),
+    };
+    let reviewed: Vec<ReviewedChange> = serde_json::from_slice(&json).map_err(io::Error::other)?;
+    Ok(reviewed
+        .into_iter()
+        .map(|change| (change.path.clone(), change))
+        .collect())
+}
+
+/// Where the overlay view of `path` is stored: the upper directory when it
+/// shadows `path`, `path` itself otherwise, or `None` when the overlay deletes
+/// it.
+pub(crate) fn overlay_content_path(root: &OverlayRoot, path: &Path) -> Option<PathBuf> {
+    let Ok(relative) = path.strip_prefix(&root.workspace) else {
+        return Some(path.to_path_buf());
+    };
+    let mut upper = root.upper.clone();
+    let mut lower_hidden = false;
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        upper.push(component);
+        let Ok(metadata) = fs::symlink_metadata(&upper) else {
+            // Nothing in the upper layer shadows `path`, so the lower layer
+            // shows through unless an opaque ancestor hides it.
+            return (!lower_hidden).then(|| path.to_path_buf());
+        };
+        if is_whiteout(&metadata) {
+            return None;
+        }
+        if components.peek().is_some() {
+            if !metadata.is_dir() {
+                return None;
+            }
+            lower_hidden |= is_opaque_dir(&upper);
+        }
+    }
+    Some(upper)
+}
+
+/// The counterpart of the workspace path `path` in the upper directory of
+/// `root`, or `None` when `path` is outside the workspace.
+pub(crate) fn upper_path(root: &OverlayRoot, path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(&root.workspace).ok()?;
+    Some(root.upper.join(relative))
+}
+
+/// Maps a file found by walking both the workspace and the upper directory
+/// of `root` to the workspace path it shows up as, or `None` when the overlay
+/// replaces or deletes it.
+pub(crate) fn overlay_view_path(root: &OverlayRoot, found: &Path) -> Option<PathBuf> {
+    if let Ok(relative) = found.strip_prefix(&root.upper) {
+        return Some(root.workspace.join(relative));
+    }
+    (overlay_content_path(root, found).as_deref() == Some(found)).then(|| found.to_path_buf())
+}
+
+/// Entries of the directory `dir` as seen through the overlay of `root`, as
+/// pairs of the entry's workspace path and the path holding its contents.
+pub(crate) fn overlay_read_dir(
+    root: &OverlayRoot,
+    dir: &Path,
+) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let is_dir = overlay_content_path(root, dir).is_some_and(|content_dir| {
+        fs::metadata(content_dir).is_ok_and(|metadata| metadata.is_dir())
+    });
+    if !is_dir {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not a directory", dir.display()),
+        ));
+    }
+    let mut names = Vec::new();
+    for listed in [Some(dir.to_path_buf()), upper_path(root, dir)]
+        .into_iter()
+        .flatten()
+    {
+        let entries = match fs::read_dir(&listed) {
+            Ok(entries) => entries,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::NotADirectory
+                ) =>
+            {
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            names.push(entry?.file_name());
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names
+        .into_iter()
+        .filter_map(|name| {
+            let path = dir.join(name);
+            overlay_content_path(root, &path).map(|content_path| (path, content_path))
+        })
+        .collect())
+}
+
+/// Walks the upper directory of `root` and describes every path whose overlay
+/// view differs from the workspace.
+fn collect_changes(root: &OverlayRoot) -> io::Result<HashMap<PathBuf, FileChange>> {
+    let mut changes = HashMap::new();
+    collect_dir_changes(root, &root.upper, &root.workspace, &mut changes)?;
+    Ok(changes)
+}
+
+fn collect_dir_changes(
+    root: &OverlayRoot,
+    upper_dir: &Path,
+    workspace_dir: &Path,
+    changes: &mut HashMap<PathBuf, FileChange>,
+) -> io::Result<()> {
+    if upper_dir != root.upper && is_opaque_dir(upper_dir) {
+        // An opaque directory hides everything in the lower layer, so lower
+        // files without an upper counterpart are deleted.
+        for lower in list_files(workspace_dir)? {
+            let Ok(relative) = lower.strip_prefix(workspace_dir) else {
+                continue;
+            };
+            if fs::symlink_metadata(upper_dir.join(relative)).is_err() {
+                insert_delete(&lower, changes);
+            }
+        }
+    }
+
+    for entry in fs::read_dir(upper_dir)? {
+        let entry = entry?;
+        let upper_path = entry.path();
+        let workspace_path = workspace_dir.join(entry.file_name());
+        let metadata = fs::symlink_metadata(&upper_path)?;
+        let lower_metadata = fs::symlink_metadata(&workspace_path).ok();
+        let lower_is_dir = lower_metadata.as_ref().is_some_and(fs::Metadata::is_dir);
+
+        if is_whiteout(&metadata) {
+            for lower in list_files(&workspace_path)? {
+                insert_delete(&lower, changes);
+            }
+            continue;
+        }
+
+        if metadata.is_dir() {
+            if lower_metadata.is_some() && !lower_is_dir {
+                insert_delete(&workspace_path, changes);
+            }
+            collect_dir_changes(root, &upper_path, &workspace_path, changes)?;
+            continue;
+        }
+
+        if lower_is_dir {
+            for lower in list_files(&workspace_path)? {
+                insert_delete(&lower, changes);
+            }
+        }
+        if let Some(change) = describe_write(&upper_path, &workspace_path)? {
+            changes.insert(workspace_path, change);
+        }
+    }
+    Ok(())
+}
+
+fn insert_delete(path: &Path, changes: &mut HashMap<PathBuf, FileChange>) {
+    let content = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => fs::read_link(path)
+            .map(|target| target.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        _ => fs::read(path)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default(),
+    };
+    changes.insert(path.to_path_buf(), FileChange::Delete { content });
+}
+
+/// Describes the file at `upper_path` as a change to `workspace_path`, or
+/// returns `None` when both have the same contents and mode.
+fn describe_write(upper_path: &Path, workspace_path: &Path) -> io::Result<Option<FileChange>> {
+    let metadata = fs::symlink_metadata(upper_path)?;
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(upper_path)?;
+        if fs::read_link(workspace_path).ok().as_ref() == Some(&target) {
+            return Ok(None);
+        }
+        return Ok(Some(FileChange::Symlink { target }));
+    }
+    if !metadata.is_file() {
+        return Ok(None);
+    }
+
+    let new_bytes = fs::read(upper_path)?;
+    let new_mode = file_mode(&metadata);
+    let lower = fs::symlink_metadata(workspace_path)
+        .ok()
+        .filter(fs::Metadata::is_file)
+        .map(|lower_metadata| {
+            fs::read(workspace_path).map(|bytes| (bytes, file_mode(&lower_metadata)))
+        })
+        .transpose()?;
+
+    let Some((old_bytes, old_mode)) = lower else {
+        return Ok(Some(match String::from_utf8(new_bytes) {
+            Ok(content) => FileChange::Add {
+                content,
+                mode: new_mode.filter(|mode| mode & 0o111 != 0),
+            },
+            Err(err) => FileChange::Binary {
+                size: err.into_bytes().len(),
+                replace: false,
+                mode: new_mode.filter(|mode| mode & 0o111 != 0),
+            },
+        }));
+    };
+
+    if old_bytes == new_bytes && old_mode == new_mode {
+        return Ok(None);
+    }
+    let mode = new_mode.filter(|_| old_mode != new_mode);
+    Ok(Some(
+        match (
+            std::str::from_utf8(&old_bytes),
+            std::str::from_utf8(&new_bytes),
+        ) {
+            (Ok(old), Ok(new)) => FileChange::Update {
+                unified_diff: similar::TextDiff::from_lines(old, new)
+                    .unified_diff()
+                    .context_radius(1)
+                    .to_string(),
+                move_path: None,
+                mode,
+            },
+            _ => FileChange::Binary {
+                size: new_bytes.len(),
+                replace: true,
+                mode,
+            },
+        },
+    ))
+}
+
+/// Applies an accepted change from the overlay of `root` to `path` in the
+/// workspace.
+fn merge_change(root: &OverlayRoot, path: &Path, change: &FileChange) -> io::Result<()> {
+    if let FileChange::Delete { .. } = change {
+        remove_path(path)?;
+        prune_empty_parents(path, &root.workspace);
+        return Ok(());
+    }
+
+    let Some(source) = overlay_content_path(root, path) else {
+        return Ok(());
+    };
+    let lower_is_file = fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_file());
+    if lower_is_file && fs::symlink_metadata(&source).is_ok_and(|metadata| metadata.is_file()) {
+        // Rewrite the file in place so it keeps its owner, permissions and
+        // hard links; only a mode change shown in the review is applied.
+        fs::write(path, fs::read(&source)?)?;
+        return match change {
+            FileChange::Update {
+                mode: Some(mode), ..
+            }
+            | FileChange::Binary {
+                mode: Some(mode), ..
+            } => set_file_mode(path, *mode),
+            _ => Ok(()),
+        };
+    }
+    if fs::symlink_metadata(path).is_ok() {
+        remove_path(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if let FileChange::Symlink { target } = change {
+        return create_symlink(target, path);
+    }
+    fs::copy(&source, path).map(|_| ())
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Removes directories left empty by a delete, stopping at `workspace`.
+fn prune_empty_parents(path: &Path, workspace: &Path) {
+    let mut current = path.parent();
+    while let Some(dir) = current {
+        if dir == workspace || !dir.starts_with(workspace) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+/// Empties `dir`, keeping the directory itself.
+fn clear_dir(dir: &Path) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        #[cfg(unix)]
+        {
+            // overlayfs leaves `work/work` with mode 000.
+            use std::os::unix::fs::PermissionsExt;
+            if let Ok(metadata) = fs::symlink_metadata(&path)
+                && metadata.is_dir()
+            {
+                let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o700));
+            }
+        }
+        remove_path(&path)?;
+    }
+    Ok(())
+}
+
+/// Regular files and symlinks under `path`, or `path` itself when it is not a
+/// directory.
+fn list_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        files.extend(list_files(&entry?.path())?);
+    }
+    Ok(files)
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn create_symlink(_target: &Path, path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("cannot create symlink {}", path.display()),
+    ))
+}
+
+/// overlayfs marks deleted lower files with a 0/0 character device.
+#[cfg(unix)]
+fn is_whiteout(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+    metadata.file_type().is_char_device() && metadata.rdev() == 0
+}
+
+#[cfg(not(unix))]
+fn is_whiteout(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// Whether overlayfs marked `dir` opaque, hiding the lower directory. bwrap
+/// mounts overlays with `userxattr`, so the marker is `user.overlay.opaque`.
+#[cfg(target_os = "linux")]
+fn is_opaque_dir(dir: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut value = [0u8; 1];
+    let len = unsafe {
+        libc::lgetxattr(
+            path.as_ptr(),
+            c"user.overlay.opaque".as_ptr(),
+            value.as_mut_ptr().cast(),
+            value.len(),
+        )
+    };
+    len == 1 && value[0] == b'y'
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_opaque_dir(_dir: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+#[path = "workspace_overlay_tests.rs"]
+mod tests;
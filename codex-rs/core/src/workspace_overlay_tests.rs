@@ -0,0 +1,276 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+struct Fixture {
+    _codex_home: TempDir,
+    workspace: TempDir,
+    overlay: WorkspaceOverlay,
+    upper: PathBuf,
+}
+
+fn fixture() -> Fixture {
+    let codex_home = TempDir::new().expect("codex home");
+    let workspace = TempDir::new().expect("workspace");
+    let overlay = WorkspaceOverlay::new(codex_home.path(), ThreadId::new());
+    let upper = overlay
+        .prepare(workspace.path())
+        .expect("prepare overlay")
+        .join(UPPER_DIR);
+    Fixture {
+        _codex_home: codex_home,
+        workspace,
+        overlay,
+        upper,
+    }
+}
+
+#[test]
+fn no_pending_changes_for_an_empty_overlay() {
+    let fixture = fixture();
+    fs::write(fixture.workspace.path().join("a.txt"), "one\n").expect("write a.txt");
+
+    assert!(
+        fixture
+            .overlay
+            .pending_changes()
+            .expect("pending changes")
+            .is_none()
+    );
+}
+
+#[test]
+fn pending_changes_describe_added_and_updated_files() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    fs::write(fixture.upper.join("b.txt"), "new\n").expect("write upper b.txt");
+
+    let event = fixture
+        .overlay
+        .pending_changes()
+        .expect("pending changes")
+        .expect("changes");
+
+    assert_eq!(event.changes.len(), 2);
+    assert!(matches!(
+        event.changes.get(&workspace.join("a.txt")),
+        Some(FileChange::Update { .. })
+    ));
+    assert!(matches!(
+        event.changes.get(&workspace.join("b.txt")),
+        Some(FileChange::Add { content, .. }) if content == "new\n"
+    ));
+    assert!(event.unified_diff.contains("-one\n+two\n"));
+    assert!(event.unified_diff.contains("+new\n"));
+}
+
+#[test]
+fn resolve_merges_accepted_paths_and_discards_the_rest() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    fs::create_dir(fixture.upper.join("src")).expect("create upper src");
+    fs::write(fixture.upper.join("src").join("b.txt"), "new\n").expect("write upper b.txt");
+    fixture.overlay.pending_changes().expect("pending changes");
+
+    let resolved = fixture
+        .overlay
+        .resolve(&WorkspaceOverlayDecision::AcceptPaths {
+            paths: vec![workspace.join("a.txt")],
+        })
+        .expect("resolve");
+
+    assert_eq!(resolved.accepted, vec![workspace.join("a.txt")]);
+    assert_eq!(resolved.rejected, vec![workspace.join("src").join("b.txt")]);
+    assert_eq!(resolved.conflicts, Vec::<PathBuf>::new());
+    assert_eq!(
+        fs::read_to_string(workspace.join("a.txt")).expect("read a.txt"),
+        "two\n"
+    );
+    assert!(!workspace.join("src").exists());
+    assert_eq!(
+        list_files(&fixture.upper).expect("list upper"),
+        Vec::<PathBuf>::new()
+    );
+    assert!(
+        fixture
+            .overlay
+            .pending_changes()
+            .expect("pending changes")
+            .is_none()
+    );
+}
+
+#[test]
+fn reject_all_leaves_the_workspace_untouched() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+
+    let resolved = fixture
+        .overlay
+        .resolve(&WorkspaceOverlayDecision::RejectAll)
+        .expect("resolve");
+
+    assert_eq!(resolved.rejected, vec![workspace.join("a.txt")]);
+    assert_eq!(
+        fs::read_to_string(workspace.join("a.txt")).expect("read a.txt"),
+        "one\n"
+    );
+}
+
+#[test]
+fn changes_that_moved_after_review_are_reported_as_conflicts() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    fs::write(fixture.upper.join("b.txt"), "new\n").expect("write upper b.txt");
+    fixture.overlay.pending_changes().expect("pending changes");
+
+    fs::write(workspace.join("a.txt"), "edited by the user\n").expect("edit a.txt");
+    fs::write(fixture.upper.join("c.txt"), "unseen\n").expect("write upper c.txt");
+
+    let resolved = fixture
+        .overlay
+        .resolve(&WorkspaceOverlayDecision::AcceptAll)
+        .expect("resolve");
+
+    assert_eq!(resolved.accepted, vec![workspace.join("b.txt")]);
+    assert_eq!(
+        resolved.conflicts,
+        vec![workspace.join("a.txt"), workspace.join("c.txt")]
+    );
+    assert_eq!(
+        fs::read_to_string(workspace.join("a.txt")).expect("read a.txt"),
+        "edited by the user\n"
+    );
+    assert!(!workspace.join("c.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn merged_updates_keep_the_workspace_file_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::set_permissions(workspace.join("a.txt"), fs::Permissions::from_mode(0o640))
+        .expect("chmod a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    fs::set_permissions(
+        fixture.upper.join("a.txt"),
+        fs::Permissions::from_mode(0o640),
+    )
+    .expect("chmod upper a.txt");
+    fs::hard_link(workspace.join("a.txt"), workspace.join("link.txt")).expect("hard link");
+    fixture.overlay.pending_changes().expect("pending changes");
+
+    fixture
+        .overlay
+        .resolve(&WorkspaceOverlayDecision::AcceptAll)
+        .expect("resolve");
+
+    let metadata = fs::metadata(workspace.join("a.txt")).expect("stat a.txt");
+    assert_eq!(metadata.permissions().mode() & 0o7777, 0o640);
+    assert_eq!(
+        fs::read_to_string(workspace.join("link.txt")).expect("read link.txt"),
+        "two\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn whiteouts_are_reported_and_merged_as_deletes() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("gone.txt"), "bye\n").expect("write gone.txt");
+    let whiteout = std::ffi::CString::new(fixture.upper.join("gone.txt").as_os_str().as_bytes())
+        .expect("whiteout path");
+    if unsafe { libc::mknod(whiteout.as_ptr(), libc::S_IFCHR | 0o600, 0) } != 0 {
+        // Creating device nodes needs privileges that CI sandboxes may lack.
+        return;
+    }
+
+    let event = fixture
+        .overlay
+        .pending_changes()
+        .expect("pending changes")
+        .expect("changes");
+    assert!(matches!(
+        event.changes.get(&workspace.join("gone.txt")),
+        Some(FileChange::Delete { content }) if content == "bye\n"
+    ));
+    assert_eq!(
+        overlay_content_path(
+            &fixture.overlay.roots().expect("roots")[0],
+            &workspace.join("gone.txt"),
+        ),
+        None
+    );
+
+    fixture
+        .overlay
+        .resolve(&WorkspaceOverlayDecision::AcceptAll)
+        .expect("resolve");
+    assert!(!workspace.join("gone.txt").exists());
+}
+
+#[test]
+fn patches_after_a_command_write_are_verified_against_the_overlay() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    // A sandboxed command rewrote a.txt, so only the overlay has "two".
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    let root = fixture.overlay.root(workspace);
+    let argv = vec![
+        "apply_patch".to_string(),
+        "*** Begin Patch\n*** Update File: a.txt\n@@\n-two\n+three\n*** End Patch".to_string(),
+    ];
+
+    let action = match codex_apply_patch::maybe_parse_apply_patch_verified_with_view(
+        &argv,
+        workspace,
+        &|path| overlay_content_path(&root, path),
+    ) {
+        codex_apply_patch::MaybeApplyPatchVerified::Body(action) => action,
+        other => panic!("expected a verified patch, got {other:?}"),
+    };
+    assert!(matches!(
+        action.changes().get(&workspace.join("a.txt")),
+        Some(codex_apply_patch::ApplyPatchFileChange::Update { new_content, .. })
+            if new_content == "three\n"
+    ));
+}
+
+#[test]
+fn reads_see_files_written_to_the_overlay() {
+    let fixture = fixture();
+    let workspace = fixture.workspace.path();
+    fs::write(workspace.join("a.txt"), "one\n").expect("write a.txt");
+    fs::write(fixture.upper.join("a.txt"), "two\n").expect("write upper a.txt");
+    fs::write(fixture.upper.join("b.txt"), "new\n").expect("write upper b.txt");
+    let root = fixture.overlay.root(workspace);
+
+    let entries = overlay_read_dir(&root, workspace).expect("read dir");
+    assert_eq!(
+        entries,
+        vec![
+            (workspace.join("a.txt"), fixture.upper.join("a.txt")),
+            (workspace.join("b.txt"), fixture.upper.join("b.txt")),
+        ]
+    );
+    assert_eq!(
+        overlay_view_path(&root, &fixture.upper.join("b.txt")),
+        Some(workspace.join("b.txt"))
+    );
+    assert_eq!(overlay_view_path(&root, &workspace.join("a.txt")), None);
+}
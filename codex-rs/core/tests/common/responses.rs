@@ -562,6 +562,17 @@ pub fn sse(events: Vec<Value>) -> String {
     out
 }
 
+/// Build a Chat Completions SSE stream body from a list of chunks, ending with `[DONE]`.
+pub fn chat_sse(chunks: Vec<Value>) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::new();
+    for chunk in chunks {
+        write!(&mut out, "data: {chunk}\n\n").unwrap();
+    }
+    out.push_str("data: [DONE]\n\n");
+    out
+}
+
 pub fn sse_completed(id: &str) -> String {
     sse(vec![ev_response_created(id), ev_completed(id)])
 }
@@ -0,0 +1,219 @@
+//! Verifies that providers configured with `wire_api = "anthropic_messages"` are driven
+//! through `/v1/messages`, including headers, thinking blocks and a tool use round trip.
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn message_start(id: &str) -> Value {
+    json!({
+        "type": "message_start",
+        "message": {
+            "id": id,
+            "type": "message",
+            "role": "assistant",
+            "content": [],
+            "usage": { "input_tokens": 12, "cache_read_input_tokens": 30, "output_tokens": 1 },
+        },
+    })
+}
+
+fn message_stop(output_tokens: i64) -> [Value; 2] {
+    [
+        json!({
+            "type": "message_delta",
+            "delta": { "stop_reason": "end_turn" },
+            "usage": { "output_tokens": output_tokens },
+        }),
+        json!({ "type": "message_stop" }),
+    ]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn anthropic_messages_wire_api_round_trips_tool_use() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let tool_use_id = "toolu_plan";
+    let plan_input = json!({
+        "plan": [{ "step": "Say hello", "status": "in_progress" }],
+    });
+    let plan_json = plan_input.to_string();
+    let (first_json, second_json) = plan_json.split_at(plan_json.len() / 2);
+
+    let mut first = vec![
+        message_start("msg_1"),
+        json!({ "type": "content_block_start", "index": 0,
+                "content_block": { "type": "thinking", "thinking": "" } }),
+        json!({ "type": "content_block_delta", "index": 0,
+                "delta": { "type": "thinking_delta", "thinking": "I should plan." } }),
+        json!({ "type": "content_block_delta", "index": 0,
+                "delta": { "type": "signature_delta", "signature": "sig-1" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+        json!({ "type": "content_block_start", "index": 1,
+                "content_block": { "type": "tool_use", "id": tool_use_id, "name": "update_plan", "input": {} } }),
+        json!({ "type": "content_block_delta", "index": 1,
+                "delta": { "type": "input_json_delta", "partial_json": first_json } }),
+        json!({ "type": "content_block_delta", "index": 1,
+                "delta": { "type": "input_json_delta", "partial_json": second_json } }),
+        json!({ "type": "content_block_stop", "index": 1 }),
+    ];
+    first.extend(message_stop(20));
+    let mut second = vec![
+        message_start("msg_2"),
+        json!({ "type": "content_block_start", "index": 0,
+                "content_block": { "type": "text", "text": "" } }),
+        json!({ "type": "content_block_delta", "index": 0,
+                "delta": { "type": "text_delta", "text": "Hello from Messages" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+    ];
+    second.extend(message_stop(4));
+    for events in [first, second] {
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(header("x-api-key", "test-key"))
+            .and(header("anthropic-version", "2023-06-01"))
+            .and(header("anthropic-beta", "prompt-caching-2024-07-31"))
+            .respond_with(sse_response(sse(events)))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+    }
+
+    let model_provider = ModelProviderInfo {
+        name: "anthropic".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        env_key_instructions: None,
+        experimental_bearer_token: Some("test-key".into()),
+        wire_api: WireApi::AnthropicMessages,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2000),
+        max_output_tokens: None,
+        requires_openai_auth: false,
+        supports_websockets: false,
+    };
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = model_provider;
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "make a plan".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let mut saw_plan_update = false;
+    let mut agent_message = None;
+    wait_for_event(&codex, |event| match event {
+        EventMsg::PlanUpdate(_) => {
+            saw_plan_update = true;
+            false
+        }
+        EventMsg::AgentMessage(message) => {
+            agent_message = Some(message.message.clone());
+            false
+        }
+        EventMsg::TurnComplete(_) => true,
+        _ => false,
+    })
+    .await;
+    assert!(saw_plan_update, "expected PlanUpdate event");
+    assert_eq!(agent_message.as_deref(), Some("Hello from Messages"));
+
+    let requests = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|request| request.url.path() == "/v1/messages")
+        .map(|request| serde_json::from_slice::<Value>(&request.body))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(requests.len(), 2);
+
+    let first = &requests[0];
+    assert_eq!(first["stream"], json!(true));
+    assert_eq!(
+        first["system"][0]["cache_control"],
+        json!({ "type": "ephemeral" })
+    );
+    let tools = first["tools"].as_array().expect("tools array");
+    assert!(
+        tools
+            .iter()
+            .any(|tool| tool["name"] == json!("update_plan"))
+    );
+    assert_eq!(
+        tools.last().map(|tool| &tool["cache_control"]),
+        Some(&json!({ "type": "ephemeral" }))
+    );
+
+    let messages = requests[1]["messages"].as_array().expect("messages array");
+    let assistant = messages
+        .iter()
+        .rev()
+        .find(|message| message["role"] == json!("assistant"))
+        .expect("assistant turn");
+    assert_eq!(
+        assistant["content"],
+        json!([
+            { "type": "thinking", "thinking": "I should plan.", "signature": "sig-1" },
+            { "type": "tool_use", "id": tool_use_id, "name": "update_plan", "input": plan_input },
+        ])
+    );
+    let last_message = messages.last().expect("tool result turn");
+    assert_eq!(last_message["role"], json!("user"));
+    assert_eq!(
+        last_message["content"],
+        json!([{
+            "type": "tool_result",
+            "tool_use_id": tool_use_id,
+            "content": "Plan updated",
+            "cache_control": { "type": "ephemeral" },
+        }])
+    );
+
+    Ok(())
+}
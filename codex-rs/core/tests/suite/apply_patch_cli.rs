@@ -583,6 +583,7 @@ async fn apply_patch_cli_rejects_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     };
     harness
         .submit_with_policy(
@@ -640,6 +641,7 @@ async fn apply_patch_cli_rejects_move_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     };
     harness
         .submit_with_policy("attempt move traversal via apply_patch", sandbox_policy)
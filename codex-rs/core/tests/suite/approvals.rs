@@ -682,6 +682,7 @@ fn scenarios() -> Vec<ScenarioSpec> {
         network_access,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        denied_read_paths: Vec::new(),
     };
 
     vec![
@@ -1703,6 +1704,7 @@ async fn approving_apply_patch_for_session_skips_future_prompts_for_same_file()
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        denied_read_paths: Vec::new(),
     };
     let sandbox_policy_for_config = sandbox_policy.clone();
 
@@ -2165,6 +2167,7 @@ allow_local_binding = true
         network_access: true,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        denied_read_paths: Vec::new(),
     };
     let sandbox_policy_for_config = sandbox_policy.clone();
     let mut builder = test_codex().with_home(home).with_config(move |config| {
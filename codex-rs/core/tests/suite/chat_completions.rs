@@ -0,0 +1,192 @@
+//! Verifies that providers configured with `wire_api = "chat"` are driven through
+//! `/v1/chat/completions`, including a tool call round trip.
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::chat_sse;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn chat_chunk(delta: Value, finish_reason: Option<&str>) -> Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn chat_wire_api_round_trips_tool_calls() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let call_id = "call_plan";
+    let plan_args = json!({
+        "plan": [{ "step": "Say hello", "status": "in_progress" }],
+    })
+    .to_string();
+    let (first_args, second_args) = plan_args.split_at(plan_args.len() / 2);
+
+    let bodies = [
+        chat_sse(vec![
+            chat_chunk(
+                json!({
+                    "role": "assistant",
+                    "tool_calls": [{
+                        "index": 0,
+                        "id": call_id,
+                        "type": "function",
+                        "function": { "name": "update_plan", "arguments": first_args },
+                    }],
+                }),
+                None,
+            ),
+            chat_chunk(
+                json!({
+                    "tool_calls": [{ "index": 0, "function": { "arguments": second_args } }],
+                }),
+                None,
+            ),
+            chat_chunk(json!({}), Some("tool_calls")),
+        ]),
+        chat_sse(vec![
+            chat_chunk(json!({ "role": "assistant", "content": "Hello " }), None),
+            chat_chunk(json!({ "content": "from chat" }), None),
+            chat_chunk(json!({}), Some("stop")),
+            json!({
+                "id": "chatcmpl-1",
+                "choices": [],
+                "usage": { "prompt_tokens": 20, "completion_tokens": 3, "total_tokens": 23 },
+            }),
+        ]),
+    ];
+    for body in bodies {
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(sse_response(body))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+    }
+
+    let model_provider = ModelProviderInfo {
+        name: "chat-provider".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: Some("PATH".into()),
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        wire_api: WireApi::Chat,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2000),
+        max_output_tokens: None,
+        requires_openai_auth: false,
+        supports_websockets: false,
+    };
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = model_provider;
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "make a plan".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let mut saw_plan_update = false;
+    let mut agent_message = None;
+    wait_for_event(&codex, |event| match event {
+        EventMsg::PlanUpdate(_) => {
+            saw_plan_update = true;
+            false
+        }
+        EventMsg::AgentMessage(message) => {
+            agent_message = Some(message.message.clone());
+            false
+        }
+        EventMsg::TurnComplete(_) => true,
+        _ => false,
+    })
+    .await;
+    assert!(saw_plan_update, "expected PlanUpdate event");
+    assert_eq!(agent_message.as_deref(), Some("Hello from chat"));
+
+    let requests = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|request| request.url.path() == "/v1/chat/completions")
+        .map(|request| serde_json::from_slice::<Value>(&request.body))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(requests.len(), 2);
+
+    let first = &requests[0];
+    assert_eq!(first["stream"], json!(true));
+    assert_eq!(first["messages"][0]["role"], json!("system"));
+    let tools = first["tools"].as_array().expect("tools array");
+    assert!(tools.iter().all(|tool| tool["type"] == json!("function")));
+    assert!(
+        tools
+            .iter()
+            .any(|tool| tool["function"]["name"] == json!("update_plan"))
+    );
+
+    let messages = requests[1]["messages"].as_array().expect("messages array");
+    let tool_call_message = messages
+        .iter()
+        .find(|message| message.get("tool_calls").is_some())
+        .expect("assistant tool call message");
+    assert_eq!(
+        tool_call_message["tool_calls"],
+        json!([{
+            "id": call_id,
+            "type": "function",
+            "function": { "name": "update_plan", "arguments": plan_args },
+        }])
+    );
+    assert_eq!(
+        messages.last(),
+        Some(&json!({ "role": "tool", "tool_call_id": call_id, "content": "Plan updated" }))
+    );
+
+    Ok(())
+}
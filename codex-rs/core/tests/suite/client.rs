@@ -1604,6 +1604,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -2203,6 +2204,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -2287,6 +2289,7 @@ async fn env_var_overrides_loaded_auth() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
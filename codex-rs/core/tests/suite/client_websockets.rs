@@ -1456,6 +1456,7 @@ fn websocket_provider(server: &WebSocketTestServer) -> ModelProviderInfo {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: true,
     }
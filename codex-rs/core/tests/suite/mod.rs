@@ -58,10 +58,12 @@ pub static CODEX_ALIASES_TEMP_DIR: TestCodexAliasesGuard = unsafe {
 mod abort_tasks;
 mod agent_jobs;
 mod agent_websocket;
+mod anthropic_messages;
 mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
 mod auth_refresh;
+mod chat_completions;
 mod cli_stream;
 mod client;
 mod client_websockets;
@@ -403,6 +403,7 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
         network_access: true,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     };
     codex
         .submit(Op::OverrideTurnContext {
@@ -667,6 +668,7 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() -> anyhow::Res
         network_access: true,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     };
     codex
         .submit(Op::UserTurn {
@@ -159,6 +159,7 @@ fn workspace_write_excluding_tmp() -> SandboxPolicy {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     }
 }
 
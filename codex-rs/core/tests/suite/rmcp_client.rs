@@ -105,6 +105,7 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
@@ -248,6 +249,7 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
@@ -467,6 +469,7 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
@@ -586,6 +589,7 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
@@ -670,6 +674,91 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_prompts_are_listed_and_fetched() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    oauth_resource: None,
+                    sampling_token_limit: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture.codex.submit(Op::ListMcpPrompts).await?;
+    let list_event = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpListPromptsResponse(_))
+    })
+    .await;
+    let EventMsg::McpListPromptsResponse(list) = list_event else {
+        unreachable!("event guard guarantees McpListPromptsResponse");
+    };
+    let prompt_names: Vec<&str> = list
+        .prompts
+        .get(server_name)
+        .into_iter()
+        .flatten()
+        .map(|prompt| prompt.name.as_str())
+        .collect();
+    assert_eq!(prompt_names, vec!["summarize"]);
+
+    fixture
+        .codex
+        .submit(Op::GetMcpPrompt {
+            server: server_name.to_string(),
+            name: "summarize".to_string(),
+            arguments: HashMap::from([
+                ("topic".to_string(), "MCP prompts".to_string()),
+                ("audience".to_string(), "reviewers".to_string()),
+            ]),
+        })
+        .await?;
+    let get_event = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::McpGetPromptResponse(_))
+    })
+    .await;
+    let EventMsg::McpGetPromptResponse(response) = get_event else {
+        unreachable!("event guard guarantees McpGetPromptResponse");
+    };
+    assert_eq!(
+        response.result,
+        Ok("Summarize MCP prompts for reviewers.".to_string())
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -746,6 +835,7 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
@@ -966,6 +1056,7 @@ async fn streamable_http_with_oauth_round_trip_impl() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_limit: None,
                 },
             );
             config
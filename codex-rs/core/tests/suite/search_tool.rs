@@ -134,6 +134,7 @@ fn rmcp_server_config(command: String) -> McpServerConfig {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_limit: None,
     }
 }
 
@@ -175,6 +175,7 @@ fn restrictive_workspace_write_policy() -> SandboxPolicy {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        denied_read_paths: Vec::new(),
     }
 }
 
@@ -51,6 +51,7 @@ fn extract_output_text(item: &Value) -> Option<&str> {
 struct ParsedUnifiedExecOutput {
     chunk_id: Option<String>,
     wall_time_seconds: f64,
+    wait_outcome: Option<String>,
     process_id: Option<String>,
     exit_code: Option<i32>,
     original_token_count: Option<usize>,
@@ -65,6 +66,7 @@ fn parse_unified_exec_output(raw: &str) -> Result<ParsedUnifiedExecOutput> {
             r#"(?s)^(?:Total output lines: \d+\n\n)?"#,
             r#"(?:Chunk ID: (?P<chunk_id>[^\n]+)\n)?"#,
             r#"Wall time: (?P<wall_time>-?\d+(?:\.\d+)?) seconds\n"#,
+            r#"(?:Wait ended: (?P<wait_outcome>[^\n]+)\n)?"#,
             r#"(?:Process exited with code (?P<exit_code>-?\d+)\n)?"#,
             r#"(?:Process running with session ID (?P<process_id>-?\d+)\n)?"#,
             r#"(?:Original token count: (?P<original_token_count>\d+)\n)?"#,
@@ -89,6 +91,10 @@ fn parse_unified_exec_output(raw: &str) -> Result<ParsedUnifiedExecOutput> {
         .parse::<f64>()
         .context("failed to parse wall time seconds")?;
 
+    let wait_outcome = captures
+        .name("wait_outcome")
+        .map(|value| value.as_str().to_string());
+
     let exit_code = captures
         .name("exit_code")
         .map(|value| {
@@ -122,6 +128,7 @@ fn parse_unified_exec_output(raw: &str) -> Result<ParsedUnifiedExecOutput> {
     Ok(ParsedUnifiedExecOutput {
         chunk_id,
         wall_time_seconds,
+        wait_outcome,
         process_id,
         exit_code,
         original_token_count,
@@ -2400,6 +2407,127 @@ async fn unified_exec_timeout_and_followup_poll() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unified_exec_waits_for_pattern_then_exit() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_sandbox!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::UnifiedExec);
+    });
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = builder.build(&server).await?;
+
+    let first_call_id = "uexec-until-pattern";
+    let first_args = serde_json::json!({
+        "cmd": "echo booting; sleep 0.3; echo 'server ready'; sleep 1; echo done",
+        "yield_time_ms": 20_000,
+        "until_pattern": "server ready",
+    });
+
+    let second_call_id = "uexec-until-exit";
+    let second_args = serde_json::json!({
+        "chars": "",
+        "session_id": 1000,
+        "yield_time_ms": 20_000,
+        "until_exit": true,
+    });
+
+    let responses = vec![
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call(
+                first_call_id,
+                "exec_command",
+                &serde_json::to_string(&first_args)?,
+            ),
+            ev_completed("resp-1"),
+        ]),
+        sse(vec![
+            ev_response_created("resp-2"),
+            ev_function_call(
+                second_call_id,
+                "write_stdin",
+                &serde_json::to_string(&second_args)?,
+            ),
+            ev_completed("resp-2"),
+        ]),
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-3"),
+        ]),
+    ];
+    let request_log = mount_sse_sequence(&server, responses).await;
+
+    let session_model = session_configured.model.clone();
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "wait for the server".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_model,
+            effort: None,
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let bodies = request_log
+        .requests()
+        .into_iter()
+        .map(|request| request.body_json())
+        .collect::<Vec<_>>();
+    let outputs = collect_tool_outputs(&bodies)?;
+
+    let first_output = outputs.get(first_call_id).expect("missing pattern output");
+    assert_eq!(
+        first_output.wait_outcome.as_deref(),
+        Some("pattern matched")
+    );
+    assert_eq!(first_output.process_id.as_deref(), Some("1000"));
+    assert!(
+        first_output.wall_time_seconds < 10.0,
+        "pattern wait should return early, took {}s",
+        first_output.wall_time_seconds
+    );
+    assert!(
+        first_output.output.contains("server ready"),
+        "expected ready line, got {:?}",
+        first_output.output
+    );
+
+    let second_output = outputs.get(second_call_id).expect("missing exit output");
+    assert_eq!(
+        second_output.wait_outcome.as_deref(),
+        Some("process exited")
+    );
+    assert_eq!(second_output.exit_code, Some(0));
+    assert!(second_output.process_id.is_none());
+    assert!(
+        second_output.output.contains("done"),
+        "expected trailing output, got {:?}",
+        second_output.output
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 // Skipped on arm because the ctor logic to handle arg0 doesn't work on ARM
 #[cfg(not(target_arch = "arm"))]
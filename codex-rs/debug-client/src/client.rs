@@ -182,6 +182,7 @@ impl AppServerClient {
                 archived: None,
                 cwd: None,
                 search_term: None,
+                query: None,
             },
         };
         self.send(&request)?;
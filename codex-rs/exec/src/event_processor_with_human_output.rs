@@ -34,6 +34,8 @@ use codex_protocol::protocol::TurnCompleteEvent;
 use codex_protocol::protocol::TurnDiffEvent;
 use codex_protocol::protocol::WarningEvent;
 use codex_protocol::protocol::WebSearchEndEvent;
+use codex_protocol::protocol::WorkspaceOverlayChangesEvent;
+use codex_protocol::protocol::WorkspaceOverlayResolvedEvent;
 use codex_utils_elapsed::format_duration;
 use codex_utils_elapsed::format_elapsed;
 use owo_colors::OwoColorize;
@@ -279,6 +281,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{} {}", "mcp startup:".style(self.cyan), joined);
             }
+            EventMsg::McpToolsChanged(ev) => {
+                let mut parts = Vec::new();
+                if !ev.added.is_empty() {
+                    parts.push(format!("added: {}", ev.added.join(", ")));
+                }
+                if !ev.removed.is_empty() {
+                    parts.push(format!("removed: {}", ev.removed.join(", ")));
+                }
+                ts_msg!(
+                    self,
+                    "{} {} tools changed ({})",
+                    "mcp:".style(self.cyan),
+                    ev.server,
+                    parts.join("; ")
+                );
+            }
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
@@ -432,6 +450,29 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     }
                 }
             }
+            EventMsg::McpSamplingEnd(ev) => match ev.result {
+                Ok(_) => {
+                    let tokens = ev
+                        .token_usage
+                        .map(|usage| format!(" ({} tokens)", usage.blended_total()))
+                        .unwrap_or_default();
+                    ts_msg!(
+                        self,
+                        "{} {} sampled {}{tokens}",
+                        "mcp:".style(self.cyan),
+                        ev.server,
+                        ev.model
+                    );
+                }
+                Err(err) => {
+                    ts_msg!(
+                        self,
+                        "{} {} sampling failed: {err}",
+                        "mcp:".style(self.red),
+                        ev.server
+                    );
+                }
+            },
             EventMsg::WebSearchBegin(_) => {
                 ts_msg!(self, "🌐 Searching the web...");
             }
@@ -473,11 +514,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 // it's easy to scan in the terminal output.
                 for (path, change) in changes.iter() {
                     match change {
-                        FileChange::Add { content } => {
+                        FileChange::Add { content, mode } => {
                             let header = format!(
-                                "{} {}",
+                                "{} {}{}",
                                 format_file_change(change),
-                                path.to_string_lossy()
+                                path.to_string_lossy(),
+                                format_file_mode(*mode)
                             );
                             eprintln!("{}", header.style(self.magenta));
                             for line in content.lines() {
@@ -498,16 +540,23 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         FileChange::Update {
                             unified_diff,
                             move_path,
+                            mode,
                         } => {
                             let header = if let Some(dest) = move_path {
                                 format!(
-                                    "{} {} -> {}",
+                                    "{} {} -> {}{}",
                                     format_file_change(change),
                                     path.to_string_lossy(),
-                                    dest.to_string_lossy()
+                                    dest.to_string_lossy(),
+                                    format_file_mode(*mode)
                                 )
                             } else {
-                                format!("{} {}", format_file_change(change), path.to_string_lossy())
+                                format!(
+                                    "{} {}{}",
+                                    format_file_change(change),
+                                    path.to_string_lossy(),
+                                    format_file_mode(*mode)
+                                )
                             };
                             eprintln!("{}", header.style(self.magenta));
 
@@ -526,6 +575,28 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                                 }
                             }
                         }
+                        FileChange::Binary { size, mode, .. } => {
+                            let header = format!(
+                                "{} {}{}",
+                                format_file_change(change),
+                                path.to_string_lossy(),
+                                format_file_mode(*mode)
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                            eprintln!(
+                                "{}",
+                                format!("binary file, {size} bytes").style(self.dimmed)
+                            );
+                        }
+                        FileChange::Symlink { target } => {
+                            let header = format!(
+                                "{} {} -> {}",
+                                format_file_change(change),
+                                path.to_string_lossy(),
+                                target.to_string_lossy()
+                            );
+                            eprintln!("{}", header.style(self.magenta));
+                        }
                     }
                 }
             }
@@ -572,6 +643,38 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 );
                 eprintln!("{unified_diff}");
             }
+            EventMsg::WorkspaceOverlayChanges(WorkspaceOverlayChangesEvent {
+                unified_diff,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    "sandbox changes pending review:"
+                        .style(self.magenta)
+                        .style(self.italic)
+                );
+                eprintln!("{unified_diff}");
+            }
+            EventMsg::WorkspaceOverlayResolved(WorkspaceOverlayResolvedEvent {
+                accepted,
+                rejected,
+                conflicts,
+            }) => {
+                ts_msg!(
+                    self,
+                    "sandbox changes: merged {}, discarded {}",
+                    accepted.len(),
+                    rejected.len()
+                );
+                for path in conflicts {
+                    ts_msg!(
+                        self,
+                        "sandbox change not merged, {} changed since it was reviewed",
+                        path.display()
+                    );
+                }
+            }
             EventMsg::AgentReasoning(agent_reasoning_event) => {
                 if self.show_agent_reasoning {
                     ts_msg!(
@@ -827,6 +930,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::McpListPromptsResponse(_)
+            | EventMsg::McpGetPromptResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
             | EventMsg::RemoteSkillDownloaded(_)
@@ -903,6 +1008,8 @@ impl EventProcessorWithHumanOutput {
                 | EventMsg::GetHistoryEntryResponse(_)
                 | EventMsg::McpListToolsResponse(_)
                 | EventMsg::ListCustomPromptsResponse(_)
+                | EventMsg::McpListPromptsResponse(_)
+                | EventMsg::McpGetPromptResponse(_)
                 | EventMsg::ListSkillsResponse(_)
                 | EventMsg::ListRemoteSkillsResponse(_)
                 | EventMsg::RemoteSkillDownloaded(_)
@@ -1100,9 +1207,16 @@ fn format_file_change(change: &FileChange) -> &'static str {
         FileChange::Update {
             move_path: None, ..
         } => "M",
+        FileChange::Binary { replace: true, .. } => "M",
+        FileChange::Binary { replace: false, .. } | FileChange::Symlink { .. } => "A",
     }
 }
 
+fn format_file_mode(mode: Option<u32>) -> String {
+    mode.map(|mode| format!(" (mode {mode:o})"))
+        .unwrap_or_default()
+}
+
 fn format_collab_invocation(tool: &str, call_id: &str, prompt: Option<&str>) -> String {
     let prompt = prompt
         .map(str::trim)
@@ -641,6 +641,9 @@ impl EventProcessorWithJsonOutput {
             protocol::FileChange::Add { .. } => PatchChangeKind::Add,
             protocol::FileChange::Delete { .. } => PatchChangeKind::Delete,
             protocol::FileChange::Update { .. } => PatchChangeKind::Update,
+            protocol::FileChange::Binary { replace: true, .. } => PatchChangeKind::Update,
+            protocol::FileChange::Binary { replace: false, .. }
+            | protocol::FileChange::Symlink { .. } => PatchChangeKind::Add,
         }
     }
 
@@ -908,6 +908,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            resource_limit_exceeded: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -996,6 +997,7 @@ fn command_execution_output_delta_updates_item_progress() {
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            resource_limit_exceeded: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1070,6 +1072,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Failed,
+            resource_limit_exceeded: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1112,6 +1115,7 @@ fn exec_command_end_without_begin_is_ignored() {
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            resource_limit_exceeded: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1128,6 +1132,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         PathBuf::from("a/added.txt"),
         FileChange::Add {
             content: "+hello".to_string(),
+            mode: None,
         },
     );
     changes.insert(
@@ -1141,6 +1146,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         FileChange::Update {
             unified_diff: "--- c/modified.txt\n+++ c/modified.txt\n@@\n-old\n+new\n".to_string(),
             move_path: Some(PathBuf::from("c/renamed.txt")),
+            mode: None,
         },
     );
 
@@ -1214,6 +1220,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
         FileChange::Update {
             unified_diff: "--- file.txt\n+++ file.txt\n@@\n-old\n+new\n".to_string(),
             move_path: None,
+            mode: None,
         },
     );
 
@@ -1284,6 +1291,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_protocol::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            estimated_tokens: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Sandbox a command runs under, as seen by `when = {"sandbox": ...}`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SandboxKind {
+    ReadOnly,
+    WorkspaceWrite,
+    FullAccess,
+    ExternalSandbox,
+}
+
+impl SandboxKind {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "read-only" => Ok(Self::ReadOnly),
+            "workspace-write" => Ok(Self::WorkspaceWrite),
+            "full-access" | "danger-full-access" => Ok(Self::FullAccess),
+            "external-sandbox" => Ok(Self::ExternalSandbox),
+            other => Err(Error::InvalidRule(format!(
+                "sandbox must be one of read-only, workspace-write, full-access, external-sandbox \
+                 (got {other})"
+            ))),
+        }
+    }
+}
+
+impl FromStr for SandboxKind {
+    type Err = Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        Self::parse(raw)
+    }
+}
+
+/// Approval policy a command runs under, as seen by `when = {"approval_policy": ...}`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ApprovalPolicyKind {
+    Untrusted,
+    OnFailure,
+    OnRequest,
+    Reject,
+    Never,
+}
+
+impl ApprovalPolicyKind {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "untrusted" => Ok(Self::Untrusted),
+            "on-failure" => Ok(Self::OnFailure),
+            "on-request" => Ok(Self::OnRequest),
+            "reject" => Ok(Self::Reject),
+            "never" => Ok(Self::Never),
+            other => Err(Error::InvalidRule(format!(
+                "approval_policy must be one of untrusted, on-failure, on-request, reject, never \
+                 (got {other})"
+            ))),
+        }
+    }
+}
+
+impl FromStr for ApprovalPolicyKind {
+    type Err = Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        Self::parse(raw)
+    }
+}
+
+/// Where and how a command is about to run. Fields left as `None` are unknown, and any
+/// condition that depends on an unknown field does not hold.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatchContext {
+    pub cwd: Option<PathBuf>,
+    pub sandbox: Option<SandboxKind>,
+    pub approval_policy: Option<ApprovalPolicyKind>,
+    /// Environment visible to the command. `None` reads the current process environment.
+    pub env: Option<HashMap<String, String>>,
+}
+
+impl MatchContext {
+    fn env_is_set(&self, name: &str) -> bool {
+        match &self.env {
+            Some(env) => env.contains_key(name),
+            None => std::env::var_os(name).is_some(),
+        }
+    }
+}
+
+/// Conditions from a rule's `when` argument. Every populated field must hold for the rule to
+/// apply; list-valued fields hold when any entry matches, except `cwd_outside`, `env_set` and
+/// `env_unset`, which require every entry.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RuleConditions {
+    pub cwd_inside: Vec<PathBuf>,
+    pub cwd_outside: Vec<PathBuf>,
+    pub sandbox: Vec<SandboxKind>,
+    pub approval_policy: Vec<ApprovalPolicyKind>,
+    pub env_set: Vec<String>,
+    pub env_unset: Vec<String>,
+}
+
+impl RuleConditions {
+    pub fn holds(&self, context: &MatchContext) -> bool {
+        self.cwd_holds(context.cwd.as_deref())
+            && holds_for_any(&self.sandbox, context.sandbox)
+            && holds_for_any(&self.approval_policy, context.approval_policy)
+            && self.env_set.iter().all(|name| context.env_is_set(name))
+            && self.env_unset.iter().all(|name| !context.env_is_set(name))
+    }
+
+    fn cwd_holds(&self, cwd: Option<&Path>) -> bool {
+        if self.cwd_inside.is_empty() && self.cwd_outside.is_empty() {
+            return true;
+        }
+        let Some(cwd) = cwd else {
+            return false;
+        };
+        // `/work/release/../scratch` must not count as inside `/work/release`. Roots are
+        // normalized when the rule is parsed.
+        let cwd = normalize_path(cwd);
+        let is_inside = |root: &PathBuf| cwd.starts_with(root);
+        (self.cwd_inside.is_empty() || self.cwd_inside.iter().any(is_inside))
+            && !self.cwd_outside.iter().any(is_inside)
+    }
+}
+
+/// Lexically resolves `.` and `..` components without touching the filesystem.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() && !normalized.has_root() {
+                    normalized.push(component);
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn holds_for_any<T: PartialEq>(allowed: &[T], actual: Option<T>) -> bool {
+    allowed.is_empty() || actual.is_some_and(|actual| allowed.contains(&actual))
+}
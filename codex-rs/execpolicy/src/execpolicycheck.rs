@@ -6,10 +6,13 @@ use anyhow::Result;
 use clap::Parser;
 use serde::Serialize;
 
+use crate::ApprovalPolicyKind;
 use crate::Decision;
+use crate::MatchContext;
 use crate::Policy;
 use crate::PolicyParser;
 use crate::RuleMatch;
+use crate::SandboxKind;
 
 /// Arguments for evaluating a command against one or more execpolicy files.
 #[derive(Debug, Parser, Clone)]
@@ -22,6 +25,21 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub pretty: bool,
 
+    /// Working directory to evaluate `when` conditions against. Rules with `cwd_inside` or
+    /// `cwd_outside` conditions do not apply when omitted.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Sandbox to evaluate `when` conditions against: read-only, workspace-write, full-access or
+    /// external-sandbox.
+    #[arg(long, value_name = "SANDBOX")]
+    pub sandbox: Option<SandboxKind>,
+
+    /// Approval policy to evaluate `when` conditions against: untrusted, on-failure, on-request,
+    /// reject or never.
+    #[arg(long, value_name = "POLICY")]
+    pub approval_policy: Option<ApprovalPolicyKind>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -36,7 +54,13 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
-        let matched_rules = policy.matches_for_command(&self.command, None);
+        let context = MatchContext {
+            cwd: self.cwd.clone(),
+            sandbox: self.sandbox,
+            approval_policy: self.approval_policy,
+            env: None,
+        };
+        let matched_rules = policy.matches_for_command_with_context(&self.command, &context, None);
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
         println!("{json}");
@@ -1,14 +1,20 @@
 pub mod amend;
+pub mod condition;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
 pub mod parser;
+mod pattern_value;
 pub mod policy;
 pub mod rule;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
 pub use amend::blocking_append_network_rule;
+pub use condition::ApprovalPolicyKind;
+pub use condition::MatchContext;
+pub use condition::RuleConditions;
+pub use condition::SandboxKind;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
@@ -8,21 +8,31 @@ use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
 use starlark::values::Value;
+use starlark::values::ValueLike;
+use starlark::values::dict::DictRef;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
 use std::cell::RefCell;
 use std::cell::RefMut;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::condition::ApprovalPolicyKind;
+use crate::condition::RuleConditions;
+use crate::condition::SandboxKind;
+use crate::condition::normalize_path;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::pattern_value::PatternValue;
+use crate::rule::GlobToken;
 use crate::rule::NetworkRule;
 use crate::rule::NetworkRuleProtocol;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
+use crate::rule::RegexToken;
 use crate::rule::RuleRef;
 use crate::rule::validate_match_examples;
 use crate::rule::validate_not_match_examples;
@@ -105,14 +115,42 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
         .map(parse_pattern_token)
         .collect::<Result<_>>()?;
     if tokens.is_empty() {
-        Err(Error::InvalidPattern("pattern cannot be empty".to_string()))
-    } else {
-        Ok(tokens)
+        return Err(Error::InvalidPattern("pattern cannot be empty".to_string()));
     }
+    if !tokens[0].is_literal() {
+        return Err(Error::InvalidPattern(
+            "first pattern element must be a string or list of strings".to_string(),
+        ));
+    }
+    if let Some(position) = tokens
+        .iter()
+        .position(|token| matches!(token, PatternToken::Rest(_)))
+        && position != tokens.len() - 1
+    {
+        return Err(Error::InvalidPattern(
+            "rest_args() must be the last pattern element".to_string(),
+        ));
+    }
+    Ok(tokens)
+}
+
+fn parse_flags<'v>(flags: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>> {
+    flags
+        .items
+        .into_iter()
+        .map(|value| match parse_pattern_token(value)? {
+            PatternToken::Rest(_) => Err(Error::InvalidPattern(
+                "rest_args() cannot be used in flags".to_string(),
+            )),
+            token => Ok(token),
+        })
+        .collect()
 }
 
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
-    if let Some(s) = value.unpack_str() {
+    if let Some(pattern_value) = value.downcast_ref::<PatternValue>() {
+        Ok(pattern_value.token.clone())
+    } else if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
@@ -140,12 +178,98 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings, glob(), regex(), any_token() or \
+             rest_args() (got {})",
             value.get_type()
         )))
     }
 }
 
+fn parse_conditions<'v>(value: Value<'v>) -> Result<RuleConditions> {
+    let dict = DictRef::from_value(value).ok_or_else(|| {
+        Error::InvalidRule(format!("when must be a dict (got {})", value.get_type()))
+    })?;
+    let mut conditions = RuleConditions::default();
+    for (key, value) in dict.iter() {
+        let key = key.unpack_str().ok_or_else(|| {
+            Error::InvalidRule(format!(
+                "when keys must be strings (got {})",
+                key.get_type()
+            ))
+        })?;
+        let entries = parse_condition_entries(key, value)?;
+        match key {
+            "cwd_inside" => conditions.cwd_inside = parse_condition_paths(key, entries)?,
+            "cwd_outside" => conditions.cwd_outside = parse_condition_paths(key, entries)?,
+            "sandbox" => {
+                conditions.sandbox = entries
+                    .iter()
+                    .map(|raw| SandboxKind::parse(raw))
+                    .collect::<Result<_>>()?;
+            }
+            "approval_policy" => {
+                conditions.approval_policy = entries
+                    .iter()
+                    .map(|raw| ApprovalPolicyKind::parse(raw))
+                    .collect::<Result<_>>()?;
+            }
+            "env_set" => conditions.env_set = entries,
+            "env_unset" => conditions.env_unset = entries,
+            other => {
+                return Err(Error::InvalidRule(format!(
+                    "unknown when condition `{other}`; expected cwd_inside, cwd_outside, \
+                     sandbox, approval_policy, env_set or env_unset"
+                )));
+            }
+        }
+    }
+    Ok(conditions)
+}
+
+/// Condition values may be a single string or a non-empty list of strings.
+fn parse_condition_entries<'v>(key: &str, value: Value<'v>) -> Result<Vec<String>> {
+    let entries: Vec<String> = if let Some(raw) = value.unpack_str() {
+        vec![raw.to_string()]
+    } else if let Some(list) = ListRef::from_value(value) {
+        list.content()
+            .iter()
+            .map(|value| {
+                value.unpack_str().map(str::to_string).ok_or_else(|| {
+                    Error::InvalidRule(format!(
+                        "when.{key} entries must be strings (got {})",
+                        value.get_type()
+                    ))
+                })
+            })
+            .collect::<Result<_>>()?
+    } else {
+        return Err(Error::InvalidRule(format!(
+            "when.{key} must be a string or list of strings (got {})",
+            value.get_type()
+        )));
+    };
+    if entries.is_empty() {
+        return Err(Error::InvalidRule(format!("when.{key} cannot be empty")));
+    }
+    Ok(entries)
+}
+
+fn parse_condition_paths(key: &str, entries: Vec<String>) -> Result<Vec<PathBuf>> {
+    entries
+        .into_iter()
+        .map(|raw| {
+            let path = PathBuf::from(&raw);
+            if path.is_absolute() {
+                Ok(normalize_path(&path))
+            } else {
+                Err(Error::InvalidRule(format!(
+                    "when.{key} paths must be absolute (got {raw})"
+                )))
+            }
+        })
+        .collect()
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -220,6 +344,7 @@ fn policy_builder<'v, 'a>(eval: &Evaluator<'v, 'a, '_>) -> RefMut<'a, PolicyBuil
         .borrow_mut()
 }
 
+#[allow(clippy::too_many_arguments)]
 #[starlark_module]
 fn policy_builtins(builder: &mut GlobalsBuilder) {
     fn prefix_rule<'v>(
@@ -228,6 +353,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
         justification: Option<&'v str>,
+        flags: Option<UnpackList<Value<'v>>>,
+        when: Option<Value<'v>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -244,6 +371,12 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         };
 
         let pattern_tokens = parse_pattern(pattern)?;
+        let flags: Arc<[PatternToken]> = flags
+            .map(parse_flags)
+            .transpose()?
+            .unwrap_or_default()
+            .into();
+        let when = when.map(parse_conditions).transpose()?;
 
         let matches: Vec<Vec<String>> =
             r#match.map(parse_examples).transpose()?.unwrap_or_default();
@@ -268,9 +401,11 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                     pattern: PrefixPattern {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
+                        flags: flags.clone(),
                     },
                     decision,
                     justification: justification.clone(),
+                    when: when.clone(),
                 }) as RuleRef
             })
             .collect();
@@ -282,11 +417,48 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         Ok(NoneType)
     }
 
+    /// Matches a token against a shell-style glob, e.g. `glob("/tmp/**")`.
+    fn glob(pattern: &str) -> anyhow::Result<PatternValue> {
+        Ok(PatternValue {
+            token: PatternToken::Glob(GlobToken::new(pattern)?),
+        })
+    }
+
+    /// Matches a token against a regular expression that must match the whole token.
+    fn regex(pattern: &str) -> anyhow::Result<PatternValue> {
+        Ok(PatternValue {
+            token: PatternToken::Regex(RegexToken::new(pattern)?),
+        })
+    }
+
+    /// Matches any single token.
+    fn any_token() -> anyhow::Result<PatternValue> {
+        Ok(PatternValue {
+            token: PatternToken::Any,
+        })
+    }
+
+    /// Matches one or more remaining tokens, each of which must match `token`.
+    fn rest_args<'v>(token: Value<'v>) -> anyhow::Result<PatternValue> {
+        let token = match parse_pattern_token(token)? {
+            PatternToken::Rest(_) => {
+                return Err(
+                    Error::InvalidPattern("rest_args() cannot be nested".to_string()).into(),
+                );
+            }
+            token => token,
+        };
+        Ok(PatternValue {
+            token: PatternToken::Rest(Box::new(token)),
+        })
+    }
+
     fn network_rule<'v>(
         host: &'v str,
         protocol: &'v str,
         decision: &'v str,
         justification: Option<&'v str>,
+        when: Option<Value<'v>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let protocol = NetworkRuleProtocol::parse(protocol)?;
@@ -298,6 +470,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             Some(raw) => Some(raw.to_string()),
             None => None,
         };
+        let when = when.map(parse_conditions).transpose()?;
 
         let mut builder = policy_builder(eval);
         builder.add_network_rule(NetworkRule {
@@ -305,6 +478,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             protocol,
             decision,
             justification,
+            when,
         });
         Ok(NoneType)
     }
@@ -0,0 +1,48 @@
+#![allow(clippy::needless_lifetimes)]
+
+use std::fmt;
+
+use allocative::Allocative;
+use starlark::any::ProvidesStaticType;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
+use starlark::values::Value;
+use starlark::values::starlark_value;
+
+use crate::rule::PatternToken;
+
+/// Starlark value produced by `glob()`, `regex()`, `any_token()` and `rest_args()` so those
+/// matchers can be used as `pattern` and `flags` elements alongside plain strings.
+#[derive(Clone, Debug, PartialEq, Eq, ProvidesStaticType, NoSerialize, Allocative)]
+pub(crate) struct PatternValue {
+    #[allocative(skip)]
+    pub(crate) token: PatternToken,
+}
+
+impl fmt::Display for PatternValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render(&self.token))
+    }
+}
+
+fn render(token: &PatternToken) -> String {
+    match token {
+        PatternToken::Single(value) => format!("{value:?}"),
+        PatternToken::Alts(alternatives) => format!("{alternatives:?}"),
+        PatternToken::Any => "any_token()".to_string(),
+        PatternToken::Glob(glob) => format!("glob({:?})", glob.as_str()),
+        PatternToken::Regex(regex) => format!("regex({:?})", regex.as_str()),
+        PatternToken::Rest(inner) => format!("rest_args({})", render(inner)),
+    }
+}
+
+#[starlark_value(type = "PatternToken")]
+impl<'v> StarlarkValue<'v> for PatternValue {}
+
+impl<'v> AllocValue<'v> for PatternValue {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::condition::MatchContext;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
@@ -57,7 +58,12 @@ impl Policy {
                 let Some(prefix_rule) = rule.as_any().downcast_ref::<PrefixRule>() else {
                     continue;
                 };
-                if prefix_rule.decision != Decision::Allow {
+                // Flags can appear anywhere in the command, so the rule has no prefix to show, and
+                // conditional rules only allow the prefix some of the time.
+                if prefix_rule.decision != Decision::Allow
+                    || !prefix_rule.pattern.flags.is_empty()
+                    || prefix_rule.when.is_some()
+                {
                     continue;
                 }
 
@@ -86,9 +92,11 @@ impl Policy {
                     .map(|token| PatternToken::Single(token.clone()))
                     .collect::<Vec<_>>()
                     .into(),
+                flags: Vec::new().into(),
             },
             decision,
             justification: None,
+            when: None,
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -115,15 +123,29 @@ impl Policy {
             protocol,
             decision,
             justification,
+            when: None,
         });
         Ok(())
     }
 
     pub fn compiled_network_domains(&self) -> (Vec<String>, Vec<String>) {
+        self.compiled_network_domains_with_context(&MatchContext::default())
+    }
+
+    /// Like [`Policy::compiled_network_domains`], skipping rules whose `when` conditions do not
+    /// hold in `context`.
+    pub fn compiled_network_domains_with_context(
+        &self,
+        context: &MatchContext,
+    ) -> (Vec<String>, Vec<String>) {
         let mut allowed = Vec::new();
         let mut denied = Vec::new();
 
-        for rule in &self.network_rules {
+        for rule in self
+            .network_rules
+            .iter()
+            .filter(|rule| rule.applies_in(context))
+        {
             match rule.decision {
                 Decision::Allow => {
                     denied.retain(|entry| entry != &rule.host);
@@ -144,7 +166,21 @@ impl Policy {
     where
         F: Fn(&[String]) -> Decision,
     {
-        let matched_rules = self.matches_for_command(cmd, Some(heuristics_fallback));
+        self.check_with_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Like [`Policy::check`], skipping rules whose `when` conditions do not hold in `context`.
+    pub fn check_with_context<F>(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
+    where
+        F: Fn(&[String]) -> Decision,
+    {
+        let matched_rules =
+            self.matches_for_command_with_context(cmd, context, Some(heuristics_fallback));
         Evaluation::from_matches(matched_rules)
     }
 
@@ -154,6 +190,22 @@ impl Policy {
         commands: Commands,
         heuristics_fallback: &F,
     ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        self.check_multiple_with_context(commands, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Like [`Policy::check_multiple`], skipping rules whose `when` conditions do not hold in
+    /// `context`.
+    pub fn check_multiple_with_context<Commands, F>(
+        &self,
+        commands: Commands,
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
     where
         Commands: IntoIterator,
         Commands::Item: AsRef<[String]>,
@@ -162,7 +214,11 @@ impl Policy {
         let matched_rules: Vec<RuleMatch> = commands
             .into_iter()
             .flat_map(|command| {
-                self.matches_for_command(command.as_ref(), Some(heuristics_fallback))
+                self.matches_for_command_with_context(
+                    command.as_ref(),
+                    context,
+                    Some(heuristics_fallback),
+                )
             })
             .collect();
 
@@ -180,12 +236,28 @@ impl Policy {
         &self,
         cmd: &[String],
         heuristics_fallback: HeuristicsFallback<'_>,
+    ) -> Vec<RuleMatch> {
+        self.matches_for_command_with_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Like [`Policy::matches_for_command`], skipping rules whose `when` conditions do not hold
+    /// in `context`.
+    pub fn matches_for_command_with_context(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: HeuristicsFallback<'_>,
     ) -> Vec<RuleMatch> {
         let matched_rules: Vec<RuleMatch> = match cmd.first() {
             Some(first) => self
                 .rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches_in_context(cmd, context))
+                        .collect()
+                })
                 .unwrap_or_default(),
             None => Vec::new(),
         };
@@ -212,6 +284,10 @@ fn render_pattern_token(token: &PatternToken) -> String {
     match token {
         PatternToken::Single(value) => value.clone(),
         PatternToken::Alts(alternatives) => format!("[{}]", alternatives.join("|")),
+        PatternToken::Any => "*".to_string(),
+        PatternToken::Glob(glob) => glob.as_str().to_string(),
+        PatternToken::Regex(regex) => format!("/{}/", regex.as_str()),
+        PatternToken::Rest(inner) => format!("{}...", render_pattern_token(inner)),
     }
 }
 
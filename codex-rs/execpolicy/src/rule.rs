@@ -1,6 +1,11 @@
+use crate::condition::MatchContext;
+use crate::condition::RuleConditions;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use regex_lite::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
@@ -8,11 +13,22 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
+    /// A fixed string.
     Single(String),
+    /// One of several fixed strings.
     Alts(Vec<String>),
+    /// Any token at all.
+    Any,
+    /// A shell-style glob.
+    Glob(GlobToken),
+    /// A regular expression that must match the whole token.
+    Regex(RegexToken),
+    /// Only valid as the last pattern element: one or more remaining tokens, each of which must
+    /// match the inner token.
+    Rest(Box<PatternToken>),
 }
 
 impl PatternToken {
@@ -20,39 +36,141 @@ impl PatternToken {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Any => true,
+            Self::Glob(glob) => glob.is_match(token),
+            Self::Regex(regex) => regex.regex.is_match(token),
+            Self::Rest(inner) => inner.matches(token),
         }
     }
 
+    /// Fixed strings this token accepts. Empty for tokens that match by wildcard, glob or regex.
     pub fn alternatives(&self) -> &[String] {
         match self {
             Self::Single(expected) => std::slice::from_ref(expected),
             Self::Alts(alternatives) => alternatives,
+            Self::Any | Self::Glob(_) | Self::Regex(_) | Self::Rest(_) => &[],
         }
     }
+
+    /// True when the token only accepts fixed strings.
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Single(_) | Self::Alts(_))
+    }
+}
+
+/// Glob matched against a whole token. `*` and `?` do not cross `/`; `**` does. Tokens with a
+/// `..` component never match, since `/tmp/../etc` would otherwise match `/tmp/**`.
+#[derive(Clone, Debug)]
+pub struct GlobToken {
+    source: String,
+    matcher: GlobMatcher,
 }
 
+impl GlobToken {
+    pub fn new(source: &str) -> Result<Self> {
+        let matcher = GlobBuilder::new(source)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| Error::InvalidPattern(format!("invalid glob `{source}`: {err}")))?
+            .compile_matcher();
+        Ok(Self {
+            source: source.to_string(),
+            matcher,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn is_match(&self, token: &str) -> bool {
+        !token.split(['/', '\\']).any(|component| component == "..") && self.matcher.is_match(token)
+    }
+}
+
+impl PartialEq for GlobToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for GlobToken {}
+
+/// Regular expression matched against a whole token, as if wrapped in `^(?:...)$`.
+#[derive(Clone, Debug)]
+pub struct RegexToken {
+    source: String,
+    regex: Regex,
+}
+
+impl RegexToken {
+    pub fn new(source: &str) -> Result<Self> {
+        let regex = Regex::new(&format!("^(?:{source})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for RegexToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for RegexToken {}
+
 /// Prefix matcher for commands with support for alternative match tokens.
 /// First token is fixed since we key by the first token in policy.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrefixPattern {
     pub first: Arc<str>,
     pub rest: Arc<[PatternToken]>,
+    /// Tokens that must each appear somewhere after the program, in any order. Each flag is
+    /// matched against whole tokens, so `-f` does not match `-fu` and `--force` does not match
+    /// `--force=yes`.
+    pub flags: Arc<[PatternToken]>,
 }
 
 impl PrefixPattern {
     pub fn matches_prefix(&self, cmd: &[String]) -> Option<Vec<String>> {
-        let pattern_length = self.rest.len() + 1;
-        if cmd.len() < pattern_length || cmd[0] != self.first.as_ref() {
+        let (program, args) = cmd.split_first()?;
+        if program != self.first.as_ref() {
             return None;
         }
 
-        for (pattern_token, cmd_token) in self.rest.iter().zip(&cmd[1..pattern_length]) {
+        let mut matched_len = 1;
+        for pattern_token in self.rest.iter() {
+            if let PatternToken::Rest(inner) = pattern_token {
+                let remaining = &cmd[matched_len..];
+                if remaining.is_empty() || !remaining.iter().all(|token| inner.matches(token)) {
+                    return None;
+                }
+                matched_len = cmd.len();
+                break;
+            }
+            let cmd_token = cmd.get(matched_len)?;
             if !pattern_token.matches(cmd_token) {
                 return None;
             }
+            matched_len += 1;
+        }
+
+        let has_all_flags = self
+            .flags
+            .iter()
+            .all(|flag| args.iter().any(|arg| flag.matches(arg)));
+        if !has_all_flags {
+            return None;
         }
 
-        Some(cmd[..pattern_length].to_vec())
+        Some(cmd[..matched_len].to_vec())
     }
 }
 
@@ -90,6 +208,8 @@ pub struct PrefixRule {
     pub pattern: PrefixPattern,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// Conditions under which the rule applies; `None` applies everywhere.
+    pub when: Option<RuleConditions>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -129,6 +249,15 @@ pub struct NetworkRule {
     pub protocol: NetworkRuleProtocol,
     pub decision: Decision,
     pub justification: Option<String>,
+    pub when: Option<RuleConditions>,
+}
+
+impl NetworkRule {
+    pub fn applies_in(&self, context: &MatchContext) -> bool {
+        self.when
+            .as_ref()
+            .is_none_or(|conditions| conditions.holds(context))
+    }
 }
 
 pub(crate) fn normalize_network_rule_host(raw: &str) -> Result<String> {
@@ -194,6 +323,11 @@ pub trait Rule: Any + Debug + Send + Sync {
 
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
 
+    /// Like [`Rule::matches`], but also requires the rule's `when` conditions to hold.
+    fn matches_in_context(&self, cmd: &[String], _context: &MatchContext) -> Option<RuleMatch> {
+        self.matches(cmd)
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -214,6 +348,15 @@ impl Rule for PrefixRule {
             })
     }
 
+    fn matches_in_context(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch> {
+        if let Some(conditions) = &self.when
+            && !conditions.holds(context)
+        {
+            return None;
+        }
+        self.matches(cmd)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
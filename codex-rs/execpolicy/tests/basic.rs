@@ -1,17 +1,22 @@
 use std::any::Any;
+use std::collections::HashMap;
 use std::fs;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
+use codex_execpolicy::ApprovalPolicyKind;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::NetworkRuleProtocol;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::SandboxKind;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
@@ -222,9 +227,11 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             pattern: PrefixPattern {
                 first: Arc::from("ls"),
                 rest: vec![PatternToken::Single(String::from("-l"))].into(),
+                flags: Vec::<PatternToken>::new().into(),
             },
             decision: Decision::Prompt,
             justification: None,
+            when: None,
         })],
         rules
     );
@@ -282,17 +289,21 @@ prefix_rule(
                 pattern: PrefixPattern {
                     first: Arc::from("git"),
                     rest: Vec::<PatternToken>::new().into(),
+                    flags: Vec::<PatternToken>::new().into(),
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                when: None,
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
                     first: Arc::from("git"),
                     rest: vec![PatternToken::Single("commit".to_string())].into(),
+                    flags: Vec::<PatternToken>::new().into(),
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                when: None,
             }),
         ],
         git_rules
@@ -356,9 +367,11 @@ prefix_rule(
             pattern: PrefixPattern {
                 first: Arc::from("bash"),
                 rest: vec![PatternToken::Alts(vec!["-c".to_string(), "-l".to_string()])].into(),
+                flags: Vec::<PatternToken>::new().into(),
             },
             decision: Decision::Allow,
             justification: None,
+            when: None,
         })],
         bash_rules
     );
@@ -367,9 +380,11 @@ prefix_rule(
             pattern: PrefixPattern {
                 first: Arc::from("sh"),
                 rest: vec![PatternToken::Alts(vec!["-c".to_string(), "-l".to_string()])].into(),
+                flags: Vec::<PatternToken>::new().into(),
             },
             decision: Decision::Allow,
             justification: None,
+            when: None,
         })],
         sh_rules
     );
@@ -426,9 +441,11 @@ prefix_rule(
                     ]),
                 ]
                 .into(),
+                flags: Vec::<PatternToken>::new().into(),
             },
             decision: Decision::Allow,
             justification: None,
+            when: None,
         })],
         rules
     );
@@ -612,3 +629,318 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn rest_args_glob_matches_every_remaining_argument() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", "-rf", rest_args(glob("/tmp/**"))],
+    match = ["rm -rf /tmp/build", "rm -rf /tmp/a /tmp/b/c"],
+    not_match = ["rm -rf", "rm -rf /tmp/a /etc", "rm -rf /tmpfoo", "rm -rf /tmp/../etc"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    for escape in ["/tmp/../etc", "/tmp/a/../../etc/passwd", "/tmp/.."] {
+        let command = tokens(&["rm", "-rf", escape]);
+        assert_eq!(
+            Evaluation {
+                decision: Decision::Prompt,
+                matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                    command: command.clone(),
+                    decision: Decision::Prompt,
+                }],
+            },
+            policy.check(&command, &prompt_all),
+            "{escape} must not match /tmp/**"
+        );
+    }
+
+    let command = tokens(&["rm", "-rf", "/tmp/a", "/tmp/b/c"]);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: command.clone(),
+                decision: Decision::Allow,
+                justification: None,
+            }],
+        },
+        policy.check(&command, &prompt_all)
+    );
+    Ok(())
+}
+
+#[test]
+fn flags_match_anywhere_after_the_program() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    flags = [["--force", "-f"]],
+    decision = "forbidden",
+    justification = "Use --force-with-lease instead.",
+    match = ["git push --force", "git push origin main -f"],
+    not_match = ["git push origin main", "git push --force-with-lease"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["git", "push", "origin", "--force", "main"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Forbidden,
+                justification: Some("Use --force-with-lease instead.".to_string()),
+            }],
+        },
+        evaluation
+    );
+    assert!(policy.get_allowed_prefixes().is_empty());
+    Ok(())
+}
+
+#[test]
+fn any_token_and_regex_match_single_positions() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["kubectl", any_token(), regex("prod-[a-z]+")],
+    decision = "prompt",
+    match = ["kubectl delete prod-api", "kubectl get prod-db -o yaml"],
+    not_match = ["kubectl delete staging-api", "kubectl delete prod-api2"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["kubectl", "delete", "prod-api", "--now"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["kubectl", "delete", "prod-api"]),
+                decision: Decision::Prompt,
+                justification: None,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn argument_matchers_reject_invalid_placement() {
+    let cases = [
+        (
+            r#"prefix_rule(pattern = [glob("g*"), "status"])"#,
+            "first pattern element must be a string or list of strings",
+        ),
+        (
+            r#"prefix_rule(pattern = ["rm", rest_args(any_token()), "-rf"])"#,
+            "rest_args() must be the last pattern element",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], flags = [rest_args("-f")])"#,
+            "rest_args() cannot be used in flags",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git", regex("(")])"#,
+            "invalid regex `(`",
+        ),
+    ];
+
+    for (policy_src, expected) in cases {
+        let mut parser = PolicyParser::new();
+        let err = parser
+            .parse("test.rules", policy_src)
+            .expect_err("expected parse error");
+        assert!(
+            err.to_string().contains(expected),
+            "expected {expected:?} in {err}"
+        );
+    }
+}
+
+#[test]
+fn argument_matcher_examples_are_validated() {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", "-rf", rest_args(glob("/tmp/**"))],
+    match = ["rm -rf /var/tmp"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("unmatched example should fail");
+    assert!(
+        err.to_string()
+            .contains("expected every example to match at least one rule"),
+        "{err}"
+    );
+}
+
+#[test]
+fn when_conditions_scope_prefix_rules() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cargo", "publish"],
+    when = {"cwd_inside": "/work/tools/../release/.", "env_set": "RELEASE"},
+)
+prefix_rule(
+    pattern = ["docker"],
+    decision = "forbidden",
+    when = {"sandbox": ["full-access"]},
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+    let release = MatchContext {
+        cwd: Some(PathBuf::from("/work/release/crate")),
+        sandbox: Some(SandboxKind::WorkspaceWrite),
+        approval_policy: None,
+        env: Some(HashMap::from([("RELEASE".to_string(), "1".to_string())])),
+    };
+
+    let publish = tokens(&["cargo", "publish"]);
+    assert_eq!(
+        Decision::Allow,
+        policy
+            .check_with_context(&publish, &release, &prompt_all)
+            .decision
+    );
+    let outside_release = MatchContext {
+        cwd: Some(PathBuf::from("/work/scratch")),
+        ..release.clone()
+    };
+    assert_eq!(
+        Decision::Prompt,
+        policy
+            .check_with_context(&publish, &outside_release, &prompt_all)
+            .decision
+    );
+    let escaped_release = MatchContext {
+        cwd: Some(PathBuf::from("/work/release/../scratch")),
+        ..release.clone()
+    };
+    assert_eq!(
+        Decision::Prompt,
+        policy
+            .check_with_context(&publish, &escaped_release, &prompt_all)
+            .decision
+    );
+    let without_env = MatchContext {
+        env: Some(HashMap::new()),
+        ..release.clone()
+    };
+    assert_eq!(
+        Decision::Prompt,
+        policy
+            .check_with_context(&publish, &without_env, &prompt_all)
+            .decision
+    );
+
+    let docker = tokens(&["docker", "run", "alpine"]);
+    assert_eq!(
+        Decision::Allow,
+        policy
+            .check_with_context(&docker, &release, &allow_all)
+            .decision
+    );
+    let unsandboxed = MatchContext {
+        sandbox: Some(SandboxKind::FullAccess),
+        ..release
+    };
+    assert_eq!(
+        Decision::Forbidden,
+        policy
+            .check_with_context(&docker, &unsandboxed, &allow_all)
+            .decision
+    );
+    // Without a context, conditions that depend on unknown fields do not hold.
+    assert_eq!(Decision::Allow, policy.check(&docker, &allow_all).decision);
+    Ok(())
+}
+
+#[test]
+fn when_conditions_scope_network_rules() -> Result<()> {
+    let policy_src = r#"
+network_rule(host = "api.github.com", protocol = "https", decision = "allow")
+network_rule(
+    host = "registry.npmjs.org",
+    protocol = "https",
+    decision = "allow",
+    when = {"approval_policy": ["on-request", "untrusted"]},
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("network.rules", policy_src)?;
+    let policy = parser.build();
+
+    let (allowed, _) = policy.compiled_network_domains();
+    assert_eq!(allowed, vec!["api.github.com".to_string()]);
+
+    let on_request = MatchContext {
+        approval_policy: Some(ApprovalPolicyKind::OnRequest),
+        ..MatchContext::default()
+    };
+    let (allowed, _) = policy.compiled_network_domains_with_context(&on_request);
+    assert_eq!(
+        allowed,
+        vec![
+            "api.github.com".to_string(),
+            "registry.npmjs.org".to_string()
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn when_conditions_reject_invalid_values() {
+    let cases = [
+        (
+            r#"prefix_rule(pattern = ["git"], when = {"cwd": "/repo"})"#,
+            "unknown when condition `cwd`",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], when = {"cwd_inside": "repo"})"#,
+            "when.cwd_inside paths must be absolute",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], when = {"sandbox": "none"})"#,
+            "sandbox must be one of",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], when = {"env_set": []})"#,
+            "when.env_set cannot be empty",
+        ),
+        (
+            r#"network_rule(host = "a.com", protocol = "https", decision = "allow", when = ["x"])"#,
+            "when must be a dict",
+        ),
+    ];
+
+    for (policy_src, expected) in cases {
+        let mut parser = PolicyParser::new();
+        let err = parser
+            .parse("test.rules", policy_src)
+            .expect_err("expected parse error");
+        assert!(
+            err.to_string().contains(expected),
+            "expected {expected:?} in {err}"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use tokio::io::AsyncWriteExt;
+
+use crate::Hook;
+use crate::HookContextDecision;
+use crate::HookEventKind;
+use crate::HookMatcher;
+use crate::HookPayload;
+use crate::HookResult;
+use crate::HookToolDecision;
+use crate::command_from_argv;
+
+pub const DEFAULT_COMMAND_HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A hook declared in config that runs an external command.
+///
+/// The command receives the serialized [`HookPayload`] on stdin. For
+/// `before_tool_use` it may print a JSON [`HookToolDecision`] on stdout, and for
+/// `session_start` and `before_user_turn` a JSON [`HookContextDecision`]; empty
+/// output means "no opinion". Output from other events is ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandHookConfig {
+    pub name: String,
+    pub event: HookEventKind,
+    pub matcher: HookMatcher,
+    pub argv: Vec<String>,
+    pub timeout: Duration,
+    /// When true, failures (spawn errors, non-zero exit, timeouts, malformed
+    /// output) abort the operation instead of only being logged.
+    pub required: bool,
+}
+
+pub fn command_hook(config: CommandHookConfig) -> Hook {
+    let config = Arc::new(config);
+    Hook {
+        name: config.name.clone(),
+        func: Arc::new(move |payload: &HookPayload| {
+            let config = Arc::clone(&config);
+            Box::pin(async move {
+                if !config.matcher.matches(&payload.hook_event) {
+                    return HookResult::Success;
+                }
+                match run_command_hook(&config, payload).await {
+                    Ok(result) => result,
+                    Err(err) if config.required => HookResult::FailedAbort(err.into()),
+                    Err(err) => HookResult::FailedContinue(err.into()),
+                }
+            })
+        }),
+    }
+}
+
+async fn run_command_hook(
+    config: &CommandHookConfig,
+    payload: &HookPayload,
+) -> Result<HookResult, std::io::Error> {
+    let Some(mut command) = command_from_argv(&config.argv) else {
+        return Ok(HookResult::Success);
+    };
+    let payload_json = serde_json::to_vec(payload).map_err(std::io::Error::other)?;
+
+    command
+        .current_dir(&payload.cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn()?;
+
+    let output = tokio::time::timeout(config.timeout, async move {
+        if let Some(mut stdin) = child.stdin.take() {
+            // Hooks are free to ignore stdin, so a closed pipe is not an error.
+            match stdin.write_all(&payload_json).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => {}
+                Err(err) => return Err(err),
+            }
+        }
+        child.wait_with_output().await
+    })
+    .await
+    .map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            format!("hook timed out after {}ms", config.timeout.as_millis()),
+        )
+    })??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "hook exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return Ok(HookResult::Success);
+    }
+    match payload.hook_event.kind() {
+        HookEventKind::BeforeToolUse => {
+            parse_decision::<HookToolDecision>(stdout).map(HookResult::Decision)
+        }
+        HookEventKind::SessionStart | HookEventKind::BeforeUserTurn => {
+            parse_decision::<HookContextDecision>(stdout).map(HookResult::Context)
+        }
+        _ => Ok(HookResult::Success),
+    }
+}
+
+fn parse_decision<T: DeserializeOwned>(stdout: &str) -> Result<T, std::io::Error> {
+    serde_json::from_str(stdout).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("hook printed an invalid decision: {err}"),
+        )
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(windows))]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::HookEvent;
+    use crate::HookEventBeforeToolUse;
+    use crate::HookEventBeforeUserTurn;
+    use crate::HookToolInput;
+    use crate::HookToolKind;
+
+    fn before_tool_use_payload(cwd: PathBuf) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd,
+            client: None,
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-1".to_string(),
+                    call_id: "call-1".to_string(),
+                    tool_name: "shell".to_string(),
+                    tool_kind: HookToolKind::LocalShell,
+                    tool_input: HookToolInput::Function {
+                        arguments: "{}".to_string(),
+                    },
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "read-only".to_string(),
+                },
+            },
+        }
+    }
+
+    fn sh_hook(script: &str, required: bool) -> CommandHookConfig {
+        CommandHookConfig {
+            name: "test".to_string(),
+            event: HookEventKind::BeforeToolUse,
+            matcher: HookMatcher::default(),
+            argv: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout: Duration::from_secs(5),
+            required,
+        }
+    }
+
+    #[tokio::test]
+    async fn command_hook_returns_decision_from_stdout() {
+        let temp_dir = tempdir().expect("tempdir");
+        let hook = command_hook(sh_hook(
+            r#"cat > payload.json; printf '{"decision":"deny","reason":"no prod"}'"#,
+            false,
+        ));
+
+        let payload = before_tool_use_payload(temp_dir.path().to_path_buf());
+        let outcome = hook.execute(&payload).await;
+
+        let HookResult::Decision(decision) = outcome.result else {
+            panic!("expected decision, got {:?}", outcome.result);
+        };
+        assert_eq!(
+            decision,
+            HookToolDecision::Deny {
+                reason: "no prod".to_string(),
+            }
+        );
+        let written = std::fs::read_to_string(temp_dir.path().join("payload.json"))
+            .expect("read payload written by hook");
+        assert_eq!(
+            written,
+            serde_json::to_string(&payload).expect("serialize payload")
+        );
+    }
+
+    #[tokio::test]
+    async fn command_hook_returns_context_decision_for_user_turn() {
+        let temp_dir = tempdir().expect("tempdir");
+        let mut config = sh_hook(
+            r#"printf '{"decision":"block","reason":"secret in prompt"}'"#,
+            false,
+        );
+        config.event = HookEventKind::BeforeUserTurn;
+        let session_id = ThreadId::new();
+        let payload = HookPayload {
+            hook_event: HookEvent::BeforeUserTurn {
+                event: HookEventBeforeUserTurn {
+                    thread_id: session_id,
+                    turn_id: "turn-1".to_string(),
+                    input_messages: vec!["token=abc".to_string()],
+                },
+            },
+            session_id,
+            ..before_tool_use_payload(temp_dir.path().to_path_buf())
+        };
+
+        let outcome = command_hook(config).execute(&payload).await;
+
+        let HookResult::Context(decision) = outcome.result else {
+            panic!("expected context decision, got {:?}", outcome.result);
+        };
+        assert_eq!(
+            decision,
+            HookContextDecision::Block {
+                reason: "secret in prompt".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn command_hook_with_empty_output_succeeds() {
+        let temp_dir = tempdir().expect("tempdir");
+        let hook = command_hook(sh_hook("exit 0", false));
+
+        let outcome = hook
+            .execute(&before_tool_use_payload(temp_dir.path().to_path_buf()))
+            .await;
+
+        assert!(matches!(outcome.result, HookResult::Success));
+    }
+
+    #[tokio::test]
+    async fn command_hook_failure_respects_required_flag() {
+        let temp_dir = tempdir().expect("tempdir");
+        let payload = before_tool_use_payload(temp_dir.path().to_path_buf());
+
+        let optional = command_hook(sh_hook("exit 3", false))
+            .execute(&payload)
+            .await;
+        assert!(matches!(optional.result, HookResult::FailedContinue(_)));
+
+        let required = command_hook(sh_hook("exit 3", true))
+            .execute(&payload)
+            .await;
+        assert!(matches!(required.result, HookResult::FailedAbort(_)));
+    }
+
+    #[tokio::test]
+    async fn command_hook_times_out() {
+        let temp_dir = tempdir().expect("tempdir");
+        let mut config = sh_hook("sleep 5", false);
+        config.timeout = Duration::from_millis(100);
+
+        let outcome = command_hook(config)
+            .execute(&before_tool_use_payload(temp_dir.path().to_path_buf()))
+            .await;
+
+        let HookResult::FailedContinue(error) = outcome.result else {
+            panic!("expected timeout failure, got {:?}", outcome.result);
+        };
+        assert_eq!(error.to_string(), "hook timed out after 100ms");
+    }
+
+    #[tokio::test]
+    async fn command_hook_skips_non_matching_tool() {
+        let temp_dir = tempdir().expect("tempdir");
+        let mut config = sh_hook("exit 1", true);
+        config.matcher = HookMatcher {
+            tool_name: Some("apply_patch".to_string()),
+            ..HookMatcher::default()
+        };
+
+        let outcome = command_hook(config)
+            .execute(&before_tool_use_payload(temp_dir.path().to_path_buf()))
+            .await;
+
+        assert!(matches!(outcome.result, HookResult::Success));
+    }
+}
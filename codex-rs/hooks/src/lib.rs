@@ -1,17 +1,32 @@
+mod command_hook;
 mod registry;
 mod types;
 mod user_notification;
 
+pub use command_hook::CommandHookConfig;
+pub use command_hook::DEFAULT_COMMAND_HOOK_TIMEOUT;
+pub use command_hook::command_hook;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
 pub use registry::command_from_argv;
 pub use types::Hook;
+pub use types::HookCompactionTrigger;
+pub use types::HookContextDecision;
 pub use types::HookEvent;
 pub use types::HookEventAfterAgent;
 pub use types::HookEventAfterToolUse;
+pub use types::HookEventBeforeToolUse;
+pub use types::HookEventBeforeUserTurn;
+pub use types::HookEventCompaction;
+pub use types::HookEventKind;
+pub use types::HookEventSessionShutdown;
+pub use types::HookEventSessionStart;
+pub use types::HookMatcher;
 pub use types::HookPayload;
 pub use types::HookResponse;
 pub use types::HookResult;
+pub use types::HookSessionSource;
+pub use types::HookToolDecision;
 pub use types::HookToolInput;
 pub use types::HookToolInputLocalShell;
 pub use types::HookToolKind;
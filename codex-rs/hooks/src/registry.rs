@@ -1,19 +1,29 @@
 use tokio::process::Command;
 
+use crate::command_hook::CommandHookConfig;
 use crate::types::Hook;
 use crate::types::HookEvent;
+use crate::types::HookEventKind;
 use crate::types::HookPayload;
 use crate::types::HookResponse;
 
 #[derive(Default, Clone)]
 pub struct HooksConfig {
     pub legacy_notify_argv: Option<Vec<String>>,
+    /// Hooks declared in `[[hooks]]` config tables, in config layer order.
+    pub command_hooks: Vec<CommandHookConfig>,
 }
 
 #[derive(Clone)]
 pub struct Hooks {
+    session_start: Vec<Hook>,
+    before_user_turn: Vec<Hook>,
     after_agent: Vec<Hook>,
+    before_tool_use: Vec<Hook>,
     after_tool_use: Vec<Hook>,
+    before_compaction: Vec<Hook>,
+    after_compaction: Vec<Hook>,
+    session_shutdown: Vec<Hook>,
 }
 
 impl Default for Hooks {
@@ -23,7 +33,7 @@ impl Default for Hooks {
 }
 
 // Hooks are arbitrary, user-specified functions that are deterministically
-// executed after specific events in the Codex lifecycle.
+// executed around specific events in the Codex lifecycle.
 impl Hooks {
     pub fn new(config: HooksConfig) -> Self {
         let after_agent = config
@@ -32,16 +42,42 @@ impl Hooks {
             .map(crate::notify_hook)
             .into_iter()
             .collect();
-        Self {
+        let mut hooks = Self {
+            session_start: Vec::new(),
+            before_user_turn: Vec::new(),
             after_agent,
+            before_tool_use: Vec::new(),
             after_tool_use: Vec::new(),
+            before_compaction: Vec::new(),
+            after_compaction: Vec::new(),
+            session_shutdown: Vec::new(),
+        };
+        for command_hook in config.command_hooks {
+            let hooks_for_event = match command_hook.event {
+                HookEventKind::SessionStart => &mut hooks.session_start,
+                HookEventKind::BeforeUserTurn => &mut hooks.before_user_turn,
+                HookEventKind::AfterAgent => &mut hooks.after_agent,
+                HookEventKind::BeforeToolUse => &mut hooks.before_tool_use,
+                HookEventKind::AfterToolUse => &mut hooks.after_tool_use,
+                HookEventKind::BeforeCompaction => &mut hooks.before_compaction,
+                HookEventKind::AfterCompaction => &mut hooks.after_compaction,
+                HookEventKind::SessionShutdown => &mut hooks.session_shutdown,
+            };
+            hooks_for_event.push(crate::command_hook(command_hook));
         }
+        hooks
     }
 
     fn hooks_for_event(&self, hook_event: &HookEvent) -> &[Hook] {
         match hook_event {
+            HookEvent::SessionStart { .. } => &self.session_start,
+            HookEvent::BeforeUserTurn { .. } => &self.before_user_turn,
             HookEvent::AfterAgent { .. } => &self.after_agent,
+            HookEvent::BeforeToolUse { .. } => &self.before_tool_use,
             HookEvent::AfterToolUse { .. } => &self.after_tool_use,
+            HookEvent::BeforeCompaction { .. } => &self.before_compaction,
+            HookEvent::AfterCompaction { .. } => &self.after_compaction,
+            HookEvent::SessionShutdown { .. } => &self.session_shutdown,
         }
     }
 
@@ -93,7 +129,10 @@ mod tests {
     use super::*;
     use crate::types::HookEventAfterAgent;
     use crate::types::HookEventAfterToolUse;
+    use crate::types::HookEventBeforeToolUse;
+    use crate::types::HookMatcher;
     use crate::types::HookResult;
+    use crate::types::HookToolDecision;
     use crate::types::HookToolInput;
     use crate::types::HookToolKind;
 
@@ -199,6 +238,47 @@ mod tests {
         }
     }
 
+    fn before_tool_use_payload(label: &str) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from(CWD),
+            client: None,
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: format!("turn-{label}"),
+                    call_id: format!("call-{label}"),
+                    tool_name: "apply_patch".to_string(),
+                    tool_kind: HookToolKind::Custom,
+                    tool_input: HookToolInput::Custom {
+                        input: "*** Begin Patch".to_string(),
+                    },
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "danger-full-access".to_string(),
+                },
+            },
+        }
+    }
+
+    fn deciding_hook(calls: &Arc<AtomicUsize>, name: &str, decision: HookToolDecision) -> Hook {
+        let hook_name = name.to_string();
+        let calls = Arc::clone(calls);
+        Hook {
+            name: hook_name,
+            func: Arc::new(move |_| {
+                let calls = Arc::clone(&calls);
+                let decision = decision.clone();
+                Box::pin(async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    HookResult::Decision(decision)
+                })
+            }),
+        }
+    }
+
     #[test]
     fn command_from_argv_returns_none_for_empty_args() {
         assert!(command_from_argv(&[]).is_none());
@@ -231,6 +311,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec![]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -238,6 +319,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -245,6 +327,7 @@ mod tests {
         assert_eq!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .len(),
@@ -252,6 +335,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hooks_new_registers_command_hooks_by_event() {
+        let command_hook = |event| CommandHookConfig {
+            name: "audit".to_string(),
+            event,
+            matcher: HookMatcher::default(),
+            argv: vec!["audit".to_string()],
+            timeout: Duration::from_secs(1),
+            required: false,
+        };
+        let hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+            command_hooks: vec![
+                command_hook(HookEventKind::BeforeToolUse),
+                command_hook(HookEventKind::BeforeToolUse),
+                command_hook(HookEventKind::AfterAgent),
+            ],
+        });
+
+        assert_eq!(hooks.after_agent.len(), 2);
+        assert_eq!(hooks.before_tool_use.len(), 2);
+        assert!(hooks.after_tool_use.is_empty());
+    }
+
     #[tokio::test]
     async fn dispatch_executes_hook() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -328,6 +435,39 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn dispatch_stops_before_tool_use_hooks_on_deny() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![
+                deciding_hook(&calls, "allow", HookToolDecision::Allow),
+                deciding_hook(
+                    &calls,
+                    "deny",
+                    HookToolDecision::Deny {
+                        reason: "blocked".to_string(),
+                    },
+                ),
+                counting_success_hook(&calls, "counting"),
+            ],
+            ..Hooks::default()
+        };
+
+        let outcomes = hooks.dispatch(before_tool_use_payload("deny")).await;
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].hook_name, "allow");
+        assert!(matches!(
+            outcomes[0].result,
+            HookResult::Decision(HookToolDecision::Allow)
+        ));
+        assert_eq!(outcomes[1].hook_name, "deny");
+        assert!(matches!(
+            outcomes[1].result,
+            HookResult::Decision(HookToolDecision::Deny { .. })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn dispatch_continues_after_continueable_failure() {
         let calls = Arc::new(AtomicUsize::new(0));
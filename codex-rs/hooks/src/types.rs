@@ -7,6 +7,8 @@ use chrono::Utc;
 use codex_protocol::ThreadId;
 use codex_protocol::models::SandboxPermissions;
 use futures::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
 
@@ -22,14 +24,59 @@ pub enum HookResult {
     /// FailedAbort: hook failed, other subsequent hooks should not execute, and the operation
     /// should be aborted.
     FailedAbort(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// Decision: hook completed and returned a decision about the pending operation. Only
+    /// meaningful for `before_tool_use`; other events treat it like `Success`.
+    Decision(HookToolDecision),
+    /// Context: hook completed and either added context or blocked the operation. Only
+    /// meaningful for `session_start` and `before_user_turn`; other events treat it like
+    /// `Success`.
+    Context(HookContextDecision),
 }
 
 impl HookResult {
     pub fn should_abort_operation(&self) -> bool {
-        matches!(self, Self::FailedAbort(_))
+        matches!(
+            self,
+            Self::FailedAbort(_)
+                | Self::Decision(HookToolDecision::Deny { .. })
+                | Self::Context(HookContextDecision::Block { .. })
+        )
     }
 }
 
+/// Reply from a `before_tool_use` hook describing how the tool call should proceed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum HookToolDecision {
+    /// Continue with the regular approval and sandbox flow.
+    Allow,
+    /// Reject the tool call. `reason` is surfaced to the model.
+    Deny { reason: String },
+    /// Require an approval prompt even when policy would otherwise skip it. Calls to tools
+    /// that cannot prompt are rejected.
+    Ask {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// Replace the tool input before the call is handled, so approval and execpolicy see
+    /// the new input. It must keep the call's `input_type`, and MCP rewrites may only change
+    /// `arguments`.
+    Rewrite { tool_input: HookToolInput },
+}
+
+/// Reply from a `session_start` or `before_user_turn` hook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum HookContextDecision {
+    /// Proceed, optionally adding developer context for the model.
+    Continue {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        additional_context: Option<String>,
+    },
+    /// Refuse to start the session or submit the prompt. `reason` is shown to the user.
+    Block { reason: String },
+}
+
 #[derive(Debug)]
 pub struct HookResponse {
     pub hook_name: String,
@@ -72,6 +119,31 @@ pub struct HookPayload {
     pub hook_event: HookEvent,
 }
 
+/// How the session that fired `session_start` came to exist.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookSessionSource {
+    Startup,
+    Resume,
+    Fork,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionStart {
+    pub thread_id: ThreadId,
+    pub source: HookSessionSource,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeUserTurn {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub input_messages: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterAgent {
@@ -81,7 +153,7 @@ pub struct HookEventAfterAgent {
     pub last_assistant_message: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum HookToolKind {
     Function,
@@ -90,7 +162,7 @@ pub enum HookToolKind {
     Mcp,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookToolInputLocalShell {
     pub command: Vec<String>,
@@ -101,7 +173,7 @@ pub struct HookToolInputLocalShell {
     pub justification: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "input_type", rename_all = "snake_case")]
 pub enum HookToolInput {
     Function {
@@ -120,6 +192,29 @@ pub enum HookToolInput {
     },
 }
 
+impl HookToolInput {
+    pub fn tool_kind(&self) -> HookToolKind {
+        match self {
+            Self::Function { .. } => HookToolKind::Function,
+            Self::Custom { .. } => HookToolKind::Custom,
+            Self::LocalShell { .. } => HookToolKind::LocalShell,
+            Self::Mcp { .. } => HookToolKind::Mcp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeToolUse {
+    pub turn_id: String,
+    pub call_id: String,
+    pub tool_name: String,
+    pub tool_kind: HookToolKind,
+    pub tool_input: HookToolInput,
+    pub sandbox: String,
+    pub sandbox_policy: String,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterToolUse {
@@ -137,6 +232,31 @@ pub struct HookEventAfterToolUse {
     pub output_preview: String,
 }
 
+/// What started a context compaction.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookCompactionTrigger {
+    /// The user asked for it, e.g. with `/compact`.
+    Manual,
+    /// The conversation reached the model's auto-compact token limit.
+    Auto,
+}
+
+/// Payload shared by `before_compaction` and `after_compaction`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventCompaction {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub trigger: HookCompactionTrigger,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionShutdown {
+    pub thread_id: ThreadId,
+}
+
 fn serialize_triggered_at<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -147,14 +267,128 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum HookEvent {
+    SessionStart {
+        #[serde(flatten)]
+        event: HookEventSessionStart,
+    },
+    BeforeUserTurn {
+        #[serde(flatten)]
+        event: HookEventBeforeUserTurn,
+    },
     AfterAgent {
         #[serde(flatten)]
         event: HookEventAfterAgent,
     },
+    BeforeToolUse {
+        #[serde(flatten)]
+        event: HookEventBeforeToolUse,
+    },
     AfterToolUse {
         #[serde(flatten)]
         event: HookEventAfterToolUse,
     },
+    BeforeCompaction {
+        #[serde(flatten)]
+        event: HookEventCompaction,
+    },
+    AfterCompaction {
+        #[serde(flatten)]
+        event: HookEventCompaction,
+    },
+    SessionShutdown {
+        #[serde(flatten)]
+        event: HookEventSessionShutdown,
+    },
+}
+
+impl HookEvent {
+    pub fn kind(&self) -> HookEventKind {
+        match self {
+            Self::SessionStart { .. } => HookEventKind::SessionStart,
+            Self::BeforeUserTurn { .. } => HookEventKind::BeforeUserTurn,
+            Self::AfterAgent { .. } => HookEventKind::AfterAgent,
+            Self::BeforeToolUse { .. } => HookEventKind::BeforeToolUse,
+            Self::AfterToolUse { .. } => HookEventKind::AfterToolUse,
+            Self::BeforeCompaction { .. } => HookEventKind::BeforeCompaction,
+            Self::AfterCompaction { .. } => HookEventKind::AfterCompaction,
+            Self::SessionShutdown { .. } => HookEventKind::SessionShutdown,
+        }
+    }
+}
+
+/// Names of the events a configured hook can subscribe to. Mirrors the
+/// `event_type` tag of [`HookEvent`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEventKind {
+    SessionStart,
+    BeforeUserTurn,
+    AfterAgent,
+    BeforeToolUse,
+    AfterToolUse,
+    BeforeCompaction,
+    AfterCompaction,
+    SessionShutdown,
+}
+
+impl HookEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SessionStart => "session_start",
+            Self::BeforeUserTurn => "before_user_turn",
+            Self::AfterAgent => "after_agent",
+            Self::BeforeToolUse => "before_tool_use",
+            Self::AfterToolUse => "after_tool_use",
+            Self::BeforeCompaction => "before_compaction",
+            Self::AfterCompaction => "after_compaction",
+            Self::SessionShutdown => "session_shutdown",
+        }
+    }
+}
+
+/// Narrows a configured hook to specific tool calls. Every field that is set
+/// must match; an empty matcher matches every event.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookMatcher {
+    /// Exact tool name, e.g. `shell` or `apply_patch`.
+    pub tool_name: Option<String>,
+    /// Kind of tool input the call carries.
+    pub tool_kind: Option<HookToolKind>,
+    /// MCP server name; only MCP tool calls can match.
+    pub mcp_server: Option<String>,
+}
+
+impl HookMatcher {
+    pub fn is_empty(&self) -> bool {
+        self.tool_name.is_none() && self.tool_kind.is_none() && self.mcp_server.is_none()
+    }
+
+    pub fn matches(&self, hook_event: &HookEvent) -> bool {
+        let (tool_name, tool_input) = match hook_event {
+            HookEvent::BeforeToolUse { event } => (&event.tool_name, &event.tool_input),
+            HookEvent::AfterToolUse { event } => (&event.tool_name, &event.tool_input),
+            _ => return self.is_empty(),
+        };
+        if self
+            .tool_name
+            .as_ref()
+            .is_some_and(|expected| expected != tool_name)
+        {
+            return false;
+        }
+        if self
+            .tool_kind
+            .is_some_and(|expected| expected != tool_input.tool_kind())
+        {
+            return false;
+        }
+        match (&self.mcp_server, tool_input) {
+            (None, _) => true,
+            (Some(expected), HookToolInput::Mcp { server, .. }) => expected == server,
+            (Some(_), _) => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -168,10 +402,20 @@ mod tests {
     use pretty_assertions::assert_eq;
     use serde_json::json;
 
+    use super::HookCompactionTrigger;
+    use super::HookContextDecision;
     use super::HookEvent;
     use super::HookEventAfterAgent;
     use super::HookEventAfterToolUse;
+    use super::HookEventBeforeToolUse;
+    use super::HookEventBeforeUserTurn;
+    use super::HookEventCompaction;
+    use super::HookEventSessionShutdown;
+    use super::HookEventSessionStart;
+    use super::HookMatcher;
     use super::HookPayload;
+    use super::HookSessionSource;
+    use super::HookToolDecision;
     use super::HookToolInput;
     use super::HookToolInputLocalShell;
     use super::HookToolKind;
@@ -287,4 +531,320 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn before_tool_use_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            client: None,
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-3".to_string(),
+                    call_id: "call-2".to_string(),
+                    tool_name: "shell".to_string(),
+                    tool_kind: HookToolKind::LocalShell,
+                    tool_input: HookToolInput::LocalShell {
+                        params: HookToolInputLocalShell {
+                            command: vec!["kubectl".to_string(), "get".to_string()],
+                            workdir: None,
+                            timeout_ms: None,
+                            sandbox_permissions: None,
+                            justification: None,
+                            prefix_rule: None,
+                        },
+                    },
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "read-only".to_string(),
+                },
+            },
+        };
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "before_tool_use",
+                "turn_id": "turn-3",
+                "call_id": "call-2",
+                "tool_name": "shell",
+                "tool_kind": "local_shell",
+                "tool_input": {
+                    "input_type": "local_shell",
+                    "params": {
+                        "command": ["kubectl", "get"],
+                        "workdir": null,
+                        "timeout_ms": null,
+                        "sandbox_permissions": null,
+                        "justification": null,
+                        "prefix_rule": null,
+                    },
+                },
+                "sandbox": "none",
+                "sandbox_policy": "read-only",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn tool_decision_deserializes_from_wire_shape() {
+        let cases = vec![
+            (json!({"decision": "allow"}), HookToolDecision::Allow),
+            (
+                json!({"decision": "deny", "reason": "prod kubeconfig is off limits"}),
+                HookToolDecision::Deny {
+                    reason: "prod kubeconfig is off limits".to_string(),
+                },
+            ),
+            (
+                json!({"decision": "ask"}),
+                HookToolDecision::Ask { reason: None },
+            ),
+            (
+                json!({
+                    "decision": "rewrite",
+                    "tool_input": {"input_type": "function", "arguments": "{}"},
+                }),
+                HookToolDecision::Rewrite {
+                    tool_input: HookToolInput::Function {
+                        arguments: "{}".to_string(),
+                    },
+                },
+            ),
+        ];
+
+        for (wire, expected) in cases {
+            let actual: HookToolDecision =
+                serde_json::from_value(wire).expect("deserialize hook decision");
+            assert_eq!(actual, expected);
+        }
+    }
+
+    fn payload_at_epoch(session_id: ThreadId, hook_event: HookEvent) -> HookPayload {
+        HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            client: Some("codex-tui".to_string()),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event,
+        }
+    }
+
+    #[test]
+    fn session_start_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = payload_at_epoch(
+            session_id,
+            HookEvent::SessionStart {
+                event: HookEventSessionStart {
+                    thread_id: session_id,
+                    source: HookSessionSource::Resume,
+                    model: "gpt-5".to_string(),
+                },
+            },
+        );
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "client": "codex-tui",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "session_start",
+                "thread_id": session_id.to_string(),
+                "source": "resume",
+                "model": "gpt-5",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn before_user_turn_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = payload_at_epoch(
+            session_id,
+            HookEvent::BeforeUserTurn {
+                event: HookEventBeforeUserTurn {
+                    thread_id: session_id,
+                    turn_id: "turn-1".to_string(),
+                    input_messages: vec!["deploy to prod".to_string()],
+                },
+            },
+        );
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "client": "codex-tui",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "before_user_turn",
+                "thread_id": session_id.to_string(),
+                "turn_id": "turn-1",
+                "input_messages": ["deploy to prod"],
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn compaction_payloads_serialize_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let event = HookEventCompaction {
+            thread_id: session_id,
+            turn_id: "turn-1".to_string(),
+            trigger: HookCompactionTrigger::Auto,
+        };
+        let cases = [
+            (
+                HookEvent::BeforeCompaction {
+                    event: event.clone(),
+                },
+                "before_compaction",
+            ),
+            (HookEvent::AfterCompaction { event }, "after_compaction"),
+        ];
+
+        for (hook_event, event_type) in cases {
+            let actual = serde_json::to_value(payload_at_epoch(session_id, hook_event))
+                .expect("serialize hook payload");
+            let expected = json!({
+                "session_id": session_id.to_string(),
+                "cwd": "tmp",
+                "client": "codex-tui",
+                "triggered_at": "2025-01-01T00:00:00Z",
+                "hook_event": {
+                    "event_type": event_type,
+                    "thread_id": session_id.to_string(),
+                    "turn_id": "turn-1",
+                    "trigger": "auto",
+                },
+            });
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn session_shutdown_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = payload_at_epoch(
+            session_id,
+            HookEvent::SessionShutdown {
+                event: HookEventSessionShutdown {
+                    thread_id: session_id,
+                },
+            },
+        );
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "client": "codex-tui",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "session_shutdown",
+                "thread_id": session_id.to_string(),
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn context_decision_deserializes_from_wire_shape() {
+        let cases = [
+            (
+                json!({"decision": "continue"}),
+                HookContextDecision::Continue {
+                    additional_context: None,
+                },
+            ),
+            (
+                json!({"decision": "continue", "additional_context": "on-call: alice"}),
+                HookContextDecision::Continue {
+                    additional_context: Some("on-call: alice".to_string()),
+                },
+            ),
+            (
+                json!({"decision": "block", "reason": "prompt contains a secret"}),
+                HookContextDecision::Block {
+                    reason: "prompt contains a secret".to_string(),
+                },
+            ),
+        ];
+
+        for (wire, expected) in cases {
+            let actual: HookContextDecision =
+                serde_json::from_value(wire).expect("deserialize hook context decision");
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn matcher_filters_tool_events() {
+        let mcp_event = HookEvent::BeforeToolUse {
+            event: HookEventBeforeToolUse {
+                turn_id: "turn-1".to_string(),
+                call_id: "call-1".to_string(),
+                tool_name: "kubectl_apply".to_string(),
+                tool_kind: HookToolKind::Mcp,
+                tool_input: HookToolInput::Mcp {
+                    server: "kube".to_string(),
+                    tool: "apply".to_string(),
+                    arguments: "{}".to_string(),
+                },
+                sandbox: "none".to_string(),
+                sandbox_policy: "read-only".to_string(),
+            },
+        };
+        let agent_event = HookEvent::AfterAgent {
+            event: HookEventAfterAgent {
+                thread_id: ThreadId::new(),
+                turn_id: "turn-1".to_string(),
+                input_messages: Vec::new(),
+                last_assistant_message: None,
+            },
+        };
+
+        assert!(HookMatcher::default().matches(&mcp_event));
+        assert!(HookMatcher::default().matches(&agent_event));
+
+        let by_server = HookMatcher {
+            mcp_server: Some("kube".to_string()),
+            ..HookMatcher::default()
+        };
+        assert!(by_server.matches(&mcp_event));
+        assert!(!by_server.matches(&agent_event));
+
+        let by_kind = HookMatcher {
+            tool_kind: Some(HookToolKind::LocalShell),
+            ..HookMatcher::default()
+        };
+        assert!(!by_kind.matches(&mcp_event));
+
+        let by_name = HookMatcher {
+            tool_name: Some("kubectl_apply".to_string()),
+            mcp_server: Some("other".to_string()),
+            ..HookMatcher::default()
+        };
+        assert!(!by_name.matches(&mcp_event));
+    }
 }
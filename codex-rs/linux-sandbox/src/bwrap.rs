@@ -4,7 +4,12 @@
 //! - the filesystem is read-only by default,
 //! - explicit writable roots are layered on top, and
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
-//!   their parent root is writable.
+//!   their parent root is writable, and
+//! - denied read paths are hidden behind empty overmounts.
+//!
+//! With a workspace overlay, the sandbox cwd is mounted as a copy-on-write
+//! overlayfs instead of a writable bind, so writes land in the overlay's upper
+//! directory and the real workspace is left untouched.
 //!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
@@ -13,6 +18,7 @@ use std::collections::BTreeSet;
 use std::path::Path;
 use std::path::PathBuf;
 
+use codex_core::denied_read_paths::DeniedReadPaths;
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
 use codex_protocol::protocol::SandboxPolicy;
@@ -20,7 +26,7 @@ use codex_protocol::protocol::WritableRoot;
 
 /// Options that control how bubblewrap is invoked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct BwrapOptions {
+pub(crate) struct BwrapOptions<'a> {
     /// Whether to mount a fresh `/proc` inside the PID namespace.
     ///
     /// This is the secure default, but some restrictive container environments
@@ -28,13 +34,17 @@ pub(crate) struct BwrapOptions {
     pub mount_proc: bool,
     /// How networking should be configured inside the bubblewrap sandbox.
     pub network_mode: BwrapNetworkMode,
+    /// Directory holding the `upper` and `work` directories of a
+    /// copy-on-write overlay to mount over the sandbox cwd.
+    pub workspace_overlay: Option<&'a Path>,
 }
 
-impl Default for BwrapOptions {
+impl Default for BwrapOptions<'_> {
     fn default() -> Self {
         Self {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            workspace_overlay: None,
         }
     }
 }
@@ -71,7 +81,7 @@ pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
-    options: BwrapOptions,
+    options: BwrapOptions<'_>,
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
         return if options.network_mode == BwrapNetworkMode::FullAccess {
@@ -84,7 +94,10 @@ pub(crate) fn create_bwrap_command_args(
     create_bwrap_flags(command, sandbox_policy, cwd, options)
 }
 
-fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOptions) -> Vec<String> {
+fn create_bwrap_flags_full_filesystem(
+    command: Vec<String>,
+    options: BwrapOptions<'_>,
+) -> Vec<String> {
     let mut args = vec![
         "--new-session".to_string(),
         "--die-with-parent".to_string(),
@@ -110,12 +123,16 @@ fn create_bwrap_flags(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
-    options: BwrapOptions,
+    options: BwrapOptions<'_>,
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    args.extend(create_filesystem_args(
+        sandbox_policy,
+        cwd,
+        options.workspace_overlay,
+    )?);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
     if options.network_mode.should_unshare_network() {
@@ -138,10 +155,19 @@ fn create_bwrap_flags(
 /// 2. `--dev /dev` mounts a minimal writable `/dev` with standard device nodes
 ///    (including `/dev/urandom`) even under a read-only root.
 /// 3. `--bind <root> <root>` re-enables writes for allowed roots, including
-///    writable subpaths under `/dev` (for example, `/dev/shm`).
+///    writable subpaths under `/dev` (for example, `/dev/shm`). With a
+///    workspace overlay, `cwd` is mounted as an overlayfs whose writes go to
+///    the overlay's upper directory instead.
 /// 4. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+/// 5. Denied read paths are covered last: directories with an empty read-only
+///    tmpfs and files with `/dev/null`. Only paths that exist when the command
+///    starts can be covered this way.
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    workspace_overlay: Option<&Path>,
+) -> Result<Vec<String>> {
     if !sandbox_policy.has_full_disk_read_access() {
         return Err(CodexErr::UnsupportedOperation(
             "Restricted read-only access is not yet supported by the Linux bubblewrap backend."
@@ -167,6 +193,17 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
 
     for writable_root in &writable_roots {
         let root = writable_root.root.as_path();
+        if let Some(overlay_dir) = workspace_overlay
+            && root == cwd
+        {
+            args.push("--overlay-src".to_string());
+            args.push(path_to_string(root));
+            args.push("--overlay".to_string());
+            args.push(path_to_string(&overlay_dir.join("upper")));
+            args.push(path_to_string(&overlay_dir.join("work")));
+            args.push(path_to_string(root));
+            continue;
+        }
         args.push("--bind".to_string());
         args.push(path_to_string(root));
         args.push(path_to_string(root));
@@ -207,6 +244,20 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         }
     }
 
+    for denied_path in DeniedReadPaths::from_policy(sandbox_policy, cwd).existing_paths()? {
+        let denied_path_str = path_to_string(&denied_path);
+        if denied_path.is_dir() {
+            args.push("--tmpfs".to_string());
+            args.push(denied_path_str.clone());
+            args.push("--remount-ro".to_string());
+            args.push(denied_path_str);
+        } else {
+            args.push("--ro-bind".to_string());
+            args.push("/dev/null".to_string());
+            args.push(denied_path_str);
+        }
+    }
+
     Ok(args)
 }
 
@@ -336,6 +387,7 @@ mod tests {
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
+                workspace_overlay: None,
             },
         )
         .expect("create bwrap args");
@@ -353,6 +405,7 @@ mod tests {
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
+                workspace_overlay: None,
             },
         )
         .expect("create bwrap args");
@@ -383,9 +436,11 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         };
 
-        let args = create_filesystem_args(&sandbox_policy, Path::new("/")).expect("bwrap fs args");
+        let args =
+            create_filesystem_args(&sandbox_policy, Path::new("/"), None).expect("bwrap fs args");
         assert_eq!(
             args,
             vec![
@@ -403,4 +458,83 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn hides_denied_read_paths_after_writable_binds() {
+        let temp_dir = tempfile::TempDir::new().expect("tempdir");
+        let cwd = temp_dir.path().canonicalize().expect("canonical tempdir");
+        std::fs::create_dir(cwd.join(".aws")).expect("create .aws");
+        std::fs::write(cwd.join(".env"), "TOKEN=secret").expect("write .env");
+        std::fs::write(cwd.join("main.rs"), "fn main() {}").expect("write main.rs");
+        let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            denied_read_paths: vec![".env*".to_string(), "./.aws".to_string()],
+        };
+
+        let args = create_filesystem_args(&sandbox_policy, &cwd, None).expect("bwrap fs args");
+        let cwd_str = path_to_string(&cwd);
+        let aws = path_to_string(&cwd.join(".aws"));
+        let env = path_to_string(&cwd.join(".env"));
+        assert_eq!(
+            args,
+            vec![
+                "--ro-bind".to_string(),
+                "/".to_string(),
+                "/".to_string(),
+                "--dev".to_string(),
+                "/dev".to_string(),
+                "--bind".to_string(),
+                cwd_str.clone(),
+                cwd_str,
+                "--tmpfs".to_string(),
+                aws.clone(),
+                "--remount-ro".to_string(),
+                aws,
+                "--ro-bind".to_string(),
+                "/dev/null".to_string(),
+                env,
+            ]
+        );
+    }
+
+    #[test]
+    fn mounts_workspace_overlay_over_cwd_instead_of_binding_it() {
+        let temp_dir = tempfile::TempDir::new().expect("tempdir");
+        let cwd = temp_dir.path().join("repo");
+        std::fs::create_dir(&cwd).expect("create repo");
+        let cwd = cwd.canonicalize().expect("canonical repo");
+        let overlay_dir = temp_dir.path().join("overlay");
+        let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
+        };
+
+        let args = create_filesystem_args(&sandbox_policy, &cwd, Some(&overlay_dir))
+            .expect("bwrap fs args");
+        let cwd_str = path_to_string(&cwd);
+        assert_eq!(
+            args,
+            vec![
+                "--ro-bind".to_string(),
+                "/".to_string(),
+                "/".to_string(),
+                "--dev".to_string(),
+                "/dev".to_string(),
+                "--overlay-src".to_string(),
+                cwd_str.clone(),
+                "--overlay".to_string(),
+                path_to_string(&overlay_dir.join("upper")),
+                path_to_string(&overlay_dir.join("work")),
+                cwd_str,
+            ]
+        );
+    }
 }
@@ -2,7 +2,8 @@
 //!
 //! On Linux, `codex-linux-sandbox` applies:
 //! - in-process restrictions (`no_new_privs` + seccomp), and
-//! - bubblewrap for filesystem isolation.
+//! - bubblewrap for filesystem isolation, and
+//! - optional resource limits (rlimits plus a cgroup v2 sub-tree).
 #[cfg(target_os = "linux")]
 mod bwrap;
 #[cfg(target_os = "linux")]
@@ -12,6 +13,8 @@ mod linux_run_main;
 #[cfg(target_os = "linux")]
 mod proxy_routing;
 #[cfg(target_os = "linux")]
+mod resource_limits;
+#[cfg(target_os = "linux")]
 mod vendored_bwrap;
 
 #[cfg(target_os = "linux")]
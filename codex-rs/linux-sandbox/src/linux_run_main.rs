@@ -12,6 +12,7 @@ use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
 use crate::proxy_routing::activate_proxy_routes_in_netns;
 use crate::proxy_routing::prepare_host_proxy_route_spec;
+use crate::resource_limits::fork_resource_supervisor;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
 
@@ -61,6 +62,26 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Internal: `[sandbox_resources]` limits, as JSON, enforced on the
+    /// command and everything it spawns.
+    #[arg(long = "resource-limits", hide = true)]
+    pub resource_limits: Option<codex_protocol::config_types::SandboxResourceLimits>,
+
+    /// Internal: existing file the supervisor writes the exceeded
+    /// `--resource-limits` entry to.
+    #[arg(
+        long = "resource-limit-report",
+        hide = true,
+        requires = "resource_limits"
+    )]
+    pub resource_limit_report: Option<PathBuf>,
+
+    /// Internal: directory holding the `upper` and `work` directories of a
+    /// copy-on-write overlay mounted over the sandbox policy cwd. Requires
+    /// `--use-bwrap-sandbox`.
+    #[arg(long = "workspace-overlay", hide = true)]
+    pub workspace_overlay: Option<PathBuf>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -69,10 +90,12 @@ pub struct LandlockCommand {
 /// Entry point for the Linux sandbox helper.
 ///
 /// The sequence is:
-/// 1. When needed, wrap the command with bubblewrap to construct the
+/// 1. When resource limits are set, fork a supervisor that enforces them and
+///    continue in the limited child.
+/// 2. When needed, wrap the command with bubblewrap to construct the
 ///    filesystem view.
-/// 2. Apply in-process restrictions (no_new_privs + seccomp).
-/// 3. `execvp` into the final command.
+/// 3. Apply in-process restrictions (no_new_privs + seccomp).
+/// 4. `execvp` into the final command.
 pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
@@ -82,6 +105,9 @@ pub fn run_main() -> ! {
         allow_network_for_proxy,
         proxy_route_spec,
         no_proc,
+        resource_limits,
+        resource_limit_report,
+        workspace_overlay,
         command,
     } = LandlockCommand::parse();
 
@@ -89,6 +115,16 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
     ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec, use_bwrap_sandbox);
+    ensure_denied_read_paths_are_enforceable(&sandbox_policy, use_bwrap_sandbox);
+    if workspace_overlay.is_some() && !use_bwrap_sandbox {
+        panic!("--workspace-overlay requires --use-bwrap-sandbox");
+    }
+
+    // The inner stage never receives `--resource-limits`; the outer stage's
+    // supervisor already covers it.
+    if let Some(resource_limits) = resource_limits.filter(|limits| !limits.is_empty()) {
+        fork_resource_supervisor(&resource_limits, resource_limit_report.as_deref());
+    }
 
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
     // established the filesystem view.
@@ -153,6 +189,7 @@ pub fn run_main() -> ! {
             inner,
             !no_proc,
             allow_network_for_proxy,
+            workspace_overlay.as_deref(),
         );
     }
 
@@ -175,12 +212,24 @@ fn ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec: bool, use_bwrap_san
     }
 }
 
+/// Landlock cannot hide individual paths inside otherwise readable trees, so a
+/// policy with denied read paths must go through bubblewrap.
+fn ensure_denied_read_paths_are_enforceable(
+    sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    use_bwrap_sandbox: bool,
+) {
+    if !use_bwrap_sandbox && !sandbox_policy.denied_read_paths().is_empty() {
+        panic!("denied read paths require --use-bwrap-sandbox");
+    }
+}
+
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
     inner: Vec<String>,
     mount_proc: bool,
     allow_network_for_proxy: bool,
+    workspace_overlay: Option<&Path>,
 ) -> ! {
     let network_mode = bwrap_network_mode(sandbox_policy, allow_network_for_proxy);
     let mut mount_proc = mount_proc;
@@ -194,6 +243,7 @@ fn run_bwrap_with_proc_fallback(
     let options = BwrapOptions {
         mount_proc,
         network_mode,
+        workspace_overlay,
     };
     let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
     exec_vendored_bwrap(argv);
@@ -216,7 +266,7 @@ fn build_bwrap_argv(
     inner: Vec<String>,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
-    options: BwrapOptions,
+    options: BwrapOptions<'_>,
 ) -> Vec<String> {
     let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
         .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
@@ -259,6 +309,7 @@ fn build_preflight_bwrap_argv(
         BwrapOptions {
             mount_proc: true,
             network_mode,
+            workspace_overlay: None,
         },
     )
 }
@@ -27,6 +27,26 @@ fn ignores_non_proc_mount_errors() {
     assert!(!is_proc_mount_failure(stderr));
 }
 
+#[test]
+#[should_panic(expected = "denied read paths require --use-bwrap-sandbox")]
+fn legacy_landlock_refuses_denied_read_paths() {
+    let policy = SandboxPolicy::ReadOnly {
+        access: Default::default(),
+        denied_read_paths: vec![".env*".to_string()],
+    };
+    ensure_denied_read_paths_are_enforceable(&policy, false);
+}
+
+#[test]
+fn bwrap_accepts_denied_read_paths() {
+    let policy = SandboxPolicy::ReadOnly {
+        access: Default::default(),
+        denied_read_paths: vec![".env*".to_string()],
+    };
+    ensure_denied_read_paths_are_enforceable(&policy, true);
+    ensure_denied_read_paths_are_enforceable(&SandboxPolicy::new_read_only_policy(), false);
+}
+
 #[test]
 fn inserts_bwrap_argv0_before_command_separator() {
     let argv = build_bwrap_argv(
@@ -36,6 +56,7 @@ fn inserts_bwrap_argv0_before_command_separator() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            workspace_overlay: None,
         },
     );
     assert_eq!(
@@ -69,6 +90,7 @@ fn inserts_unshare_net_when_network_isolation_requested() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::Isolated,
+            workspace_overlay: None,
         },
     );
     assert!(argv.contains(&"--unshare-net".to_string()));
@@ -83,6 +105,7 @@ fn inserts_unshare_net_when_proxy_only_network_mode_requested() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::ProxyOnly,
+            workspace_overlay: None,
         },
     );
     assert!(argv.contains(&"--unshare-net".to_string()));
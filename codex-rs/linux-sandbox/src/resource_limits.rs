@@ -0,0 +1,618 @@
+//! Resource limits for the sandboxed command.
+//!
+//! When `--resource-limits` is set, the helper forks before it builds the
+//! sandbox. The child moves into a fresh cgroup v2 sub-tree when one can be
+//! created, applies rlimits and carries on with the normal sandbox setup. The
+//! parent stays outside the sandbox, waits for the child, enforces the
+//! wall-clock limit and reports which limit, if any, stopped the command.
+//!
+//! The report goes to the `--resource-limit-report` file, which Codex places
+//! outside every writable root of the sandbox policy so the command cannot
+//! forge it. A note on stderr tells the command's reader what happened.
+
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::config_types::SandboxResourceLimits;
+use codex_protocol::protocol::ResourceLimitKind;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const CGROUP_NAME_PREFIX: &str = "codex-exec-";
+const WALL_CLOCK_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[cfg(target_env = "gnu")]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(not(target_env = "gnu"))]
+type RlimitResource = libc::c_int;
+
+/// Fork a supervisor that enforces `limits` on the rest of the helper run.
+///
+/// Returns in the child, which should continue with the sandbox setup and
+/// exec the command. The parent never returns: it exits with the child's
+/// status once the child is gone, after writing the exceeded limit, if any,
+/// to `report`.
+pub(crate) fn fork_resource_supervisor(limits: &SandboxResourceLimits, report: Option<&Path>) {
+    let cgroup = CommandCgroup::create(limits);
+
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to fork resource limit supervisor: {err}");
+    }
+
+    if pid == 0 {
+        let in_cgroup = cgroup
+            .as_ref()
+            .is_some_and(|cgroup| cgroup.add_self().is_ok());
+        if let Err(err) = apply_rlimits(limits, in_cgroup) {
+            panic!("error applying resource limits: {err}");
+        }
+        return;
+    }
+
+    let deadline = limits
+        .max_wall_clock_seconds
+        .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+    let (status, wall_clock_expired) = wait_for_child(pid, deadline, cgroup.as_ref());
+    let events = cgroup
+        .as_ref()
+        .map(CommandCgroup::events)
+        .unwrap_or_default();
+    if let Some(cgroup) = cgroup {
+        cgroup.remove();
+    }
+
+    let exit = ChildExit::from_wait_status(status);
+    match exceeded_limit(limits, exit, wall_clock_expired, events) {
+        Some(kind) => {
+            if let Some(report) = report
+                && let Err(err) = write_report(report, kind)
+                && err.kind() != io::ErrorKind::NotFound
+            {
+                eprintln!("codex-linux-sandbox: failed to write resource limit report: {err}");
+            }
+            eprintln!("codex-linux-sandbox: resource limit exceeded: {kind}");
+            std::process::exit(exit.shell_exit_code());
+        }
+        None => exit.propagate(),
+    }
+}
+
+/// Write `kind` to the report file Codex created for this command. The file
+/// is never created here: a missing file means Codex no longer waits for it.
+fn write_report(report: &Path, kind: ResourceLimitKind) -> io::Result<()> {
+    fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(report)?
+        .write_all(kind.to_string().as_bytes())
+}
+
+/// How the supervised child ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChildExit {
+    Code(i32),
+    Signal(i32),
+}
+
+impl ChildExit {
+    fn from_wait_status(status: libc::c_int) -> Self {
+        if libc::WIFSIGNALED(status) {
+            Self::Signal(libc::WTERMSIG(status))
+        } else {
+            Self::Code(libc::WEXITSTATUS(status))
+        }
+    }
+
+    /// Signal that ended the command, either directly or as reported by a
+    /// shell or bubblewrap through the `128 + signal` exit code convention.
+    fn terminating_signal(self) -> Option<i32> {
+        match self {
+            Self::Signal(signal) => Some(signal),
+            Self::Code(code) if code > 128 => Some(code - 128),
+            Self::Code(_) => None,
+        }
+    }
+
+    fn is_success(self) -> bool {
+        self == Self::Code(0)
+    }
+
+    fn shell_exit_code(self) -> i32 {
+        match self {
+            Self::Code(code) => code,
+            Self::Signal(signal) => 128 + signal,
+        }
+    }
+
+    /// Exit the supervisor the same way the child exited, so callers see the
+    /// same status they would without a supervisor in between.
+    fn propagate(self) -> ! {
+        if let Self::Signal(signal) = self {
+            unsafe {
+                libc::signal(signal, libc::SIG_DFL);
+                libc::kill(libc::getpid(), signal);
+            }
+        }
+        std::process::exit(self.shell_exit_code());
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CgroupEvents {
+    oom_kills: u64,
+    pids_max_hits: u64,
+}
+
+fn exceeded_limit(
+    limits: &SandboxResourceLimits,
+    exit: ChildExit,
+    wall_clock_expired: bool,
+    events: CgroupEvents,
+) -> Option<ResourceLimitKind> {
+    if wall_clock_expired {
+        return Some(ResourceLimitKind::WallClock);
+    }
+    if events.oom_kills > 0 {
+        return Some(ResourceLimitKind::Memory);
+    }
+    let signal = exit.terminating_signal();
+    if limits.max_cpu_seconds.is_some() && signal == Some(libc::SIGXCPU) {
+        return Some(ResourceLimitKind::CpuTime);
+    }
+    if limits.max_file_size_bytes.is_some() && signal == Some(libc::SIGXFSZ) {
+        return Some(ResourceLimitKind::FileSize);
+    }
+    // Hitting `pids.max` makes fork fail rather than killing anything, so it
+    // only explains a failed command.
+    if events.pids_max_hits > 0 && !exit.is_success() {
+        return Some(ResourceLimitKind::Processes);
+    }
+    None
+}
+
+fn wait_for_child(
+    pid: libc::pid_t,
+    deadline: Option<Instant>,
+    cgroup: Option<&CommandCgroup>,
+) -> (libc::c_int, bool) {
+    let mut status: libc::c_int = 0;
+    let Some(deadline) = deadline else {
+        waitpid_or_panic(pid, &mut status, 0);
+        return (status, false);
+    };
+
+    loop {
+        if waitpid_or_panic(pid, &mut status, libc::WNOHANG) == pid {
+            return (status, false);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        std::thread::sleep(WALL_CLOCK_POLL_INTERVAL.min(deadline - now));
+    }
+
+    if let Some(cgroup) = cgroup {
+        cgroup.kill();
+    }
+    unsafe {
+        libc::kill(pid, libc::SIGKILL);
+    }
+    waitpid_or_panic(pid, &mut status, 0);
+    (status, true)
+}
+
+fn waitpid_or_panic(pid: libc::pid_t, status: &mut libc::c_int, options: libc::c_int) -> i32 {
+    loop {
+        let res = unsafe { libc::waitpid(pid, status as *mut libc::c_int, options) };
+        if res >= 0 {
+            return res;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            panic!("waitpid failed for sandboxed command: {err}");
+        }
+    }
+}
+
+/// Apply the per-process limits. Memory and process counts fall back to
+/// `RLIMIT_DATA` and `RLIMIT_NPROC` when the process is not in a cgroup that
+/// enforces them.
+fn apply_rlimits(limits: &SandboxResourceLimits, in_cgroup: bool) -> io::Result<()> {
+    if let Some(seconds) = limits.max_cpu_seconds {
+        // A soft limit below the hard one delivers SIGXCPU before SIGKILL,
+        // which is how the supervisor tells a CPU limit from other kills.
+        set_rlimit(libc::RLIMIT_CPU, seconds, seconds.saturating_add(1))?;
+    }
+    if let Some(files) = limits.max_open_files {
+        set_rlimit(libc::RLIMIT_NOFILE, files, files)?;
+    }
+    if let Some(bytes) = limits.max_file_size_bytes {
+        set_rlimit(libc::RLIMIT_FSIZE, bytes, bytes)?;
+    }
+    if !in_cgroup {
+        if let Some(bytes) = limits.max_memory_bytes {
+            set_rlimit(libc::RLIMIT_DATA, bytes, bytes)?;
+        }
+        if let Some(processes) = limits.max_processes {
+            // RLIMIT_NPROC counts every task of the user, so leave room for
+            // the ones that already exist.
+            let uid = unsafe { libc::getuid() };
+            let limit = count_user_tasks(uid).saturating_add(processes);
+            set_rlimit(libc::RLIMIT_NPROC, limit, limit)?;
+        }
+    }
+    Ok(())
+}
+
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn count_user_tasks(uid: libc::uid_t) -> u64 {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().parse::<u32>().is_ok())
+        .filter_map(|entry| fs::read_to_string(entry.path().join("status")).ok())
+        .filter_map(|status| user_task_count(&status, uid))
+        .sum()
+}
+
+/// Number of threads listed in a `/proc/<pid>/status` file when the process
+/// belongs to `uid`.
+fn user_task_count(status: &str, uid: libc::uid_t) -> Option<u64> {
+    let real_uid = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))?
+        .split_whitespace()
+        .next()?;
+    if real_uid != uid.to_string() {
+        return None;
+    }
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// A cgroup v2 directory created for a single command.
+///
+/// The cgroup is created next to the helper's own cgroup rather than below
+/// it: cgroup v2 does not let a cgroup that holds processes delegate
+/// controllers to its children.
+#[derive(Debug)]
+struct CommandCgroup {
+    dir: PathBuf,
+}
+
+impl CommandCgroup {
+    fn create(limits: &SandboxResourceLimits) -> Option<Self> {
+        let controllers: Vec<&str> = [
+            limits.max_memory_bytes.map(|_| "memory"),
+            limits.max_processes.map(|_| "pids"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if controllers.is_empty() {
+            return None;
+        }
+
+        let contents = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let current = parse_unified_cgroup_path(&contents)?;
+        let parent = current.parent().unwrap_or(current.as_path());
+        let parent_dir = Path::new(CGROUP_ROOT).join(parent.strip_prefix("/").ok()?);
+        // Only a cgroup v2 mount has this file; on a v1 or hybrid host the
+        // directory would be a plain tmpfs directory.
+        if !parent_dir.join("cgroup.controllers").is_file() {
+            return None;
+        }
+        remove_stale_cgroups(&parent_dir);
+
+        let dir = parent_dir.join(format!("{CGROUP_NAME_PREFIX}{}", std::process::id()));
+        fs::create_dir(&dir).ok()?;
+        let cgroup = Self { dir };
+        if cgroup.configure(&parent_dir, &controllers, limits).is_err() {
+            cgroup.remove();
+            return None;
+        }
+        Some(cgroup)
+    }
+
+    fn configure(
+        &self,
+        parent_dir: &Path,
+        controllers: &[&str],
+        limits: &SandboxResourceLimits,
+    ) -> io::Result<()> {
+        let enabled = fs::read_to_string(parent_dir.join("cgroup.subtree_control"))?;
+        for controller in controllers {
+            if !enabled.split_whitespace().any(|name| name == *controller) {
+                write_cgroup_file(
+                    &parent_dir.join("cgroup.subtree_control"),
+                    &format!("+{controller}"),
+                )?;
+            }
+        }
+
+        if let Some(bytes) = limits.max_memory_bytes {
+            write_cgroup_file(&self.dir.join("memory.max"), &bytes.to_string())?;
+            // Without this the kernel swaps instead of OOM-killing, which
+            // defeats the point of a memory limit. Not every host has swap
+            // accounting, so a failure here is not fatal.
+            let _ = write_cgroup_file(&self.dir.join("memory.swap.max"), "0");
+        }
+        if let Some(processes) = limits.max_processes {
+            write_cgroup_file(&self.dir.join("pids.max"), &processes.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn add_self(&self) -> io::Result<()> {
+        write_cgroup_file(&self.dir.join("cgroup.procs"), "0")
+    }
+
+    fn events(&self) -> CgroupEvents {
+        let read_counter = |file: &str, key: &str| {
+            fs::read_to_string(self.dir.join(file))
+                .ok()
+                .and_then(|contents| parse_event_counter(&contents, key))
+                .unwrap_or(0)
+        };
+        CgroupEvents {
+            oom_kills: read_counter("memory.events", "oom_kill"),
+            pids_max_hits: read_counter("pids.events", "max"),
+        }
+    }
+
+    fn kill(&self) {
+        let _ = write_cgroup_file(&self.dir.join("cgroup.kill"), "1");
+    }
+
+    /// Kill whatever is left in the cgroup and remove it. Processes take a
+    /// moment to leave after being killed, so removal is retried briefly.
+    fn remove(self) {
+        self.kill();
+        for _ in 0..50 {
+            if fs::remove_dir(&self.dir).is_ok() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+}
+
+/// Write to an existing cgroup interface file. Unlike [`fs::write`], this
+/// never creates the file, which would make the cgroup impossible to remove.
+fn write_cgroup_file(path: &Path, contents: &str) -> io::Result<()> {
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Remove cgroups left behind by supervisors that were killed before they
+/// could clean up. A cgroup is named after the supervisor that created it and
+/// is only removed once that process is gone, so a concurrent helper that has
+/// not moved its child in yet keeps its empty cgroup.
+fn remove_stale_cgroups(parent_dir: &Path) {
+    let Ok(entries) = fs::read_dir(parent_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(supervisor_pid) else {
+            continue;
+        };
+        if !process_is_alive(pid) {
+            let _ = fs::remove_dir(entry.path());
+        }
+    }
+}
+
+/// Pid of the supervisor that created the cgroup called `name`.
+fn supervisor_pid(name: &str) -> Option<libc::pid_t> {
+    name.strip_prefix(CGROUP_NAME_PREFIX)?
+        .parse()
+        .ok()
+        .filter(|pid| *pid > 0)
+}
+
+fn process_is_alive(pid: libc::pid_t) -> bool {
+    // Signal 0 only checks for existence. EPERM means the process exists but
+    // belongs to another user.
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Path of the cgroup v2 hierarchy entry (`0::/path`) in `/proc/self/cgroup`.
+fn parse_unified_cgroup_path(contents: &str) -> Option<PathBuf> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(PathBuf::from)
+}
+
+fn parse_event_counter(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        if name == key {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn limits() -> SandboxResourceLimits {
+        SandboxResourceLimits {
+            max_memory_bytes: Some(1 << 30),
+            max_cpu_seconds: Some(60),
+            max_processes: Some(256),
+            max_open_files: Some(1024),
+            max_file_size_bytes: Some(1 << 20),
+            max_wall_clock_seconds: Some(600),
+        }
+    }
+
+    #[test]
+    fn exceeded_limit_prefers_wall_clock_and_oom_over_exit_status() {
+        let oom = CgroupEvents {
+            oom_kills: 1,
+            pids_max_hits: 0,
+        };
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Signal(libc::SIGKILL), true, oom),
+            Some(ResourceLimitKind::WallClock)
+        );
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Code(137), false, oom),
+            Some(ResourceLimitKind::Memory)
+        );
+    }
+
+    #[test]
+    fn exceeded_limit_recognizes_rlimit_signals_through_shell_exit_codes() {
+        let none = CgroupEvents::default();
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Signal(libc::SIGXCPU), false, none),
+            Some(ResourceLimitKind::CpuTime)
+        );
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Code(128 + libc::SIGXFSZ), false, none),
+            Some(ResourceLimitKind::FileSize)
+        );
+        assert_eq!(
+            exceeded_limit(
+                &SandboxResourceLimits::default(),
+                ChildExit::Signal(libc::SIGXCPU),
+                false,
+                none
+            ),
+            None
+        );
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Code(1), false, none),
+            None
+        );
+    }
+
+    #[test]
+    fn pids_limit_only_explains_failed_commands() {
+        let pids = CgroupEvents {
+            oom_kills: 0,
+            pids_max_hits: 3,
+        };
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Code(1), false, pids),
+            Some(ResourceLimitKind::Processes)
+        );
+        assert_eq!(
+            exceeded_limit(&limits(), ChildExit::Code(0), false, pids),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_cgroup_files() {
+        assert_eq!(
+            parse_unified_cgroup_path("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            Some(PathBuf::from("/user.slice/user-1000.slice/session-2.scope"))
+        );
+        assert_eq!(
+            parse_unified_cgroup_path("1:name=systemd:/init.scope\n"),
+            None
+        );
+
+        let events = "low 0\nhigh 0\nmax 4\noom 2\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(parse_event_counter(events, "oom_kill"), Some(1));
+        assert_eq!(parse_event_counter(events, "max"), Some(4));
+        assert_eq!(parse_event_counter(events, "missing"), None);
+    }
+
+    #[test]
+    fn only_removes_cgroups_of_exited_supervisors() {
+        let mut child = std::process::Command::new("true")
+            .spawn()
+            .expect("spawn true");
+        let exited_pid = child.id();
+        child.wait().expect("wait for true");
+
+        let parent = tempfile::tempdir().expect("tempdir");
+        let live = parent
+            .path()
+            .join(format!("{CGROUP_NAME_PREFIX}{}", std::process::id()));
+        let stale = parent
+            .path()
+            .join(format!("{CGROUP_NAME_PREFIX}{exited_pid}"));
+        let unrelated = parent.path().join(format!("{CGROUP_NAME_PREFIX}other"));
+        for dir in [&live, &stale, &unrelated] {
+            fs::create_dir(dir).expect("create cgroup dir");
+        }
+
+        remove_stale_cgroups(parent.path());
+
+        assert_eq!(live.exists(), true);
+        assert_eq!(stale.exists(), false);
+        assert_eq!(unrelated.exists(), true);
+        assert_eq!(supervisor_pid("codex-exec-0"), None);
+    }
+
+    #[test]
+    fn report_is_written_only_to_an_existing_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let report = dir.path().join("report");
+        fs::write(&report, "stale contents").expect("create report");
+
+        write_report(&report, ResourceLimitKind::Memory).expect("write report");
+        assert_eq!(
+            fs::read_to_string(&report).expect("read report"),
+            ResourceLimitKind::Memory.to_string()
+        );
+
+        let missing = dir.path().join("missing");
+        assert!(write_report(&missing, ResourceLimitKind::Memory).is_err());
+        assert_eq!(missing.exists(), false);
+    }
+
+    #[test]
+    fn counts_threads_of_matching_user() {
+        let status = "Name:\tcargo\nUid:\t1000\t1000\t1000\t1000\nThreads:\t12\n";
+        assert_eq!(user_task_count(status, 1000), Some(12));
+        assert_eq!(user_task_count(status, 0), None);
+    }
+
+    #[test]
+    fn child_exit_decodes_wait_status() {
+        assert_eq!(ChildExit::from_wait_status(3 << 8), ChildExit::Code(3));
+        assert_eq!(
+            ChildExit::from_wait_status(libc::SIGKILL),
+            ChildExit::Signal(libc::SIGKILL)
+        );
+        assert_eq!(ChildExit::Signal(libc::SIGKILL).shell_exit_code(), 137);
+    }
+}
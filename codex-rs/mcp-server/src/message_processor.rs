@@ -22,7 +22,10 @@ use rmcp::model::JsonRpcError;
 use rmcp::model::JsonRpcNotification;
 use rmcp::model::JsonRpcRequest;
 use rmcp::model::JsonRpcResponse;
+use rmcp::model::ListResourceTemplatesResult;
+use rmcp::model::PromptsCapability;
 use rmcp::model::RequestId;
+use rmcp::model::ResourcesCapability;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ToolsCapability;
 use serde_json::json;
@@ -35,11 +38,16 @@ use crate::codex_tool_config::CodexToolCallReplyParam;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::prompts::get_prompt;
+use crate::prompts::list_prompts;
+use crate::resources::list_thread_resources;
+use crate::resources::read_thread_resource;
 
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     initialized: bool,
     arg0_paths: Arg0DispatchPaths,
+    config: Arc<Config>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
 }
@@ -73,6 +81,7 @@ impl MessageProcessor {
             outgoing,
             initialized: false,
             arg0_paths,
+            config,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
         }
@@ -90,13 +99,14 @@ impl MessageProcessor {
                 self.handle_ping(request_id).await;
             }
             ClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params.params);
+                self.handle_list_resources(request_id, params.params).await;
             }
             ClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params.params);
+                self.handle_list_resource_templates(request_id, params.params)
+                    .await;
             }
             ClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params.params);
+                self.handle_read_resource(request_id, params.params).await;
             }
             ClientRequest::SubscribeRequest(params) => {
                 self.handle_subscribe(params.params);
@@ -105,10 +115,10 @@ impl MessageProcessor {
                 self.handle_unsubscribe(params.params);
             }
             ClientRequest::ListPromptsRequest(params) => {
-                self.handle_list_prompts(params.params);
+                self.handle_list_prompts(request_id, params.params).await;
             }
             ClientRequest::GetPromptRequest(params) => {
-                self.handle_get_prompt(params.params);
+                self.handle_get_prompt(request_id, params.params).await;
             }
             ClientRequest::ListToolsRequest(params) => {
                 self.handle_list_tools(request_id, params.params).await;
@@ -246,6 +256,11 @@ impl MessageProcessor {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
+                resources: Some(ResourcesCapability {
+                    subscribe: None,
+                    list_changed: None,
+                }),
+                prompts: Some(PromptsCapability { list_changed: None }),
                 ..Default::default()
             },
             instructions: None,
@@ -280,16 +295,41 @@ impl MessageProcessor {
         self.outgoing.send_response(id, json!({})).await;
     }
 
-    fn handle_list_resources(&self, params: Option<rmcp::model::PaginatedRequestParams>) {
+    async fn handle_list_resources(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParams>,
+    ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let cursor = params.and_then(|params| params.cursor);
+        match list_thread_resources(&self.config, cursor).await {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
-    fn handle_list_resource_templates(&self, params: Option<rmcp::model::PaginatedRequestParams>) {
+    async fn handle_list_resource_templates(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParams>,
+    ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        // Thread resources are listed individually; there are no templates.
+        self.outgoing
+            .send_response(id, ListResourceTemplatesResult::with_all_items(Vec::new()))
+            .await;
     }
 
-    fn handle_read_resource(&self, params: rmcp::model::ReadResourceRequestParams) {
+    async fn handle_read_resource(
+        &self,
+        id: RequestId,
+        params: rmcp::model::ReadResourceRequestParams,
+    ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        match read_thread_resource(&self.config, &params.uri).await {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
     fn handle_subscribe(&self, params: rmcp::model::SubscribeRequestParams) {
@@ -300,12 +340,31 @@ impl MessageProcessor {
         tracing::info!("resources/unsubscribe -> params: {:?}", params);
     }
 
-    fn handle_list_prompts(&self, params: Option<rmcp::model::PaginatedRequestParams>) {
+    async fn handle_list_prompts(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParams>,
+    ) {
         tracing::info!("prompts/list -> params: {:?}", params);
+        let skills_manager = self.thread_manager.skills_manager();
+        let result = list_prompts(&self.config, &skills_manager).await;
+        self.outgoing.send_response(id, result).await;
     }
 
-    fn handle_get_prompt(&self, params: rmcp::model::GetPromptRequestParams) {
+    async fn handle_get_prompt(&self, id: RequestId, params: rmcp::model::GetPromptRequestParams) {
         tracing::info!("prompts/get -> params: {:?}", params);
+        let skills_manager = self.thread_manager.skills_manager();
+        match get_prompt(
+            &self.config,
+            &skills_manager,
+            &params.name,
+            params.arguments,
+        )
+        .await
+        {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
     async fn handle_list_tools(
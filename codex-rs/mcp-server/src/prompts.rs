@@ -0,0 +1,320 @@
+//! Exposes custom prompts and skills as MCP prompts.
+//!
+//! Custom prompts from `$CODEX_HOME/prompts` are published as
+//! `prompts:<name>`, mirroring the slash command used in the TUI and sharing
+//! its placeholder parsing. Named placeholders such as `$FILE` become required
+//! arguments; prompts that only use `$1..$9` or `$ARGUMENTS` take a single
+//! optional `ARGUMENTS` argument.
+//! Enabled skills are published as `skills:<name>` and expand to the contents
+//! of their `SKILL.md`.
+
+use std::collections::HashMap;
+
+use codex_core::config::Config;
+use codex_core::custom_prompts::discover_prompts_in;
+use codex_core::skills::SkillMetadata;
+use codex_core::skills::SkillsManager;
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::custom_prompts::PromptTemplatePiece;
+use codex_protocol::custom_prompts::named_prompt_pieces;
+use codex_protocol::custom_prompts::numeric_prompt_pieces;
+use codex_protocol::custom_prompts::prompt_argument_names;
+use codex_protocol::custom_prompts::prompt_has_numeric_placeholders;
+use rmcp::model::ErrorData;
+use rmcp::model::GetPromptResult;
+use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use serde_json::json;
+
+const SKILLS_PROMPT_PREFIX: &str = "skills";
+
+/// Argument that receives the shell-split values for `$1..$9` and `$ARGUMENTS`.
+const POSITIONAL_ARGUMENT: &str = "ARGUMENTS";
+
+/// Optional argument appended after the skill instructions.
+const SKILL_REQUEST_ARGUMENT: &str = "request";
+
+pub(crate) async fn list_prompts(
+    config: &Config,
+    skills_manager: &SkillsManager,
+) -> ListPromptsResult {
+    let mut prompts: Vec<Prompt> = discover_prompts_in(&config.codex_home.join("prompts"))
+        .await
+        .iter()
+        .map(custom_prompt)
+        .collect();
+    prompts.extend(
+        enabled_skills(config, skills_manager)
+            .iter()
+            .map(skill_prompt),
+    );
+    ListPromptsResult::with_all_items(prompts)
+}
+
+pub(crate) async fn get_prompt(
+    config: &Config,
+    skills_manager: &SkillsManager,
+    name: &str,
+    arguments: Option<JsonObject>,
+) -> Result<GetPromptResult, ErrorData> {
+    let arguments: HashMap<String, String> = arguments
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(value) => (key, value),
+            other => (key, other.to_string()),
+        })
+        .collect();
+
+    if let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")) {
+        let prompts = discover_prompts_in(&config.codex_home.join("prompts")).await;
+        if let Some(prompt) = prompts.iter().find(|prompt| prompt.name == prompt_name) {
+            let text = expand_custom_prompt(name, prompt, &arguments)?;
+            return Ok(GetPromptResult {
+                description: prompt.description.clone(),
+                messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+            });
+        }
+    } else if let Some(skill_name) = name.strip_prefix(&format!("{SKILLS_PROMPT_PREFIX}:"))
+        && let Some(skill) = enabled_skills(config, skills_manager)
+            .into_iter()
+            .find(|skill| skill.name == skill_name)
+    {
+        let contents = tokio::fs::read_to_string(&skill.path_to_skills_md)
+            .await
+            .map_err(|err| {
+                ErrorData::internal_error(
+                    format!(
+                        "failed to read {}: {err}",
+                        skill.path_to_skills_md.display()
+                    ),
+                    None,
+                )
+            })?;
+        let mut text = format!(
+            "<skill>\n<name>{}</name>\n<path>{}</path>\n{contents}\n</skill>",
+            skill.name,
+            skill.path_to_skills_md.display()
+        );
+        if let Some(request) = arguments
+            .get(SKILL_REQUEST_ARGUMENT)
+            .filter(|request| !request.trim().is_empty())
+        {
+            text.push_str("\n\n");
+            text.push_str(request);
+        }
+        return Ok(GetPromptResult {
+            description: Some(skill.description),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        });
+    }
+
+    Err(ErrorData::invalid_params(
+        format!("prompt not found: {name}"),
+        Some(json!({ "name": name })),
+    ))
+}
+
+fn enabled_skills(config: &Config, skills_manager: &SkillsManager) -> Vec<SkillMetadata> {
+    let outcome = skills_manager.skills_for_config(config);
+    outcome
+        .skills_with_enabled()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(skill, _)| skill.clone())
+        .collect()
+}
+
+fn custom_prompt(prompt: &CustomPrompt) -> Prompt {
+    let named = prompt_argument_names(&prompt.content);
+    let arguments =
+        if !named.is_empty() {
+            named
+                .into_iter()
+                .map(|name| PromptArgument {
+                    name,
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                })
+                .collect()
+        } else if prompt_has_numeric_placeholders(&prompt.content) {
+            vec![PromptArgument {
+                name: POSITIONAL_ARGUMENT.to_string(),
+                title: None,
+                description: Some(prompt.argument_hint.clone().unwrap_or_else(|| {
+                    "Space-separated values for $1..$9 and $ARGUMENTS".to_string()
+                })),
+                required: Some(false),
+            }]
+        } else {
+            Vec::new()
+        };
+
+    Prompt::new(
+        format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name),
+        prompt.description.clone(),
+        (!arguments.is_empty()).then_some(arguments),
+    )
+}
+
+fn skill_prompt(skill: &SkillMetadata) -> Prompt {
+    let mut prompt = Prompt::new(
+        format!("{SKILLS_PROMPT_PREFIX}:{}", skill.name),
+        Some(skill.description.clone()),
+        Some(vec![PromptArgument {
+            name: SKILL_REQUEST_ARGUMENT.to_string(),
+            title: None,
+            description: Some("Task to perform with this skill".to_string()),
+            required: Some(false),
+        }]),
+    );
+    prompt.title = skill
+        .interface
+        .as_ref()
+        .and_then(|interface| interface.display_name.clone());
+    prompt
+}
+
+/// Expands a custom prompt the same way `/prompts:<name>` does in the TUI.
+/// Named placeholders take precedence; otherwise the `ARGUMENTS` argument is
+/// split like a shell command line and substituted positionally.
+fn expand_custom_prompt(
+    name: &str,
+    prompt: &CustomPrompt,
+    arguments: &HashMap<String, String>,
+) -> Result<String, ErrorData> {
+    let named = prompt_argument_names(&prompt.content);
+    if !named.is_empty() {
+        let missing: Vec<String> = named
+            .into_iter()
+            .filter(|key| !arguments.contains_key(key))
+            .collect();
+        if !missing.is_empty() {
+            let list = missing.join(", ");
+            return Err(ErrorData::invalid_params(
+                format!("missing required arguments for {name}: {list}"),
+                Some(json!({ "missing": missing })),
+            ));
+        }
+        return Ok(expand_pieces(
+            named_prompt_pieces(&prompt.content),
+            arguments,
+            &[],
+        ));
+    }
+
+    let positional = arguments
+        .get(POSITIONAL_ARGUMENT)
+        .map(|value| {
+            shlex::split(value)
+                .unwrap_or_else(|| value.split_whitespace().map(str::to_string).collect())
+        })
+        .unwrap_or_default();
+    Ok(expand_pieces(
+        numeric_prompt_pieces(&prompt.content),
+        &HashMap::new(),
+        &positional,
+    ))
+}
+
+fn expand_pieces(
+    pieces: Vec<PromptTemplatePiece<'_>>,
+    named: &HashMap<String, String>,
+    positional: &[String],
+) -> String {
+    let mut out = String::new();
+    for piece in pieces {
+        match piece {
+            PromptTemplatePiece::Text(text) => out.push_str(text),
+            PromptTemplatePiece::Named(name) => match named.get(name) {
+                Some(value) => out.push_str(value),
+                None => {
+                    out.push('$');
+                    out.push_str(name);
+                }
+            },
+            PromptTemplatePiece::Positional(index) => {
+                if let Some(value) = positional.get(index) {
+                    out.push_str(value);
+                }
+            }
+            PromptTemplatePiece::Arguments => out.push_str(&positional.join(" ")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn prompt(content: &str) -> CustomPrompt {
+        CustomPrompt {
+            name: "review".to_string(),
+            path: PathBuf::from("/tmp/review.md"),
+            content: content.to_string(),
+            description: Some("Review a file".to_string()),
+            argument_hint: None,
+        }
+    }
+
+    #[test]
+    fn named_placeholders_become_required_arguments() {
+        let prompt = custom_prompt(&prompt("Review $FILE for $FOCUS, then $FILE again. $$HOME"));
+
+        assert_eq!(prompt.name, "prompts:review");
+        let names: Vec<(String, Option<bool>)> = prompt
+            .arguments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|argument| (argument.name, argument.required))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("FILE".to_string(), Some(true)),
+                ("FOCUS".to_string(), Some(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn expands_named_arguments_and_reports_missing_ones() {
+        let prompt = prompt("Review $FILE for $FOCUS. $$HOME stays.");
+        let mut arguments = HashMap::from([("FILE".to_string(), "main.rs".to_string())]);
+
+        let err = expand_custom_prompt("prompts:review", &prompt, &arguments)
+            .expect_err("FOCUS is missing");
+        assert_eq!(
+            err.message,
+            "missing required arguments for prompts:review: FOCUS"
+        );
+
+        arguments.insert("FOCUS".to_string(), "bugs".to_string());
+        assert_eq!(
+            expand_custom_prompt("prompts:review", &prompt, &arguments).expect("expands"),
+            "Review main.rs for bugs. $$HOME stays."
+        );
+    }
+
+    #[test]
+    fn expands_positional_arguments() {
+        let prompt = prompt("First: $1. Second: $2. All: $ARGUMENTS");
+        let arguments = HashMap::from([(
+            POSITIONAL_ARGUMENT.to_string(),
+            "one \"two words\"".to_string(),
+        )]);
+
+        assert_eq!(
+            expand_custom_prompt("prompts:review", &prompt, &arguments).expect("expands"),
+            "First: one. Second: two words. All: one two words"
+        );
+    }
+}
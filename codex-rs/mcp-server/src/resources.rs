@@ -0,0 +1,180 @@
+//! Exposes recorded threads as MCP resources.
+//!
+//! Every rollout under `$CODEX_HOME/sessions` is listed as a
+//! `codex://thread/<id>` resource. Reading one renders the user and agent
+//! messages of the thread as a Markdown transcript.
+
+use codex_core::RolloutRecorder;
+use codex_core::ThreadItem;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::parse_cursor;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SessionSource;
+use rmcp::model::AnnotateAble;
+use rmcp::model::ErrorData;
+use rmcp::model::ListResourcesResult;
+use rmcp::model::RawResource;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::Resource;
+use rmcp::model::ResourceContents;
+use serde_json::json;
+
+/// URI scheme prefix used for thread resources.
+pub(crate) const THREAD_URI_PREFIX: &str = "codex://thread/";
+
+/// Number of threads returned per `resources/list` page.
+const THREAD_PAGE_SIZE: usize = 50;
+
+const TRANSCRIPT_MIME_TYPE: &str = "text/markdown";
+
+/// Threads started by a user, whether interactively, via `codex exec` or via
+/// this server. Sub-agent threads are omitted.
+const THREAD_SOURCES: &[SessionSource] = &[
+    SessionSource::Cli,
+    SessionSource::VSCode,
+    SessionSource::Exec,
+    SessionSource::Mcp,
+];
+
+pub(crate) async fn list_thread_resources(
+    config: &Config,
+    cursor: Option<String>,
+) -> Result<ListResourcesResult, ErrorData> {
+    let cursor = match cursor.as_deref() {
+        Some(token) => Some(parse_cursor(token).ok_or_else(|| {
+            ErrorData::invalid_params(
+                format!("invalid cursor: {token}"),
+                Some(json!({ "cursor": token })),
+            )
+        })?),
+        None => None,
+    };
+
+    let page = RolloutRecorder::list_threads(
+        config,
+        THREAD_PAGE_SIZE,
+        cursor.as_ref(),
+        ThreadSortKey::UpdatedAt,
+        THREAD_SOURCES,
+        None,
+        config.model_provider_id.as_str(),
+        None,
+    )
+    .await
+    .map_err(|err| ErrorData::internal_error(format!("failed to list threads: {err}"), None))?;
+
+    let next_cursor = page
+        .next_cursor
+        .and_then(|cursor| serde_json::to_value(cursor).ok())
+        .and_then(|value| value.as_str().map(str::to_owned));
+
+    Ok(ListResourcesResult {
+        meta: None,
+        next_cursor,
+        resources: page.items.iter().filter_map(thread_resource).collect(),
+    })
+}
+
+pub(crate) async fn read_thread_resource(
+    config: &Config,
+    uri: &str,
+) -> Result<ReadResourceResult, ErrorData> {
+    let not_found = || {
+        ErrorData::resource_not_found(
+            format!("resource not found: {uri}"),
+            Some(json!({ "uri": uri })),
+        )
+    };
+    let Some(thread_id) = uri.strip_prefix(THREAD_URI_PREFIX) else {
+        return Err(not_found());
+    };
+
+    let path = find_thread_path_by_id_str(&config.codex_home, thread_id)
+        .await
+        .map_err(|err| {
+            ErrorData::internal_error(format!("failed to locate thread {thread_id}: {err}"), None)
+        })?
+        .ok_or_else(not_found)?;
+    let history = RolloutRecorder::get_rollout_history(&path)
+        .await
+        .map_err(|err| {
+            ErrorData::internal_error(format!("failed to read thread {thread_id}: {err}"), None)
+        })?;
+    let events = history.get_event_msgs().unwrap_or_default();
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(TRANSCRIPT_MIME_TYPE.to_string()),
+            text: render_transcript(thread_id, &events),
+            meta: None,
+        }],
+    })
+}
+
+fn thread_resource(item: &ThreadItem) -> Option<Resource> {
+    let thread_id = item.thread_id?;
+    let mut resource = RawResource::new(
+        format!("{THREAD_URI_PREFIX}{thread_id}"),
+        thread_id.to_string(),
+    );
+    resource.title = item
+        .first_user_message
+        .as_deref()
+        .and_then(|message| message.lines().next())
+        .map(str::to_string);
+    resource.description = item
+        .cwd
+        .as_ref()
+        .map(|cwd| format!("Codex thread in {}", cwd.display()));
+    resource.mime_type = Some(TRANSCRIPT_MIME_TYPE.to_string());
+    Some(resource.no_annotation())
+}
+
+fn render_transcript(thread_id: &str, events: &[EventMsg]) -> String {
+    let mut out = format!("# Thread {thread_id}\n");
+    for event in events {
+        let (heading, message) = match event {
+            EventMsg::UserMessage(event) => ("User", &event.message),
+            EventMsg::AgentMessage(event) => ("Codex", &event.message),
+            _ => continue,
+        };
+        out.push_str(&format!("\n## {heading}\n\n{}\n", message.trim_end()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::AgentReasoningEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn transcript_includes_only_user_and_agent_messages() {
+        let events = vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "list the files\n".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            }),
+            EventMsg::AgentReasoning(AgentReasoningEvent {
+                text: "thinking".to_string(),
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "There are two files.".to_string(),
+                phase: None,
+            }),
+        ];
+
+        assert_eq!(
+            render_transcript("abc", &events),
+            "# Thread abc\n\n## User\n\nlist the files\n\n## Codex\n\nThere are two files.\n"
+        );
+    }
+}
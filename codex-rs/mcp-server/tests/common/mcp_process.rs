@@ -19,6 +19,7 @@ use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
+use rmcp::model::GetPromptRequestParams;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::JsonRpcMessage;
@@ -26,6 +27,7 @@ use rmcp::model::JsonRpcNotification;
 use rmcp::model::JsonRpcRequest;
 use rmcp::model::JsonRpcResponse;
 use rmcp::model::JsonRpcVersion2_0;
+use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::RequestId;
 use serde_json::json;
@@ -175,6 +177,8 @@ impl McpProcess {
                     "tools": {
                         "listChanged": true
                     },
+                    "resources": {},
+                    "prompts": {},
                 },
                 "serverInfo": {
                     "name": "codex-mcp-server",
@@ -218,6 +222,31 @@ impl McpProcess {
         .await
     }
 
+    pub async fn send_list_resources_request(
+        &mut self,
+        params: Option<PaginatedRequestParams>,
+    ) -> anyhow::Result<i64> {
+        let params = params.map(serde_json::to_value).transpose()?;
+        self.send_request("resources/list", params).await
+    }
+
+    pub async fn send_read_resource_request(&mut self, uri: &str) -> anyhow::Result<i64> {
+        self.send_request("resources/read", Some(json!({ "uri": uri })))
+            .await
+    }
+
+    pub async fn send_list_prompts_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request("prompts/list", None).await
+    }
+
+    pub async fn send_get_prompt_request(
+        &mut self,
+        params: GetPromptRequestParams,
+    ) -> anyhow::Result<i64> {
+        self.send_request("prompts/get", Some(serde_json::to_value(params)?))
+            .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -1 +1,2 @@
 mod codex_tool;
+mod prompts_and_resources;
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use mcp_test_support::McpProcess;
+use mcp_test_support::to_response;
+use pretty_assertions::assert_eq;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::ListResourcesResult;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::RequestId;
+use rmcp::model::ResourceContents;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+const THREAD_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn custom_prompts_are_listed_and_expanded() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let prompts_dir = codex_home.path().join("prompts");
+    std::fs::create_dir_all(&prompts_dir)?;
+    std::fs::write(
+        prompts_dir.join("review.md"),
+        "---\ndescription: Review a file\n---\nReview $FILE and focus on $FOCUS.\n",
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let list_id = mcp.send_list_prompts_request().await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(list_id)),
+    )
+    .await??;
+    let ListPromptsResult { prompts, .. } = to_response(response)?;
+    let review = prompts
+        .into_iter()
+        .find(|prompt| prompt.name == "prompts:review")
+        .ok_or_else(|| anyhow::anyhow!("prompts:review should be listed"))?;
+    assert_eq!(review.description.as_deref(), Some("Review a file"));
+    let arguments: Vec<String> = review
+        .arguments
+        .unwrap_or_default()
+        .into_iter()
+        .map(|argument| argument.name)
+        .collect();
+    assert_eq!(arguments, vec!["FILE".to_string(), "FOCUS".to_string()]);
+
+    let get_id = mcp
+        .send_get_prompt_request(GetPromptRequestParams {
+            meta: None,
+            name: "prompts:review".to_string(),
+            arguments: Some(
+                json!({ "FILE": "src/main.rs", "FOCUS": "error handling" })
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+        })
+        .await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(get_id)),
+    )
+    .await??;
+    let GetPromptResult { messages, .. } = to_response(response)?;
+    assert_eq!(
+        messages,
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            "Review src/main.rs and focus on error handling.\n",
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn recorded_threads_are_readable_resources() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let uri = format!("codex://thread/{THREAD_ID}");
+    let list_id = mcp.send_list_resources_request(None).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(list_id)),
+    )
+    .await??;
+    let ListResourcesResult { resources, .. } = to_response(response)?;
+    let listed: Vec<(String, Option<String>)> = resources
+        .into_iter()
+        .map(|resource| (resource.raw.uri, resource.raw.title))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![(uri.clone(), Some("fix the build".to_string()))]
+    );
+
+    let read_id = mcp.send_read_resource_request(&uri).await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(read_id)),
+    )
+    .await??;
+    let ReadResourceResult { contents } = to_response(response)?;
+    assert_eq!(
+        contents,
+        vec![ResourceContents::TextResourceContents {
+            uri,
+            mime_type: Some("text/markdown".to_string()),
+            text: format!(
+                "# Thread {THREAD_ID}\n\n## User\n\nfix the build\n\n## Codex\n\nThe build is fixed.\n"
+            ),
+            meta: None,
+        }]
+    );
+
+    Ok(())
+}
+
+fn write_rollout(codex_home: &Path) -> std::io::Result<()> {
+    let timestamp = "2025-01-02T03:04:05Z";
+    let dir = codex_home
+        .join("sessions")
+        .join("2025")
+        .join("01")
+        .join("02");
+    std::fs::create_dir_all(&dir)?;
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": THREAD_ID,
+                "timestamp": timestamp,
+                "cwd": "/",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "source": "cli",
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{ "type": "input_text", "text": "fix the build" }],
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "fix the build" },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "The build is fixed." },
+        }),
+    ];
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    std::fs::write(
+        dir.join(format!("rollout-2025-01-02T03-04-05-{THREAD_ID}.jsonl")),
+        contents,
+    )
+}
@@ -7,6 +7,7 @@ use serde::Serialize;
 use std::net::IpAddr;
 use std::net::SocketAddr;
 use std::path::Path;
+use std::path::PathBuf;
 use tracing::warn;
 use url::Url;
 
@@ -47,6 +48,20 @@ pub struct NetworkProxySettings {
     pub allow_local_binding: bool,
     #[serde(default)]
     pub mitm: bool,
+    #[serde(default)]
+    pub request_rules: Vec<NetworkRequestRule>,
+    #[serde(default)]
+    pub credentials: Vec<NetworkCredential>,
+    /// Debug option: log redacted excerpts of the request and response bodies that pass through
+    /// MITM, instead of only their lengths.
+    #[serde(default)]
+    pub mitm_log_body_excerpts: bool,
+    /// Directory to write one HAR file per session into. Recording is off when unset.
+    #[serde(default)]
+    pub har_record_dir: Option<PathBuf>,
+    /// HAR file to answer requests from instead of the network.
+    #[serde(default)]
+    pub har_replay_file: Option<PathBuf>,
 }
 
 impl Default for NetworkProxySettings {
@@ -68,6 +83,11 @@ impl Default for NetworkProxySettings {
             allow_unix_sockets: Vec::new(),
             allow_local_binding: true,
             mitm: false,
+            request_rules: Vec::new(),
+            credentials: Vec::new(),
+            mitm_log_body_excerpts: false,
+            har_record_dir: None,
+            har_replay_file: None,
         }
     }
 }
@@ -94,6 +114,58 @@ impl NetworkMode {
     }
 }
 
+/// Path- and method-level rule for requests the proxy can see in full: plain HTTP, and HTTPS when
+/// `mitm` is enabled. Rules refine the host policy; a host must still pass `allowed_domains` and
+/// `denied_domains` first.
+///
+/// Deny rules win over allow rules. Once a host matches any allow rule, requests to that host are
+/// blocked unless they match one of its allow rules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRequestRule {
+    /// Host pattern, using the same syntax as `allowed_domains`.
+    pub host: String,
+    /// Path the rule applies to, along with everything below it. `/our-org` matches
+    /// `/our-org` and `/our-org/repo` but not `/our-org-fork`.
+    #[serde(default = "default_path_prefix")]
+    pub path_prefix: String,
+    /// HTTP methods the rule applies to. Empty means every method.
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub action: NetworkRuleAction,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRuleAction {
+    Allow,
+    Deny,
+}
+
+/// Header the proxy adds to HTTPS requests it intercepts with MITM, so the secret behind it never
+/// has to enter the sandbox. Any value the client sent for the same header is replaced.
+///
+/// Only the secret name is stored in config; the value is read from the Codex secrets store when
+/// the config is loaded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkCredential {
+    /// Host pattern, using the same syntax as `allowed_domains`.
+    pub host: String,
+    /// Header to set, e.g. `Authorization` or `X-Api-Key`.
+    pub header: String,
+    /// Name of the secret holding the header value.
+    pub secret: String,
+    /// Text placed before the secret value, e.g. `Bearer `.
+    #[serde(default)]
+    pub prefix: String,
+    /// Environment the secret is scoped to. Global secrets are used when unset.
+    #[serde(default)]
+    pub environment: Option<String>,
+}
+
+fn default_path_prefix() -> String {
+    "/".to_string()
+}
+
 fn default_proxy_url() -> String {
     "http://127.0.0.1:3128".to_string()
 }
@@ -398,6 +470,11 @@ mod tests {
                 allow_unix_sockets: Vec::new(),
                 allow_local_binding: true,
                 mitm: false,
+                request_rules: Vec::new(),
+                credentials: Vec::new(),
+                mitm_log_body_excerpts: false,
+                har_record_dir: None,
+                har_replay_file: None,
             }
         );
     }
@@ -420,6 +497,30 @@ mod tests {
         assert_eq!(config.network, expected);
     }
 
+    #[test]
+    fn request_rules_default_to_every_path_and_method() {
+        let config: NetworkProxyConfig = serde_json::from_str(
+            r#"{
+                "network": {
+                    "request_rules": [
+                        { "host": "github.com", "action": "allow" }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.network.request_rules,
+            vec![NetworkRequestRule {
+                host: "github.com".to_string(),
+                path_prefix: "/".to_string(),
+                methods: Vec::new(),
+                action: NetworkRuleAction::Allow,
+            }]
+        );
+    }
+
     #[test]
     fn parse_host_port_defaults_for_empty_string() {
         assert!(parse_host_port("", 1234).is_err());
@@ -0,0 +1,275 @@
+use crate::config::NetworkCredential;
+use crate::policy::compile_globset;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::ensure;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::redact_secrets;
+use globset::GlobSet;
+use rama_http::HeaderMap;
+use rama_http::HeaderName;
+use rama_http::HeaderValue;
+use std::sync::Arc;
+use tracing::warn;
+
+const REDACTED_SECRET: &str = "[REDACTED_SECRET]";
+
+/// Headers configured through `network.credentials`, with their values already read from the
+/// secrets store.
+///
+/// The values are only added to requests the proxy terminates with MITM, and must never be
+/// logged; use [`Credentials::redact`] on anything derived from intercepted traffic.
+#[derive(Clone, Default)]
+pub struct Credentials {
+    entries: Arc<Vec<ResolvedCredential>>,
+}
+
+struct ResolvedCredential {
+    hosts: GlobSet,
+    header: HeaderName,
+    value: HeaderValue,
+    secret_value: String,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the resolved values.
+        f.debug_struct("Credentials")
+            .field("len", &self.entries.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Credentials {
+    /// Compile `credentials` and look up each secret with `lookup`. Entries whose secret is not
+    /// set are skipped with a warning so the request goes out unauthenticated rather than the
+    /// whole proxy failing to start.
+    pub(crate) fn resolve<F>(credentials: &[NetworkCredential], lookup: F) -> Result<Self>
+    where
+        F: Fn(&SecretScope, &SecretName) -> Result<Option<String>>,
+    {
+        let mut entries = Vec::new();
+        for (index, credential) in credentials.iter().enumerate() {
+            let entry = resolve_credential(credential, &lookup)
+                .with_context(|| format!("invalid network.credentials[{index}]"))?;
+            match entry {
+                Some(entry) => entries.push(entry),
+                None => warn!(
+                    "secret {} for network.credentials[{index}] is not set; requests to {} will not be authenticated",
+                    credential.secret, credential.host
+                ),
+            }
+        }
+        Ok(Self {
+            entries: Arc::new(entries),
+        })
+    }
+
+    /// Returns true if a credential applies to `host`. CONNECT tunnels to such hosts are
+    /// terminated (MITM) so the headers can be added to the inner requests.
+    pub(crate) fn covers_host(&self, host: &str) -> bool {
+        self.entries.iter().any(|entry| entry.hosts.is_match(host))
+    }
+
+    /// Set every header configured for `host`, replacing values sent by the client. Returns the
+    /// names of the headers that were set.
+    pub(crate) fn apply(&self, host: &str, headers: &mut HeaderMap) -> Vec<HeaderName> {
+        let mut applied = Vec::new();
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| entry.hosts.is_match(host))
+        {
+            headers.insert(entry.header.clone(), entry.value.clone());
+            applied.push(entry.header.clone());
+        }
+        applied
+    }
+
+    /// Replace every resolved secret value in `text`, then apply the generic secret patterns.
+    pub(crate) fn redact(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        for entry in self.entries.iter() {
+            redacted = redacted.replace(&entry.secret_value, REDACTED_SECRET);
+        }
+        redact_secrets(redacted)
+    }
+
+    /// Replace every resolved secret value in `bytes`. Unlike [`Credentials::redact`] this leaves
+    /// everything else untouched, so recorded bodies stay replayable.
+    pub(crate) fn redact_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut redacted = bytes.to_vec();
+        for entry in self.entries.iter() {
+            redacted = replace_bytes(&redacted, entry.secret_value.as_bytes());
+        }
+        redacted
+    }
+
+    /// Length of the longest secret value, so callers that cut text short can keep enough
+    /// trailing bytes to redact a value that crosses the cut.
+    pub(crate) fn max_secret_len(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| entry.secret_value.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn replace_bytes(haystack: &[u8], needle: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(haystack.len());
+    let mut rest = haystack;
+    while let Some(index) = rest
+        .windows(needle.len())
+        .position(|window| window == needle)
+    {
+        out.extend_from_slice(&rest[..index]);
+        out.extend_from_slice(REDACTED_SECRET.as_bytes());
+        rest = &rest[index + needle.len()..];
+    }
+    out.extend_from_slice(rest);
+    out
+}
+
+fn resolve_credential<F>(
+    credential: &NetworkCredential,
+    lookup: &F,
+) -> Result<Option<ResolvedCredential>>
+where
+    F: Fn(&SecretScope, &SecretName) -> Result<Option<String>>,
+{
+    ensure!(!credential.host.trim().is_empty(), "host is empty");
+    let hosts = compile_globset(std::slice::from_ref(&credential.host))?;
+    let header = HeaderName::from_bytes(credential.header.trim().as_bytes())
+        .with_context(|| format!("invalid header name {:?}", credential.header))?;
+    let name = SecretName::new(&credential.secret)?;
+    let scope = match credential.environment.as_deref() {
+        Some(environment) => SecretScope::environment(environment)?,
+        None => SecretScope::Global,
+    };
+    let Some(secret_value) = lookup(&scope, &name)? else {
+        return Ok(None);
+    };
+    ensure!(!secret_value.is_empty(), "secret {name} is empty");
+    let mut value = HeaderValue::from_str(&format!("{}{secret_value}", credential.prefix))
+        .with_context(|| format!("secret {name} is not a valid header value"))?;
+    value.set_sensitive(true);
+    Ok(Some(ResolvedCredential {
+        hosts,
+        header,
+        value,
+        secret_value,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn credential(host: &str, header: &str, secret: &str, prefix: &str) -> NetworkCredential {
+        NetworkCredential {
+            host: host.to_string(),
+            header: header.to_string(),
+            secret: secret.to_string(),
+            prefix: prefix.to_string(),
+            environment: None,
+        }
+    }
+
+    fn lookup(scope: &SecretScope, name: &SecretName) -> Result<Option<String>> {
+        Ok(match (scope, name.as_str()) {
+            (SecretScope::Global, "GITHUB_TOKEN") => Some("ghp-test-value".to_string()),
+            (SecretScope::Environment(env), "REGISTRY_KEY") if env == "repo" => {
+                Some("registry-key-value".to_string())
+            }
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn apply_sets_headers_for_matching_hosts_only() {
+        let credentials = Credentials::resolve(
+            &[
+                credential("api.github.com", "Authorization", "GITHUB_TOKEN", "Bearer "),
+                NetworkCredential {
+                    environment: Some("repo".to_string()),
+                    ..credential("**.registry.example.com", "X-Api-Key", "REGISTRY_KEY", "")
+                },
+                credential("other.example.com", "X-Api-Key", "MISSING_SECRET", ""),
+            ],
+            lookup,
+        )
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer sandbox"));
+        let applied = credentials.apply("api.github.com", &mut headers);
+        assert_eq!(applied, vec![HeaderName::from_static("authorization")]);
+        assert_eq!(
+            headers.get("authorization").unwrap(),
+            "Bearer ghp-test-value"
+        );
+        assert!(headers.get("authorization").unwrap().is_sensitive());
+
+        let mut headers = HeaderMap::new();
+        credentials.apply("eu.registry.example.com", &mut headers);
+        assert_eq!(headers.get("x-api-key").unwrap(), "registry-key-value");
+
+        let mut headers = HeaderMap::new();
+        assert!(
+            credentials
+                .apply("other.example.com", &mut headers)
+                .is_empty()
+        );
+        assert!(headers.is_empty());
+        assert!(credentials.covers_host("api.github.com"));
+        assert!(!credentials.covers_host("other.example.com"));
+    }
+
+    #[test]
+    fn redact_replaces_resolved_values() {
+        let credentials = Credentials::resolve(
+            &[credential(
+                "api.github.com",
+                "Authorization",
+                "GITHUB_TOKEN",
+                "Bearer ",
+            )],
+            lookup,
+        )
+        .unwrap();
+
+        assert_eq!(
+            credentials.redact(r#"{"echo":"token ghp-test-value"}"#),
+            r#"{"echo":"token [REDACTED_SECRET]"}"#
+        );
+        assert_eq!(
+            credentials.redact_bytes(b"a ghp-test-value b ghp-test-value"),
+            b"a [REDACTED_SECRET] b [REDACTED_SECRET]".to_vec()
+        );
+        assert_eq!(credentials.max_secret_len(), "ghp-test-value".len());
+        assert_eq!(
+            format!("{credentials:?}"),
+            "Credentials { len: 1, .. }".to_string()
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_invalid_header_names() {
+        let err = Credentials::resolve(
+            &[credential(
+                "api.github.com",
+                "Bad Header",
+                "GITHUB_TOKEN",
+                "",
+            )],
+            lookup,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid network.credentials[0]");
+    }
+}
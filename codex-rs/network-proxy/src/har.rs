@@ -0,0 +1,515 @@
+//! HAR 1.2 files for proxied traffic: an append-only writer used by `network.har_record_dir`, and
+//! the index `network.har_replay_file` answers requests from.
+
+use anyhow::Context;
+use anyhow::Result;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const HAR_VERSION: &str = "1.2";
+const HAR_TAIL: &[u8] = b"\n]}}\n";
+const BASE64_ENCODING: &str = "base64";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarEntry {
+    pub(crate) started_date_time: String,
+    /// Total time in milliseconds.
+    pub(crate) time: f64,
+    pub(crate) request: HarRequest,
+    pub(crate) response: HarResponse,
+    #[serde(default)]
+    pub(crate) cache: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub(crate) timings: HarTimings,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarRequest {
+    pub(crate) method: String,
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) http_version: String,
+    #[serde(default)]
+    pub(crate) cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub(crate) headers: Vec<HarHeader>,
+    #[serde(default)]
+    pub(crate) query_string: Vec<HarHeader>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) post_data: Option<HarPostData>,
+    #[serde(default = "unknown_size")]
+    pub(crate) headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub(crate) body_size: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarResponse {
+    pub(crate) status: u16,
+    #[serde(default)]
+    pub(crate) status_text: String,
+    #[serde(default)]
+    pub(crate) http_version: String,
+    #[serde(default)]
+    pub(crate) cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub(crate) headers: Vec<HarHeader>,
+    #[serde(default)]
+    pub(crate) content: HarContent,
+    #[serde(default, rename = "redirectURL")]
+    pub(crate) redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub(crate) headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub(crate) body_size: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct HarHeader {
+    pub(crate) name: String,
+    pub(crate) value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarPostData {
+    pub(crate) mime_type: String,
+    pub(crate) text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarContent {
+    /// Full length of the body, even when `text` holds only an excerpt.
+    pub(crate) size: i64,
+    #[serde(default)]
+    pub(crate) mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct HarTimings {
+    pub(crate) send: f64,
+    pub(crate) wait: f64,
+    pub(crate) receive: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HarCreator {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarFile {
+    log: HarLog,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarLog {
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+/// Body bytes kept for a HAR entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BodyExcerpt {
+    bytes: Vec<u8>,
+    /// Length of the whole body as it went through the proxy.
+    len: usize,
+    truncated: bool,
+}
+
+impl BodyExcerpt {
+    /// Keep at most `max_bytes` of `bytes`. The cut backs off to a UTF-8 boundary so a text body
+    /// is still recorded as text.
+    pub(crate) fn new(mut bytes: Vec<u8>, len: usize, max_bytes: usize) -> Self {
+        let truncated = len > max_bytes || bytes.len() > max_bytes;
+        if bytes.len() > max_bytes {
+            bytes.truncate(max_bytes);
+            if let Err(err) = std::str::from_utf8(&bytes)
+                && err.error_len().is_none()
+            {
+                bytes.truncate(err.valid_up_to());
+            }
+        }
+        Self {
+            bytes,
+            len,
+            truncated,
+        }
+    }
+
+    pub(crate) fn body_len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn to_content(&self, mime_type: String) -> HarContent {
+        let (text, encoding) = encode_body(&self.bytes);
+        HarContent {
+            size: i64::try_from(self.len).unwrap_or(i64::MAX),
+            mime_type,
+            text: Some(text),
+            encoding,
+            comment: self.truncation_comment(),
+        }
+    }
+
+    pub(crate) fn to_post_data(&self, mime_type: String) -> HarPostData {
+        // `postData` has no encoding field, so binary request bodies are recorded lossily.
+        HarPostData {
+            mime_type,
+            text: String::from_utf8_lossy(&self.bytes).into_owned(),
+            comment: self.truncation_comment(),
+        }
+    }
+
+    fn truncation_comment(&self) -> Option<String> {
+        self.truncated
+            .then(|| format!("truncated to {} of {} bytes", self.bytes.len(), self.len))
+    }
+}
+
+fn encode_body(bytes: &[u8]) -> (String, Option<String>) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (text.to_string(), None),
+        Err(_) => (
+            BASE64_STANDARD.encode(bytes),
+            Some(BASE64_ENCODING.to_string()),
+        ),
+    }
+}
+
+/// Writes one HAR file, appending each entry in place so the file is valid JSON after every
+/// write.
+#[derive(Debug)]
+pub(crate) struct HarRecorder {
+    dir: PathBuf,
+    path: PathBuf,
+    file: Mutex<HarWriter>,
+}
+
+#[derive(Debug)]
+struct HarWriter {
+    file: File,
+    entries: usize,
+}
+
+impl HarRecorder {
+    /// Create `<dir>/<session>.har`, adding a numeric suffix if that file already exists.
+    pub(crate) fn create(dir: &Path, session: &str) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create HAR directory {}", dir.display()))?;
+        let session = sanitize_file_stem(session);
+        let mut suffix = 0;
+        let (path, mut file) = loop {
+            let file_name = if suffix == 0 {
+                format!("{session}.har")
+            } else {
+                format!("{session}-{suffix}.har")
+            };
+            let path = dir.join(file_name);
+            match open_create_new(&path) {
+                Ok(file) => break (path, file),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => suffix += 1,
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to create {}", path.display()));
+                }
+            }
+        };
+
+        let creator = serde_json::to_string(&HarCreator {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        })?;
+        file.write_all(
+            format!(r#"{{"log":{{"version":"{HAR_VERSION}","creator":{creator},"entries":["#)
+                .as_bytes(),
+        )?;
+        file.write_all(HAR_TAIL)?;
+        file.flush()?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            path,
+            file: Mutex::new(HarWriter { file, entries: 0 }),
+        })
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn append(&self, entry: &HarEntry) -> Result<()> {
+        let json = serde_json::to_vec(entry)?;
+        let mut writer = self
+            .file
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let tail_len = i64::try_from(HAR_TAIL.len()).unwrap_or(i64::MAX);
+        writer.file.seek(SeekFrom::End(-tail_len))?;
+        if writer.entries > 0 {
+            writer.file.write_all(b",")?;
+        }
+        writer.file.write_all(b"\n")?;
+        writer.file.write_all(&json)?;
+        writer.file.write_all(HAR_TAIL)?;
+        writer.file.flush()?;
+        writer.entries += 1;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn open_create_new(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    // Recordings can hold request headers and bodies, so keep them private to the user.
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn open_create_new(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+fn sanitize_file_stem(session: &str) -> String {
+    let stem: String = session
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = stem.trim_start_matches('.');
+    if stem.is_empty() {
+        "session".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Recorded response for a replayed request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReplayedResponse {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<HarHeader>,
+    pub(crate) body: Vec<u8>,
+    /// The recording only kept the start of the body.
+    pub(crate) truncated: bool,
+}
+
+/// Responses from a recorded HAR file, keyed by method and URL. Repeated requests get the recorded
+/// responses in order; once those run out, the last one is served again.
+#[derive(Debug)]
+pub(crate) struct HarReplay {
+    path: PathBuf,
+    responses: HashMap<(String, String), Vec<ReplayedResponse>>,
+    served: Mutex<HashMap<(String, String), usize>>,
+}
+
+impl HarReplay {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read HAR file {}", path.display()))?;
+        let har: HarFile = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse HAR file {}", path.display()))?;
+        let mut responses: HashMap<(String, String), Vec<ReplayedResponse>> = HashMap::new();
+        for (index, entry) in har.log.entries.into_iter().enumerate() {
+            let response = replayed_response(entry.response)
+                .with_context(|| format!("invalid entry {index} in HAR file {}", path.display()))?;
+            responses
+                .entry(replay_key(&entry.request.method, &entry.request.url))
+                .or_default()
+                .push(response);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            responses,
+            served: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn next_response(&self, method: &str, url: &str) -> Option<ReplayedResponse> {
+        let key = replay_key(method, url);
+        let responses = self.responses.get(&key)?;
+        let mut served = self
+            .served
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let count = served.entry(key).or_default();
+        let response = responses.get(*count).or_else(|| responses.last())?;
+        *count += 1;
+        Some(response.clone())
+    }
+}
+
+fn replay_key(method: &str, url: &str) -> (String, String) {
+    (method.to_ascii_uppercase(), url.to_string())
+}
+
+fn replayed_response(response: HarResponse) -> Result<ReplayedResponse> {
+    let content = response.content;
+    let body = match (content.text, content.encoding.as_deref()) {
+        (None, _) => Vec::new(),
+        (Some(text), Some(BASE64_ENCODING)) => BASE64_STANDARD
+            .decode(text.as_bytes())
+            .context("response body is not valid base64")?,
+        (Some(text), _) => text.into_bytes(),
+    };
+    let truncated = usize::try_from(content.size).is_ok_and(|size| size > body.len());
+    Ok(ReplayedResponse {
+        status: response.status,
+        headers: response.headers,
+        body,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn entry(method: &str, url: &str, status: u16, body: &[u8]) -> HarEntry {
+        HarEntry {
+            started_date_time: "2026-01-01T00:00:00.000Z".to_string(),
+            request: HarRequest {
+                method: method.to_string(),
+                url: url.to_string(),
+                ..HarRequest::default()
+            },
+            response: HarResponse {
+                status,
+                headers: vec![HarHeader {
+                    name: "content-type".to_string(),
+                    value: "application/octet-stream".to_string(),
+                }],
+                content: BodyExcerpt::new(body.to_vec(), body.len(), 64)
+                    .to_content("application/octet-stream".to_string()),
+                ..HarResponse::default()
+            },
+            ..HarEntry::default()
+        }
+    }
+
+    #[test]
+    fn recorder_keeps_file_valid_after_each_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = HarRecorder::create(dir.path(), "conv/1").unwrap();
+        assert_eq!(recorder.path(), dir.path().join("conv_1.har"));
+
+        let parse = || -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(recorder.path()).unwrap()).unwrap()
+        };
+        assert_eq!(parse()["log"]["entries"], serde_json::json!([]));
+
+        recorder
+            .append(&entry("GET", "https://example.com/a", 200, b"first"))
+            .unwrap();
+        recorder
+            .append(&entry("GET", "https://example.com/b", 404, &[0xff, 0x00]))
+            .unwrap();
+        let har = parse();
+        assert_eq!(har["log"]["version"], "1.2");
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["response"]["content"]["text"], "first");
+        assert_eq!(entries[1]["response"]["content"]["encoding"], "base64");
+
+        let second = HarRecorder::create(dir.path(), "conv/1").unwrap();
+        assert_eq!(second.path(), dir.path().join("conv_1-1.har"));
+    }
+
+    #[test]
+    fn replay_serves_recorded_responses_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = HarRecorder::create(dir.path(), "session").unwrap();
+        recorder
+            .append(&entry("GET", "https://example.com/a", 200, b"first"))
+            .unwrap();
+        recorder
+            .append(&entry("GET", "https://example.com/a", 500, &[0xff, 0x00]))
+            .unwrap();
+        let replay = HarReplay::load(recorder.path()).unwrap();
+
+        let first = replay
+            .next_response("get", "https://example.com/a")
+            .unwrap();
+        assert_eq!((first.status, first.body), (200, b"first".to_vec()));
+        let second = replay
+            .next_response("GET", "https://example.com/a")
+            .unwrap();
+        assert_eq!((second.status, second.body), (500, vec![0xff, 0x00]));
+        let again = replay
+            .next_response("GET", "https://example.com/a")
+            .unwrap();
+        assert_eq!(again.status, 500);
+        assert_eq!(replay.next_response("GET", "https://example.com/b"), None);
+    }
+
+    #[test]
+    fn body_excerpt_notes_truncation() {
+        // The cut lands inside "é", so the excerpt backs off to stay valid UTF-8.
+        let excerpt = BodyExcerpt::new("abcé-rest".as_bytes().to_vec(), 10, 4);
+        assert_eq!(
+            excerpt.to_content("text/plain".to_string()),
+            HarContent {
+                size: 10,
+                mime_type: "text/plain".to_string(),
+                text: Some("abc".to_string()),
+                encoding: None,
+                comment: Some("truncated to 3 of 10 bytes".to_string()),
+            }
+        );
+        let replayed = replayed_response(HarResponse {
+            status: 200,
+            content: excerpt.to_content("text/plain".to_string()),
+            ..HarResponse::default()
+        })
+        .unwrap();
+        assert!(replayed.truncated);
+    }
+}
@@ -8,15 +8,19 @@ use crate::network_policy::NetworkPolicyDecision;
 use crate::network_policy::NetworkPolicyRequest;
 use crate::network_policy::NetworkPolicyRequestArgs;
 use crate::network_policy::NetworkProtocol;
+use crate::network_policy::RequestRuleEvaluationArgs;
 use crate::network_policy::emit_allow_decision_audit_event;
 use crate::network_policy::emit_block_decision_audit_event;
 use crate::network_policy::evaluate_host_policy;
+use crate::network_policy::evaluate_request_rules;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
 use crate::reasons::REASON_UNIX_SOCKET_UNSUPPORTED;
+use crate::recording::replay_response;
+use crate::recording::start_recording;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_header_value;
 use crate::responses::blocked_message_with_policy;
@@ -143,6 +147,25 @@ async fn run_http_proxy_with_listener(
 }
 
 async fn http_connect_accept(
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    req: Request,
+) -> Result<(Response, Request), Response> {
+    let Some(app_state) = req.extensions().get::<Arc<NetworkProxyState>>().cloned() else {
+        return accept_connect(policy_decider, req).await;
+    };
+    let url = req.uri().to_string();
+    let (req, recording) = start_recording(&app_state, req, url).await;
+    let result = accept_connect(policy_decider, req).await;
+    match recording {
+        Some(recording) => match result {
+            Ok((response, req)) => Ok((recording.finish(response), req)),
+            Err(response) => Err(recording.finish(response)),
+        },
+        None => result,
+    }
+}
+
+async fn accept_connect(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     mut req: Request,
 ) -> Result<(Response, Request), Response> {
@@ -292,9 +315,82 @@ async fn http_connect_accept(
         return Err(blocked_text_with_details(REASON_MITM_REQUIRED, &details));
     }
 
+    let request_rules_cover_host = app_state
+        .request_rules_cover_host(&host)
+        .await
+        .map_err(|err| internal_error("failed to read request rules", err))?;
+    let replaying = app_state
+        .har_replay()
+        .await
+        .map_err(|err| internal_error("failed to read HAR replay", err))?
+        .is_some();
+    if (request_rules_cover_host || replaying) && mitm_state.is_none() {
+        // Request rules match on path and method, and replay answers individual requests; a
+        // plain tunnel would hide both.
+        let source = if request_rules_cover_host {
+            NetworkDecisionSource::RequestRule
+        } else {
+            NetworkDecisionSource::ProxyState
+        };
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source,
+                reason: REASON_MITM_REQUIRED,
+                protocol: NetworkProtocol::HttpsConnect,
+                server_address: host.as_str(),
+                server_port: authority.port,
+                method: Some("CONNECT"),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_MITM_REQUIRED,
+            source,
+            protocol: NetworkProtocol::HttpsConnect,
+            host: &host,
+            port: authority.port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: REASON_MITM_REQUIRED.to_string(),
+                client: client.clone(),
+                method: Some("CONNECT".to_string()),
+                mode: Some(mode),
+                protocol: "http-connect".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(authority.port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let what = if request_rules_cover_host {
+            "enforce request rules"
+        } else {
+            "replay recorded responses"
+        };
+        warn!("CONNECT blocked; MITM required to {what} (client={client}, host={host})");
+        return Err(blocked_text_with_details(REASON_MITM_REQUIRED, &details));
+    }
+
+    let credentials_cover_host = app_state
+        .credentials()
+        .await
+        .map_err(|err| internal_error("failed to read network credentials", err))?
+        .covers_host(&host);
+
     req.extensions_mut().insert(ProxyTarget(authority));
     req.extensions_mut().insert(mode);
-    if let Some(mitm_state) = mitm_state {
+    // Full mode only needs to see inner requests when request rules or credentials apply to this
+    // host, or when responses are replayed. Without MITM, credentials are simply not added.
+    if let Some(mitm_state) = mitm_state
+        && (mode == NetworkMode::Limited
+            || request_rules_cover_host
+            || credentials_cover_host
+            || replaying)
+    {
         req.extensions_mut().insert(mitm_state);
     }
 
@@ -323,11 +419,10 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         return Ok(());
     };
 
-    if mode == NetworkMode::Limited
-        && upgraded
-            .extensions()
-            .get::<Arc<mitm::MitmState>>()
-            .is_some()
+    if upgraded
+        .extensions()
+        .get::<Arc<mitm::MitmState>>()
+        .is_some()
     {
         let host = normalize_host(&target.host.to_string());
         let port = target.port;
@@ -414,6 +509,22 @@ async fn forward_connect_tunnel(
 }
 
 async fn http_plain_proxy(
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    req: Request,
+) -> Result<Response, Infallible> {
+    let Some(app_state) = req.extensions().get::<Arc<NetworkProxyState>>().cloned() else {
+        return proxy_plain_request(policy_decider, req).await;
+    };
+    let url = plain_http_url(&req);
+    let (req, recording) = start_recording(&app_state, req, url).await;
+    let response = proxy_plain_request(policy_decider, req).await?;
+    Ok(match recording {
+        Some(recording) => recording.finish(response),
+        None => response,
+    })
+}
+
+async fn proxy_plain_request(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     mut req: Request,
 ) -> Result<Response, Infallible> {
@@ -693,10 +804,70 @@ async fn http_plain_proxy(
         ));
     }
 
+    let path = req.uri().path().to_string();
+    match evaluate_request_rules(
+        &app_state,
+        RequestRuleEvaluationArgs {
+            protocol: NetworkProtocol::Http,
+            host: &host,
+            port,
+            method: req.method().as_str(),
+            path: &path,
+            client_addr: client.as_deref(),
+        },
+    )
+    .await
+    {
+        Ok(NetworkDecision::Deny {
+            reason,
+            source,
+            decision,
+        }) => {
+            let details = PolicyDecisionDetails {
+                decision,
+                reason: &reason,
+                source,
+                protocol: NetworkProtocol::Http,
+                host: &host,
+                port,
+            };
+            let _ = app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: reason.clone(),
+                    client: client.clone(),
+                    method: Some(req.method().as_str().to_string()),
+                    mode: None,
+                    protocol: "http".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            let method = req.method();
+            warn!(
+                "request blocked by request rules (client={client}, host={host}, method={method}, path={path}, reason={reason})"
+            );
+            return Ok(json_blocked(&host, &reason, Some(&details)));
+        }
+        Ok(NetworkDecision::Allow) => {}
+        Err(err) => {
+            error!("failed to evaluate request rules for {host}: {err}");
+            return Ok(text_response(StatusCode::INTERNAL_SERVER_ERROR, "error"));
+        }
+    }
+
     let client = client.as_deref().unwrap_or_default();
     let method = req.method();
     info!("request allowed (client={client}, host={host}, method={method})");
 
+    match replay_response(&app_state, method.as_str(), &plain_http_url(&req)).await {
+        Ok(Some(resp)) => return Ok(resp),
+        Ok(None) => {}
+        Err(err) => return Ok(internal_error("failed to read HAR replay", err)),
+    }
+
     let allow_upstream_proxy = match app_state
         .allow_upstream_proxy()
         .await
@@ -750,6 +921,24 @@ async fn proxy_via_unix_socket(req: Request, socket_path: &str) -> Result<Respon
     }
 }
 
+/// Absolute URL of a plain HTTP request, as recorded in and looked up from HAR files.
+fn plain_http_url(req: &Request) -> String {
+    if req.uri().scheme().is_some() {
+        return req.uri().to_string();
+    }
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(rama_http::uri::PathAndQuery::as_str)
+        .unwrap_or("/");
+    format!("http://{host}{path}")
+}
+
 fn client_addr<T: ExtensionsRef>(input: &T) -> Option<String> {
     input
         .extensions()
@@ -3,20 +3,26 @@
 mod admin;
 mod certs;
 mod config;
+mod credentials;
+mod har;
 mod http_proxy;
 mod mitm;
 mod network_policy;
 mod policy;
 mod proxy;
 mod reasons;
+mod recording;
 mod responses;
 mod runtime;
 mod socks5;
 mod state;
 mod upstream;
 
+pub use config::NetworkCredential;
 pub use config::NetworkMode;
 pub use config::NetworkProxyConfig;
+pub use config::NetworkRequestRule;
+pub use config::NetworkRuleAction;
 pub use config::host_and_port_from_network_addr;
 pub use network_policy::NetworkDecision;
 pub use network_policy::NetworkDecisionSource;
@@ -1,7 +1,14 @@
 use crate::certs::ManagedMitmCa;
 use crate::config::NetworkMode;
+use crate::credentials::Credentials;
+use crate::network_policy::NetworkDecision;
+use crate::network_policy::NetworkProtocol;
+use crate::network_policy::RequestRuleEvaluationArgs;
+use crate::network_policy::evaluate_request_rules;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::recording::replay_response;
+use crate::recording::start_recording;
 use crate::responses::blocked_text_response;
 use crate::responses::text_response;
 use crate::runtime::HostBlockDecision;
@@ -23,6 +30,7 @@ use rama_core::rt::Executor;
 use rama_core::service::service_fn;
 use rama_http::Body;
 use rama_http::BodyDataStream;
+use rama_http::HeaderName;
 use rama_http::HeaderValue;
 use rama_http::Request;
 use rama_http::Response;
@@ -41,6 +49,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context as TaskContext;
 use std::task::Poll;
+use tracing::debug;
 use tracing::info;
 use tracing::warn;
 
@@ -49,6 +58,7 @@ pub struct MitmState {
     ca: ManagedMitmCa,
     upstream: UpstreamClient,
     inspect: bool,
+    log_body_excerpts: bool,
     max_body_bytes: usize,
 }
 
@@ -74,13 +84,14 @@ impl std::fmt::Debug for MitmState {
         // Avoid dumping internal state (CA material, connectors, etc.) to logs.
         f.debug_struct("MitmState")
             .field("inspect", &self.inspect)
+            .field("log_body_excerpts", &self.log_body_excerpts)
             .field("max_body_bytes", &self.max_body_bytes)
             .finish_non_exhaustive()
     }
 }
 
 impl MitmState {
-    pub(crate) fn new(allow_upstream_proxy: bool) -> Result<Self> {
+    pub(crate) fn new(allow_upstream_proxy: bool, log_body_excerpts: bool) -> Result<Self> {
         // MITM exists to make limited-mode HTTPS enforceable: once CONNECT is established, plain
         // proxying would lose visibility into the inner HTTP request. We generate/load a local CA
         // and issue per-host leaf certs so we can terminate TLS and apply policy.
@@ -95,7 +106,9 @@ impl MitmState {
         Ok(Self {
             ca,
             upstream,
-            inspect: MITM_INSPECT_BODIES,
+            // Excerpts are logged by the inspection pass, so asking for them turns it on.
+            inspect: MITM_INSPECT_BODIES || log_body_excerpts,
+            log_body_excerpts,
             max_body_bytes: MITM_MAX_BODY_BYTES,
         })
     }
@@ -108,6 +121,10 @@ impl MitmState {
         self.inspect
     }
 
+    pub(crate) fn log_body_excerpts(&self) -> bool {
+        self.log_body_excerpts
+    }
+
     pub(crate) fn max_body_bytes(&self) -> usize {
         self.max_body_bytes
     }
@@ -184,6 +201,8 @@ async fn handle_mitm_request(
     req: Request,
     request_ctx: Arc<MitmRequestContext>,
 ) -> Result<Response, std::convert::Infallible> {
+    let url = recorded_url(&req, &request_ctx.policy);
+    let (req, recording) = start_recording(&request_ctx.policy.app_state, req, url).await;
     let response = match forward_request(req, &request_ctx).await {
         Ok(resp) => resp,
         Err(err) => {
@@ -191,7 +210,10 @@ async fn handle_mitm_request(
             text_response(StatusCode::BAD_GATEWAY, "mitm upstream error")
         }
     };
-    Ok(response)
+    Ok(match recording {
+        Some(recording) => recording.finish(response),
+        None => response,
+    })
 }
 
 async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Result<Response> {
@@ -201,7 +223,18 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
 
     let target_host = request_ctx.policy.target_host.clone();
     let target_port = request_ctx.policy.target_port;
+    let replay_url = recorded_url(&req, &request_ctx.policy);
+    if let Some(response) = replay_response(
+        &request_ctx.policy.app_state,
+        req.method().as_str(),
+        &replay_url,
+    )
+    .await?
+    {
+        return Ok(response);
+    }
     let mitm = request_ctx.mitm.clone();
+    let credentials = request_ctx.policy.app_state.credentials().await?;
 
     let method = req.method().as_str().to_string();
     let path = path_and_query(req.uri());
@@ -213,13 +246,27 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     parts
         .headers
         .insert(HOST, HeaderValue::from_str(&authority)?);
+    let injected = credentials.apply(&target_host, &mut parts.headers);
+    if !injected.is_empty() {
+        let headers = injected
+            .iter()
+            .map(HeaderName::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        debug!(
+            "MITM added credentials (host={authority}, method={method}, path={log_path}, headers={headers})"
+        );
+    }
 
     let inspect = mitm.inspect_enabled();
     let max_body_bytes = mitm.max_body_bytes();
+    // Only the explicit debug option logs body contents; otherwise just lengths are logged.
+    let excerpt_credentials = mitm.log_body_excerpts().then_some(credentials);
     let body = if inspect {
         inspect_body(
             body,
             max_body_bytes,
+            excerpt_credentials.clone(),
             RequestLogContext {
                 host: authority.clone(),
                 method: method.clone(),
@@ -236,6 +283,7 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
         upstream_resp,
         inspect,
         max_body_bytes,
+        excerpt_credentials,
         &method,
         &log_path,
         &authority,
@@ -327,6 +375,45 @@ async fn mitm_blocking_response(
         return Ok(Some(blocked_text_response(REASON_METHOD_NOT_ALLOWED)));
     }
 
+    let decision = evaluate_request_rules(
+        &policy.app_state,
+        RequestRuleEvaluationArgs {
+            protocol: NetworkProtocol::HttpsConnect,
+            host: &policy.target_host,
+            port: policy.target_port,
+            method: &method,
+            path: req.uri().path(),
+            client_addr: client.as_deref(),
+        },
+    )
+    .await?;
+    if let NetworkDecision::Deny {
+        reason,
+        source,
+        decision,
+    } = decision
+    {
+        let _ = policy
+            .app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: policy.target_host.clone(),
+                reason: reason.clone(),
+                client,
+                method: Some(method.clone()),
+                mode: Some(policy.mode),
+                protocol: "https".to_string(),
+                decision: Some(decision.as_str().to_string()),
+                source: Some(source.as_str().to_string()),
+                port: Some(policy.target_port),
+            }))
+            .await;
+        warn!(
+            "MITM blocked by request rules (host={}, method={method}, path={log_path}, reason={reason})",
+            policy.target_host
+        );
+        return Ok(Some(blocked_text_response(&reason)));
+    }
+
     Ok(None)
 }
 
@@ -334,6 +421,7 @@ fn respond_with_inspection(
     resp: Response,
     inspect: bool,
     max_body_bytes: usize,
+    excerpt_credentials: Option<Credentials>,
     method: &str,
     log_path: &str,
     authority: &str,
@@ -346,6 +434,7 @@ fn respond_with_inspection(
     let body = inspect_body(
         body,
         max_body_bytes,
+        excerpt_credentials,
         ResponseLogContext {
             host: authority.to_string(),
             method: method.to_string(),
@@ -356,16 +445,26 @@ fn respond_with_inspection(
     Ok(Response::from_parts(parts, body))
 }
 
+/// Pass `body` through unchanged and log its length once it ends. With `excerpt_credentials`, an
+/// excerpt of up to `max_body_bytes` is logged too, with those credential values redacted.
 fn inspect_body<T: BodyLoggable + Send + 'static>(
     body: Body,
     max_body_bytes: usize,
+    excerpt_credentials: Option<Credentials>,
     ctx: T,
 ) -> Body {
+    // Capture enough past the cut to redact a secret that straddles it.
+    let capture_limit = excerpt_credentials.as_ref().map_or(0, |credentials| {
+        max_body_bytes.saturating_add(credentials.max_secret_len())
+    });
     Body::from_stream(InspectStream {
         inner: Box::pin(body.into_data_stream()),
         ctx: Some(Box::new(ctx)),
         len: 0,
         max_body_bytes,
+        captured: Vec::new(),
+        capture_limit,
+        excerpt_credentials,
     })
 }
 
@@ -374,6 +473,9 @@ struct InspectStream<T> {
     ctx: Option<Box<T>>,
     len: usize,
     max_body_bytes: usize,
+    captured: Vec<u8>,
+    capture_limit: usize,
+    excerpt_credentials: Option<Credentials>,
 }
 
 impl<T: BodyLoggable> Stream for InspectStream<T> {
@@ -384,12 +486,18 @@ impl<T: BodyLoggable> Stream for InspectStream<T> {
         match this.inner.as_mut().poll_next(cx) {
             Poll::Ready(Some(Ok(bytes))) => {
                 this.len = this.len.saturating_add(bytes.len());
+                let remaining = this.capture_limit.saturating_sub(this.captured.len());
+                this.captured
+                    .extend_from_slice(&bytes[..bytes.len().min(remaining)]);
                 Poll::Ready(Some(Ok(bytes)))
             }
             Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err))),
             Poll::Ready(None) => {
                 if let Some(ctx) = this.ctx.take() {
-                    ctx.log(this.len, this.len > this.max_body_bytes);
+                    let excerpt = this.excerpt_credentials.as_ref().map(|credentials| {
+                        body_excerpt(&this.captured, this.max_body_bytes, credentials)
+                    });
+                    ctx.log(this.len, this.len > this.max_body_bytes, excerpt.as_deref());
                 }
                 Poll::Ready(None)
             }
@@ -412,22 +520,27 @@ struct ResponseLogContext {
 }
 
 trait BodyLoggable {
-    fn log(self, len: usize, truncated: bool);
+    fn log(self, len: usize, truncated: bool, excerpt: Option<&str>);
 }
 
 impl BodyLoggable for RequestLogContext {
-    fn log(self, len: usize, truncated: bool) {
+    fn log(self, len: usize, truncated: bool, excerpt: Option<&str>) {
         let host = self.host;
         let method = self.method;
         let path = self.path;
         info!(
             "MITM inspected request body (host={host}, method={method}, path={path}, body_len={len}, truncated={truncated})"
         );
+        if let Some(excerpt) = excerpt {
+            debug!(
+                "MITM request body excerpt (host={host}, method={method}, path={path}, excerpt={excerpt:?})"
+            );
+        }
     }
 }
 
 impl BodyLoggable for ResponseLogContext {
-    fn log(self, len: usize, truncated: bool) {
+    fn log(self, len: usize, truncated: bool, excerpt: Option<&str>) {
         let host = self.host;
         let method = self.method;
         let path = self.path;
@@ -435,7 +548,23 @@ impl BodyLoggable for ResponseLogContext {
         info!(
             "MITM inspected response body (host={host}, method={method}, path={path}, status={status}, body_len={len}, truncated={truncated})"
         );
+        if let Some(excerpt) = excerpt {
+            debug!(
+                "MITM response body excerpt (host={host}, method={method}, path={path}, status={status}, excerpt={excerpt:?})"
+            );
+        }
+    }
+}
+
+/// Redact before cutting to `max_body_bytes` so a secret crossing the cut is never half shown.
+fn body_excerpt(captured: &[u8], max_body_bytes: usize, credentials: &Credentials) -> String {
+    let mut excerpt = credentials.redact(&String::from_utf8_lossy(captured));
+    let mut end = excerpt.len().min(max_body_bytes);
+    while !excerpt.is_char_boundary(end) {
+        end -= 1;
     }
+    excerpt.truncate(end);
+    excerpt
 }
 
 fn extract_request_host(req: &Request) -> Option<String> {
@@ -473,6 +602,12 @@ fn path_and_query(uri: &Uri) -> String {
         .to_string()
 }
 
+/// URL of an intercepted request as it appears in HAR recordings.
+fn recorded_url(req: &Request, policy: &MitmPolicyContext) -> String {
+    let authority = authority_header_value(&policy.target_host, policy.target_port);
+    format!("https://{authority}{}", path_and_query(req.uri()))
+}
+
 fn path_for_log(uri: &Uri) -> String {
     uri.path().to_string()
 }
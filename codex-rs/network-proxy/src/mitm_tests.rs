@@ -1,8 +1,12 @@
 use super::*;
 
+use crate::config::NetworkCredential;
 use crate::config::NetworkProxySettings;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRuleAction;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_PATH_NOT_ALLOWED;
 use crate::runtime::network_proxy_state_for_policy;
 use pretty_assertions::assert_eq;
 use rama_http::Body;
@@ -57,6 +61,50 @@ async fn mitm_policy_blocks_disallowed_method_and_records_telemetry() {
     assert_eq!(blocked[0].port, Some(443));
 }
 
+#[tokio::test]
+async fn mitm_policy_enforces_request_rules_on_inner_requests() {
+    let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
+        allowed_domains: vec!["github.com".to_string()],
+        request_rules: vec![NetworkRequestRule {
+            host: "github.com".to_string(),
+            path_prefix: "/our-org".to_string(),
+            methods: vec!["GET".to_string()],
+            action: NetworkRuleAction::Allow,
+        }],
+        ..NetworkProxySettings::default()
+    }));
+    let ctx = policy_ctx(app_state.clone(), NetworkMode::Full, "github.com", 443);
+    let request = |path: &str| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(path)
+            .header(HOST, "github.com")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let allowed = mitm_blocking_response(&request("/our-org/repo.git/info/refs"), &ctx)
+        .await
+        .unwrap();
+    assert!(allowed.is_none(), "allowed path should be forwarded");
+
+    let response = mitm_blocking_response(&request("/other-org/repo"), &ctx)
+        .await
+        .unwrap()
+        .expect("path outside the allow rules should be blocked");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        response.headers().get("x-proxy-error").unwrap(),
+        "blocked-by-request-rule"
+    );
+
+    let blocked = app_state.drain_blocked().await.unwrap();
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].reason, REASON_PATH_NOT_ALLOWED);
+    assert_eq!(blocked[0].source.as_deref(), Some("request_rule"));
+    assert_eq!(blocked[0].host, "github.com");
+}
+
 #[tokio::test]
 async fn mitm_policy_rejects_host_mismatch() {
     let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
@@ -108,3 +156,27 @@ async fn mitm_policy_rechecks_local_private_target_after_connect() {
     assert_eq!(blocked[0].host, "10.0.0.1");
     assert_eq!(blocked[0].port, Some(443));
 }
+
+#[test]
+fn body_excerpt_redacts_credentials_that_cross_the_cut() {
+    let credentials = Credentials::resolve(
+        &[NetworkCredential {
+            host: "api.example.com".to_string(),
+            header: "Authorization".to_string(),
+            secret: "API_TOKEN".to_string(),
+            prefix: "Bearer ".to_string(),
+            environment: None,
+        }],
+        |_, _| Ok(Some("tok-1234".to_string())),
+    )
+    .unwrap();
+
+    assert_eq!(
+        body_excerpt(b"echo: tok-1234 done", 10, &credentials),
+        "echo: [RED"
+    );
+    assert_eq!(
+        body_excerpt(b"{\"token\":\"tok-1234\"}", 64, &credentials),
+        "{\"token\":\"[REDACTED_SECRET]\"}"
+    );
+}
@@ -1,3 +1,6 @@
+use crate::policy::RequestRuleDecision;
+use crate::reasons::REASON_PATH_DENIED;
+use crate::reasons::REASON_PATH_NOT_ALLOWED;
 use crate::reasons::REASON_POLICY_DENIED;
 use crate::runtime::HostBlockDecision;
 use crate::runtime::HostBlockReason;
@@ -13,6 +16,7 @@ const AUDIT_TARGET: &str = "codex_otel.network_proxy";
 const POLICY_DECISION_EVENT_NAME: &str = "codex.network_proxy.policy_decision";
 const POLICY_SCOPE_DOMAIN: &str = "domain";
 const POLICY_SCOPE_NON_DOMAIN: &str = "non_domain";
+const POLICY_SCOPE_REQUEST: &str = "request";
 const POLICY_DECISION_ALLOW: &str = "allow";
 const POLICY_DECISION_DENY: &str = "deny";
 const POLICY_REASON_ALLOW: &str = "allow";
@@ -61,6 +65,7 @@ pub enum NetworkDecisionSource {
     ModeGuard,
     ProxyState,
     Decider,
+    RequestRule,
 }
 
 impl NetworkDecisionSource {
@@ -70,6 +75,7 @@ impl NetworkDecisionSource {
             Self::ModeGuard => "mode_guard",
             Self::ProxyState => "proxy_state",
             Self::Decider => "decider",
+            Self::RequestRule => "request_rule",
         }
     }
 }
@@ -212,6 +218,67 @@ fn emit_non_domain_policy_decision_audit_event(
     );
 }
 
+pub(crate) struct RequestRuleEvaluationArgs<'a> {
+    pub protocol: NetworkProtocol,
+    pub host: &'a str,
+    pub port: u16,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub client_addr: Option<&'a str>,
+}
+
+/// Check a request the proxy can see in full against `network.request_rules`.
+///
+/// Runs after the host policy has allowed the request. A `request`-scoped audit event is emitted
+/// whenever a rule applies; like the other audit events it leaves out the path.
+pub(crate) async fn evaluate_request_rules(
+    state: &NetworkProxyState,
+    args: RequestRuleEvaluationArgs<'_>,
+) -> Result<NetworkDecision> {
+    let rule_decision = state
+        .request_rule_decision(args.host, args.method, args.path)
+        .await?;
+    let (decision, reason) = match rule_decision {
+        RequestRuleDecision::Unrestricted => return Ok(NetworkDecision::Allow),
+        RequestRuleDecision::Allowed => (NetworkDecision::Allow, POLICY_REASON_ALLOW),
+        RequestRuleDecision::Denied => (
+            NetworkDecision::deny_with_source(
+                REASON_PATH_DENIED,
+                NetworkDecisionSource::RequestRule,
+            ),
+            REASON_PATH_DENIED,
+        ),
+        RequestRuleDecision::NotAllowed => (
+            NetworkDecision::deny_with_source(
+                REASON_PATH_NOT_ALLOWED,
+                NetworkDecisionSource::RequestRule,
+            ),
+            REASON_PATH_NOT_ALLOWED,
+        ),
+    };
+
+    emit_policy_audit_event(
+        state,
+        PolicyAuditEventArgs {
+            scope: POLICY_SCOPE_REQUEST,
+            decision: match &decision {
+                NetworkDecision::Allow => POLICY_DECISION_ALLOW,
+                NetworkDecision::Deny { .. } => POLICY_DECISION_DENY,
+            },
+            source: NetworkDecisionSource::RequestRule.as_str(),
+            reason,
+            protocol: args.protocol,
+            server_address: args.host,
+            server_port: args.port,
+            method: Some(args.method),
+            client_addr: args.client_addr,
+            policy_override: false,
+        },
+    );
+
+    Ok(decision)
+}
+
 struct PolicyAuditEventArgs<'a> {
     scope: &'static str,
     decision: &'a str,
@@ -536,6 +603,8 @@ mod tests {
     use crate::config::NetworkMode;
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
+    use crate::config::NetworkRuleAction;
     use crate::reasons::REASON_DENIED;
     use crate::reasons::REASON_METHOD_NOT_ALLOWED;
     use crate::reasons::REASON_NOT_ALLOWED;
@@ -848,6 +917,63 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn evaluate_request_rules_emits_request_event_for_denied_path() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_domains: vec!["registry.example.com".to_string()],
+            request_rules: vec![NetworkRequestRule {
+                host: "registry.example.com".to_string(),
+                path_prefix: "/upload".to_string(),
+                methods: vec!["POST".to_string()],
+                action: NetworkRuleAction::Deny,
+            }],
+            ..NetworkProxySettings::default()
+        });
+        let args = |method| RequestRuleEvaluationArgs {
+            protocol: NetworkProtocol::Http,
+            host: "registry.example.com",
+            port: 443,
+            method,
+            path: "/upload/pkg-1.0.tgz",
+            client_addr: None,
+        };
+
+        let (decision, events) = capture_events(|| async {
+            evaluate_request_rules(&state, args("POST")).await.unwrap()
+        })
+        .await;
+
+        assert_eq!(
+            decision,
+            NetworkDecision::Deny {
+                reason: REASON_PATH_DENIED.to_string(),
+                source: NetworkDecisionSource::RequestRule,
+                decision: NetworkPolicyDecision::Deny,
+            }
+        );
+        let event = find_event_by_name(&events, POLICY_DECISION_EVENT_NAME)
+            .expect("expected policy decision audit event");
+        assert_eq!(
+            event.field("network.policy.scope"),
+            Some(POLICY_SCOPE_REQUEST)
+        );
+        assert_eq!(event.field("network.policy.source"), Some("request_rule"));
+        assert_eq!(
+            event.field("network.policy.reason"),
+            Some(REASON_PATH_DENIED)
+        );
+        assert_eq!(event.field("http.request.method"), Some("POST"));
+
+        let (decision, events) =
+            capture_events(|| async { evaluate_request_rules(&state, args("GET")).await.unwrap() })
+                .await;
+        assert_eq!(decision, NetworkDecision::Allow);
+        assert_eq!(
+            find_event_by_name(&events, POLICY_DECISION_EVENT_NAME),
+            None
+        );
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn evaluate_host_policy_still_denies_not_allowed_local_without_decider_override() {
         let state = network_proxy_state_for_policy(NetworkProxySettings {
@@ -1,5 +1,7 @@
 #[cfg(test)]
 use crate::config::NetworkMode;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRuleAction;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::ensure;
@@ -168,6 +170,185 @@ pub(crate) fn compile_globset(patterns: &[String]) -> Result<GlobSet> {
     Ok(builder.build()?)
 }
 
+/// Outcome of checking a request against the configured [`NetworkRequestRule`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RequestRuleDecision {
+    /// No allow or deny rule applies, so only the host policy decides.
+    Unrestricted,
+    Allowed,
+    /// A deny rule matched.
+    Denied,
+    /// The host has allow rules, but none of them matched.
+    NotAllowed,
+}
+
+/// Compiled form of `network.request_rules`.
+#[derive(Clone, Debug, Default)]
+pub struct RequestRules {
+    rules: Vec<CompiledRequestRule>,
+}
+
+#[derive(Clone, Debug)]
+struct CompiledRequestRule {
+    hosts: GlobSet,
+    path_prefix: String,
+    methods: Vec<String>,
+    action: NetworkRuleAction,
+}
+
+impl RequestRules {
+    pub(crate) fn compile(rules: &[NetworkRequestRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                CompiledRequestRule::compile(rule)
+                    .with_context(|| format!("invalid network.request_rules[{index}]"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Returns true if any rule names `host`. CONNECT tunnels to such hosts must be terminated
+    /// (MITM) so the rules can see the inner requests.
+    pub(crate) fn covers_host(&self, host: &str) -> bool {
+        self.rules.iter().any(|rule| rule.hosts.is_match(host))
+    }
+
+    pub(crate) fn evaluate(&self, host: &str, method: &str, path: &str) -> RequestRuleDecision {
+        let mut host_rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.hosts.is_match(host))
+            .peekable();
+        if host_rules.peek().is_none() {
+            return RequestRuleDecision::Unrestricted;
+        }
+        // The upstream may decode the path differently than we would, so a path we cannot
+        // normalize unambiguously is never matched against the rules.
+        let Some(path) = normalize_request_path(path) else {
+            return RequestRuleDecision::Denied;
+        };
+        let mut host_has_allow_rules = false;
+        let mut allowed = false;
+        for rule in host_rules {
+            let matches = rule.matches(method, &path);
+            match rule.action {
+                NetworkRuleAction::Deny if matches => return RequestRuleDecision::Denied,
+                NetworkRuleAction::Deny => {}
+                NetworkRuleAction::Allow => {
+                    host_has_allow_rules = true;
+                    allowed |= matches;
+                }
+            }
+        }
+
+        if allowed {
+            RequestRuleDecision::Allowed
+        } else if host_has_allow_rules {
+            RequestRuleDecision::NotAllowed
+        } else {
+            RequestRuleDecision::Unrestricted
+        }
+    }
+}
+
+impl CompiledRequestRule {
+    fn compile(rule: &NetworkRequestRule) -> Result<Self> {
+        ensure!(!rule.host.trim().is_empty(), "host is empty");
+        ensure!(
+            rule.path_prefix.starts_with('/'),
+            "path_prefix must start with `/`, got {:?}",
+            rule.path_prefix
+        );
+        let methods = rule
+            .methods
+            .iter()
+            .map(|method| {
+                let method = method.trim().to_ascii_uppercase();
+                ensure!(
+                    !method.is_empty() && method.bytes().all(|b| b.is_ascii_alphabetic()),
+                    "invalid HTTP method {method:?}"
+                );
+                Ok(method)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let path_prefix = normalize_request_path(&rule.path_prefix).with_context(|| {
+            format!(
+                "path_prefix {:?} contains an escaped `/` or dot segment",
+                rule.path_prefix
+            )
+        })?;
+        Ok(Self {
+            hosts: compile_globset(std::slice::from_ref(&rule.host))?,
+            path_prefix,
+            methods,
+            action: rule.action,
+        })
+    }
+
+    fn matches(&self, method: &str, path: &str) -> bool {
+        let method_matches = self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method));
+        method_matches && path_has_prefix(path, &self.path_prefix)
+    }
+}
+
+/// Decode escaped unreserved characters, then resolve `.`/`..` segments and repeated slashes so
+/// rules cannot be sidestepped with paths like `/%75pload` or `/our-org/../other-org`.
+///
+/// Returns `None` for paths servers disagree on: an escaped `/` or `\`, a segment such as
+/// `%2e%2e` that only becomes a dot segment once decoded, or a malformed escape.
+fn normalize_request_path(path: &str) -> Option<String> {
+    let mut segments = Vec::new();
+    for raw in path.split('/') {
+        let segment = decode_unreserved(raw)?;
+        match segment.as_str() {
+            "." | ".." if segment != raw => return None,
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// Percent-decode the RFC 3986 unreserved characters in a path segment. Other escapes are kept,
+/// with their hex digits uppercased so equivalent spellings compare equal.
+fn decode_unreserved(segment: &str) -> Option<String> {
+    let mut decoded = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(index) = rest.find('%') {
+        decoded.push_str(&rest[..index]);
+        let hex = rest.get(index + 1..index + 3)?;
+        let byte = u8::from_str_radix(hex, 16).ok()?;
+        match byte {
+            b'/' | b'\\' => return None,
+            b if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') => {
+                decoded.push(char::from(b));
+            }
+            b => decoded.push_str(&format!("%{b:02X}")),
+        }
+        rest = &rest[index + 3..];
+    }
+    decoded.push_str(rest);
+    Some(decoded)
+}
+
+/// Segment-aware prefix check on normalized paths: `/a` matches `/a` and `/a/b`, not `/ab`.
+fn path_has_prefix(path: &str, prefix: &str) -> bool {
+    prefix == "/"
+        || path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum DomainPattern {
     Any,
@@ -362,6 +543,161 @@ mod tests {
         assert_eq!(true, set.is_match("::1"));
     }
 
+    fn request_rule(
+        host: &str,
+        path_prefix: &str,
+        methods: &[&str],
+        action: NetworkRuleAction,
+    ) -> NetworkRequestRule {
+        NetworkRequestRule {
+            host: host.to_string(),
+            path_prefix: path_prefix.to_string(),
+            methods: methods.iter().map(ToString::to_string).collect(),
+            action,
+        }
+    }
+
+    #[test]
+    fn request_rules_scope_allowed_paths_per_host() {
+        let rules = RequestRules::compile(&[
+            request_rule(
+                "github.com",
+                "/our-org",
+                &["GET", "HEAD"],
+                NetworkRuleAction::Allow,
+            ),
+            request_rule(
+                "registry.example.com",
+                "/upload/",
+                &["post"],
+                NetworkRuleAction::Deny,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/our-org/repo"),
+            RequestRuleDecision::Allowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/our-org"),
+            RequestRuleDecision::Allowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/our-org-fork/repo"),
+            RequestRuleDecision::NotAllowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/our-org/../other-org/repo"),
+            RequestRuleDecision::NotAllowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "POST", "/our-org/repo"),
+            RequestRuleDecision::NotAllowed
+        );
+        assert_eq!(
+            rules.evaluate("registry.example.com", "POST", "/upload/pkg"),
+            RequestRuleDecision::Denied
+        );
+        assert_eq!(
+            rules.evaluate("registry.example.com", "GET", "/upload/pkg"),
+            RequestRuleDecision::Unrestricted
+        );
+        assert_eq!(
+            rules.evaluate("example.com", "POST", "/upload/pkg"),
+            RequestRuleDecision::Unrestricted
+        );
+        assert!(rules.covers_host("registry.example.com"));
+        assert!(!rules.covers_host("example.com"));
+    }
+
+    #[test]
+    fn request_rules_deny_wins_over_allow() {
+        let rules = RequestRules::compile(&[
+            request_rule("**.example.com", "/", &[], NetworkRuleAction::Allow),
+            request_rule("api.example.com", "/admin", &[], NetworkRuleAction::Deny),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate("api.example.com", "GET", "/admin/users"),
+            RequestRuleDecision::Denied
+        );
+        assert_eq!(
+            rules.evaluate("api.example.com", "GET", "/v1/users"),
+            RequestRuleDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn request_rules_match_percent_decoded_paths() {
+        let rules = RequestRules::compile(&[
+            request_rule("github.com", "/our-org", &[], NetworkRuleAction::Allow),
+            request_rule(
+                "registry.example.com",
+                "/upload",
+                &[],
+                NetworkRuleAction::Deny,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate("registry.example.com", "POST", "/%75pload/x"),
+            RequestRuleDecision::Denied
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/%6fur-org/repo"),
+            RequestRuleDecision::Allowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "GET", "/our-org/repo%20name"),
+            RequestRuleDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn request_rules_deny_escaped_slashes_and_dot_segments() {
+        let rules = RequestRules::compile(&[request_rule(
+            "github.com",
+            "/our-org",
+            &[],
+            NetworkRuleAction::Allow,
+        )])
+        .unwrap();
+
+        for path in [
+            "/our-org/%2e%2e/other-org/repo",
+            "/our-org/%2E/repo",
+            "/our-org%2fother-org",
+            "/our-org%5C..%5Cother-org",
+            "/our-org/%zz",
+        ] {
+            assert_eq!(
+                rules.evaluate("github.com", "GET", path),
+                RequestRuleDecision::Denied,
+                "{path}"
+            );
+        }
+        assert_eq!(
+            rules.evaluate("example.com", "GET", "/%2e%2e/x"),
+            RequestRuleDecision::Unrestricted
+        );
+    }
+
+    #[test]
+    fn request_rules_reject_relative_path_prefix() {
+        let err = RequestRules::compile(&[request_rule(
+            "example.com",
+            "our-org",
+            &[],
+            NetworkRuleAction::Allow,
+        )])
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid network.request_rules[0]");
+    }
+
     #[test]
     fn is_loopback_host_handles_localhost_variants() {
         assert!(is_loopback_host(&Host::parse("localhost").unwrap()));
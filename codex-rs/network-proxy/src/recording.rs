@@ -0,0 +1,348 @@
+//! Records proxied exchanges to the session HAR file (`network.har_record_dir`) and answers
+//! requests from a recorded one (`network.har_replay_file`).
+
+use crate::credentials::Credentials;
+use crate::har::BodyExcerpt;
+use crate::har::HarEntry;
+use crate::har::HarHeader;
+use crate::har::HarRecorder;
+use crate::har::HarRequest;
+use crate::har::HarResponse;
+use crate::har::HarTimings;
+use crate::har::ReplayedResponse;
+use crate::state::NetworkProxyState;
+use anyhow::Result;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::HeaderMap;
+use rama_http::HeaderName;
+use rama_http::HeaderValue;
+use rama_http::Request;
+use rama_http::Response;
+use rama_http::StatusCode;
+use rama_http::header;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Instant;
+use tracing::warn;
+
+/// Request and response bodies are kept up to this size in recordings.
+const HAR_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// A request being recorded. Pass the response sent back to the client to
+/// [`HarRecording::finish`]; the entry is written once that response body has been sent or
+/// dropped.
+pub(crate) struct HarRecording {
+    recorder: Arc<HarRecorder>,
+    credentials: Credentials,
+    started_date_time: String,
+    started: Instant,
+    request: HarRequest,
+    request_mime_type: Option<String>,
+    request_body: Arc<Mutex<BodyCapture>>,
+}
+
+/// Start recording `req` if `network.har_record_dir` is set. Headers are taken as the client sent
+/// them, before any credentials are injected.
+pub(crate) async fn start_recording(
+    app_state: &NetworkProxyState,
+    req: Request,
+    url: String,
+) -> (Request, Option<HarRecording>) {
+    let recorder = match app_state.har_recorder().await {
+        Ok(Some(recorder)) => recorder,
+        Ok(None) => return (req, None),
+        Err(err) => {
+            warn!("failed to open HAR recording: {err:#}");
+            return (req, None);
+        }
+    };
+    let credentials = match app_state.credentials().await {
+        Ok(credentials) => credentials,
+        Err(err) => {
+            warn!("failed to read network credentials; not recording request: {err:#}");
+            return (req, None);
+        }
+    };
+
+    let (parts, body) = req.into_parts();
+    let request = HarRequest {
+        method: parts.method.as_str().to_string(),
+        url,
+        http_version: format!("{:?}", parts.version),
+        headers: har_headers(&parts.headers, &credentials),
+        query_string: parts
+            .uri
+            .query()
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .map(|(name, value)| HarHeader {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..HarRequest::default()
+    };
+    let request_body = Arc::new(Mutex::new(BodyCapture::new(&credentials)));
+    let body = Body::from_stream(CaptureStream {
+        inner: Box::pin(body.into_data_stream()),
+        capture: request_body.clone(),
+        pending: None,
+    });
+    let recording = HarRecording {
+        recorder,
+        request_mime_type: content_type(&parts.headers),
+        credentials,
+        started_date_time: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        started: Instant::now(),
+        request,
+        request_body,
+    };
+    (Request::from_parts(parts, body), Some(recording))
+}
+
+impl HarRecording {
+    pub(crate) fn finish(self, response: Response) -> Response {
+        let (parts, body) = response.into_parts();
+        let response_body = Arc::new(Mutex::new(BodyCapture::new(&self.credentials)));
+        let response = HarResponse {
+            status: parts.status.as_u16(),
+            status_text: parts
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            http_version: format!("{:?}", parts.version),
+            headers: har_headers(&parts.headers, &self.credentials),
+            ..HarResponse::default()
+        };
+        let pending = PendingEntry {
+            response_mime_type: content_type(&parts.headers),
+            recording: self,
+            response,
+            response_body: response_body.clone(),
+        };
+        let body = Body::from_stream(CaptureStream {
+            inner: Box::pin(body.into_data_stream()),
+            capture: response_body,
+            pending: Some(pending),
+        });
+        Response::from_parts(parts, body)
+    }
+}
+
+struct PendingEntry {
+    recording: HarRecording,
+    response: HarResponse,
+    response_mime_type: Option<String>,
+    response_body: Arc<Mutex<BodyCapture>>,
+}
+
+impl PendingEntry {
+    fn write(self) {
+        let recording = self.recording;
+        let time = recording.started.elapsed().as_secs_f64() * 1000.0;
+
+        let mut request = recording.request;
+        let request_body = lock(&recording.request_body).excerpt(&recording.credentials);
+        if let Some(excerpt) = request_body {
+            request.body_size = i64::try_from(excerpt.body_len()).unwrap_or(i64::MAX);
+            request.post_data =
+                Some(excerpt.to_post_data(recording.request_mime_type.unwrap_or_default()));
+        } else {
+            request.body_size = 0;
+        }
+
+        let mut response = self.response;
+        let response_body = lock(&self.response_body).excerpt(&recording.credentials);
+        let response_body = response_body.unwrap_or_default();
+        response.body_size = i64::try_from(response_body.body_len()).unwrap_or(i64::MAX);
+        response.content = response_body.to_content(self.response_mime_type.unwrap_or_default());
+
+        let entry = HarEntry {
+            started_date_time: recording.started_date_time,
+            time,
+            request,
+            response,
+            timings: HarTimings {
+                send: 0.0,
+                wait: time,
+                receive: 0.0,
+            },
+            ..HarEntry::default()
+        };
+        let recorder = recording.recorder;
+        let append = move || {
+            if let Err(err) = recorder.append(&entry) {
+                warn!(
+                    "failed to write HAR entry to {}: {err:#}",
+                    recorder.path().display()
+                );
+            }
+        };
+        // The body stream is dropped on a runtime worker; keep the file IO off it.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(append);
+            }
+            Err(_) => append(),
+        }
+    }
+}
+
+/// Bytes seen on a body stream, kept up to [`HAR_MAX_BODY_BYTES`] plus enough extra to redact a
+/// credential value that crosses the cut.
+struct BodyCapture {
+    bytes: Vec<u8>,
+    len: usize,
+    capture_limit: usize,
+}
+
+impl BodyCapture {
+    fn new(credentials: &Credentials) -> Self {
+        Self {
+            bytes: Vec::new(),
+            len: 0,
+            capture_limit: HAR_MAX_BODY_BYTES.saturating_add(credentials.max_secret_len()),
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.len = self.len.saturating_add(chunk.len());
+        let remaining = self.capture_limit.saturating_sub(self.bytes.len());
+        self.bytes
+            .extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+    }
+
+    fn excerpt(&self, credentials: &Credentials) -> Option<BodyExcerpt> {
+        (self.len > 0).then(|| {
+            BodyExcerpt::new(
+                credentials.redact_bytes(&self.bytes),
+                self.len,
+                HAR_MAX_BODY_BYTES,
+            )
+        })
+    }
+}
+
+fn lock(capture: &Mutex<BodyCapture>) -> std::sync::MutexGuard<'_, BodyCapture> {
+    capture
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Passes a body through unchanged while copying it into a [`BodyCapture`]. The response stream
+/// also carries the pending entry and writes it when dropped, whether or not the body finished.
+struct CaptureStream {
+    inner: Pin<Box<BodyDataStream>>,
+    capture: Arc<Mutex<BodyCapture>>,
+    pending: Option<PendingEntry>,
+}
+
+impl Stream for CaptureStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.inner.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(Ok(bytes))) = &poll {
+            lock(&this.capture).push(bytes);
+        }
+        poll
+    }
+}
+
+impl Drop for CaptureStream {
+    fn drop(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            pending.write();
+        }
+    }
+}
+
+fn har_headers(headers: &HeaderMap, credentials: &Credentials) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.as_str().to_string(),
+            value: String::from_utf8_lossy(&credentials.redact_bytes(value.as_bytes()))
+                .into_owned(),
+        })
+        .collect()
+}
+
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+/// Answer from `network.har_replay_file` instead of the network. Returns `None` when replay is
+/// off; requests missing from the recording get a 502 rather than going out.
+pub(crate) async fn replay_response(
+    app_state: &NetworkProxyState,
+    method: &str,
+    url: &str,
+) -> Result<Option<Response>> {
+    let Some(replay) = app_state.har_replay().await? else {
+        return Ok(None);
+    };
+    let Some(recorded) = replay.next_response(method, url) else {
+        warn!(
+            "no recorded response in {} for {method} {url}",
+            replay.path().display()
+        );
+        let mut response = crate::responses::text_response(
+            StatusCode::BAD_GATEWAY,
+            "no recorded response for this request",
+        );
+        response
+            .headers_mut()
+            .insert("x-proxy-error", HeaderValue::from_static("replay-miss"));
+        return Ok(Some(response));
+    };
+    if recorded.truncated {
+        warn!("replaying a truncated recorded body for {method} {url}");
+    }
+    Ok(Some(response_from_recording(recorded)))
+}
+
+fn response_from_recording(recorded: ReplayedResponse) -> Response {
+    let mut response = Response::new(Body::from(recorded.body));
+    *response.status_mut() =
+        StatusCode::from_u16(recorded.status).unwrap_or(StatusCode::BAD_GATEWAY);
+    for HarHeader { name, value } in recorded.headers {
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) else {
+            continue;
+        };
+        // The body length is set from the recorded body, and hop-by-hop headers belonged to the
+        // recorded connection.
+        if [
+            header::CONTENT_LENGTH,
+            header::TRANSFER_ENCODING,
+            header::CONNECTION,
+            header::KEEP_ALIVE,
+            header::UPGRADE,
+        ]
+        .contains(&name)
+        {
+            continue;
+        }
+        response.headers_mut().append(name, value);
+    }
+    response
+}
@@ -6,6 +6,8 @@ use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_PATH_DENIED;
+use crate::reasons::REASON_PATH_NOT_ALLOWED;
 use rama_http::Body;
 use rama_http::Response;
 use rama_http::StatusCode;
@@ -53,6 +55,7 @@ pub fn blocked_header_value(reason: &str) -> &'static str {
         REASON_DENIED => "blocked-by-denylist",
         REASON_METHOD_NOT_ALLOWED => "blocked-by-method-policy",
         REASON_MITM_REQUIRED => "blocked-by-mitm-required",
+        REASON_PATH_DENIED | REASON_PATH_NOT_ALLOWED => "blocked-by-request-rule",
         _ => "blocked-by-policy",
     }
 }
@@ -69,7 +72,13 @@ pub fn blocked_message(reason: &str) -> &'static str {
         REASON_METHOD_NOT_ALLOWED => {
             "Codex blocked this request: method not allowed in limited mode."
         }
-        REASON_MITM_REQUIRED => "Codex blocked this request: MITM required for limited HTTPS.",
+        REASON_MITM_REQUIRED => {
+            "Codex blocked this request: MITM required to enforce HTTPS policy."
+        }
+        REASON_PATH_DENIED => "Codex blocked this request: path or method denied by policy.",
+        REASON_PATH_NOT_ALLOWED => {
+            "Codex blocked this request: path or method not in the allowed request rules."
+        }
         _ => "Codex blocked this request by network policy.",
     }
 }
@@ -1,8 +1,13 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::config::ValidatedUnixSocketPath;
+use crate::credentials::Credentials;
+use crate::har::HarRecorder;
+use crate::har::HarReplay;
 use crate::mitm::MitmState;
 use crate::policy::Host;
+use crate::policy::RequestRuleDecision;
+use crate::policy::RequestRules;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -155,6 +160,9 @@ pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
     pub deny_set: GlobSet,
+    pub request_rules: RequestRules,
+    pub credentials: Credentials,
+    pub har_replay: Option<Arc<HarReplay>>,
     pub mitm: Option<Arc<MitmState>>,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
@@ -201,6 +209,9 @@ pub struct NetworkProxyState {
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
     audit_metadata: NetworkProxyAuditMetadata,
+    /// Opened on first use and kept across reloads while `har_record_dir` is unchanged, so a
+    /// session writes a single file.
+    har_recorder: Arc<std::sync::Mutex<Option<Arc<HarRecorder>>>>,
 }
 
 impl std::fmt::Debug for NetworkProxyState {
@@ -218,6 +229,7 @@ impl Clone for NetworkProxyState {
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
             audit_metadata: self.audit_metadata.clone(),
+            har_recorder: self.har_recorder.clone(),
         }
     }
 }
@@ -268,6 +280,7 @@ impl NetworkProxyState {
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
             audit_metadata,
+            har_recorder: Arc::new(std::sync::Mutex::new(None)),
         }
     }
 
@@ -515,6 +528,68 @@ impl NetworkProxyState {
         Ok(guard.config.network.mode.allows_method(method))
     }
 
+    /// Check a fully visible request (plain HTTP, or HTTPS under MITM) against
+    /// `network.request_rules`. `host` must already be normalized.
+    pub(crate) async fn request_rule_decision(
+        &self,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Result<RequestRuleDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.evaluate(host, method, path))
+    }
+
+    pub(crate) async fn request_rules_cover_host(&self, host: &str) -> Result<bool> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.covers_host(host))
+    }
+
+    pub(crate) async fn credentials(&self) -> Result<Credentials> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.credentials.clone())
+    }
+
+    /// Recorder for `network.har_record_dir`, creating the session file on first use.
+    pub(crate) async fn har_recorder(&self) -> Result<Option<Arc<HarRecorder>>> {
+        self.reload_if_needed().await?;
+        let dir = {
+            let guard = self.state.read().await;
+            guard.config.network.har_record_dir.clone()
+        };
+        let Some(dir) = dir else {
+            return Ok(None);
+        };
+
+        let mut recorder = self
+            .har_recorder
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(recorder) = recorder.as_ref()
+            && recorder.dir() == dir
+        {
+            return Ok(Some(recorder.clone()));
+        }
+        let session = self
+            .audit_metadata
+            .conversation_id
+            .clone()
+            .unwrap_or_else(|| format!("session-{}", unix_timestamp()));
+        let created = Arc::new(HarRecorder::create(&dir, &session)?);
+        info!("recording proxied traffic to {}", created.path().display());
+        *recorder = Some(created.clone());
+        Ok(Some(created))
+    }
+
+    pub(crate) async fn har_replay(&self) -> Result<Option<Arc<HarReplay>>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.har_replay.clone())
+    }
+
     pub async fn allow_upstream_proxy(&self) -> Result<bool> {
         self.reload_if_needed().await?;
         let guard = self.state.read().await;
@@ -726,6 +801,18 @@ fn log_policy_changes(previous: &NetworkProxyConfig, next: &NetworkProxyConfig)
         &previous.network.denied_domains,
         &next.network.denied_domains,
     );
+    if previous.network.request_rules != next.network.request_rules {
+        info!(
+            "network request rules updated ({} rules)",
+            next.network.request_rules.len()
+        );
+    }
+    if previous.network.credentials != next.network.credentials {
+        info!(
+            "network credentials updated ({} entries)",
+            next.network.credentials.len()
+        );
+    }
 }
 
 fn log_domain_list_changes(list_name: &str, previous: &[String], next: &[String]) {
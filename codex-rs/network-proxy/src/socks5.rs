@@ -11,6 +11,7 @@ use crate::network_policy::emit_block_decision_audit_event;
 use crate::network_policy::evaluate_host_policy;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_PROXY_DISABLED;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_message_with_policy;
@@ -280,16 +281,77 @@ async fn handle_socks5_tcp(
             warn!("SOCKS blocked (client={client}, host={host}, reason={reason})");
             return Err(policy_denied_error(&reason, &details).into());
         }
-        Ok(NetworkDecision::Allow) => {
-            let client = client.as_deref().unwrap_or_default();
-            info!("SOCKS allowed (client={client}, host={host}, port={port})");
-        }
+        Ok(NetworkDecision::Allow) => {}
         Err(err) => {
             error!("failed to evaluate host: {err}");
             return Err(io::Error::other("proxy error").into());
         }
     }
 
+    let request_rules_cover_host = match app_state.request_rules_cover_host(&host).await {
+        Ok(covered) => covered,
+        Err(err) => {
+            error!("failed to read request rules: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    };
+    let replaying = match app_state.har_replay().await {
+        Ok(replay) => replay.is_some(),
+        Err(err) => {
+            error!("failed to read HAR replay: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    };
+    if request_rules_cover_host || replaying {
+        // SOCKS tunnels cannot be intercepted, so request rules and replay could not see
+        // the requests inside them.
+        let source = if request_rules_cover_host {
+            NetworkDecisionSource::RequestRule
+        } else {
+            NetworkDecisionSource::ProxyState
+        };
+        emit_socks_block_decision_audit_event(
+            &app_state,
+            source,
+            REASON_MITM_REQUIRED,
+            NetworkProtocol::Socks5Tcp,
+            host.as_str(),
+            port,
+            client.as_deref(),
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_MITM_REQUIRED,
+            source,
+            protocol: NetworkProtocol::Socks5Tcp,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: REASON_MITM_REQUIRED.to_string(),
+                client: client.clone(),
+                method: None,
+                mode: Some(NetworkMode::Full),
+                protocol: "socks5".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let what = if request_rules_cover_host {
+            "enforce request rules"
+        } else {
+            "replay recorded responses"
+        };
+        warn!("SOCKS blocked; MITM required to {what} (client={client}, host={host})");
+        return Err(policy_denied_error(REASON_MITM_REQUIRED, &details).into());
+    }
+
+    let client = client.as_deref().unwrap_or_default();
+    info!("SOCKS allowed (client={client}, host={host}, port={port})");
     tcp_connector.serve(req).await
 }
 
@@ -438,17 +500,81 @@ async fn inspect_socks5_udp(
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("SOCKS UDP blocked (client={client}, host={host}, reason={reason})");
-            Err(policy_denied_error(&reason, &details))
+            return Err(policy_denied_error(&reason, &details));
         }
-        Ok(NetworkDecision::Allow) => Ok(RelayResponse {
-            maybe_payload: Some(payload),
-            extensions,
-        }),
+        Ok(NetworkDecision::Allow) => {}
         Err(err) => {
             error!("failed to evaluate UDP host: {err}");
-            Err(io::Error::other("proxy error"))
+            return Err(io::Error::other("proxy error"));
+        }
+    }
+
+    let request_rules_cover_host = match state.request_rules_cover_host(&host).await {
+        Ok(covered) => covered,
+        Err(err) => {
+            error!("failed to read request rules: {err}");
+            return Err(io::Error::other("proxy error"));
         }
+    };
+    let replaying = match state.har_replay().await {
+        Ok(replay) => replay.is_some(),
+        Err(err) => {
+            error!("failed to read HAR replay: {err}");
+            return Err(io::Error::other("proxy error"));
+        }
+    };
+    if request_rules_cover_host || replaying {
+        // Relayed datagrams (DNS, QUIC) cannot be intercepted either, so request rules and
+        // replay could not see them.
+        let source = if request_rules_cover_host {
+            NetworkDecisionSource::RequestRule
+        } else {
+            NetworkDecisionSource::ProxyState
+        };
+        emit_socks_block_decision_audit_event(
+            &state,
+            source,
+            REASON_MITM_REQUIRED,
+            NetworkProtocol::Socks5Udp,
+            host.as_str(),
+            port,
+            client.as_deref(),
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_MITM_REQUIRED,
+            source,
+            protocol: NetworkProtocol::Socks5Udp,
+            host: &host,
+            port,
+        };
+        let _ = state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: REASON_MITM_REQUIRED.to_string(),
+                client: client.clone(),
+                method: None,
+                mode: Some(NetworkMode::Full),
+                protocol: "socks5-udp".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let what = if request_rules_cover_host {
+            "enforce request rules"
+        } else {
+            "replay recorded responses"
+        };
+        warn!("SOCKS UDP blocked; MITM required to {what} (client={client}, host={host})");
+        return Err(policy_denied_error(REASON_MITM_REQUIRED, &details));
     }
+
+    Ok(RelayResponse {
+        maybe_payload: Some(payload),
+        extensions,
+    })
 }
 
 fn emit_socks_block_decision_audit_event(
@@ -487,6 +613,9 @@ mod tests {
     use crate::config::NetworkMode;
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
+    use crate::config::NetworkRuleAction;
+    use crate::har::HarRecorder;
     use crate::network_policy::test_support::POLICY_DECISION_EVENT_NAME;
     use crate::network_policy::test_support::capture_events;
     use crate::network_policy::test_support::find_event_by_name;
@@ -570,6 +699,49 @@ mod tests {
         assert_eq!(event.field("client.address"), Some("unknown"));
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn handle_socks5_tcp_refuses_tunnels_to_request_rule_hosts() {
+        let state = state_for_settings(NetworkProxySettings {
+            enabled: true,
+            mode: NetworkMode::Full,
+            allowed_domains: vec!["registry.example.com".to_string()],
+            request_rules: vec![NetworkRequestRule {
+                host: "registry.example.com".to_string(),
+                path_prefix: "/upload".to_string(),
+                methods: vec!["POST".to_string()],
+                action: NetworkRuleAction::Deny,
+            }],
+            ..NetworkProxySettings::default()
+        });
+        let mut request = TcpRequest::new(
+            HostWithPort::try_from("registry.example.com:443").expect("valid authority"),
+        );
+        request.extensions_mut().insert(state.clone());
+
+        let (result, events) = capture_events(|| async {
+            handle_socks5_tcp(request, TcpConnector::default(), None).await
+        })
+        .await;
+        assert!(
+            result.is_err(),
+            "tunnel to a request-rule host should be refused"
+        );
+
+        let event = find_event_by_name(&events, POLICY_DECISION_EVENT_NAME)
+            .expect("expected policy decision event");
+        assert_eq!(event.field("network.policy.decision"), Some("deny"));
+        assert_eq!(event.field("network.policy.source"), Some("request_rule"));
+        assert_eq!(
+            event.field("network.policy.reason"),
+            Some(REASON_MITM_REQUIRED)
+        );
+        assert_eq!(
+            event.field("network.transport.protocol"),
+            Some("socks5_tcp")
+        );
+        assert_eq!(event.field("server.address"), Some("registry.example.com"));
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn inspect_socks5_udp_emits_block_decision_for_mode_guard_deny() {
         let state = state_for_settings(NetworkProxySettings {
@@ -606,4 +778,40 @@ mod tests {
         assert_eq!(event.field("http.request.method"), Some("none"));
         assert_eq!(event.field("client.address"), Some("unknown"));
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn inspect_socks5_udp_refuses_relays_while_replaying() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let recording = HarRecorder::create(dir.path(), "session").expect("create HAR");
+        let state = state_for_settings(NetworkProxySettings {
+            enabled: true,
+            mode: NetworkMode::Full,
+            allowed_domains: vec!["*".to_string()],
+            har_replay_file: Some(recording.path().to_path_buf()),
+            ..NetworkProxySettings::default()
+        });
+        let request = RelayRequest {
+            direction: RelayDirection::South,
+            server_address: SocketAddress::new(IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)), 53),
+            payload: Default::default(),
+            extensions: Extensions::new(),
+        };
+
+        let (result, events) =
+            capture_events(|| async { inspect_socks5_udp(request, state, None).await }).await;
+        assert!(result.is_err(), "UDP relay should be refused under replay");
+
+        let event = find_event_by_name(&events, POLICY_DECISION_EVENT_NAME)
+            .expect("expected policy decision event");
+        assert_eq!(event.field("network.policy.decision"), Some("deny"));
+        assert_eq!(event.field("network.policy.source"), Some("proxy_state"));
+        assert_eq!(
+            event.field("network.policy.reason"),
+            Some(REASON_MITM_REQUIRED)
+        );
+        assert_eq!(
+            event.field("network.transport.protocol"),
+            Some("socks5_udp")
+        );
+    }
 }
@@ -1,12 +1,20 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
+use crate::credentials::Credentials;
+use crate::har::HarReplay;
 use crate::mitm::MitmState;
 use crate::policy::DomainPattern;
+use crate::policy::RequestRules;
 use crate::policy::compile_globset;
 use crate::runtime::ConfigState;
+use anyhow::Context;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_utils_home_dir::find_codex_home;
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::sync::Arc;
+use tracing::warn;
 
 pub use crate::runtime::BlockedRequest;
 pub use crate::runtime::BlockedRequestArgs;
@@ -60,9 +68,19 @@ pub fn build_config_state(
     crate::config::validate_unix_socket_allowlist_paths(&config)?;
     let deny_set = compile_globset(&config.network.denied_domains)?;
     let allow_set = compile_globset(&config.network.allowed_domains)?;
+    let request_rules = RequestRules::compile(&config.network.request_rules)?;
+    let credentials = resolve_credentials(&config)?;
+    let har_replay = config
+        .network
+        .har_replay_file
+        .as_deref()
+        .map(|path| HarReplay::load(path).map(Arc::new))
+        .transpose()
+        .context("invalid network.har_replay_file")?;
     let mitm = if config.network.mitm {
         Some(Arc::new(MitmState::new(
             config.network.allow_upstream_proxy,
+            config.network.mitm_log_body_excerpts,
         )?))
     } else {
         None
@@ -71,6 +89,9 @@ pub fn build_config_state(
         config,
         allow_set,
         deny_set,
+        request_rules,
+        credentials,
+        har_replay,
         mitm,
         constraints,
         blocked: std::collections::VecDeque::new(),
@@ -78,6 +99,23 @@ pub fn build_config_state(
     })
 }
 
+fn resolve_credentials(config: &NetworkProxyConfig) -> anyhow::Result<Credentials> {
+    if config.network.credentials.is_empty() {
+        return Ok(Credentials::default());
+    }
+    if !config.network.mitm {
+        warn!(
+            "network.credentials are only added to intercepted HTTPS requests; set network.mitm = true to use them"
+        );
+    }
+    let codex_home =
+        find_codex_home().context("failed to resolve CODEX_HOME for network credentials")?;
+    let secrets = SecretsManager::new(codex_home, SecretsBackendKind::Local);
+    Credentials::resolve(&config.network.credentials, |scope, name| {
+        secrets.get(scope, name)
+    })
+}
+
 pub fn validate_policy_against_constraints(
     config: &NetworkProxyConfig,
     constraints: &NetworkProxyConstraints,
@@ -66,6 +66,43 @@ pub enum SandboxMode {
     DangerFullAccess,
 }
 
+/// Resource limits for commands run under the Linux sandbox helper. Unset
+/// fields leave the corresponding resource unlimited.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxResourceLimits {
+    /// Maximum resident memory, in bytes, shared by the command and its
+    /// children. Enforced through a cgroup when one can be created; otherwise
+    /// each process's data segment is capped instead.
+    pub max_memory_bytes: Option<u64>,
+    /// Maximum CPU time, in seconds, for each process of the command.
+    pub max_cpu_seconds: Option<u64>,
+    /// Maximum number of processes and threads. Enforced through a cgroup
+    /// when one can be created; otherwise through `RLIMIT_NPROC`, on top of
+    /// the processes the user already runs.
+    pub max_processes: Option<u64>,
+    /// Maximum number of open file descriptors per process.
+    pub max_open_files: Option<u64>,
+    /// Maximum size, in bytes, of any file the command writes.
+    pub max_file_size_bytes: Option<u64>,
+    /// Maximum wall-clock time, in seconds, before the command is killed.
+    pub max_wall_clock_seconds: Option<u64>,
+}
+
+impl SandboxResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::str::FromStr for SandboxResourceLimits {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 #[derive(
     Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Display, JsonSchema, TS,
 )]
@@ -18,3 +18,167 @@ pub struct CustomPrompt {
     pub description: Option<String>,
     pub argument_hint: Option<String>,
 }
+
+/// A piece of a custom prompt template: literal text or a placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptTemplatePiece<'a> {
+    Text(&'a str),
+    /// `$NAME`, without the leading `$`.
+    Named(&'a str),
+    /// `$1..$9`, as a zero-based index.
+    Positional(usize),
+    /// `$ARGUMENTS`.
+    Arguments,
+}
+
+/// Splits a template into text and `$NAME` placeholders, where a name matches
+/// `[A-Z][A-Z0-9_]*`. A placeholder preceded by another `$` (as in `$$NAME`)
+/// is escaped and stays in the text.
+pub fn named_prompt_pieces(content: &str) -> Vec<PromptTemplatePiece<'_>> {
+    let bytes = content.as_bytes();
+    let mut pieces = Vec::new();
+    let mut cursor = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'$' || !bytes.get(i + 1).is_some_and(u8::is_ascii_uppercase) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let end = bytes[start + 1..]
+            .iter()
+            .position(|b| !(b.is_ascii_uppercase() || b.is_ascii_digit() || *b == b'_'))
+            .map_or(bytes.len(), |len| start + 1 + len);
+        i = end;
+        if start > 0 && bytes[start - 1] == b'$' {
+            continue;
+        }
+        if cursor < start {
+            pieces.push(PromptTemplatePiece::Text(&content[cursor..start]));
+        }
+        pieces.push(PromptTemplatePiece::Named(&content[start + 1..end]));
+        cursor = end;
+    }
+    if cursor < content.len() {
+        pieces.push(PromptTemplatePiece::Text(&content[cursor..]));
+    }
+    pieces
+}
+
+/// Splits a template into text and the `$1..$9` and `$ARGUMENTS`
+/// placeholders. `$$` is an escape and stays in the text.
+pub fn numeric_prompt_pieces(content: &str) -> Vec<PromptTemplatePiece<'_>> {
+    const ARGUMENTS: &str = "ARGUMENTS";
+    let mut pieces = Vec::new();
+    let mut cursor = 0;
+    let mut i = 0;
+    while let Some(off) = content[i..].find('$') {
+        let start = i + off;
+        let rest = &content[start..];
+        let (piece, end) = match rest.as_bytes().get(1) {
+            Some(b'$') => (None, start + 2),
+            Some(digit @ b'1'..=b'9') => (
+                Some(PromptTemplatePiece::Positional(usize::from(digit - b'1'))),
+                start + 2,
+            ),
+            _ if rest[1..].starts_with(ARGUMENTS) => (
+                Some(PromptTemplatePiece::Arguments),
+                start + 1 + ARGUMENTS.len(),
+            ),
+            _ => (None, start + 1),
+        };
+        i = end;
+        let Some(piece) = piece else {
+            continue;
+        };
+        if cursor < start {
+            pieces.push(PromptTemplatePiece::Text(&content[cursor..start]));
+        }
+        pieces.push(piece);
+        cursor = end;
+    }
+    if cursor < content.len() {
+        pieces.push(PromptTemplatePiece::Text(&content[cursor..]));
+    }
+    pieces
+}
+
+/// Extracts the unique placeholder variable names from a prompt template.
+///
+/// A placeholder is any token that matches the pattern `$[A-Z][A-Z0-9_]*`
+/// (for example `$USER`). The function returns the variable names without
+/// the leading `$`, de-duplicated and in the order of first appearance.
+/// `$ARGUMENTS` is positional and not included.
+pub fn prompt_argument_names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for piece in named_prompt_pieces(content) {
+        if let PromptTemplatePiece::Named(name) = piece
+            && name != "ARGUMENTS"
+            && !names.iter().any(|seen| seen == name)
+        {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
+pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
+    if content.contains("$ARGUMENTS") {
+        return true;
+    }
+    let bytes = content.as_bytes();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] == b'$' {
+            let b1 = bytes[i + 1];
+            if (b'1'..=b'9').contains(&b1) {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn named_pieces_skip_escaped_placeholders() {
+        assert_eq!(
+            named_prompt_pieces("Review $FILE for $FOCUS_2. $$HOME, $ARGUMENTS, $1 $"),
+            vec![
+                PromptTemplatePiece::Text("Review "),
+                PromptTemplatePiece::Named("FILE"),
+                PromptTemplatePiece::Text(" for "),
+                PromptTemplatePiece::Named("FOCUS_2"),
+                PromptTemplatePiece::Text(". $$HOME, "),
+                PromptTemplatePiece::Named("ARGUMENTS"),
+                PromptTemplatePiece::Text(", $1 $"),
+            ]
+        );
+        assert_eq!(
+            prompt_argument_names("$FILE $FOCUS $FILE $$HOME $ARGUMENTS"),
+            vec!["FILE".to_string(), "FOCUS".to_string()]
+        );
+    }
+
+    #[test]
+    fn numeric_pieces_keep_escapes_and_unknown_placeholders_as_text() {
+        assert_eq!(
+            numeric_prompt_pieces("First: $1, $$2, $0, $USER; all: $ARGUMENTS$9"),
+            vec![
+                PromptTemplatePiece::Text("First: "),
+                PromptTemplatePiece::Positional(0),
+                PromptTemplatePiece::Text(", $$2, $0, $USER; all: "),
+                PromptTemplatePiece::Arguments,
+                PromptTemplatePiece::Positional(8),
+            ]
+        );
+        assert!(prompt_has_numeric_placeholders("run $2"));
+        assert!(!prompt_has_numeric_placeholders("run $FILE $0"));
+    }
+}
@@ -1355,6 +1355,7 @@ mod tests {
             network_access: true,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         };
 
         let instructions = DeveloperInstructions::from_policy(
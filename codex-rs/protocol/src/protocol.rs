@@ -26,6 +26,7 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::mcp::CallToolResult;
+use crate::mcp::Prompt as McpPrompt;
 use crate::mcp::RequestId;
 use crate::mcp::Resource as McpResource;
 use crate::mcp::ResourceTemplate as McpResourceTemplate;
@@ -49,6 +50,7 @@ use serde::Serialize;
 use serde_json::Value;
 use serde_with::serde_as;
 use strum_macros::Display;
+use strum_macros::EnumString;
 use tracing::error;
 use ts_rs::TS;
 
@@ -326,6 +328,18 @@ pub enum Op {
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request the prompts offered by connected MCP servers.
+    /// Reply is delivered via `EventMsg::McpListPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Fetch an MCP prompt with the given arguments via `prompts/get`.
+    /// Reply is delivered via `EventMsg::McpGetPromptResponse`.
+    GetMcpPrompt {
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    },
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -368,6 +382,11 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Merge or discard the file changes that sandboxed commands wrote to the
+    /// copy-on-write workspace overlay. Changes that are not accepted are
+    /// discarded. The server replies with [`EventMsg::WorkspaceOverlayResolved`].
+    ResolveWorkspaceOverlay { decision: WorkspaceOverlayDecision },
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
@@ -574,6 +593,11 @@ pub enum SandboxPolicy {
             skip_serializing_if = "ReadOnlyAccess::has_full_disk_read_access"
         )]
         access: ReadOnlyAccess,
+
+        /// Paths that stay unreadable even when read access would otherwise
+        /// grant them. See [`SandboxPolicy::denied_read_paths`].
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        denied_read_paths: Vec<String>,
     },
 
     /// Indicates the process is already in an external sandbox. Allows full
@@ -616,6 +640,11 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// Paths that stay unreadable even when read access would otherwise
+        /// grant them. See [`SandboxPolicy::denied_read_paths`].
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        denied_read_paths: Vec<String>,
     },
 }
 
@@ -663,6 +692,7 @@ impl SandboxPolicy {
     pub fn new_read_only_policy() -> Self {
         SandboxPolicy::ReadOnly {
             access: ReadOnlyAccess::FullAccess,
+            denied_read_paths: Vec::new(),
         }
     }
 
@@ -676,6 +706,7 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         }
     }
 
@@ -683,7 +714,7 @@ impl SandboxPolicy {
         match self {
             SandboxPolicy::DangerFullAccess => true,
             SandboxPolicy::ExternalSandbox { .. } => true,
-            SandboxPolicy::ReadOnly { access } => access.has_full_disk_read_access(),
+            SandboxPolicy::ReadOnly { access, .. } => access.has_full_disk_read_access(),
             SandboxPolicy::WorkspaceWrite {
                 read_only_access, ..
             } => read_only_access.has_full_disk_read_access(),
@@ -708,13 +739,32 @@ impl SandboxPolicy {
         }
     }
 
+    /// Returns the paths that must not be readable from within the sandbox.
+    ///
+    /// Entries are glob patterns. A leading `~/` is relative to the home
+    /// directory, an absolute pattern is matched as is, and a relative
+    /// pattern is resolved against the cwd; a bare name such as `.env*`
+    /// matches at any depth below it. Denying a directory denies everything
+    /// beneath it.
+    pub fn denied_read_paths(&self) -> &[String] {
+        match self {
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => &[],
+            SandboxPolicy::ReadOnly {
+                denied_read_paths, ..
+            }
+            | SandboxPolicy::WorkspaceWrite {
+                denied_read_paths, ..
+            } => denied_read_paths,
+        }
+    }
+
     /// Returns true if platform defaults should be included for restricted read access.
     pub fn include_platform_defaults(&self) -> bool {
         if self.has_full_disk_read_access() {
             return false;
         }
         match self {
-            SandboxPolicy::ReadOnly { access } => access.include_platform_defaults(),
+            SandboxPolicy::ReadOnly { access, .. } => access.include_platform_defaults(),
             SandboxPolicy::WorkspaceWrite {
                 read_only_access, ..
             } => read_only_access.include_platform_defaults(),
@@ -730,7 +780,7 @@ impl SandboxPolicy {
     pub fn get_readable_roots_with_cwd(&self, cwd: &Path) -> Vec<AbsolutePathBuf> {
         let mut roots = match self {
             SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => Vec::new(),
-            SandboxPolicy::ReadOnly { access } => access.get_readable_roots_with_cwd(cwd),
+            SandboxPolicy::ReadOnly { access, .. } => access.get_readable_roots_with_cwd(cwd),
             SandboxPolicy::WorkspaceWrite {
                 read_only_access, ..
             } => {
@@ -762,6 +812,7 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                denied_read_paths: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<AbsolutePathBuf> = writable_roots.clone();
@@ -1020,10 +1071,17 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// An MCP server's tool list changed after startup.
+    McpToolsChanged(McpToolsChangedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
     McpToolCallEnd(McpToolCallEndEvent),
 
+    /// An MCP server's `sampling/createMessage` request ran against the
+    /// session's model.
+    McpSamplingEnd(McpSamplingEndEvent),
+
     WebSearchBegin(WebSearchBeginEvent),
 
     WebSearchEnd(WebSearchEndEvent),
@@ -1077,6 +1135,13 @@ pub enum EventMsg {
 
     TurnDiff(TurnDiffEvent),
 
+    /// Files that sandboxed commands changed in the copy-on-write workspace
+    /// overlay and that are waiting to be merged into the real workspace.
+    WorkspaceOverlayChanges(WorkspaceOverlayChangesEvent),
+
+    /// Result of [`Op::ResolveWorkspaceOverlay`].
+    WorkspaceOverlayResolved(WorkspaceOverlayResolvedEvent),
+
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
@@ -1086,6 +1151,12 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// Prompts offered by connected MCP servers.
+    McpListPromptsResponse(McpListPromptsResponseEvent),
+
+    /// Expanded text of an MCP prompt requested via `Op::GetMcpPrompt`.
+    McpGetPromptResponse(McpGetPromptResponseEvent),
+
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
@@ -1557,8 +1628,14 @@ impl TokenUsageInfo {
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TokenCountEvent {
+    /// Usage as reported by the server.
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Tokens in the current context as counted locally, with the model's
+    /// tokenizer when it is known and a byte estimate otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub estimated_tokens: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1776,6 +1853,19 @@ pub struct McpToolCallEndEvent {
     pub result: Result<CallToolResult, String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpSamplingEndEvent {
+    /// Server that sent `sampling/createMessage`.
+    pub server: String,
+    /// Model the request ran on.
+    pub model: String,
+    /// Text of each message the server sent, in order.
+    pub messages: Vec<String>,
+    /// Generated text, or the reason the request failed.
+    pub result: Result<String, String>,
+    pub token_usage: Option<TokenUsage>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
 pub struct DynamicToolCallResponseEvent {
     /// Identifier for the corresponding DynamicToolCallRequest.
@@ -2293,6 +2383,20 @@ pub enum ExecCommandStatus {
     Declined,
 }
 
+/// Sandbox resource limit that stopped a command.
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, EnumString, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ResourceLimitKind {
+    Memory,
+    CpuTime,
+    Processes,
+    FileSize,
+    WallClock,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecCommandBeginEvent {
     /// Identifier so this can be paired with the ExecCommandEnd event.
@@ -2356,6 +2460,11 @@ pub struct ExecCommandEndEvent {
     pub formatted_output: String,
     /// Completion status for this command execution.
     pub status: ExecCommandStatus,
+    /// Set when a sandbox resource limit stopped the command, as opposed to
+    /// the command failing on its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub resource_limit_exceeded: Option<ResourceLimitKind>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2496,6 +2605,39 @@ pub struct TurnDiffEvent {
     pub unified_diff: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct WorkspaceOverlayChangesEvent {
+    /// Pending changes keyed by their path in the real workspace.
+    pub changes: HashMap<PathBuf, FileChange>,
+    /// Unified diff of the pending changes against the real workspace.
+    pub unified_diff: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct WorkspaceOverlayResolvedEvent {
+    /// Paths whose changes were merged into the real workspace.
+    pub accepted: Vec<PathBuf>,
+    /// Paths whose changes were discarded.
+    pub rejected: Vec<PathBuf>,
+    /// Accepted paths that were discarded because the workspace or overlay
+    /// file changed after the changes were reported.
+    #[serde(default)]
+    pub conflicts: Vec<PathBuf>,
+}
+
+/// How to resolve the pending changes in the copy-on-write workspace overlay.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum WorkspaceOverlayDecision {
+    /// Merge every pending change into the workspace.
+    AcceptAll,
+    /// Discard every pending change.
+    RejectAll,
+    /// Merge the changes to `paths` and discard the rest.
+    AcceptPaths { paths: Vec<PathBuf> },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct GetHistoryEntryResponseEvent {
     pub offset: usize,
@@ -2548,6 +2690,16 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpToolsChangedEvent {
+    /// Server that sent `notifications/tools/list_changed`.
+    pub server: String,
+    /// Names of tools the server now offers that it did not before.
+    pub added: Vec<String>,
+    /// Names of tools the server no longer offers.
+    pub removed: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -2576,6 +2728,23 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpListPromptsResponseEvent {
+    /// Prompts grouped by server name.
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Response payload for `Op::GetMcpPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpGetPromptResponseEvent {
+    pub server: String,
+    pub name: String,
+    /// The prompt messages flattened into a single user message, or an error
+    /// describing why the prompt could not be fetched.
+    pub result: Result<String, String>,
+}
+
 /// Response payload for `Op::ListSkills`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListSkillsResponseEvent {
@@ -2832,6 +3001,10 @@ impl ReviewDecision {
 pub enum FileChange {
     Add {
         content: String,
+        /// Unix permission bits the file is created with, e.g. `0o755`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        mode: Option<u32>,
     },
     Delete {
         content: String,
@@ -2839,6 +3012,22 @@ pub enum FileChange {
     Update {
         unified_diff: String,
         move_path: Option<PathBuf>,
+        /// Unix permission bits the file is changed to. The diff is empty for a mode-only change.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        mode: Option<u32>,
+    },
+    /// A file written with binary contents, which are not included.
+    Binary {
+        size: usize,
+        /// Whether an existing file is overwritten rather than a new one added.
+        replace: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        mode: Option<u32>,
+    },
+    Symlink {
+        target: PathBuf,
     },
 }
 
@@ -3107,6 +3296,7 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         };
 
         let readable_roots = policy.get_readable_roots_with_cwd(cwd);
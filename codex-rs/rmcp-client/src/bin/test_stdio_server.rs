@@ -7,11 +7,19 @@ use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParams;
@@ -19,18 +27,23 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::Resource;
 use rmcp::model::ResourceContents;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
 use rmcp::model::Tool;
 use serde::Deserialize;
 use serde_json::json;
+use tokio::sync::Mutex;
 use tokio::task;
 
 #[derive(Clone)]
 struct TestToolServer {
-    tools: Arc<Vec<Tool>>,
+    tools: Arc<Mutex<Vec<Tool>>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -47,13 +60,18 @@ impl TestToolServer {
             Self::echo_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::add_tool_tool(),
+            Self::sample_tool(),
+            Self::list_roots_tool(),
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::summarize_prompt()];
         Self {
-            tools: Arc::new(tools),
+            tools: Arc::new(Mutex::new(tools)),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
         }
     }
 
@@ -93,6 +111,65 @@ impl TestToolServer {
         )
     }
 
+    /// Registers a new tool at runtime and sends `notifications/tools/list_changed`.
+    fn add_tool_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" }
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        }))
+        .expect("add_tool tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("add_tool"),
+            Cow::Borrowed("Register a new tool with the given name and announce the change."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Sends `sampling/createMessage` with the given prompt and returns the
+    /// client's reply as text.
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "prompt": { "type": "string" },
+                "max_tokens": { "type": "integer" }
+            },
+            "required": ["prompt"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed("Ask the client to sample its model with the given prompt."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Sends `roots/list` and returns the client's roots as structured content.
+    fn list_roots_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("list_roots tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("list_roots"),
+            Cow::Borrowed("Ask the client for its workspace roots."),
+            Arc::new(schema),
+        )
+    }
+
     /// Tool intended for manual testing of Codex TUI rendering for MCP image tool results.
     ///
     /// This exists to exercise edge cases where a `CallToolResult.content` includes image blocks
@@ -162,6 +239,27 @@ impl TestToolServer {
         Resource::new(raw, None)
     }
 
+    fn summarize_prompt() -> Prompt {
+        Prompt::new(
+            "summarize",
+            Some("Summarize a topic for a given audience."),
+            Some(vec![
+                PromptArgument {
+                    name: "topic".to_string(),
+                    title: None,
+                    description: Some("What to summarize".to_string()),
+                    required: Some(true),
+                },
+                PromptArgument {
+                    name: "audience".to_string(),
+                    title: None,
+                    description: Some("Who the summary is for".to_string()),
+                    required: Some(false),
+                },
+            ]),
+        )
+    }
+
     fn memo_template() -> ResourceTemplate {
         let raw = RawResourceTemplate {
             uri_template: "memo://codex/{slug}".to_string(),
@@ -181,6 +279,17 @@ impl TestToolServer {
     }
 }
 
+#[derive(Deserialize)]
+struct AddToolArgs {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SampleArgs {
+    prompt: String,
+    max_tokens: Option<u32>,
+}
+
 #[derive(Deserialize)]
 struct EchoArgs {
     message: String,
@@ -221,6 +330,7 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
@@ -234,7 +344,7 @@ impl ServerHandler for TestToolServer {
         let tools = self.tools.clone();
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools: tools.lock().await.clone(),
                 next_cursor: None,
                 meta: None,
             })
@@ -290,10 +400,51 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult::with_all_items((*self.prompts).clone()))
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParams {
+            name, arguments, ..
+        }: GetPromptRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != "summarize" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let arguments = arguments.unwrap_or_default();
+        let Some(topic) = arguments.get("topic").and_then(|value| value.as_str()) else {
+            return Err(McpError::invalid_params(
+                "missing required argument: topic",
+                None,
+            ));
+        };
+        let audience = arguments
+            .get("audience")
+            .and_then(|value| value.as_str())
+            .unwrap_or("engineers");
+        Ok(GetPromptResult {
+            description: Some("Summarize a topic for a given audience.".to_string()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                format!("Summarize {topic} for {audience}."),
+            )],
+        })
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" => {
@@ -348,6 +499,77 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<ImageScenarioArgs>(&request, "image_scenario")?;
                 Self::image_scenario_result(args)
             }
+            "add_tool" => {
+                let args = Self::parse_call_args::<AddToolArgs>(&request, "add_tool")?;
+                self.tools.lock().await.push(Tool::new(
+                    Cow::Owned(args.name.clone()),
+                    Cow::Borrowed("Tool registered at runtime by add_tool."),
+                    Arc::new(JsonObject::from_iter([(
+                        "type".to_string(),
+                        json!("object"),
+                    )])),
+                ));
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("added {}", args.name),
+                )]))
+            }
+            "sample" => {
+                let args = Self::parse_call_args::<SampleArgs>(&request, "sample")?;
+                let result = context
+                    .peer
+                    .create_message(CreateMessageRequestParams {
+                        meta: None,
+                        task: None,
+                        messages: vec![SamplingMessage {
+                            role: Role::User,
+                            content: SamplingMessageContent::text(args.prompt).into(),
+                            meta: None,
+                        }],
+                        model_preferences: None,
+                        system_prompt: Some("Answer in one word.".to_string()),
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: args.max_tokens.unwrap_or(64),
+                        stop_sequences: None,
+                        metadata: None,
+                        tools: None,
+                        tool_choice: None,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let text = result
+                    .message
+                    .content
+                    .into_vec()
+                    .into_iter()
+                    .filter_map(|content| match content {
+                        SamplingMessageContent::Text(text) => Some(text.text),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("");
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("{}: {text}", result.model),
+                )]))
+            }
+            "list_roots" => {
+                let result = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult {
+                    content: Vec::new(),
+                    structured_content: Some(json!({ "roots": result.roots })),
+                    is_error: Some(false),
+                    meta: None,
+                })
+            }
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
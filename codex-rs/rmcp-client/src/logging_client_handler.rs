@@ -6,32 +6,57 @@ use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestMethod;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::broadcast;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::rmcp_client::ListRoots;
+use crate::rmcp_client::McpListChanged;
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    list_roots: Option<Arc<ListRoots>>,
+    list_changed_tx: broadcast::Sender<McpListChanged>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
+        list_changed_tx: broadcast::Sender<McpListChanged>,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            send_sampling: send_sampling.map(Arc::new),
+            list_roots: list_roots.map(Arc::new),
+            list_changed_tx,
         }
     }
+
+    fn notify_list_changed(&self, kind: McpListChanged) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        let _ = self.list_changed_tx.send(kind);
+    }
 }
 
 impl ClientHandler for LoggingClientHandler {
@@ -45,6 +70,33 @@ impl ClientHandler for LoggingClientHandler {
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        let Some(send_sampling) = self.send_sampling.as_ref() else {
+            return Err(rmcp::ErrorData::method_not_found::<
+                CreateMessageRequestMethod,
+            >());
+        };
+        send_sampling(context.id, params)
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, rmcp::ErrorData> {
+        let Some(list_roots) = self.list_roots.as_ref() else {
+            return Ok(ListRootsResult::default());
+        };
+        list_roots()
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
     async fn on_cancelled(
         &self,
         params: CancelledNotificationParam,
@@ -77,14 +129,17 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.notify_list_changed(McpListChanged::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.notify_list_changed(McpListChanged::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        self.notify_list_changed(McpListChanged::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
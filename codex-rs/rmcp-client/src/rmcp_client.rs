@@ -19,13 +19,19 @@ use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::InitializeResult;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
+use rmcp::model::ListRootsResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ReadResourceRequestParams;
@@ -47,6 +53,7 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
@@ -154,6 +161,21 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+pub type SamplingRequest = CreateMessageRequestParams;
+pub type SamplingResponse = CreateMessageResult;
+
+/// Interface for running `sampling/createMessage` requests against the
+/// client's model. When absent, the client does not advertise sampling.
+pub type SendSampling = Box<
+    dyn Fn(RequestId, SamplingRequest) -> BoxFuture<'static, Result<SamplingResponse>>
+        + Send
+        + Sync,
+>;
+
+/// Interface for answering `roots/list` requests. When absent, the client
+/// does not advertise roots.
+pub type ListRoots = Box<dyn Fn() -> BoxFuture<'static, Result<ListRootsResult>> + Send + Sync>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -165,10 +187,23 @@ pub struct ListToolsWithConnectorIdResult {
     pub tools: Vec<ToolWithConnectorId>,
 }
 
+/// A `notifications/*/list_changed` notification received from the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpListChanged {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+/// Capacity of the list-changed broadcast channel. Receivers only need to know
+/// that a list changed, so a lagging receiver loses nothing by skipping ahead.
+const LIST_CHANGED_CHANNEL_CAPACITY: usize = 16;
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    list_changed_tx: broadcast::Sender<McpListChanged>,
 }
 
 impl RmcpClient {
@@ -231,6 +266,7 @@ impl RmcpClient {
                     process_group_guard,
                 }),
             }),
+            list_changed_tx: broadcast::channel(LIST_CHANGED_CHANNEL_CAPACITY).0,
         })
     }
 
@@ -314,6 +350,7 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
+            list_changed_tx: broadcast::channel(LIST_CHANGED_CHANNEL_CAPACITY).0,
         })
     }
 
@@ -324,8 +361,16 @@ impl RmcpClient {
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
     ) -> Result<InitializeResult> {
-        let client_handler = LoggingClientHandler::new(params.clone(), send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            params.clone(),
+            send_elicitation,
+            send_sampling,
+            list_roots,
+            self.list_changed_tx.clone(),
+        );
 
         let (transport, oauth_persistor, process_group_guard) = {
             let mut guard = self.state.lock().await;
@@ -392,6 +437,12 @@ impl RmcpClient {
         Ok(initialize_result)
     }
 
+    /// Subscribe to `list_changed` notifications sent by the server after
+    /// initialization.
+    pub fn subscribe_list_changed(&self) -> broadcast::Receiver<McpListChanged> {
+        self.list_changed_tx.subscribe()
+    }
+
     pub async fn list_tools(
         &self,
         params: Option<PaginatedRequestParams>,
@@ -486,6 +537,33 @@ impl RmcpClient {
         Ok(result)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+
+        let fut = service.list_prompts(params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let fut = service.get_prompt(params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -531,6 +609,13 @@ impl RmcpClient {
         Ok(())
     }
 
+    /// Tell the server that the list returned by `roots/list` has changed.
+    pub async fn notify_roots_list_changed(&self) -> Result<()> {
+        let service: Arc<RunningService<RoleClient, LoggingClientHandler>> = self.service().await?;
+        service.notify_roots_list_changed().await?;
+        Ok(())
+    }
+
     pub async fn send_custom_request(
         &self,
         method: &str,
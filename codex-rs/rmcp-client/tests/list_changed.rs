@@ -0,0 +1,95 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::McpListChanged;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use pretty_assertions::assert_eq;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ProtocolVersion;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: None,
+            sampling: None,
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp list_changed test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn tool_list_changed_notification_is_broadcast() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+    let mut list_changed = client.subscribe_list_changed();
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Decline,
+                        content: None,
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+        )
+        .await?;
+
+    client
+        .call_tool(
+            "add_tool".to_string(),
+            Some(json!({ "name": "late_tool" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+
+    let notification = tokio::time::timeout(Duration::from_secs(5), list_changed.recv()).await??;
+    assert_eq!(notification, McpListChanged::Tools);
+
+    let tools = client
+        .list_tools(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert!(
+        tools.tools.iter().any(|tool| tool.name == "late_tool"),
+        "late_tool should be listed after add_tool"
+    );
+
+    Ok(())
+}
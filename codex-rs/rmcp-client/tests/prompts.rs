@@ -0,0 +1,117 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use pretty_assertions::assert_eq;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use rmcp::model::ProtocolVersion;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: None,
+            sampling: None,
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp prompts test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_can_list_and_get_prompts() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Decline,
+                        content: None,
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+        )
+        .await?;
+
+    let list = client
+        .list_prompts(None, Some(Duration::from_secs(5)))
+        .await?;
+    let summarize = list
+        .prompts
+        .iter()
+        .find(|prompt| prompt.name == "summarize")
+        .expect("summarize prompt present");
+    let arguments: Vec<(String, Option<bool>)> = summarize
+        .arguments
+        .iter()
+        .flatten()
+        .map(|argument| (argument.name.clone(), argument.required))
+        .collect();
+    assert_eq!(
+        arguments,
+        vec![
+            ("topic".to_string(), Some(true)),
+            ("audience".to_string(), Some(false)),
+        ]
+    );
+
+    let result = client
+        .get_prompt(
+            GetPromptRequestParams {
+                meta: None,
+                name: "summarize".to_string(),
+                arguments: json!({ "topic": "MCP prompts" }).as_object().cloned(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(
+        result.messages,
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            "Summarize MCP prompts for engineers.",
+        )]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SamplingRequest;
+use codex_rmcp_client::SamplingResponse;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use pretty_assertions::assert_eq;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ListRootsResult;
+use rmcp::model::ProtocolVersion;
+use rmcp::model::RawContent;
+use rmcp::model::Role;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SamplingCapability;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: Some(SamplingCapability::default()),
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp sampling test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn server_requests_for_sampling_and_roots_reach_the_client() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    let sampled: Arc<Mutex<Vec<SamplingRequest>>> = Arc::new(Mutex::new(Vec::new()));
+    let sampled_for_handler = Arc::clone(&sampled);
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Decline,
+                        content: None,
+                    })
+                }
+                .boxed()
+            }),
+            Some(Box::new(move |_, request| {
+                sampled_for_handler
+                    .lock()
+                    .expect("sampling lock")
+                    .push(request);
+                async {
+                    Ok(SamplingResponse {
+                        model: "test-model".to_string(),
+                        stop_reason: Some(SamplingResponse::STOP_REASON_END_TURN.to_string()),
+                        message: SamplingMessage {
+                            role: Role::Assistant,
+                            content: SamplingMessageContent::text("blue").into(),
+                            meta: None,
+                        },
+                    })
+                }
+                .boxed()
+            })),
+            Some(Box::new(|| {
+                async {
+                    Ok(ListRootsResult {
+                        roots: vec![Root {
+                            uri: "file:///workspace".to_string(),
+                            name: Some("workspace".to_string()),
+                        }],
+                    })
+                }
+                .boxed()
+            })),
+        )
+        .await?;
+
+    let result = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "prompt": "What colour is the sky?", "max_tokens": 8 })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    let text: Vec<String> = result
+        .content
+        .into_iter()
+        .filter_map(|content| match content.raw {
+            RawContent::Text(text) => Some(text.text),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, vec!["test-model: blue".to_string()]);
+
+    let requests = sampled.lock().expect("sampling lock").clone();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].max_tokens, 8);
+    assert_eq!(
+        requests[0].system_prompt.as_deref(),
+        Some("Answer in one word.")
+    );
+
+    let result = client
+        .call_tool(
+            "list_roots".to_string(),
+            Some(json!({})),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(
+        result.structured_content,
+        Some(json!({
+            "roots": [{ "uri": "file:///workspace", "name": "workspace" }]
+        }))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+CREATE VIRTUAL TABLE thread_search USING fts5(
+    thread_id UNINDEXED,
+    kind UNINDEXED,
+    content,
+    tokenize = 'porter unicode61'
+);
+
+-- Re-run the rollout backfill so existing threads get indexed.
+UPDATE backfill_state
+SET status = 'pending',
+    last_watermark = NULL,
+    updated_at = CAST(strftime('%s', 'now') AS INTEGER)
+WHERE id = 1;
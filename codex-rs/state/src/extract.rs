@@ -1,4 +1,7 @@
 use crate::model::ThreadMetadata;
+use crate::model::ThreadSearchEntry;
+use crate::model::ThreadSearchKind;
+use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::RolloutItem;
@@ -10,6 +13,8 @@ use serde::Serialize;
 use serde_json::Value;
 
 const IMAGE_ONLY_USER_MESSAGE_PLACEHOLDER: &str = "[Image]";
+/// Function tools whose arguments carry a command line worth indexing.
+const SHELL_TOOL_NAMES: &[&str] = &["shell", "shell_command", "exec_command", "container.exec"];
 
 /// Apply a rollout item to the metadata structure.
 pub fn apply_rollout_item(
@@ -89,6 +94,54 @@ fn apply_response_item(_metadata: &mut ThreadMetadata, _item: &ResponseItem) {
     // Title and first_user_message are derived from EventMsg::UserMessage only.
 }
 
+/// Extract searchable text (user messages, agent messages and command lines) from rollout items.
+pub fn search_entries_from_items(items: &[RolloutItem]) -> Vec<ThreadSearchEntry> {
+    items.iter().filter_map(search_entry_from_item).collect()
+}
+
+fn search_entry_from_item(item: &RolloutItem) -> Option<ThreadSearchEntry> {
+    let (kind, text) = match item {
+        RolloutItem::EventMsg(EventMsg::UserMessage(user)) => (
+            ThreadSearchKind::UserMessage,
+            strip_user_message_prefix(user.message.as_str()).to_string(),
+        ),
+        RolloutItem::EventMsg(EventMsg::AgentMessage(agent)) => (
+            ThreadSearchKind::AgentMessage,
+            agent.message.trim().to_string(),
+        ),
+        RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        }) => (ThreadSearchKind::Command, exec.command.join(" ")),
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            name, arguments, ..
+        }) if SHELL_TOOL_NAMES.contains(&name.as_str()) => (
+            ThreadSearchKind::Command,
+            command_from_arguments(arguments.as_str())?,
+        ),
+        _ => return None,
+    };
+    if text.is_empty() {
+        return None;
+    }
+    Some(ThreadSearchEntry { kind, text })
+}
+
+fn command_from_arguments(arguments: &str) -> Option<String> {
+    let arguments = serde_json::from_str::<Value>(arguments).ok()?;
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.trim().to_string()),
+        Value::Array(parts) => Some(
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
 fn strip_user_message_prefix(text: &str) -> &str {
     match text.find(USER_MESSAGE_BEGIN) {
         Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim(),
@@ -123,13 +176,20 @@ pub(crate) fn enum_to_string<T: Serialize>(value: &T) -> String {
 #[cfg(test)]
 mod tests {
     use super::apply_rollout_item;
+    use super::search_entries_from_items;
     use crate::model::ThreadMetadata;
+    use crate::model::ThreadSearchEntry;
+    use crate::model::ThreadSearchKind;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::config_types::ReasoningSummary;
     use codex_protocol::models::ContentItem;
+    use codex_protocol::models::LocalShellAction;
+    use codex_protocol::models::LocalShellExecAction;
+    use codex_protocol::models::LocalShellStatus;
     use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::AskForApproval;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
@@ -309,6 +369,68 @@ mod tests {
         assert_eq!(metadata.cwd, PathBuf::from("/fallback/workspace"));
     }
 
+    #[test]
+    fn search_entries_cover_messages_and_commands() {
+        let items = vec![
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: format!("{USER_MESSAGE_BEGIN} fix the flaky test"),
+                images: None,
+                local_images: vec![],
+                text_elements: vec![],
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell_command".to_string(),
+                arguments: r#"{"command":"cargo test -p codex-state"}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                id: None,
+                call_id: Some("call-2".to_string()),
+                status: LocalShellStatus::Completed,
+                action: LocalShellAction::Exec(LocalShellExecAction {
+                    command: vec!["git".to_string(), "status".to_string()],
+                    timeout_ms: None,
+                    working_directory: None,
+                    env: None,
+                    user: None,
+                }),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "view_image".to_string(),
+                arguments: r#"{"path":"/tmp/a.png"}"#.to_string(),
+                call_id: "call-3".to_string(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "The test is fixed.".to_string(),
+                phase: None,
+            })),
+        ];
+
+        assert_eq!(
+            search_entries_from_items(&items),
+            vec![
+                ThreadSearchEntry {
+                    kind: ThreadSearchKind::UserMessage,
+                    text: "fix the flaky test".to_string(),
+                },
+                ThreadSearchEntry {
+                    kind: ThreadSearchKind::Command,
+                    text: "cargo test -p codex-state".to_string(),
+                },
+                ThreadSearchEntry {
+                    kind: ThreadSearchKind::Command,
+                    text: "git status".to_string(),
+                },
+                ThreadSearchEntry {
+                    kind: ThreadSearchKind::AgentMessage,
+                    text: "The test is fixed.".to_string(),
+                },
+            ]
+        );
+    }
+
     fn metadata_for_test() -> ThreadMetadata {
         let id = ThreadId::from_string(&Uuid::from_u128(42).to_string()).expect("thread id");
         let created_at = DateTime::<Utc>::from_timestamp(1_735_689_600, 0).expect("timestamp");
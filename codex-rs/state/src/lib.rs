@@ -23,6 +23,7 @@ pub use runtime::StateRuntime;
 ///
 /// Most consumers should prefer [`StateRuntime`].
 pub use extract::apply_rollout_item;
+pub use extract::search_entries_from_items;
 pub use model::AgentJob;
 pub use model::AgentJobCreateParams;
 pub use model::AgentJobItem;
@@ -43,6 +44,9 @@ pub use model::Stage1OutputRef;
 pub use model::Stage1StartupClaimParams;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchEntry;
+pub use model::ThreadSearchHit;
+pub use model::ThreadSearchKind;
 pub use model::ThreadsPage;
 pub use runtime::state_db_filename;
 pub use runtime::state_db_path;
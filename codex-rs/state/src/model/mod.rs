@@ -3,6 +3,7 @@ mod backfill_state;
 mod log;
 mod memories;
 mod thread_metadata;
+mod thread_search;
 
 pub use agent_job::AgentJob;
 pub use agent_job::AgentJobCreateParams;
@@ -30,6 +31,9 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::ThreadSearchEntry;
+pub use thread_search::ThreadSearchHit;
+pub use thread_search::ThreadSearchKind;
 
 pub(crate) use agent_job::AgentJobItemRow;
 pub(crate) use agent_job::AgentJobRow;
@@ -11,6 +11,8 @@ use sqlx::sqlite::SqliteRow;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use super::ThreadSearchEntry;
+
 /// The sort key to use when listing threads.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortKey {
@@ -47,6 +49,8 @@ pub struct ExtractionOutcome {
     pub metadata: ThreadMetadata,
     /// The number of rollout lines that failed to parse.
     pub parse_errors: usize,
+    /// Searchable text extracted from the rollout.
+    pub search_entries: Vec<ThreadSearchEntry>,
 }
 
 /// Canonical thread metadata derived from rollout files.
@@ -0,0 +1,55 @@
+use anyhow::Result;
+
+use super::ThreadMetadata;
+
+/// The kind of rollout content a search entry was extracted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSearchKind {
+    /// A message typed by the user.
+    UserMessage,
+    /// A message produced by the agent.
+    AgentMessage,
+    /// A command line the agent ran.
+    Command,
+}
+
+impl ThreadSearchKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ThreadSearchKind::UserMessage => "user_message",
+            ThreadSearchKind::AgentMessage => "agent_message",
+            ThreadSearchKind::Command => "command",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "user_message" => Ok(Self::UserMessage),
+            "agent_message" => Ok(Self::AgentMessage),
+            "command" => Ok(Self::Command),
+            _ => Err(anyhow::anyhow!("invalid thread search kind: {value}")),
+        }
+    }
+}
+
+/// A piece of searchable text extracted from a rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchEntry {
+    /// Where the text came from.
+    pub kind: ThreadSearchKind,
+    /// The indexed text.
+    pub text: String,
+}
+
+/// A thread matching a full-text search, with its best-ranked match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadSearchHit {
+    /// The matching thread.
+    pub thread: ThreadMetadata,
+    /// The kind of entry that matched best.
+    pub kind: ThreadSearchKind,
+    /// An excerpt of the matching entry with matched terms wrapped in `[` and `]`.
+    pub snippet: String,
+    /// The bm25 rank of the match. Lower is more relevant.
+    pub rank: f64,
+}
@@ -17,6 +17,7 @@ use crate::ThreadMetadata;
 use crate::ThreadMetadataBuilder;
 use crate::ThreadsPage;
 use crate::apply_rollout_item;
+use crate::search_entries_from_items;
 use crate::migrations::MIGRATOR;
 use crate::model::AgentJobRow;
 use crate::model::ThreadRow;
@@ -54,6 +55,7 @@ mod logs;
 mod memories;
 #[cfg(test)]
 mod test_support;
+mod thread_search;
 mod threads;
 
 // "Partition" is the retention bucket we cap at 10 MiB:
@@ -173,6 +173,7 @@ LEFT JOIN jobs
             None,
             SortKey::UpdatedAt,
             None,
+            None,
         );
         builder
             .push(" AND id != ")
@@ -0,0 +1,307 @@
+use super::threads::push_thread_filters;
+use super::*;
+use crate::ThreadSearchEntry;
+use crate::ThreadSearchHit;
+use crate::ThreadSearchKind;
+
+/// Number of tokens around a match kept in search snippets.
+const SNIPPET_TOKENS: i64 = 12;
+
+impl StateRuntime {
+    /// Add searchable text for a thread to the full-text index.
+    pub async fn append_thread_search_entries(
+        &self,
+        thread_id: ThreadId,
+        entries: &[ThreadSearchEntry],
+    ) -> anyhow::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        insert_thread_search_entries(&mut tx, thread_id, entries).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replace all indexed text for a thread, e.g. after re-reading its rollout.
+    pub async fn replace_thread_search_entries(
+        &self,
+        thread_id: ThreadId,
+        entries: &[ThreadSearchEntry],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM thread_search WHERE thread_id = ?")
+            .bind(thread_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        insert_thread_search_entries(&mut tx, thread_id, entries).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Search thread contents, returning at most one hit per thread ordered by relevance.
+    ///
+    /// Every whitespace-separated term in `query` must match, either as a word or as the
+    /// prefix of one. Filters behave like [`StateRuntime::list_threads`]; `cwd` keeps only
+    /// threads started in that directory.
+    pub async fn search_threads(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[String],
+        model_providers: Option<&[String]>,
+        archived_only: bool,
+        cwd: Option<&Path>,
+    ) -> anyhow::Result<Vec<ThreadSearchHit>> {
+        let Some(match_expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+WITH hits AS (
+    SELECT
+        thread_id,
+        kind,
+        snippet(thread_search, 2, '[', ']', '…', "#,
+        );
+        builder.push(SNIPPET_TOKENS);
+        builder.push(
+            r#") AS snippet,
+        bm25(thread_search) AS rank
+    FROM thread_search
+    WHERE thread_search MATCH "#,
+        );
+        builder.push_bind(match_expression);
+        builder.push(
+            r#"
+),
+best_hits AS (
+    SELECT
+        thread_id,
+        kind,
+        snippet,
+        rank,
+        ROW_NUMBER() OVER (PARTITION BY thread_id ORDER BY rank ASC) AS hit_index
+    FROM hits
+)
+SELECT
+    threads.id,
+    threads.rollout_path,
+    threads.created_at,
+    threads.updated_at,
+    threads.source,
+    threads.agent_nickname,
+    threads.agent_role,
+    threads.model_provider,
+    threads.cwd,
+    threads.cli_version,
+    threads.title,
+    threads.sandbox_policy,
+    threads.approval_mode,
+    threads.tokens_used,
+    threads.first_user_message,
+    threads.archived_at,
+    threads.git_sha,
+    threads.git_branch,
+    threads.git_origin_url,
+    best_hits.kind AS hit_kind,
+    best_hits.snippet AS hit_snippet,
+    best_hits.rank AS hit_rank
+FROM best_hits
+JOIN threads ON threads.id = best_hits.thread_id
+            "#,
+        );
+        push_thread_filters(
+            &mut builder,
+            archived_only,
+            allowed_sources,
+            model_providers,
+            None,
+            SortKey::UpdatedAt,
+            None,
+            cwd,
+        );
+        builder.push(" AND best_hits.hit_index = 1");
+        builder.push(" ORDER BY best_hits.rank ASC, threads.updated_at DESC, threads.id DESC");
+        builder.push(" LIMIT ");
+        builder.push_bind(limit as i64);
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| {
+                let thread = ThreadRow::try_from_row(&row).and_then(ThreadMetadata::try_from)?;
+                let kind: String = row.try_get("hit_kind")?;
+                Ok(ThreadSearchHit {
+                    thread,
+                    kind: ThreadSearchKind::parse(kind.as_str())?,
+                    snippet: row.try_get("hit_snippet")?,
+                    rank: row.try_get("hit_rank")?,
+                })
+            })
+            .collect()
+    }
+}
+
+async fn insert_thread_search_entries(
+    conn: &mut SqliteConnection,
+    thread_id: ThreadId,
+    entries: &[ThreadSearchEntry],
+) -> anyhow::Result<()> {
+    let thread_id = thread_id.to_string();
+    for entry in entries {
+        sqlx::query("INSERT INTO thread_search (thread_id, kind, content) VALUES (?, ?, ?)")
+            .bind(thread_id.as_str())
+            .bind(entry.kind.as_str())
+            .bind(entry.text.as_str())
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Turn free-form user input into an FTS5 expression where every term is a quoted prefix
+/// query, so operators and punctuation in the input are matched literally.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateRuntime;
+    use super::fts_match_expression;
+    use super::test_support::test_thread_metadata;
+    use super::test_support::unique_temp_dir;
+    use crate::ThreadSearchEntry;
+    use crate::ThreadSearchKind;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn match_expression_quotes_terms() {
+        assert_eq!(fts_match_expression("   "), None);
+        assert_eq!(
+            fts_match_expression(r#"cargo "test OR"#),
+            Some(r#""cargo"* """test"* "OR"*"#.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn search_threads_ranks_matches_and_returns_snippets() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let flaky = ThreadId::new();
+        let docs = ThreadId::new();
+        let archived = ThreadId::new();
+        let docs_cwd = codex_home.join("docs");
+        for thread_id in [flaky, docs, archived] {
+            let cwd = if thread_id == docs {
+                docs_cwd.clone()
+            } else {
+                codex_home.clone()
+            };
+            let mut metadata = test_thread_metadata(codex_home.as_path(), thread_id, cwd);
+            if thread_id == archived {
+                metadata.archived_at = Some(metadata.updated_at);
+            }
+            runtime
+                .upsert_thread(&metadata)
+                .await
+                .expect("upsert thread");
+        }
+        runtime
+            .append_thread_search_entries(
+                flaky,
+                &[
+                    ThreadSearchEntry {
+                        kind: ThreadSearchKind::UserMessage,
+                        text: "please fix the flaky sqlite test".to_string(),
+                    },
+                    ThreadSearchEntry {
+                        kind: ThreadSearchKind::Command,
+                        text: "cargo test -p codex-state sqlite".to_string(),
+                    },
+                ],
+            )
+            .await
+            .expect("index flaky thread");
+        runtime
+            .append_thread_search_entries(
+                docs,
+                &[ThreadSearchEntry {
+                    kind: ThreadSearchKind::AgentMessage,
+                    text: "I updated the docs to mention the sqlite home directory and several \
+                           other configuration options that are unrelated"
+                        .to_string(),
+                }],
+            )
+            .await
+            .expect("index docs thread");
+        runtime
+            .append_thread_search_entries(
+                archived,
+                &[ThreadSearchEntry {
+                    kind: ThreadSearchKind::UserMessage,
+                    text: "sqlite".to_string(),
+                }],
+            )
+            .await
+            .expect("index archived thread");
+
+        let hits = runtime
+            .search_threads("sqlite", 10, &[], None, false, None)
+            .await
+            .expect("search");
+        let thread_ids = hits.iter().map(|hit| hit.thread.id).collect::<Vec<_>>();
+        assert_eq!(thread_ids, vec![flaky, docs]);
+        assert_eq!(hits[0].snippet.contains("[sqlite]"), true);
+
+        let hits = runtime
+            .search_threads("sqlite", 10, &[], None, false, Some(docs_cwd.as_path()))
+            .await
+            .expect("cwd search");
+        assert_eq!(
+            hits.iter().map(|hit| hit.thread.id).collect::<Vec<_>>(),
+            vec![docs]
+        );
+
+        let hits = runtime
+            .search_threads("flak", 10, &[], None, false, None)
+            .await
+            .expect("prefix search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, ThreadSearchKind::UserMessage);
+        assert_eq!(hits[0].snippet, "please fix the [flaky] sqlite test");
+
+        let hits = runtime
+            .search_threads("sqlite", 10, &[], None, true, None)
+            .await
+            .expect("archived search");
+        assert_eq!(
+            hits.iter().map(|hit| hit.thread.id).collect::<Vec<_>>(),
+            vec![archived]
+        );
+
+        runtime
+            .replace_thread_search_entries(flaky, &[])
+            .await
+            .expect("clear flaky thread");
+        let hits = runtime
+            .search_threads("flaky", 10, &[], None, false, None)
+            .await
+            .expect("search after replace");
+        assert_eq!(hits, Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+}
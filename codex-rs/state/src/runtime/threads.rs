@@ -138,6 +138,7 @@ FROM threads
             anchor,
             sort_key,
             search_term,
+            None,
         );
         push_thread_order_and_limit(&mut builder, sort_key, limit);
 
@@ -181,6 +182,7 @@ FROM threads
             anchor,
             sort_key,
             None,
+            None,
         );
         push_thread_order_and_limit(&mut builder, sort_key, limit);
 
@@ -350,6 +352,16 @@ ON CONFLICT(thread_id, position) DO NOTHING
             }
             return Err(err);
         }
+        let search_entries = search_entries_from_items(items);
+        if let Err(err) = self
+            .append_thread_search_entries(builder.id, search_entries.as_slice())
+            .await
+        {
+            if let Some(otel) = otel {
+                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "thread_search")]);
+            }
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -400,12 +412,18 @@ ON CONFLICT(thread_id, position) DO NOTHING
         self.upsert_thread(&metadata).await
     }
 
-    /// Delete a thread metadata row by id.
+    /// Delete a thread metadata row and its search index entries by id.
     pub async fn delete_thread(&self, thread_id: ThreadId) -> anyhow::Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM thread_search WHERE thread_id = ?")
+            .bind(thread_id.to_string())
+            .execute(&mut *tx)
+            .await?;
         let result = sqlx::query("DELETE FROM threads WHERE id = ?")
             .bind(thread_id.to_string())
-            .execute(self.pool.as_ref())
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
         Ok(result.rows_affected())
     }
 }
@@ -420,6 +438,7 @@ pub(super) fn extract_dynamic_tools(items: &[RolloutItem]) -> Option<Option<Vec<
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn push_thread_filters<'a>(
     builder: &mut QueryBuilder<'a, Sqlite>,
     archived_only: bool,
@@ -428,6 +447,7 @@ pub(super) fn push_thread_filters<'a>(
     anchor: Option<&crate::Anchor>,
     sort_key: SortKey,
     search_term: Option<&'a str>,
+    cwd: Option<&Path>,
 ) {
     builder.push(" WHERE 1 = 1");
     if archived_only {
@@ -454,6 +474,10 @@ pub(super) fn push_thread_filters<'a>(
         }
         separated.push_unseparated(")");
     }
+    if let Some(cwd) = cwd {
+        builder.push(" AND cwd = ");
+        builder.push_bind(cwd.display().to_string());
+    }
     if let Some(search_term) = search_term {
         builder.push(" AND instr(title, ");
         builder.push_bind(search_term);
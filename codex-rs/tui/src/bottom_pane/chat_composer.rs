@@ -174,8 +174,11 @@ use super::skill_popup::MentionItem;
 use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptCommand;
+use crate::bottom_pane::prompt_args::McpPromptInvocation;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_invocation;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
@@ -249,6 +252,9 @@ pub enum InputResult {
     },
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String, Vec<TextElement>),
+    /// The user invoked a prompt offered by an MCP server; the expanded text
+    /// is submitted once the server returns it.
+    McpPrompt(McpPromptInvocation),
     None,
 }
 
@@ -372,6 +378,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     remote_image_urls: Vec<String>,
@@ -481,6 +488,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             remote_image_urls: Vec::new(),
@@ -1393,6 +1401,19 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = if prompt.argument_names().is_empty() {
+                                    let text = format!("/{}", prompt.command());
+                                    let cursor = text.len();
+                                    (text, cursor)
+                                } else {
+                                    prompt.command_with_arg_placeholders()
+                                };
+                                self.textarea.set_text_clearing_elements(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -1480,6 +1501,24 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let command = format!("/{}", prompt.command());
+                                let typed_command =
+                                    first_line.split_whitespace().next() == Some(command.as_str());
+                                if !typed_command {
+                                    if !prompt.argument_names().is_empty() {
+                                        let (text, cursor) = prompt.command_with_arg_placeholders();
+                                        self.textarea.set_text_clearing_elements(&text);
+                                        self.textarea.set_cursor(cursor);
+                                        return (InputResult::None, true);
+                                    }
+                                    self.textarea.set_text_clearing_elements(&command);
+                                }
+                            }
+                            // Submit the completed command so it is dispatched as an MCP prompt.
+                            return self.handle_submission(false);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -2438,6 +2477,9 @@ impl ChatComposer {
         if let Some(result) = self.try_dispatch_slash_command_with_args() {
             return (result, true);
         }
+        if let Some(result) = self.try_dispatch_mcp_prompt() {
+            return (result, true);
+        }
 
         if let Some((text, text_elements)) = self.prepare_submission_text(true) {
             if should_queue {
@@ -2544,6 +2586,44 @@ impl ChatComposer {
         ))
     }
 
+    /// Check if the input invokes an MCP server prompt (e.g. `/mcp__docs__summarize topic=x`).
+    /// Returns Some(InputResult) if the input named a known MCP prompt, None otherwise.
+    /// Invalid or missing arguments are reported and the draft is kept for editing.
+    fn try_dispatch_mcp_prompt(&mut self) -> Option<InputResult> {
+        if !self.slash_commands_enabled() || self.mcp_prompts.is_empty() {
+            return None;
+        }
+        let mut text = self.textarea.text().to_string();
+        if text.starts_with(' ') {
+            return None;
+        }
+        if !self.pending_pastes.is_empty() {
+            let (expanded, _) = Self::expand_pending_pastes(
+                &text,
+                self.textarea.text_elements(),
+                &self.pending_pastes,
+            );
+            text = expanded;
+        }
+        let text = text.trim();
+        match parse_mcp_prompt_invocation(text, &self.mcp_prompts) {
+            Ok(None) => None,
+            Ok(Some(invocation)) => {
+                self.history
+                    .record_local_submission(HistoryEntry::new(text.to_string()));
+                self.pending_pastes.clear();
+                self.textarea.set_text_clearing_elements("");
+                Some(InputResult::McpPrompt(invocation))
+            }
+            Err(err) => {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(err.user_message()),
+                )));
+                Some(InputResult::None)
+            }
+        }
+    }
+
     /// Expand pending placeholders and extract normalized inline-command args.
     ///
     /// Inline-arg commands are initially dispatched using the raw draft so command rejection does
@@ -3357,7 +3437,9 @@ impl ChatComposer {
                 .iter()
                 .any(|prompt| prompt.name == prompt_name);
         }
-        false
+        self.mcp_prompts
+            .iter()
+            .any(|prompt| prompt.command() == name)
     }
 
     /// If the cursor is currently within a slash command on the first line,
@@ -3413,7 +3495,10 @@ impl ChatComposer {
 
         self.custom_prompts.iter().any(|prompt| {
             fuzzy_match(&format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name), name).is_some()
-        })
+        }) || self
+            .mcp_prompts
+            .iter()
+            .any(|prompt| fuzzy_match(&prompt.command(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -3472,6 +3557,7 @@ impl ChatComposer {
                             windows_degraded_sandbox_active: self.windows_degraded_sandbox_active,
                         },
                     );
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -3485,6 +3571,13 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -6258,7 +6351,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -6314,7 +6407,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -8469,6 +8562,79 @@ mod tests {
         );
     }
 
+    fn mcp_summarize_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: codex_protocol::mcp::Prompt {
+                name: "summarize".to_string(),
+                title: None,
+                description: None,
+                arguments: Some(vec![codex_protocol::mcp::PromptArgument {
+                    name: "topic".to_string(),
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                }]),
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompt_submission_dispatches_invocation() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_mcp_prompts(vec![mcp_summarize_prompt()]);
+
+        composer
+            .textarea
+            .set_text_clearing_elements("/mcp__docs__summarize topic=\"MCP prompts\"");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            result,
+            InputResult::McpPrompt(McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "summarize".to_string(),
+                arguments: HashMap::from([("topic".to_string(), "MCP prompts".to_string())]),
+            })
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
+    #[test]
+    fn selecting_mcp_prompt_with_args_inserts_placeholders() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_mcp_prompts(vec![mcp_summarize_prompt()]);
+
+        type_chars_humanlike(&mut composer, &['/', 's', 'u', 'm', 'm']);
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(result, InputResult::None);
+        assert_eq!(composer.textarea.text(), "/mcp__docs__summarize topic=\"\"");
+        assert_eq!(
+            composer.textarea.cursor(),
+            "/mcp__docs__summarize topic=\"".len()
+        );
+    }
+
     #[test]
     fn selecting_custom_prompt_with_args_expands_placeholders() {
         // Support $1..$9 and $ARGUMENTS in prompt content.
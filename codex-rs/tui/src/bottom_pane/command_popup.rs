@@ -3,6 +3,7 @@ use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
 
 use super::popup_consts::MAX_POPUP_ROWS;
+use super::prompt_args::McpPromptCommand;
 use super::scroll_state::ScrollState;
 use super::selection_popup_common::GenericDisplayRow;
 use super::selection_popup_common::render_rows;
@@ -19,18 +20,22 @@ use std::collections::HashSet;
 // `approvals` is an alias of `permissions`.
 const ALIAS_COMMANDS: &[SlashCommand] = &[SlashCommand::Quit, SlashCommand::Approvals];
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt or a
+/// prompt offered by an MCP server.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
@@ -66,6 +71,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -85,6 +91,15 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, mut prompts: Vec<McpPromptCommand>) {
+        prompts.sort_by_key(McpPromptCommand::command);
+        self.mcp_prompts = prompts;
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/' on the *first* line becomes the active filter that is used
@@ -143,6 +158,9 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None));
             }
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None));
+            }
             return out;
         }
 
@@ -189,6 +207,17 @@ impl CommandPopup {
                 prompt_prefix_len,
             );
         }
+        // MCP prompts match on the full command or on the bare prompt name.
+        for (idx, p) in self.mcp_prompts.iter().enumerate() {
+            let display = p.command();
+            let name_offset = display.chars().count() - p.prompt.name.chars().count();
+            push_match(
+                CommandItem::McpPrompt(idx),
+                &display,
+                Some(&p.prompt.name),
+                name_offset,
+            );
+        }
 
         out.extend(exact);
         out.extend(prefix);
@@ -221,6 +250,16 @@ impl CommandPopup {
                             description,
                         )
                     }
+                    CommandItem::McpPrompt(i) => {
+                        let prompt = &self.mcp_prompts[i];
+                        let description = prompt
+                            .prompt
+                            .description
+                            .clone()
+                            .or_else(|| prompt.prompt.title.clone())
+                            .unwrap_or_else(|| format!("send prompt from {}", prompt.server));
+                        (format!("/{}", prompt.command()), description)
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -292,7 +331,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -310,7 +349,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -322,7 +363,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -339,7 +380,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert_eq!(cmds, vec!["model", "mention", "mcp"]);
@@ -447,7 +488,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -478,7 +519,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -553,7 +594,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -603,7 +644,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
 
@@ -621,7 +662,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
 
@@ -630,4 +671,32 @@ mod tests {
             "expected no /debug* command in popup menu, got {cmds:?}"
         );
     }
+
+    #[test]
+    fn mcp_prompts_match_by_command_or_prompt_name() {
+        let mut popup = CommandPopup::new(Vec::new(), CommandPopupFlags::default());
+        popup.set_mcp_prompts(vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: codex_protocol::mcp::Prompt {
+                name: "summarize".to_string(),
+                title: None,
+                description: None,
+                arguments: None,
+            },
+        }]);
+
+        for filter in ["/summ", "/mcp__docs__s"] {
+            popup.on_composer_text_change(filter.to_string());
+            assert_eq!(
+                popup.selected_item(),
+                Some(CommandItem::McpPrompt(0)),
+                "expected MCP prompt to match {filter}"
+            );
+        }
+
+        let rows = popup.rows_from_matches(vec![(CommandItem::McpPrompt(0), None)]);
+        let row = rows.first().expect("row");
+        assert_eq!(row.name, "/mcp__docs__summarize");
+        assert_eq!(row.description.as_deref(), Some("send prompt from docs"));
+    }
 }
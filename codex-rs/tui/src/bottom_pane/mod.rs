@@ -45,6 +45,7 @@ mod approval_overlay;
 mod multi_select_picker;
 mod request_user_input;
 mod status_line_setup;
+mod workspace_overlay_review;
 pub(crate) use app_link_view::AppLinkView;
 pub(crate) use app_link_view::AppLinkViewParams;
 pub(crate) use approval_overlay::ApprovalOverlay;
@@ -92,6 +93,7 @@ pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
 pub(crate) use status_line_setup::StatusLineItem;
 pub(crate) use status_line_setup::StatusLineSetupView;
+pub(crate) use workspace_overlay_review::WorkspaceOverlayReviewView;
 mod paste_burst;
 mod pending_thread_approvals;
 pub mod popup_consts;
@@ -133,6 +135,7 @@ pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::ChatComposerConfig;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+pub(crate) use prompt_args::McpPromptCommand;
 
 use crate::status_indicator_widget::StatusDetailsCapitalization;
 use crate::status_indicator_widget::StatusIndicatorWidget;
@@ -804,6 +807,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update MCP server prompts available for the slash popup.
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.composer.set_mcp_prompts(prompts);
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -1,17 +1,15 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::custom_prompts::PromptTemplatePiece;
+use codex_protocol::custom_prompts::named_prompt_pieces;
+use codex_protocol::custom_prompts::numeric_prompt_pieces;
+pub use codex_protocol::custom_prompts::prompt_argument_names;
+pub use codex_protocol::custom_prompts::prompt_has_numeric_placeholders;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
-use lazy_static::lazy_static;
-use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
-use std::collections::HashSet;
-
-lazy_static! {
-    static ref PROMPT_ARG_REGEX: Regex =
-        Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
-}
 
 #[derive(Debug)]
 pub enum PromptArgsError {
@@ -104,31 +102,6 @@ pub fn parse_positional_args(rest: &str, text_elements: &[TextElement]) -> Vec<P
     parse_tokens_with_elements(rest, text_elements)
 }
 
-/// Extracts the unique placeholder variable names from a prompt template.
-///
-/// A placeholder is any token that matches the pattern `$[A-Z][A-Z0-9_]*`
-/// (for example `$USER`). The function returns the variable names without
-/// the leading `$`, de-duplicated and in the order of first appearance.
-pub fn prompt_argument_names(content: &str) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut names = Vec::new();
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        if m.start() > 0 && content.as_bytes()[m.start() - 1] == b'$' {
-            continue;
-        }
-        let name = &content[m.start() + 1..m.end()];
-        // Exclude special positional aggregate token from named args.
-        if name == "ARGUMENTS" {
-            continue;
-        }
-        let name = name.to_string();
-        if seen.insert(name.clone()) {
-            names.push(name);
-        }
-    }
-    names
-}
-
 /// Shift a text element's byte range left by `offset`, returning `None` if empty.
 ///
 /// `offset` is the byte length of the prefix removed from the original text.
@@ -252,25 +225,6 @@ pub fn expand_custom_prompt(
     )))
 }
 
-/// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
-pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
-    if content.contains("$ARGUMENTS") {
-        return true;
-    }
-    let bytes = content.as_bytes();
-    let mut i = 0;
-    while i + 1 < bytes.len() {
-        if bytes[i] == b'$' {
-            let b1 = bytes[i + 1];
-            if (b'1'..=b'9').contains(&b1) {
-                return true;
-            }
-        }
-        i += 1;
-    }
-    false
-}
-
 /// Extract positional arguments from a composer first line like "/name a b" for a given prompt name.
 /// Returns empty when the command name does not match or when there are no args.
 pub fn extract_positional_args_for_prompt_line(
@@ -335,41 +289,21 @@ pub fn expand_if_numeric_with_positional_args(
 pub fn expand_numeric_placeholders(content: &str, args: &[PromptArg]) -> PromptExpansion {
     let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
-    let mut i = 0;
-    while let Some(off) = content[i..].find('$') {
-        let j = i + off;
-        out.push_str(&content[i..j]);
-        let rest = &content[j..];
-        let bytes = rest.as_bytes();
-        if bytes.len() >= 2 {
-            match bytes[1] {
-                b'$' => {
-                    out.push_str("$$");
-                    i = j + 2;
-                    continue;
-                }
-                b'1'..=b'9' => {
-                    let idx = (bytes[1] - b'1') as usize;
-                    if let Some(arg) = args.get(idx) {
-                        append_arg_with_elements(&mut out, &mut out_elements, arg);
-                    }
-                    i = j + 2;
-                    continue;
+    for piece in numeric_prompt_pieces(content) {
+        match piece {
+            PromptTemplatePiece::Text(text) => out.push_str(text),
+            PromptTemplatePiece::Positional(idx) => {
+                if let Some(arg) = args.get(idx) {
+                    append_arg_with_elements(&mut out, &mut out_elements, arg);
                 }
-                _ => {}
             }
-        }
-        if rest.len() > "ARGUMENTS".len() && rest[1..].starts_with("ARGUMENTS") {
-            if !args.is_empty() {
+            PromptTemplatePiece::Arguments => {
                 append_joined_args_with_elements(&mut out, &mut out_elements, args);
             }
-            i = j + 1 + "ARGUMENTS".len();
-            continue;
+            // Numeric templates leave `$NAME` in the text.
+            PromptTemplatePiece::Named(_) => {}
         }
-        out.push('$');
-        i = j + 1;
     }
-    out.push_str(&content[i..]);
     PromptExpansion {
         text: out,
         text_elements: out_elements,
@@ -491,25 +425,20 @@ fn expand_named_placeholders_with_elements(
 ) -> (String, Vec<TextElement>) {
     let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
-    let mut cursor = 0;
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        let start = m.start();
-        let end = m.end();
-        if start > 0 && content.as_bytes()[start - 1] == b'$' {
-            out.push_str(&content[cursor..end]);
-            cursor = end;
-            continue;
-        }
-        out.push_str(&content[cursor..start]);
-        cursor = end;
-        let key = &content[start + 1..end];
-        if let Some(arg) = args.get(key) {
-            append_arg_with_elements(&mut out, &mut out_elements, arg);
-        } else {
-            out.push_str(&content[start..end]);
+    for piece in named_prompt_pieces(content) {
+        match piece {
+            PromptTemplatePiece::Text(text) => out.push_str(text),
+            PromptTemplatePiece::Named(key) => match args.get(key) {
+                Some(arg) => append_arg_with_elements(&mut out, &mut out_elements, arg),
+                None => {
+                    out.push('$');
+                    out.push_str(key);
+                }
+            },
+            // Named templates leave `$1..$9` and `$ARGUMENTS` in the text.
+            PromptTemplatePiece::Positional(_) | PromptTemplatePiece::Arguments => {}
         }
     }
-    out.push_str(&content[cursor..]);
     (out, out_elements)
 }
 
@@ -548,7 +477,11 @@ fn append_joined_args_with_elements(
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(&format!("{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+fn command_with_arg_placeholders(command: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{command}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -559,6 +492,101 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
     (text, cursor)
 }
 
+const MCP_PROMPT_CMD_PREFIX: &str = "mcp";
+const MCP_PROMPT_DELIMITER: &str = "__";
+
+/// A prompt offered by a connected MCP server, invoked as
+/// `/mcp__<server>__<prompt>` using the same delimiter as MCP tool names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpPromptCommand {
+    pub server: String,
+    pub prompt: McpPrompt,
+}
+
+impl McpPromptCommand {
+    /// Slash command name without the leading `/`.
+    pub fn command(&self) -> String {
+        format!(
+            "{MCP_PROMPT_CMD_PREFIX}{MCP_PROMPT_DELIMITER}{}{MCP_PROMPT_DELIMITER}{}",
+            self.server, self.prompt.name
+        )
+    }
+
+    pub fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+
+    fn required_argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .filter(|argument| argument.required == Some(true))
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+
+    /// Command text with an empty `key=""` pair per argument, and the cursor
+    /// position inside the first pair of quotes.
+    pub fn command_with_arg_placeholders(&self) -> (String, usize) {
+        command_with_arg_placeholders(&self.command(), &self.argument_names())
+    }
+}
+
+/// A request to fetch an MCP prompt, parsed from the composer text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpPromptInvocation {
+    pub server: String,
+    pub name: String,
+    pub arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/mcp__server__prompt [key=value] …` against
+/// the known MCP prompts.
+///
+/// Returns `Ok(None)` when the text does not name a known MCP prompt. Values
+/// follow the same quoting rules as custom prompt arguments, and every
+/// argument the server marks as required must be present.
+pub fn parse_mcp_prompt_invocation(
+    text: &str,
+    mcp_prompts: &[McpPromptCommand],
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((name, rest, _rest_offset)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some(command) = mcp_prompts.iter().find(|prompt| prompt.command() == name) else {
+        return Ok(None);
+    };
+    let inputs = parse_prompt_inputs(rest, &[]).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{name}"),
+        error,
+    })?;
+    let missing: Vec<String> = command
+        .required_argument_names()
+        .into_iter()
+        .filter(|key| !inputs.contains_key(key))
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{name}"),
+            missing,
+        });
+    }
+    Ok(Some(McpPromptInvocation {
+        server: command.server.clone(),
+        name: command.prompt.name.clone(),
+        arguments: inputs
+            .into_iter()
+            .map(|(key, value)| (key, value.text))
+            .collect(),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -851,4 +879,71 @@ mod tests {
             })
         );
     }
+
+    fn summarize_command() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: McpPrompt {
+                name: "summarize".to_string(),
+                title: None,
+                description: Some("Summarize a topic".to_string()),
+                arguments: Some(vec![
+                    codex_protocol::mcp::PromptArgument {
+                        name: "topic".to_string(),
+                        title: None,
+                        description: None,
+                        required: Some(true),
+                    },
+                    codex_protocol::mcp::PromptArgument {
+                        name: "audience".to_string(),
+                        title: None,
+                        description: None,
+                        required: None,
+                    },
+                ]),
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompt_command_inserts_placeholders_for_all_arguments() {
+        let command = summarize_command();
+        assert_eq!(command.command(), "mcp__docs__summarize");
+        let (text, cursor) = command.command_with_arg_placeholders();
+        assert_eq!(text, "/mcp__docs__summarize topic=\"\" audience=\"\"");
+        assert_eq!(cursor, "/mcp__docs__summarize topic=\"".len());
+    }
+
+    #[test]
+    fn parses_mcp_prompt_invocation_with_quoted_values() {
+        let prompts = vec![summarize_command()];
+        let invocation =
+            parse_mcp_prompt_invocation("/mcp__docs__summarize topic=\"MCP prompts\"", &prompts)
+                .expect("parses")
+                .expect("known prompt");
+        assert_eq!(
+            invocation,
+            McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "summarize".to_string(),
+                arguments: HashMap::from([("topic".to_string(), "MCP prompts".to_string())]),
+            }
+        );
+
+        assert_eq!(
+            parse_mcp_prompt_invocation("/mcp__docs__unknown", &prompts).expect("parses"),
+            None
+        );
+    }
+
+    #[test]
+    fn mcp_prompt_invocation_requires_required_arguments() {
+        let prompts = vec![summarize_command()];
+        let err = parse_mcp_prompt_invocation("/mcp__docs__summarize audience=ops", &prompts)
+            .expect_err("topic is required");
+        assert_eq!(
+            err.user_message(),
+            "Missing required args for /mcp__docs__summarize: topic. Provide as key=value (quote values with spaces)."
+        );
+    }
 }
@@ -0,0 +1,113 @@
+//! Review view for changes captured by the copy-on-write workspace overlay.
+//!
+//! When the sandbox runs commands against an overlay of the workspace, their
+//! file changes stay pending until the user decides which ones to merge. This
+//! view lists every pending file with all of them selected; confirming merges
+//! the selected files and discards the rest, while cancelling keeps everything
+//! pending for a later decision.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::WorkspaceOverlayDecision;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::bottom_pane_view::BottomPaneView;
+use crate::bottom_pane::multi_select_picker::MultiSelectItem;
+use crate::bottom_pane::multi_select_picker::MultiSelectPicker;
+use crate::diff_render::display_path_for;
+use crate::render::renderable::Renderable;
+
+/// Wraps a [`MultiSelectPicker`] that sends
+/// [`Op::ResolveWorkspaceOverlay`] with the selected paths on confirmation.
+pub(crate) struct WorkspaceOverlayReviewView {
+    picker: MultiSelectPicker,
+}
+
+impl WorkspaceOverlayReviewView {
+    pub(crate) fn new(
+        changes: &HashMap<PathBuf, FileChange>,
+        cwd: &Path,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let mut paths = changes.keys().collect::<Vec<_>>();
+        paths.sort();
+        let items = paths
+            .into_iter()
+            .map(|path| MultiSelectItem {
+                id: path.to_string_lossy().into_owned(),
+                name: display_path_for(path, cwd),
+                description: changes
+                    .get(path)
+                    .map(|change| change_label(change).to_string()),
+                enabled: true,
+            })
+            .collect();
+
+        Self {
+            picker: MultiSelectPicker::builder(
+                "Review sandbox changes".to_string(),
+                Some(
+                    "Selected files are merged into the workspace; the rest are discarded."
+                        .to_string(),
+                ),
+                app_event_tx,
+            )
+            .instructions(vec![
+                "Use ↑↓ to navigate, space to select, enter to confirm, esc to decide later."
+                    .into(),
+            ])
+            .items(items)
+            .on_confirm(|ids, app_event| {
+                let paths = ids.iter().map(PathBuf::from).collect();
+                app_event.send(AppEvent::CodexOp(Op::ResolveWorkspaceOverlay {
+                    decision: WorkspaceOverlayDecision::AcceptPaths { paths },
+                }));
+            })
+            .build(),
+        }
+    }
+}
+
+fn change_label(change: &FileChange) -> &'static str {
+    match change {
+        FileChange::Add { .. } => "added",
+        FileChange::Delete { .. } => "deleted",
+        FileChange::Update { .. } => "modified",
+        FileChange::Binary { replace: true, .. } => "binary, modified",
+        FileChange::Binary { replace: false, .. } => "binary, added",
+        FileChange::Symlink { .. } => "symlink",
+    }
+}
+
+impl BottomPaneView for WorkspaceOverlayReviewView {
+    fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) {
+        self.picker.handle_key_event(key_event);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.picker.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.picker.close();
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for WorkspaceOverlayReviewView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.picker.render(area, buf)
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.picker.desired_height(width)
+    }
+}
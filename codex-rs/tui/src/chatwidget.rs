@@ -42,6 +42,7 @@ use crate::app_event::RealtimeAudioDeviceKind;
 use crate::audio_device::list_realtime_audio_device_names;
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::StatusLineSetupView;
+use crate::bottom_pane::WorkspaceOverlayReviewView;
 use crate::status::RateLimitWindowDisplay;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
@@ -108,12 +109,16 @@ use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ListCustomPromptsResponseEvent;
 use codex_protocol::protocol::ListSkillsResponseEvent;
+use codex_protocol::protocol::McpGetPromptResponseEvent;
+use codex_protocol::protocol::McpListPromptsResponseEvent;
 use codex_protocol::protocol::McpListToolsResponseEvent;
+use codex_protocol::protocol::McpSamplingEndEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
+use codex_protocol::protocol::McpToolsChangedEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::PatchApplyBeginEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
@@ -134,6 +139,8 @@ use codex_protocol::protocol::ViewImageToolCallEvent;
 use codex_protocol::protocol::WarningEvent;
 use codex_protocol::protocol::WebSearchBeginEvent;
 use codex_protocol::protocol::WebSearchEndEvent;
+use codex_protocol::protocol::WorkspaceOverlayChangesEvent;
+use codex_protocol::protocol::WorkspaceOverlayResolvedEvent;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use codex_protocol::user_input::TextElement;
 use codex_protocol::user_input::UserInput;
@@ -213,6 +220,7 @@ use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::MentionBinding;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
 use crate::bottom_pane::SelectionAction;
@@ -1801,9 +1809,49 @@ impl ChatWidget {
         self.mcp_startup_status = None;
         self.update_task_running_state();
         self.maybe_send_next_queued_input();
+        if !ev.ready.is_empty() {
+            self.submit_op(Op::ListMcpPrompts);
+        }
         self.request_redraw();
     }
 
+    fn on_mcp_tools_changed(&mut self, ev: McpToolsChangedEvent) {
+        let mut parts = Vec::new();
+        if !ev.added.is_empty() {
+            parts.push(format!("added {}", ev.added.join(", ")));
+        }
+        if !ev.removed.is_empty() {
+            parts.push(format!("removed {}", ev.removed.join(", ")));
+        }
+        self.add_info_message(
+            format!(
+                "MCP server {} tools changed: {}",
+                ev.server,
+                parts.join("; ")
+            ),
+            None,
+        );
+    }
+
+    fn on_mcp_sampling_end(&mut self, ev: McpSamplingEndEvent) {
+        match ev.result {
+            Ok(_) => {
+                let tokens = ev
+                    .token_usage
+                    .map(|usage| format!(" ({} tokens)", usage.blended_total()))
+                    .unwrap_or_default();
+                self.add_info_message(
+                    format!("MCP server {} sampled {}{tokens}", ev.server, ev.model),
+                    None,
+                );
+            }
+            Err(err) => self.add_error_message(format!(
+                "Sampling request from MCP server {} failed: {err}",
+                ev.server
+            )),
+        }
+    }
+
     /// Handle a turn aborted due to user interrupt (Esc).
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
@@ -2236,6 +2284,46 @@ impl ChatWidget {
         self.refresh_status_line();
     }
 
+    fn on_workspace_overlay_changes(&mut self, event: WorkspaceOverlayChangesEvent) {
+        let view = WorkspaceOverlayReviewView::new(
+            &event.changes,
+            &self.config.cwd,
+            self.app_event_tx.clone(),
+        );
+        self.add_to_history(history_cell::new_patch_event(
+            event.changes,
+            &self.config.cwd,
+        ));
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    fn on_workspace_overlay_resolved(&mut self, event: WorkspaceOverlayResolvedEvent) {
+        let WorkspaceOverlayResolvedEvent {
+            accepted,
+            rejected,
+            conflicts,
+        } = event;
+        self.add_info_message(
+            format!(
+                "Sandbox changes merged: {}, discarded: {}",
+                accepted.len(),
+                rejected.len()
+            ),
+            None,
+        );
+        if !conflicts.is_empty() {
+            let paths = conflicts
+                .iter()
+                .map(|path| display_path_for(path, &self.config.cwd))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.add_error_message(format!(
+                "Not merged because they changed since they were reviewed: {paths}"
+            ));
+        }
+    }
+
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
         let DeprecationNoticeEvent { summary, details } = event;
         self.add_to_history(history_cell::new_deprecation_notice(summary, details));
@@ -3409,6 +3497,13 @@ impl ChatWidget {
                 InputResult::CommandWithArgs(cmd, args, text_elements) => {
                     self.dispatch_command_with_args(cmd, args, text_elements);
                 }
+                InputResult::McpPrompt(invocation) => {
+                    self.submit_op(Op::GetMcpPrompt {
+                        server: invocation.server,
+                        name: invocation.name,
+                        arguments: invocation.arguments,
+                    });
+                }
                 InputResult::None => {}
             },
         }
@@ -3776,6 +3871,7 @@ impl ChatWidget {
                                 PathBuf::from("/tmp/test.txt"),
                                 FileChange::Add {
                                     content: "test".to_string(),
+                                    mode: None,
                                 },
                             ),
                             (
@@ -3783,6 +3879,7 @@ impl ChatWidget {
                                 FileChange::Update {
                                     unified_diff: "+test\n-test2".to_string(),
                                     move_path: None,
+                                    mode: None,
                                 },
                             ),
                         ]),
@@ -4372,6 +4469,7 @@ impl ChatWidget {
             }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::McpToolsChanged(ev) => self.on_mcp_tools_changed(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -4406,11 +4504,14 @@ impl ChatWidget {
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
+            EventMsg::McpSamplingEnd(ev) => self.on_mcp_sampling_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::McpListPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::McpGetPromptResponse(ev) => self.on_mcp_get_prompt_response(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
             EventMsg::SkillsUpdateAvailable => {
@@ -4421,6 +4522,8 @@ impl ChatWidget {
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::WorkspaceOverlayChanges(ev) => self.on_workspace_overlay_changes(ev),
+            EventMsg::WorkspaceOverlayResolved(ev) => self.on_workspace_overlay_resolved(ev),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
@@ -7570,6 +7673,31 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: McpListPromptsResponseEvent) {
+        let prompts: Vec<McpPromptCommand> = ev
+            .prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
+    fn on_mcp_get_prompt_response(&mut self, ev: McpGetPromptResponseEvent) {
+        match ev.result {
+            Ok(text) => self.queue_user_message(UserMessage::from(text)),
+            Err(err) => self.add_error_message(format!(
+                "Failed to get prompt {} from MCP server {}: {err}",
+                ev.name, ev.server
+            )),
+        }
+    }
+
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
         self.set_skills_from_response(&ev);
     }
@@ -1453,6 +1453,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_review_tokens, context_window)),
             rate_limits: None,
+            estimated_tokens: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -1472,6 +1473,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(review_tokens, context_window)),
             rate_limits: None,
+            estimated_tokens: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(97));
@@ -1501,6 +1503,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_compact_tokens, context_window)),
             rate_limits: None,
+            estimated_tokens: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -1510,6 +1513,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: None,
             rate_limits: None,
+            estimated_tokens: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), None);
@@ -1540,6 +1544,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(token_info),
             rate_limits: None,
+            estimated_tokens: None,
         }),
     });
 
@@ -3094,6 +3099,7 @@ fn end_exec(
             } else {
                 CoreExecCommandStatus::Failed
             },
+            resource_limit_exceeded: None,
         }),
     });
 }
@@ -3729,6 +3735,7 @@ async fn exec_end_without_begin_uses_event_command() {
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
             status: CoreExecCommandStatus::Completed,
+            resource_limit_exceeded: None,
         }),
     });
 
@@ -6187,6 +6194,7 @@ async fn preset_matching_requires_exact_workspace_write_settings() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        denied_read_paths: Vec::new(),
     };
 
     assert!(
@@ -6963,6 +6971,7 @@ async fn approval_modal_patch_snapshot() -> anyhow::Result<()> {
         PathBuf::from("README.md"),
         FileChange::Add {
             content: "hello\nworld\n".into(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -7416,6 +7425,7 @@ async fn apply_patch_events_emit_history_cells() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -7457,6 +7467,7 @@ async fn apply_patch_events_emit_history_cells() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     let begin = PatchApplyBeginEvent {
@@ -7483,6 +7494,7 @@ async fn apply_patch_events_emit_history_cells() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     let end = PatchApplyEndEvent {
@@ -7514,6 +7526,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -7533,6 +7546,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -7563,6 +7577,7 @@ async fn apply_patch_manual_flow_snapshot() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -7586,6 +7601,7 @@ async fn apply_patch_manual_flow_snapshot() {
         PathBuf::from("foo.txt"),
         FileChange::Add {
             content: "hello\n".to_string(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -7616,6 +7632,7 @@ async fn apply_patch_approval_sends_op_with_call_id() {
         PathBuf::from("file.rs"),
         FileChange::Add {
             content: "fn main(){}\n".into(),
+            mode: None,
         },
     );
     let ev = ApplyPatchApprovalRequestEvent {
@@ -7654,7 +7671,10 @@ async fn apply_patch_full_flow_integration_like() {
     let mut changes = HashMap::new();
     changes.insert(
         PathBuf::from("pkg.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-xyz".into(),
@@ -7695,7 +7715,10 @@ async fn apply_patch_full_flow_integration_like() {
     let mut changes2 = HashMap::new();
     changes2.insert(
         PathBuf::from("pkg.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-xyz".into(),
@@ -7709,7 +7732,10 @@ async fn apply_patch_full_flow_integration_like() {
     let mut end_changes = HashMap::new();
     end_changes.insert(
         PathBuf::from("pkg.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-xyz".into(),
@@ -7738,7 +7764,10 @@ async fn apply_patch_untrusted_shows_approval_modal() -> anyhow::Result<()> {
     let mut changes = HashMap::new();
     changes.insert(
         PathBuf::from("a.rs"),
-        FileChange::Add { content: "".into() },
+        FileChange::Add {
+            content: "".into(),
+            mode: None,
+        },
     );
     chat.handle_codex_event(Event {
         id: "sub-1".into(),
@@ -7792,6 +7821,7 @@ async fn apply_patch_request_shows_diff_summary() -> anyhow::Result<()> {
         FileChange::Add {
             // Two lines (no trailing empty line counted)
             content: "line one\nline two\n".into(),
+            mode: None,
         },
     );
     chat.handle_codex_event(Event {
@@ -8481,6 +8511,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            resource_limit_exceeded: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -189,9 +189,10 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
         let (added, removed) = match change {
-            FileChange::Add { content } => (content.lines().count(), 0),
+            FileChange::Add { content, .. } => (content.lines().count(), 0),
             FileChange::Delete { content } => (0, content.lines().count()),
             FileChange::Update { unified_diff, .. } => calculate_add_remove_from_diff(unified_diff),
+            FileChange::Binary { .. } | FileChange::Symlink { .. } => (0, 0),
         };
         let move_path = match change {
             FileChange::Update {
@@ -242,15 +243,19 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim()];
     if let [row] = &rows[..] {
         let verb = match &row.change {
-            FileChange::Add { .. } => "Added",
+            FileChange::Add { .. }
+            | FileChange::Binary { replace: false, .. }
+            | FileChange::Symlink { .. } => "Added",
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
         header_spans.push(verb.bold());
         header_spans.push(" ".into());
         header_spans.extend(render_path(row));
-        header_spans.push(" ".into());
-        header_spans.extend(render_line_count_summary(row.added, row.removed));
+        if has_text_lines(&row.change) {
+            header_spans.push(" ".into());
+            header_spans.extend(render_line_count_summary(row.added, row.removed));
+        }
     } else {
         header_spans.push("Edited".bold());
         header_spans.push(format!(" {file_count} {noun} ").into());
@@ -269,8 +274,10 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
             let mut header: Vec<RtSpan<'static>> = Vec::new();
             header.push("  └ ".dim());
             header.extend(render_path(&r));
-            header.push(" ".into());
-            header.extend(render_line_count_summary(r.added, r.removed));
+            if has_text_lines(&r.change) {
+                header.push(" ".into());
+                header.extend(render_line_count_summary(r.added, r.removed));
+            }
             out.push(RtLine::from(header));
         }
 
@@ -294,6 +301,38 @@ fn detect_lang_for_path(path: &Path) -> Option<String> {
     Some(ext.to_string())
 }
 
+/// Whether the change carries text lines worth counting. Binary files, symlinks and mode-only
+/// updates render as a short description instead.
+fn has_text_lines(change: &FileChange) -> bool {
+    match change {
+        FileChange::Add { .. } | FileChange::Delete { .. } => true,
+        FileChange::Update { unified_diff, .. } => !unified_diff.is_empty(),
+        FileChange::Binary { .. } | FileChange::Symlink { .. } => false,
+    }
+}
+
+/// Describe what a text diff cannot show: binary contents, link targets and file modes.
+fn render_change_details(change: &FileChange, out: &mut Vec<RtLine<'static>>) {
+    let mode = match change {
+        FileChange::Add { mode, .. } | FileChange::Update { mode, .. } => *mode,
+        FileChange::Binary { size, mode, .. } => {
+            let noun = if *size == 1 { "byte" } else { "bytes" };
+            out.push(RtLine::from(format!("binary file, {size} {noun}").dim()));
+            *mode
+        }
+        FileChange::Symlink { target } => {
+            out.push(RtLine::from(
+                format!("symlink to {}", target.display()).dim(),
+            ));
+            None
+        }
+        FileChange::Delete { .. } => None,
+    };
+    if let Some(mode) = mode {
+        out.push(RtLine::from(format!("mode {mode:o}").dim()));
+    }
+}
+
 fn render_change(
     change: &FileChange,
     out: &mut Vec<RtLine<'static>>,
@@ -302,8 +341,10 @@ fn render_change(
 ) {
     let theme = diff_theme();
     let color_level = diff_color_level();
+    render_change_details(change, out);
     match change {
-        FileChange::Add { content } => {
+        FileChange::Binary { .. } | FileChange::Symlink { .. } => {}
+        FileChange::Add { content, .. } => {
             // Pre-highlight the entire file content as a whole.
             let syntax_lines = lang.and_then(|l| highlight_code_to_styled_spans(content, l));
             let line_number_width = line_number_width(content.lines().count());
@@ -1126,6 +1167,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: rust_patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1137,6 +1179,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: py_patch,
                 move_path: Some(PathBuf::from("scripts/calc.py")),
+                mode: None,
             },
         );
 
@@ -1144,6 +1187,7 @@ mod tests {
             PathBuf::from("assets/banner.txt"),
             FileChange::Add {
                 content: "HEADER\tVALUE\nrocket\t🚀\ncity\t東京\n".to_string(),
+                mode: None,
             },
         );
         changes.insert(
@@ -1151,6 +1195,7 @@ mod tests {
             FileChange::Add {
                 content: "pub fn greet(name: &str) {\n    println!(\"Hello, {name}!\");\n}\n"
                     .to_string(),
+                mode: None,
             },
         );
 
@@ -1224,6 +1269,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1244,6 +1290,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(PathBuf::from("new_name.rs")),
+                mode: None,
             },
         );
 
@@ -1264,6 +1311,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch_a,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1272,6 +1320,7 @@ mod tests {
             PathBuf::from("b.txt"),
             FileChange::Add {
                 content: "new\n".to_string(),
+                mode: None,
             },
         );
 
@@ -1287,6 +1336,7 @@ mod tests {
             PathBuf::from("new_file.txt"),
             FileChange::Add {
                 content: "alpha\nbeta\n".to_string(),
+                mode: None,
             },
         );
 
@@ -1322,6 +1372,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1345,6 +1396,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1372,6 +1424,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1379,6 +1432,44 @@ mod tests {
         snapshot_lines_text("apply_update_block_line_numbers_three_digits_text", &lines);
     }
 
+    #[test]
+    fn ui_snapshot_apply_binary_symlink_and_mode_changes_text() {
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        changes.insert(
+            PathBuf::from("run.sh"),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: None,
+                mode: Some(0o755),
+            },
+        );
+        changes.insert(
+            PathBuf::from("logo.png"),
+            FileChange::Binary {
+                size: 4,
+                replace: false,
+                mode: None,
+            },
+        );
+        changes.insert(
+            PathBuf::from("data.bin"),
+            FileChange::Binary {
+                size: 1,
+                replace: true,
+                mode: Some(0o600),
+            },
+        );
+        changes.insert(
+            PathBuf::from("latest"),
+            FileChange::Symlink {
+                target: PathBuf::from("releases/v2"),
+            },
+        );
+
+        let lines = create_diff_summary(&changes, &PathBuf::from("/"), 80);
+        snapshot_lines_text("apply_binary_symlink_and_mode_changes_text", &lines);
+    }
+
     #[test]
     fn ui_snapshot_apply_update_block_relativizes_path() {
         let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
@@ -1395,6 +1486,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(abs_new),
+                mode: None,
             },
         );
 
@@ -1615,6 +1707,7 @@ mod tests {
             PathBuf::from("highlight_add.rs"),
             FileChange::Add {
                 content: "pub fn sum(a: i32, b: i32) -> i32 { a + b }\n".to_string(),
+                mode: None,
             },
         );
 
@@ -1809,6 +1902,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -1855,6 +1949,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: Some(PathBuf::from("foo.rs")),
+                mode: None,
             },
         );
 
@@ -1882,6 +1977,7 @@ mod tests {
             FileChange::Update {
                 unified_diff: patch,
                 move_path: None,
+                mode: None,
             },
         );
 
@@ -2634,6 +2634,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2658,6 +2659,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_limit: None,
         };
         servers.insert("http".to_string(), http_config);
         config
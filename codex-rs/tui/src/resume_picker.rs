@@ -16,6 +16,7 @@ use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
 use codex_core::ThreadItem;
+use codex_core::ThreadSearchItem;
 use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
 use codex_core::config::Config;
@@ -39,6 +40,7 @@ use unicode_width::UnicodeWidthStr;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const CONTENT_SEARCH_LIMIT: usize = 50;
 
 #[derive(Debug, Clone)]
 pub struct SessionTarget {
@@ -95,12 +97,24 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct ContentSearchRequest {
+    query: String,
+    default_provider: String,
+}
+
+type ContentSearchLoader = Arc<dyn Fn(ContentSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    ContentSearchLoaded {
+        query: String,
+        results: std::io::Result<Vec<ThreadSearchItem>>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -119,6 +133,10 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// Search matches previews and thread names of loaded sessions, and also runs a
+/// full-text query over session contents when the SQLite state DB is available.
+/// Content matches are merged into the list with a snippet of the matching text.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     config: &Config,
@@ -152,6 +170,31 @@ async fn run_session_picker(
         std::env::current_dir().ok()
     };
 
+    let search_config = config.clone();
+    let search_tx = bg_tx.clone();
+    let content_search_loader: ContentSearchLoader =
+        Arc::new(move |request: ContentSearchRequest| {
+            let tx = search_tx.clone();
+            let config = search_config.clone();
+            tokio::spawn(async move {
+                let provider_filter = vec![request.default_provider.clone()];
+                let results = RolloutRecorder::search_threads(
+                    &config,
+                    request.query.as_str(),
+                    CONTENT_SEARCH_LIMIT,
+                    INTERACTIVE_SESSION_SOURCES,
+                    Some(provider_filter.as_slice()),
+                    false,
+                    None,
+                )
+                .await;
+                let _ = tx.send(BackgroundEvent::ContentSearchLoaded {
+                    query: request.query,
+                    results,
+                });
+            });
+        });
+
     let config = config.clone();
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
@@ -186,7 +229,8 @@ async fn run_session_picker(
         show_all,
         filter_cwd,
         action,
-    );
+    )
+    .with_content_search(content_search_loader);
     state.start_initial_load();
     state.request_frame();
 
@@ -275,6 +319,9 @@ struct PickerState {
     sort_key: ThreadSortKey,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
     inline_error: Option<String>,
+    content_search_loader: Option<ContentSearchLoader>,
+    /// Snippets of full-text matches for the current query, keyed by rollout path.
+    content_matches: HashMap<PathBuf, String>,
 }
 
 struct PaginationState {
@@ -393,9 +440,16 @@ impl PickerState {
             sort_key: ThreadSortKey::CreatedAt,
             thread_name_cache: HashMap::new(),
             inline_error: None,
+            content_search_loader: None,
+            content_matches: HashMap::new(),
         }
     }
 
+    fn with_content_search(mut self, loader: ContentSearchLoader) -> Self {
+        self.content_search_loader = Some(loader);
+        self
+    }
+
     fn request_frame(&self) {
         self.requester.schedule_frame();
     }
@@ -540,10 +594,49 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::ContentSearchLoaded { query, results } => {
+                if query != self.query {
+                    return Ok(());
+                }
+                // Content search is best-effort: without the state DB the picker keeps
+                // filtering loaded sessions only.
+                let Ok(results) = results else {
+                    return Ok(());
+                };
+                self.ingest_content_matches(results);
+                self.update_thread_names().await;
+                self.continue_search_if_needed();
+            }
         }
         Ok(())
     }
 
+    fn ingest_content_matches(&mut self, results: Vec<ThreadSearchItem>) {
+        for result in results {
+            let row = head_to_row(&result.thread);
+            let snippet = result.snippet.split_whitespace().collect::<Vec<_>>();
+            self.content_matches
+                .insert(row.path.clone(), snippet.join(" "));
+            if self.seen_paths.insert(row.path.clone()) {
+                self.all_rows.push(row);
+            }
+        }
+        self.apply_filter();
+    }
+
+    fn request_content_search(&self) {
+        let Some(loader) = self.content_search_loader.as_ref() else {
+            return;
+        };
+        if self.query.trim().is_empty() {
+            return;
+        }
+        loader(ContentSearchRequest {
+            query: self.query.clone(),
+            default_provider: self.default_provider.clone(),
+        });
+    }
+
     fn reset_pagination(&mut self) {
         self.pagination.next_cursor = None;
         self.pagination.num_scanned_files = 0;
@@ -626,7 +719,10 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            self.filtered_rows = base_iter
+                .filter(|r| r.matches_query(&q) || self.content_matches.contains_key(&r.path))
+                .cloned()
+                .collect();
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -657,6 +753,8 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.content_matches.clear();
+        self.request_content_search();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -1030,6 +1128,12 @@ fn render_list(
             preview_width = preview_width.saturating_sub(2);
         }
         let preview = truncate_text(row.display_preview(), preview_width);
+        let snippet_width = preview_width.saturating_sub(preview.width() + 2);
+        let snippet = state
+            .content_matches
+            .get(&row.path)
+            .filter(|_| snippet_width > 0)
+            .map(|snippet| truncate_text(snippet, snippet_width));
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -1051,6 +1155,10 @@ fn render_list(
             spans.push("  ".into());
         }
         spans.push(preview.into());
+        if let Some(snippet) = snippet {
+            spans.push("  ".into());
+            spans.push(Span::from(snippet).dim());
+        }
 
         let line: Line = spans.into();
         let rect = Rect::new(area.x, y, area.width, 1);
@@ -2246,6 +2354,86 @@ mod tests {
         assert_eq!(state.selected, state.filtered_rows.len().saturating_sub(2));
     }
 
+    #[tokio::test]
+    async fn content_search_merges_matches_for_current_query() {
+        let loader: PageLoader = Arc::new(|_| {});
+        let recorded_searches: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let search_sink = recorded_searches.clone();
+        let content_search_loader: ContentSearchLoader =
+            Arc::new(move |req: ContentSearchRequest| {
+                search_sink.lock().unwrap().push(req.query);
+            });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        )
+        .with_content_search(content_search_loader);
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![make_item(
+                "/tmp/start.jsonl",
+                "2025-01-01T00:00:00Z",
+                "alpha",
+            )],
+            None,
+            1,
+            false,
+        ));
+
+        state.set_query("cargo".to_string());
+        state.set_query("cargo t".to_string());
+        assert_eq!(
+            recorded_searches.lock().unwrap().clone(),
+            vec!["cargo".to_string(), "cargo t".to_string()]
+        );
+        assert!(state.filtered_rows.is_empty());
+
+        let search_item = |path: &str| ThreadSearchItem {
+            thread: make_item(path, "2025-01-02T00:00:00Z", "run the suite"),
+            kind: codex_state::ThreadSearchKind::Command,
+            snippet: "[cargo]\n[test] -p codex-tui".to_string(),
+        };
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchLoaded {
+                query: "cargo".to_string(),
+                results: Ok(vec![search_item("/tmp/stale.jsonl")]),
+            })
+            .await
+            .unwrap();
+        assert!(state.filtered_rows.is_empty());
+
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchLoaded {
+                query: "cargo t".to_string(),
+                results: Ok(vec![search_item("/tmp/match.jsonl")]),
+            })
+            .await
+            .unwrap();
+        let paths: Vec<_> = state
+            .filtered_rows
+            .iter()
+            .map(|row| row.path.clone())
+            .collect();
+        assert_eq!(paths, vec![PathBuf::from("/tmp/match.jsonl")]);
+        assert_eq!(
+            state
+                .content_matches
+                .get(Path::new("/tmp/match.jsonl"))
+                .map(String::as_str),
+            Some("[cargo] [test] -p codex-tui")
+        );
+
+        state.set_query(String::new());
+        assert!(state.content_matches.is_empty());
+        assert_eq!(recorded_searches.lock().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn set_query_loads_until_match_and_respects_scan_cap() {
         let recorded_requests: Arc<Mutex<Vec<PageLoadRequest>>> = Arc::new(Mutex::new(Vec::new()));
@@ -106,6 +106,7 @@ async fn status_snapshot_includes_reasoning_details() {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         })
         .expect("set sandbox policy");
 
@@ -191,6 +192,7 @@ async fn status_permissions_non_default_workspace_write_is_custom() {
             network_access: true,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            denied_read_paths: Vec::new(),
         })
         .expect("set sandbox policy");
     config.cwd = PathBuf::from("/workspace/tests");
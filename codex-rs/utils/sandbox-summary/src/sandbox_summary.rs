@@ -18,6 +18,7 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
             read_only_access: _,
+            denied_read_paths: _,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -76,6 +77,7 @@ mod tests {
             network_access: true,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            denied_read_paths: Vec::new(),
         });
         assert_eq!(
             summary,
@@ -0,0 +1,351 @@
+//! BPE tokenizers for the model families Codex talks to. The vocabularies are
+//! embedded in the binary and loaded the first time an encoding is used.
+
+use tiktoken_rs::CoreBPE;
+
+/// Model families that share an encoding, matched on the slug and the
+/// `-`/`.` separated variants after it (`gpt-5` covers `gpt-5.1-codex`).
+const O200K_BASE_FAMILIES: &[&str] = &[
+    "gpt-5",
+    "gpt-oss",
+    "gpt-4.1",
+    "gpt-4o",
+    "chatgpt-4o",
+    "codex",
+    "o1",
+    "o3",
+    "o4",
+];
+const CL100K_BASE_FAMILIES: &[&str] = &["gpt-4", "gpt-3.5-turbo", "gpt-35-turbo", "gpt-3.5"];
+
+/// Longest slice of text encoded at once. BPE merging is quadratic in the
+/// length of a run without whitespace (minified code, base64, a repeated
+/// character), so longer text is encoded in parts, cut before whitespace where
+/// possible. A run that has to be cut can gain a token per part.
+const MAX_CHUNK_BYTES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingKind {
+    O200kBase,
+    Cl100kBase,
+}
+
+impl EncodingKind {
+    /// Encoding used by `model`, or `None` when the model has no known public
+    /// tokenizer. A provider prefix such as `openai/` is ignored.
+    pub fn for_model(model: &str) -> Option<Self> {
+        let model = model.rsplit('/').next().unwrap_or(model);
+        if O200K_BASE_FAMILIES
+            .iter()
+            .any(|family| is_in_family(model, family))
+        {
+            Some(Self::O200kBase)
+        } else if CL100K_BASE_FAMILIES
+            .iter()
+            .any(|family| is_in_family(model, family))
+        {
+            Some(Self::Cl100kBase)
+        } else {
+            None
+        }
+    }
+}
+
+fn is_in_family(model: &str, family: &str) -> bool {
+    model
+        .strip_prefix(family)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '.']))
+}
+
+#[derive(Clone, Copy)]
+pub struct Tokenizer {
+    kind: EncodingKind,
+    bpe: &'static CoreBPE,
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tokenizer")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tokenizer {
+    pub fn new(kind: EncodingKind) -> Self {
+        let bpe = match kind {
+            EncodingKind::O200kBase => tiktoken_rs::o200k_base_singleton(),
+            EncodingKind::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+        };
+        Self { kind, bpe }
+    }
+
+    /// Tokenizer for `model`; see [`EncodingKind::for_model`].
+    pub fn for_model(model: &str) -> Option<Self> {
+        EncodingKind::for_model(model).map(Self::new)
+    }
+
+    pub fn kind(&self) -> EncodingKind {
+        self.kind
+    }
+
+    /// Encode `text`. Special-token markers such as `<|endoftext|>` are
+    /// encoded as ordinary text, the way user content reaches the model.
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        chunks(text)
+            .flat_map(|chunk| self.bpe.encode_ordinary(chunk))
+            .collect()
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        chunks(text).map(|chunk| self.chunk_count(chunk)).sum()
+    }
+
+    /// Tokens in `text` if there are at most `limit`, otherwise `None`. Stops
+    /// encoding once the count passes `limit`.
+    pub fn count_up_to(&self, text: &str, limit: usize) -> Option<usize> {
+        let mut count = 0usize;
+        for chunk in chunks(text) {
+            count += self.chunk_count(chunk);
+            if count > limit {
+                return None;
+            }
+        }
+        Some(count)
+    }
+
+    /// Longest prefix of `text` that fits in `head_tokens` and longest suffix
+    /// that fits in the remaining `tail_tokens`, for cutting out the middle.
+    /// `text` is encoded once; only the part that straddles each cut is
+    /// encoded again. When the whole text fits, `head` is `text`.
+    pub fn head_and_tail<'a>(
+        &self,
+        text: &'a str,
+        head_tokens: usize,
+        tail_tokens: usize,
+    ) -> HeadAndTail<'a> {
+        let counted: Vec<(&str, usize)> = chunks(text)
+            .map(|chunk| (chunk, self.chunk_count(chunk)))
+            .collect();
+        let total_tokens = counted.iter().map(|(_, count)| count).sum();
+        if total_tokens <= head_tokens.saturating_add(tail_tokens) {
+            return HeadAndTail {
+                total_tokens,
+                head: text,
+                tail: "",
+            };
+        }
+
+        let mut head_end = 0;
+        let mut remaining = head_tokens;
+        for &(chunk, count) in &counted {
+            if count > remaining {
+                head_end += self.longest_prefix_within(chunk, remaining);
+                break;
+            }
+            remaining -= count;
+            head_end += chunk.len();
+        }
+
+        let mut tail_start = text.len();
+        let mut remaining = tail_tokens;
+        for &(chunk, count) in counted.iter().rev() {
+            if count > remaining {
+                tail_start -= self.longest_suffix_within(chunk, remaining);
+                break;
+            }
+            remaining -= count;
+            tail_start -= chunk.len();
+        }
+
+        HeadAndTail {
+            total_tokens,
+            head: &text[..head_end],
+            tail: &text[tail_start.max(head_end)..],
+        }
+    }
+
+    fn chunk_count(&self, chunk: &str) -> usize {
+        self.bpe.encode_ordinary(chunk).len()
+    }
+
+    /// Byte length of the longest prefix of `chunk` that encodes to at most
+    /// `limit` tokens, given that all of `chunk` does not.
+    fn longest_prefix_within(&self, chunk: &str, limit: usize) -> usize {
+        let boundaries = char_boundaries(chunk);
+        // `boundaries[fits]` always fits and `boundaries[too_long]` never does.
+        let (mut fits, mut too_long) = (0, boundaries.len() - 1);
+        while too_long - fits > 1 {
+            let mid = fits + (too_long - fits) / 2;
+            if self.chunk_count(&chunk[..boundaries[mid]]) <= limit {
+                fits = mid;
+            } else {
+                too_long = mid;
+            }
+        }
+        boundaries[fits]
+    }
+
+    /// Byte length of the longest suffix of `chunk` that encodes to at most
+    /// `limit` tokens, given that all of `chunk` does not.
+    fn longest_suffix_within(&self, chunk: &str, limit: usize) -> usize {
+        let boundaries = char_boundaries(chunk);
+        // `chunk[boundaries[fits]..]` always fits and `chunk[boundaries[too_long]..]` never does.
+        let (mut too_long, mut fits) = (0, boundaries.len() - 1);
+        while fits - too_long > 1 {
+            let mid = too_long + (fits - too_long) / 2;
+            if self.chunk_count(&chunk[boundaries[mid]..]) <= limit {
+                fits = mid;
+            } else {
+                too_long = mid;
+            }
+        }
+        chunk.len() - boundaries[fits]
+    }
+}
+
+/// What [`Tokenizer::head_and_tail`] keeps of a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadAndTail<'a> {
+    pub total_tokens: usize,
+    pub head: &'a str,
+    pub tail: &'a str,
+}
+
+/// Byte offsets of every character boundary in `text`, including both ends.
+fn char_boundaries(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(text.len()))
+        .collect()
+}
+
+/// Splits `text` into slices of at most [`MAX_CHUNK_BYTES`].
+fn chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut end = rest.len();
+        if end > MAX_CHUNK_BYTES {
+            end = MAX_CHUNK_BYTES;
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            // The pre-tokenizer starts a new piece at whitespace anyway.
+            if let Some(whitespace) = rest[..end]
+                .rfind(char::is_whitespace)
+                .filter(|&index| index > 0)
+            {
+                end = whitespace;
+            }
+        }
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn picks_the_encoding_for_model_families() {
+        for model in [
+            "gpt-5",
+            "gpt-5.1-codex-max",
+            "gpt-5.2",
+            "gpt-oss-120b",
+            "gpt-4o-mini",
+            "gpt-4.1",
+            "o3",
+            "o4-mini",
+            "codex-mini-latest",
+            "openai/gpt-5",
+        ] {
+            assert_eq!(
+                EncodingKind::for_model(model),
+                Some(EncodingKind::O200kBase),
+                "{model}"
+            );
+        }
+        for model in ["gpt-4", "gpt-4-turbo", "gpt-3.5-turbo-0125"] {
+            assert_eq!(
+                EncodingKind::for_model(model),
+                Some(EncodingKind::Cl100kBase),
+                "{model}"
+            );
+        }
+        for model in [
+            "claude-sonnet-4-5",
+            "llama3.2",
+            "gpt-50",
+            "o3mini",
+            "mistral",
+        ] {
+            assert_eq!(EncodingKind::for_model(model), None, "{model}");
+        }
+    }
+
+    #[test]
+    fn counts_tokens() {
+        let o200k = Tokenizer::new(EncodingKind::O200kBase);
+        let cl100k = Tokenizer::new(EncodingKind::Cl100kBase);
+
+        assert_eq!(o200k.count(""), 0);
+        assert_eq!(o200k.count("hello world"), 2);
+        assert_eq!(cl100k.count("hello world"), 2);
+        assert_eq!(o200k.encode("hello world"), vec![24912, 2375]);
+        assert_eq!(cl100k.encode("hello world"), vec![15339, 1917]);
+        assert!(o200k.count("<|endoftext|>") > 1);
+    }
+
+    #[test]
+    fn count_up_to_stops_past_the_limit() {
+        let o200k = Tokenizer::new(EncodingKind::O200kBase);
+
+        assert_eq!(o200k.count_up_to("hello world", 2), Some(2));
+        assert_eq!(o200k.count_up_to("hello world", 1), None);
+        assert_eq!(o200k.count_up_to("", 0), Some(0));
+    }
+
+    #[test]
+    fn head_and_tail_keep_the_ends_within_their_budgets() {
+        let o200k = Tokenizer::new(EncodingKind::O200kBase);
+        let text = "hello world, naïve café 🦀 and one more sentence at the end";
+        let total_tokens = o200k.count(text);
+
+        let kept = o200k.head_and_tail(text, 3, 4);
+
+        assert_eq!(kept.total_tokens, total_tokens);
+        assert!(text.starts_with(kept.head) && text.ends_with(kept.tail));
+        assert!(o200k.count(kept.head) <= 3 && o200k.count(kept.tail) <= 4);
+        assert_eq!(kept.head, "hello world,");
+        assert_eq!(kept.tail, " sentence at the end");
+        assert_eq!(
+            o200k.head_and_tail(text, total_tokens, 0),
+            HeadAndTail {
+                total_tokens,
+                head: text,
+                tail: "",
+            }
+        );
+    }
+
+    #[test]
+    fn long_text_is_encoded_in_chunks_without_changing_word_counts() {
+        let o200k = Tokenizer::new(EncodingKind::O200kBase);
+        let words = "lorem ipsum dolor sit amet ".repeat(200);
+        let run = "é".repeat(3 * MAX_CHUNK_BYTES);
+
+        assert!(chunks(&words).all(|chunk| chunk.len() <= MAX_CHUNK_BYTES));
+        assert_eq!(chunks(&run).collect::<String>(), run);
+        assert_eq!(o200k.count(&words), o200k.bpe.encode_ordinary(&words).len());
+        let kept = o200k.head_and_tail(&run, 5, 5);
+        assert!(o200k.count(kept.head) <= 5 && o200k.count(kept.tail) <= 5);
+        assert!(kept.head.len() + kept.tail.len() < run.len());
+    }
+}
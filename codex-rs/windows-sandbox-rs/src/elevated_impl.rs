@@ -478,6 +478,7 @@ mod windows_impl {
                 network_access,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                denied_read_paths: Vec::new(),
             }
         }
 
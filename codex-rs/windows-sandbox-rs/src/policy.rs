@@ -18,6 +18,9 @@ pub fn parse_policy(value: &str) -> Result<SandboxPolicy> {
                     "DangerFullAccess and ExternalSandbox are not supported for sandboxing"
                 );
             }
+            if !parsed.denied_read_paths().is_empty() {
+                anyhow::bail!("denied_read_paths is not yet supported by the Windows sandbox backend");
+            }
             Ok(parsed)
         }
     }
@@ -50,6 +53,19 @@ mod tests {
             .contains("DangerFullAccess and ExternalSandbox are not supported"));
     }
 
+    #[test]
+    fn rejects_denied_read_paths() {
+        let payload = serde_json::to_string(&SandboxPolicy::ReadOnly {
+            access: Default::default(),
+            denied_read_paths: vec![".env*".to_string()],
+        })
+        .unwrap();
+        let err = parse_policy(&payload).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("denied_read_paths is not yet supported"));
+    }
+
     #[test]
     fn parses_read_only_policy() {
         assert_eq!(